@@ -28,6 +28,7 @@ export default class GameController {
             engineMinTimeMs = 1000,
             engineTtMb = 64,
             autoOpponent = true,
+            ponder = false,
             bookPauseMs = 3000,
             onEngineThinkStart = null,
             onEngineThinkProgress = null,
@@ -40,6 +41,11 @@ export default class GameController {
         this.engineMinTimeMs = engineMinTimeMs;
         this.engineTtMb = engineTtMb;
         this.autoOpponent = autoOpponent === true;
+        // Pondern: Engine rechnet während der Bedenkzeit des Menschen auf dessen erwarteten Zug.
+        this.ponder = ponder === true;
+        this.ponderTask = null;
+        // Zählt Auflösungen/Abbrüche, damit ein verspäteter `_startPonder` verworfen wird.
+        this.ponderSeq = 0;
         this.bookPauseMs = bookPauseMs;
         this.onEngineThinkStart = typeof onEngineThinkStart === "function" ? onEngineThinkStart : null;
         this.onEngineThinkProgress = typeof onEngineThinkProgress === "function" ? onEngineThinkProgress : null;
@@ -58,17 +64,22 @@ export default class GameController {
 
         // Hier wird der Worker als „Behelfs-Engine“ instanziert
         this.engine = new ValidMovesEngine("../worker/moveWorker.js");
+        // Eigene Engine-Instanz für die Partie (Gegnerzüge, Pondern); Analysen über `search`
+        // ohne `engineId` verschmutzen ihre TT nicht.
+        this.gameEngineId = "game";
 
         this.gameId = 0;
     }
 
     // FEN initialisieren und rendern (neue Partie / neues Puzzle).
     initPosition(fen) {
+        this._cancelPonder();
         this.gameId += 1;
         this.baseFen     = fen;
         this.currentFen  = fen;
         this.currentMeta = fenZuFigurenListe(fen);
         this.moveList.clear();     // neue Partie -> History resetten
+        this.engine.newGame(this.gameEngineId).catch((err) => console.warn("newGame failed:", err));
 
         this.setPositionFromFen(fen);
     }
@@ -91,6 +102,7 @@ export default class GameController {
 
     // Navigation in der History (Undo/Redo via Ply-Index).
     goToPly(plyIndex) {
+        this._cancelPonder();
         this.moveList.index = plyIndex;
 
         let fen;
@@ -137,6 +149,9 @@ export default class GameController {
                 }
             }
 
+            // Ponder-Suche auflösen: Ponderhit liefert später direkt den Engine-Zug.
+            const ponderResult = this._resolvePonder(fenBefore, from, to, promotion);
+
             // Zug regelkonform im Worker anwenden und neue FEN erhalten.
             const fenAfter = await this.engine.applyMove(fenBefore, from, to, promotion);
 
@@ -163,7 +178,7 @@ export default class GameController {
             this._reportCheckState(this.currentFen);
 
             if (this.autoOpponent) {
-                await this._autoOpponentMove();
+                await this._autoOpponentMove(ponderResult);
             }
 
         } catch (err) {
@@ -285,6 +300,10 @@ export default class GameController {
             fen = null,
             debugRootEval = false,
             onProgress = null,
+            onInfo = null,
+            skillLevel = null,
+            clock = null,
+            engineId = null,
             useHistory = true,
             useBook = true
         } = options || {};
@@ -303,7 +322,8 @@ export default class GameController {
         }
 
         const safeTimeMs = Number.isFinite(t) && t > 0 ? t : 0;
-        const safeDepth = safeTimeMs > 0 ? 0 : (Number.isFinite(d) && d > 0 ? d : 0);
+        // Zeit- bzw. Uhr-gesteuerte Suchen laufen ohne Tiefenlimit.
+        const safeDepth = safeTimeMs > 0 || clock ? 0 : (Number.isFinite(d) && d > 0 ? d : 0);
         const safeTtMb = Number.isFinite(m) && m > 0 ? m : 0;
 
         // Nur in der "echten" Partie (nicht bei Fremd-FEN) History/Book nutzen.
@@ -312,19 +332,7 @@ export default class GameController {
         let uciHistory = "";
         let bookEnabled = false;
         if (isCurrent && useHistory === true) {
-            const historyList = [];
-            if (this.baseFen) {
-                historyList.push(this.baseFen);
-            }
-            if (this.moveList && Array.isArray(this.moveList.moves)) {
-                const end = Math.min(this.moveList.index, this.moveList.moves.length - 1);
-                for (let i = 0; i < end; i += 1) {
-                    const entry = this.moveList.moves[i];
-                    if (entry && typeof entry.fenAfter === "string") {
-                        historyList.push(entry.fenAfter);
-                    }
-                }
-            }
+            const historyList = this._buildFenHistory().split("\n").filter(Boolean);
             const HISTORY_LIMIT = 128;
             if (historyList.length > HISTORY_LIMIT) {
                 historyList.splice(0, historyList.length - HISTORY_LIMIT);
@@ -359,7 +367,11 @@ export default class GameController {
                     bookEnabled,
                     uciHistory,
                     debugRootEval: debugRootEval === true,
-                    onProgress
+                    onProgress,
+                    onInfo,
+                    skillLevel,
+                    clock,
+                    engineId: typeof engineId === "string" ? engineId : ""
                 }
             );
         } catch (err) {
@@ -368,7 +380,7 @@ export default class GameController {
         }
     }
 
-    async _autoOpponentMove() {
+    async _autoOpponentMove(ponderResult = null) {
         const fenBefore = this.currentFen;
         if (!fenBefore) return;
 
@@ -398,12 +410,17 @@ export default class GameController {
             });
         };
         try {
-            result = await this.search({
-                fen: fenBefore,
-                timeMs: this.engineTimeMs,
-                ttMb: this.engineTtMb,
-                onProgress: handleProgress
-            });
+            // Nach einem Ponderhit läuft die Ponder-Suche mit Zeitlimit weiter; sonst neu suchen.
+            result = ponderResult ? await ponderResult : null;
+            if (!this._extractBestMove(result)) {
+                result = await this.search({
+                    fen: fenBefore,
+                    timeMs: this.engineTimeMs,
+                    ttMb: this.engineTtMb,
+                    engineId: this.gameEngineId,
+                    onProgress: handleProgress
+                });
+            }
         } catch (err) {
             console.error("autoOpponent: search failed:", err);
             searchFailed = true;
@@ -515,7 +532,70 @@ export default class GameController {
             await this._applyEngineMove(bestUci, fenBefore);
         } catch (err) {
             console.error("autoOpponent: apply failed:", err);
+            return;
+        }
+
+        this._startPonder(result);
+    }
+
+    // Startet die Ponder-Suche auf den erwarteten Antwortzug (zweiter PV-Zug), falls aktiv.
+    async _startPonder(result) {
+        if (!this.ponder || !this.autoOpponent || !this.engine.canStop) return;
+        const pv = typeof result?.pv === "string" ? result.pv.trim().split(/\s+/) : [];
+        const expected = this._parseUciMove(pv[1] || "");
+        if (!expected) return;
+
+        const fenBefore = this.currentFen;
+        const gameId = this.gameId;
+        const seq = this.ponderSeq;
+        let ponderFen;
+        try {
+            ponderFen = await this.engine.applyMove(fenBefore, expected.from, expected.to, expected.promo);
+        } catch (err) {
+            console.warn("ponder: expected move not applicable:", err);
+            return;
         }
+        // Partie hat sich inzwischen geändert (neue Partie, Navigation, schneller Zug).
+        if (!ponderFen || seq !== this.ponderSeq || gameId !== this.gameId || fenBefore !== this.currentFen) return;
+
+        const history = [this._buildFenHistory(), fenBefore].filter(Boolean).join("\n");
+        const promise = this.engine.search(ponderFen, 0, this.engineTimeMs, this.engineTtMb, history, {
+            gameId,
+            ponder: true,
+            engineId: this.gameEngineId
+        }).catch((err) => {
+            console.error("ponder: search failed:", err);
+            return null;
+        });
+        this.ponderTask = { fenBefore, expected, promise };
+    }
+
+    // Spielt der Mensch den erwarteten Zug: Ponderhit und Promise der laufenden Suche
+    // zurückgeben. Sonst die Ponder-Suche abbrechen und `null` liefern.
+    _resolvePonder(fenBefore, from, to, promotion) {
+        const task = this.ponderTask;
+        this.ponderTask = null;
+        this.ponderSeq += 1;
+        if (!task) return null;
+        const { expected } = task;
+        const hit = task.fenBefore === fenBefore
+            && expected.from === Number(from)
+            && expected.to === Number(to)
+            && expected.promo === String(promotion || "").toLowerCase();
+        if (hit) {
+            this.engine.ponderHit();
+            return task.promise;
+        }
+        this.engine.stopSearch();
+        return null;
+    }
+
+    // Verwirft eine laufende Ponder-Suche (neue Partie, Navigation in der History).
+    _cancelPonder() {
+        this.ponderSeq += 1;
+        if (!this.ponderTask) return;
+        this.ponderTask = null;
+        this.engine.stopSearch();
     }
 
     _extractBestMove(result) {
@@ -807,79 +887,52 @@ export default class GameController {
 
     async _maybeReportGameEnd(fen, context = "game_end_probe") {
         const sideToMove = this._getSideToMove(fen);
-        // Früher Exit: triviales Remis (nur Könige).
-        if (this._isKingsOnlyPosition(fen)) {
-            this._reportOutcome({
-                reason: "insufficient_material",
-                winner: null,
-                sideToMove,
-                score: 0,
-                message: "Draw (insufficient material: kings only)."
-            }, null, context);
-            return true;
-        }
-
-        const halfmoveClock = this._getHalfmoveClock(fen);
-        // 50-Züge-Regel direkt aus FEN ableiten.
-        if (Number.isFinite(halfmoveClock) && halfmoveClock >= 100) {
-            this._reportOutcome({
-                reason: "fifty_move_rule",
-                winner: null,
-                sideToMove,
-                score: 0,
-                message: "Draw (50-move rule)."
-            }, null, context);
-            return true;
-        }
+        // Regeln (Matt, Patt, Remisregeln, tote Stellung) prüft die Engine.
+        // Repetition braucht die History und ist nur für die laufende Partie sinnvoll.
+        const history = fen === this.currentFen ? this._buildFenHistory() : "";
 
-        // Erst legal-move-Check, danach bei Bedarf Mini-Search für Einordnung.
-        const hasLegalMove = await this._hasAnyLegalMove(fen);
-        if (hasLegalMove !== false) {
-            return false;
-        }
-
-        let result = null;
+        let status = null;
         try {
-            result = await this.search({
-                fen,
-                depth: 1,
-                timeMs: 0,
-                ttMb: 0
-            });
+            status = await this.engine.gameStatus(fen, history);
         } catch (err) {
-            console.error("game end probe: search failed:", err);
+            console.error("game end probe: game_status failed:", err);
+            return false;
         }
-
-        const outcome = this._inferNoBestMoveOutcome(result, fen);
-        if (outcome) {
-            this._reportOutcome(outcome, result, context);
-            return true;
+        if (!status || status.error || status.reason === "ongoing") {
+            return false;
         }
-        return false;
-    }
 
-    _isKingsOnlyPosition(fen) {
-        if (!fen || typeof fen !== "string") return false;
-        const boardPart = fen.trim().split(/\s+/)[0] || "";
-        if (!boardPart) return false;
+        // Reklamierbare Remis (50 Züge, dreifache Wiederholung) werden automatisch reklamiert.
+        const winner = status.winner === "w" || status.winner === "b" ? status.winner : null;
+        this._reportOutcome({
+            reason: status.reason,
+            winner,
+            sideToMove,
+            score: winner ? null : 0,
+            message: this._gameStatusMessage(status.reason, winner)
+        }, null, context);
+        return true;
+    }
 
-        let whiteKing = 0;
-        let blackKing = 0;
-        for (const ch of boardPart) {
-            if (ch === "/" || (ch >= "1" && ch <= "8")) {
-                continue;
-            }
-            if (ch === "K") {
-                whiteKing += 1;
-                continue;
-            }
-            if (ch === "k") {
-                blackKing += 1;
-                continue;
-            }
-            return false;
+    _gameStatusMessage(reason, winner) {
+        switch (reason) {
+            case "checkmate":
+                return `${this._colorName(winner)} has won.`;
+            case "stalemate":
+                return "Draw (stalemate).";
+            case "insufficient_material":
+                return "Draw (insufficient material).";
+            case "fifty_move_rule":
+                return "Draw (50-move rule).";
+            case "seventy_five_move_rule":
+                return "Draw (75-move rule).";
+            case "threefold_repetition":
+                return "Draw (threefold repetition).";
+            case "fivefold_repetition":
+                return "Draw (fivefold repetition).";
+            default:
+                return "Game over.";
         }
-        return whiteKing === 1 && blackKing === 1;
     }
 
     _isSideInCheck(fen, side) {
@@ -999,50 +1052,22 @@ export default class GameController {
         return false;
     }
 
-    async _hasAnyLegalMove(fen) {
-        const sideToMove = this._getSideToMove(fen);
-        if (!sideToMove) return null;
-
-        const fields = this._getPieceFieldsForSide(fen, sideToMove);
-        for (const from of fields) {
-            const moves = await this.engine.getValidMoves(fen, from);
-            if (Array.isArray(moves) && moves.length > 0) {
-                return true;
-            }
+    // FENs der Stellungen vor `currentFen` (Basis + alle Züge bis zum aktuellen Index).
+    _buildFenHistory() {
+        const historyList = [];
+        if (this.baseFen) {
+            historyList.push(this.baseFen);
         }
-        return false;
-    }
-
-    _getPieceFieldsForSide(fen, side) {
-        if (!fen || typeof fen !== "string") return [];
-        if (side !== "w" && side !== "b") return [];
-
-        const boardPart = fen.trim().split(/\s+/)[0] || "";
-        const ranks = boardPart.split("/");
-        if (ranks.length !== 8) return [];
-
-        const wantWhite = side === "w";
-        const fields = [];
-        let rank = 7;
-
-        for (const rankPart of ranks) {
-            let file = 0;
-            for (const ch of rankPart) {
-                if (ch >= "1" && ch <= "8") {
-                    file += Number(ch);
-                    continue;
+        if (this.moveList && Array.isArray(this.moveList.moves)) {
+            const end = Math.min(this.moveList.index, this.moveList.moves.length - 1);
+            for (let i = 0; i < end; i += 1) {
+                const entry = this.moveList.moves[i];
+                if (entry && typeof entry.fenAfter === "string") {
+                    historyList.push(entry.fenAfter);
                 }
-
-                const isWhitePiece = ch >= "A" && ch <= "Z";
-                if ((wantWhite && isWhitePiece) || (!wantWhite && !isWhitePiece)) {
-                    fields.push(rank * 8 + file);
-                }
-                file += 1;
             }
-            rank -= 1;
         }
-
-        return fields;
+        return historyList.join("\n");
     }
 
     _buildUciHistory() {
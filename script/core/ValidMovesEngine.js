@@ -1,5 +1,9 @@
 // script/ValidMovesEngine.js
 
+// Schnelle Abfragen, die während einer langen Suche (Pondern, Analyse) über den
+// Neben-Worker laufen, damit z. B. Zugvorschläge beim Anheben einer Figur nicht warten.
+const QUICK_TASKS = new Set(["moves", "apply", "san", "san_to_uci", "status", "parse_pgn", "write_pgn"]);
+
 /**
  * Asynchrone Brücke zwischen UI-Thread und Move-Worker.
  * Verwaltet genau einen aktiven Worker-Request plus Queue.
@@ -9,9 +13,22 @@ export default class ValidMovesEngine {
         const workerUrl = new URL("../worker/moveWorker.js", import.meta.url);
         console.log("ValidMovesEngine: Initialisiere Worker mit URL:", workerUrl.href);
 
+        this.workerUrl = workerUrl;
         this.worker = new Worker(workerUrl, { type: "module" });
         this.pending = null;
         this.queue = [];
+        // Neben-Worker `{ worker, pending, queue }`, erst bei Bedarf angelegt (siehe `_enqueue`).
+        this.side = null;
+
+        // Stop-Flag (Index 0, siehe `stopSearch`) und Ponderhit-Flag (Index 1, siehe
+        // `ponderHit`) für laufende Suchen. SharedArrayBuffer gibt es nur bei
+        // Cross-Origin-Isolation (COOP/COEP-Header, siehe vite.config.js).
+        this.stopFlag = null;
+        if (typeof SharedArrayBuffer === "function" && globalThis.crossOriginIsolated) {
+            const buffer = new SharedArrayBuffer(2 * Int32Array.BYTES_PER_ELEMENT);
+            this.stopFlag = new Int32Array(buffer);
+            this.worker.postMessage({ action: "stop_buffer", buffer });
+        }
 
         // Zentraler Eingang für alle Worker-Antworten.
         this.worker.onmessage = (e) => {
@@ -31,29 +48,27 @@ export default class ValidMovesEngine {
                 return;
             }
 
+            // Info-Events (Bestzug, Score, PV je Iteration) ebenfalls ohne Auflösung.
+            if (payload.action === "search-info") {
+                if (
+                    this.pending &&
+                    this.pending.type === "search" &&
+                    typeof this.pending.onInfo === "function"
+                ) {
+                    this.pending.onInfo(payload);
+                }
+                return;
+            }
+
             // Defensive Prüfung: Antwort ohne passenden aktiven Task.
             if (!this.pending) {
                 console.warn("ValidMovesEngine: Message ohne pending:", payload);
                 return;
             }
 
-            const { resolve, type } = this.pending;
+            const task = this.pending;
             this.pending = null;
-
-            // Antwort je nach Task-Typ auflösen.
-            if (type === "moves") {
-                resolve(payload.moves || []);
-            } else if (type === "apply") {
-                resolve(payload.fen);
-            } else if (type === "perft") {
-                resolve(payload);
-            } else if (type === "search") {
-                resolve(payload);
-            } else {
-                console.warn("ValidMovesEngine: unbekannter pending-Typ:", type, payload);
-                resolve(payload);
-            }
-
+            this._resolveTask(task, payload);
             this._drainQueue();
         };
 
@@ -82,6 +97,43 @@ export default class ValidMovesEngine {
         };
     }
 
+    /** Löst das Promise eines Tasks je nach Task-Typ mit der Worker-Antwort auf. */
+    _resolveTask(task, payload) {
+        const { resolve, type } = task;
+        if (type === "moves") {
+            resolve(payload.moves || []);
+        } else if (type === "apply") {
+            resolve(payload.fen);
+        } else if (type === "parse_pgn") {
+            resolve(payload.game || null);
+        } else if (type === "write_pgn") {
+            resolve(payload.pgn || "");
+        } else if (type === "san") {
+            resolve(payload.san || "");
+        } else if (type === "san_to_uci") {
+            resolve(payload.uci || "");
+        } else if (type === "status") {
+            resolve(payload.status || null);
+        } else if (type.startsWith("engine_")) {
+            resolve(payload.engineId);
+        } else if (type === "threads") {
+            resolve(payload.threads || 1);
+        } else if (type === "skill_levels") {
+            resolve(payload.levels || []);
+        } else if (type === "perft" || type.startsWith("syzygy_")) {
+            resolve(payload);
+        } else if (type === "book_probe") {
+            resolve(payload.moves || []);
+        } else if (type === "book_load" || type === "book_clear") {
+            resolve(payload.entries || 0);
+        } else if (type === "search" || type === "solve_mate") {
+            resolve(payload);
+        } else {
+            console.warn("ValidMovesEngine: unbekannter pending-Typ:", type, payload);
+            resolve(payload);
+        }
+    }
+
     /** Legt einen Task an und stellt Reihenfolge über die interne Queue sicher. */
     _enqueue(type, message, options = {}) {
         return new Promise((resolve, reject) => {
@@ -90,8 +142,15 @@ export default class ValidMovesEngine {
                 message,
                 resolve,
                 reject,
-                onProgress: typeof options.onProgress === "function" ? options.onProgress : null
+                onProgress: typeof options.onProgress === "function" ? options.onProgress : null,
+                onInfo: typeof options.onInfo === "function" ? options.onInfo : null,
+                // Ponder-/Analysesuchen enden erst über `ponderHit`/`stopSearch`.
+                long: message.infinite === true || message.ponder === true
             };
+            if (QUICK_TASKS.has(type) && this.pending && this.pending.long) {
+                this._enqueueSide(task);
+                return;
+            }
             if (this.pending) {
                 this.queue.push(task);
                 return;
@@ -102,11 +161,18 @@ export default class ValidMovesEngine {
 
     /** Dispatcht den Task direkt an den Worker und setzt ihn auf "pending". */
     _dispatch(task) {
+        // Stop-Wunsch gilt nur für die Suche, die bei `stopSearch` lief.
+        if ((task.type === "search" || task.type === "solve_mate") && this.stopFlag) {
+            Atomics.store(this.stopFlag, 0, 0);
+            Atomics.store(this.stopFlag, 1, 0);
+        }
         this.pending = {
             resolve: task.resolve,
             reject: task.reject,
             type: task.type,
-            onProgress: task.onProgress
+            onProgress: task.onProgress,
+            onInfo: task.onInfo,
+            long: task.long
         };
         try {
             this.worker.postMessage(task.message);
@@ -128,6 +194,48 @@ export default class ValidMovesEngine {
         }
     }
 
+    /** Reiht einen schnellen Task beim Neben-Worker ein (legt ihn bei Bedarf an). */
+    _enqueueSide(task) {
+        if (!this.side) {
+            const side = { worker: new Worker(this.workerUrl, { type: "module" }), pending: null, queue: [] };
+            side.worker.onmessage = (e) => {
+                const current = side.pending;
+                side.pending = null;
+                if (current) {
+                    this._resolveTask(current, e.data || {});
+                }
+                this._drainSide();
+            };
+            side.worker.onerror = (err) => {
+                console.error("ValidMovesEngine: Neben-Worker onerror", err.message);
+                const tasks = side.pending ? [side.pending, ...side.queue] : side.queue;
+                side.pending = null;
+                side.queue = [];
+                tasks.forEach((t) => t.reject(err));
+            };
+            this.side = side;
+        }
+        this.side.queue.push(task);
+        this._drainSide();
+    }
+
+    /** Startet den nächsten Task des Neben-Workers, sobald er frei ist. */
+    _drainSide() {
+        const side = this.side;
+        if (!side || side.pending || side.queue.length === 0) {
+            return;
+        }
+        const next = side.queue.shift();
+        side.pending = next;
+        try {
+            side.worker.postMessage(next.message);
+        } catch (err) {
+            side.pending = null;
+            next.reject(err);
+            this._drainSide();
+        }
+    }
+
     /** API: legale Zielfelder für eine Startposition abrufen. */
     getValidMoves(fen, field) {
         if (!this.worker) {
@@ -158,6 +266,218 @@ export default class ValidMovesEngine {
         });
     }
 
+    /** API: UCI-Zug (z. B. `e7e8q`) als SAN (`e8=Q+`); leer bei illegalem Zug. */
+    moveToSan(fen, uci) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("san", {
+            action: "san",
+            fen,
+            uci
+        });
+    }
+
+    /** API: SAN-Zug als UCI-Zug; leer bei illegalem oder mehrdeutigem Zug. */
+    sanToUci(fen, san) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("san_to_uci", {
+            action: "san_to_uci",
+            fen,
+            san
+        });
+    }
+
+    /**
+     * API: PGN laden. Liefert `{ tags, start_fen, moves, fens, result }` (Züge in UCI)
+     * oder `{ error }`.
+     */
+    parsePgn(pgn) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("parse_pgn", {
+            action: "parse_pgn",
+            pgn
+        });
+    }
+
+    /**
+     * API: PGN aus Start-FEN und UCI-Zügen erzeugen.
+     * `tags` sind PGN-Tag-Zeilen, z. B. `[White "Alice"]\n[Black "Bob"]`.
+     */
+    writePgn(fen, moves = [], tags = "") {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("write_pgn", {
+            action: "write_pgn",
+            fen,
+            moves,
+            tags
+        });
+    }
+
+    /**
+     * API: Partiestatus (Matt, Patt, Remisregeln, tote Stellung) aus der Engine.
+     * `history` sind die FENs vor `fen`, zeilengetrennt wie bei `search`.
+     */
+    gameStatus(fen, history = "") {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("status", {
+            action: "status",
+            fen,
+            history
+        });
+    }
+
+    /**
+     * API: Beweist "Matt in `moves`" für die Seite am Zug.
+     * Liefert `{ mate_in, line, complete, searched, nodes, time_ms }`; `mate_in === null`
+     * mit `complete === true` heißt: definitiv kein Matt. Abbrechbar über `stopSearch`.
+     */
+    solveMate(fen, moves, timeMs = 0, ttMb = 16) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("solve_mate", {
+            action: "solve_mate",
+            fen,
+            moves,
+            timeMs,
+            ttMb
+        });
+    }
+
+    /** API: Spielstärke-Stufen mit ungefährer Elo, `[{ level, elo }, ...]`. */
+    skillLevels() {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("skill_levels", {
+            action: "skill_levels"
+        });
+    }
+
+    /**
+     * API: Verwaltung einer Engine-Instanz (`meta.engineId` bei `search`). Jede Instanz hat
+     * eigene TT und Heuristiken; sie entsteht bei der ersten Suche bzw. bei `setHashSize`.
+     * `newGame` verwirft alles Gelernte, `clearHash` nur die TT, `freeEngine` gibt sie frei.
+     */
+    newGame(engineId) {
+        return this._engineAction("engine_new_game", engineId);
+    }
+
+    clearHash(engineId) {
+        return this._engineAction("engine_clear_hash", engineId);
+    }
+
+    setHashSize(engineId, ttMb) {
+        return this._engineAction("engine_set_hash", engineId, { ttMb });
+    }
+
+    freeEngine(engineId) {
+        return this._engineAction("engine_free", engineId);
+    }
+
+    /**
+     * API: Such-Threads (Lazy SMP) inklusive Haupt-Thread für alle folgenden Suchen. Mehr als
+     * einer läuft nur mit dem Thread-Build der Engine auf einer cross-origin-isolierten Seite;
+     * Antwort: tatsächlich genutzte Threads.
+     */
+    setThreads(threads) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("threads", { action: "threads", threads });
+    }
+
+    /** Gemeinsamer Pfad der Instanz-Aktionen; läuft in Reihenfolge nach laufenden Suchen. */
+    _engineAction(action, engineId, extra = {}) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue(action, { action, engineId: String(engineId || ""), ...extra });
+    }
+
+    /**
+     * API: Polyglot-Eröffnungsbuch (`.bin` als ArrayBuffer) laden; es hat bei Suchen mit
+     * `bookEnabled` Vorrang vor den JSON-Büchern. Antwort: Anzahl der Einträge.
+     */
+    loadPolyglotBook(buffer) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("book_load", {
+            action: "book_load",
+            buffer
+        });
+    }
+
+    /** API: Polyglot-Buch entladen (danach wieder nur die JSON-Bücher). */
+    clearPolyglotBook() {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("book_clear", {
+            action: "book_clear"
+        });
+    }
+
+    /** API: Alle Buchzüge des Polyglot-Buchs für `fen`, `[{ uci, weight, learn }, ...]`. */
+    probeBook(fen) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("book_probe", {
+            action: "book_probe",
+            fen
+        });
+    }
+
+    /**
+     * API: Syzygy-Endspieldatenbanken für alle folgenden Suchen laden. `files` ist eine Liste
+     * `{ name, buffer }` mit Dateinamen wie `KRvK.rtbw` und ArrayBuffer-Inhalt; die Buffer
+     * werden in den Worker kopiert. Antwort: `{ added, wdl, dtz, max_pieces }`.
+     */
+    loadSyzygy(files) {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("syzygy_add", {
+            action: "syzygy_add",
+            files: Array.from(files || [], (f) => ({ name: f.name, buffer: f.buffer }))
+        });
+    }
+
+    /** API: Alle geladenen Syzygy-Tabellen verwerfen. */
+    clearSyzygy() {
+        if (!this.worker) {
+            return Promise.reject(new Error("Worker nicht initialisiert"));
+        }
+
+        return this._enqueue("syzygy_clear", {
+            action: "syzygy_clear"
+        });
+    }
+
     /** API: Perft-Berechnung (Knotenanzahl) für eine Position. */
     perft(fen, depth = 1) {
         if (!this.worker) {
@@ -171,7 +491,20 @@ export default class ValidMovesEngine {
         });
     }
 
-    /** API: Engine-Suche mit optionalem Progress-Callback. */
+    /**
+     * API: Engine-Suche mit optionalen Callbacks `onProgress` (Timebar) und `onInfo`
+     * (depth, seldepth, score, mate, bound, best, pv, nodes, nps, hashfull, tbhits je Iteration).
+     * `meta.skillLevel` (0..20, siehe `skillLevels`) schwächt die Suche; ohne Angabe volle Stärke.
+     * `meta.ponder` startet eine Ponder-Suche: ohne Zeitlimit bis `ponderHit`, danach mit `timeMs`.
+     * `meta.clock` (`{ wtime, btime, winc, binc, movestogo }` in ms) ersetzt `timeMs` durch das
+     * Zeitmanagement der Engine.
+     * `meta.nodes` (Knotenlimit) bzw. `meta.deterministic` liefern reproduzierbare Ergebnisse:
+     * kein Zeitlimit, frische TT/Heuristiken, kein Eröffnungsbuch.
+     * `meta.searchMoves` (UCI-Züge) beschränkt die Suche auf diese Wurzelzüge; mit
+     * `meta.scoreEach` enthält `lines` je Zug eine eigene Bewertung samt PV.
+     * `meta.engineId` sucht mit einer eigenen Engine-Instanz (siehe `newGame`) statt der
+     * gemeinsamen Standard-Engine des Workers.
+     */
     search(fen, depth = 4, timeMs = 0, ttMb = 0, history = "", bookMeta = null) {
         if (!this.worker) {
             return Promise.reject(new Error("Worker nicht initialisiert"));
@@ -183,6 +516,23 @@ export default class ValidMovesEngine {
         const uciHistory = typeof meta.uciHistory === "string" ? meta.uciHistory : "";
         const debugRootEval = meta.debugRootEval === true;
         const onProgress = typeof meta.onProgress === "function" ? meta.onProgress : null;
+        const onInfo = typeof meta.onInfo === "function" ? meta.onInfo : null;
+        const multiPv = Number.isFinite(meta.multiPv) ? meta.multiPv : 1;
+        const skillLevel = Number.isFinite(meta.skillLevel) ? meta.skillLevel : -1;
+        const infinite = meta.infinite === true;
+        if (infinite && !this.canStop) {
+            return Promise.reject(new Error("Unendliche Analyse benötigt SharedArrayBuffer (Cross-Origin-Isolation)"));
+        }
+        const ponder = meta.ponder === true;
+        if (ponder && !this.canStop) {
+            return Promise.reject(new Error("Pondern benötigt SharedArrayBuffer (Cross-Origin-Isolation)"));
+        }
+        const clock = meta.clock && typeof meta.clock === "object" ? meta.clock : null;
+        const nodes = Number.isFinite(meta.nodes) && meta.nodes > 0 ? Math.floor(meta.nodes) : 0;
+        const deterministic = meta.deterministic === true;
+        const searchMoves = Array.isArray(meta.searchMoves) ? meta.searchMoves : [];
+        const scoreEach = meta.scoreEach === true;
+        const engineId = typeof meta.engineId === "string" ? meta.engineId : "";
 
         return this._enqueue("search", {
             action: "search",
@@ -194,8 +544,52 @@ export default class ValidMovesEngine {
             gameId,
             bookEnabled,
             uciHistory,
-            debugRootEval
-        }, { onProgress });
+            debugRootEval,
+            multiPv,
+            skillLevel,
+            infinite,
+            ponder,
+            clock,
+            nodes,
+            deterministic,
+            searchMoves,
+            scoreEach,
+            engineId
+        }, { onProgress, onInfo });
+    }
+
+    /** Ob laufende Suchen per `stopSearch` abgebrochen werden können. */
+    get canStop() {
+        return this.stopFlag !== null;
+    }
+
+    /**
+     * API: Bricht die laufende Suche ab; ihr Promise liefert den Bestzug der letzten
+     * abgeschlossenen Iteration. Wartende Suchen in der Queue bleiben unberührt.
+     */
+    stopSearch() {
+        const stoppable = this.pending && (this.pending.type === "search" || this.pending.type === "solve_mate");
+        if (!this.stopFlag || !stoppable) {
+            return false;
+        }
+        Atomics.store(this.stopFlag, 0, 1);
+        // Weckt eine Suche, die nach der Maximaltiefe auf den Stop wartet.
+        Atomics.notify(this.stopFlag, 0);
+        return true;
+    }
+
+    /**
+     * API: Ponderhit für die laufende Ponder-Suche; sie läuft mit ihrem Zeitlimit weiter
+     * und ihr Promise liefert dann den Zug für die Partie.
+     */
+    ponderHit() {
+        if (!this.stopFlag || !this.pending || this.pending.type !== "search") {
+            return false;
+        }
+        Atomics.store(this.stopFlag, 1, 1);
+        // Der wartende Worker schläft auf Index 0 (siehe `__engine_wait_stop`).
+        Atomics.notify(this.stopFlag, 0);
+        return true;
     }
 
     /** Worker explizit beenden, z. B. bei Cleanup/Hot-Reload. */
@@ -204,5 +598,9 @@ export default class ValidMovesEngine {
             this.worker.terminate();
             this.worker = null;
         }
+        if (this.side) {
+            this.side.worker.terminate();
+            this.side = null;
+        }
     }
 }
@@ -0,0 +1,134 @@
+// =====================================================================================
+// Eröffnungsbuch-Generator (Kommandozeile)
+// -------------------------------------------------------------------------------------
+// Liest PGN-Dateien, sammelt die Zugstatistik der ersten Halbzüge und schreibt das
+// history-basierte JSON der Browser-Bücher, ein Polyglot-Buch (`.bin`) und/oder ein
+// stellungsbasiertes Buch (`PositionBook`). Eingaben mit Endung `.json` sind fertige
+// JSON-Bücher und gehen nur in das stellungsbasierte Buch ein.
+//
+//   book-builder [Optionen] partien1.pgn [partien2.pgn ...] [buch.json ...]
+//     --json <datei>        JSON-Buch schreiben (Format wie `opening_book_1000.json`)
+//     --bin <datei>         Polyglot-Buch schreiben
+//     --positions <datei>   stellungsbasiertes Buch schreiben (für `positionbook_load` im Worker)
+//     --max-ply <n>         nur die ersten n Halbzüge (Standard 20)
+//     --min-games <n>       Zug muss in mindestens n Partien vorkommen (Standard 2)
+//     --min-share <x>       Zug muss mindestens diesen Anteil seiner Stellung haben (0..1)
+//     --score-depth <n>     Buchzüge mit Suchtiefe n bewerten (Standard 0 = aus)
+//     --score-margin <cp>   bewertete Züge mehr als cp hinter dem besten verwerfen (Standard 100)
+// =====================================================================================
+
+use std::process::ExitCode;
+
+use chess_engine::{BookBuilder, BookBuilderOptions, PositionBook, SystemClock};
+
+struct Args {
+    json: Option<String>,
+    bin: Option<String>,
+    positions: Option<String>,
+    options: BookBuilderOptions,
+    inputs: Vec<String>,
+}
+
+const USAGE: &str = "usage: book-builder [--json out.json] [--bin out.bin] [--positions out.cpb] [--max-ply N] \
+                     [--min-games N] [--min-share X] [--score-depth N] [--score-margin CP] games.pgn|book.json...";
+
+fn parse_args(mut raw: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args =
+        Args { json: None, bin: None, positions: None, options: BookBuilderOptions::default(), inputs: Vec::new() };
+    while let Some(arg) = raw.next() {
+        if !arg.starts_with("--") {
+            args.inputs.push(arg);
+            continue;
+        }
+        let value = raw.next().ok_or_else(|| format!("{} braucht einen Wert", arg))?;
+        let bad = || format!("ungültiger Wert für {}: {}", arg, value);
+        match arg.as_str() {
+            "--json" => args.json = Some(value.clone()),
+            "--bin" => args.bin = Some(value.clone()),
+            "--positions" => args.positions = Some(value.clone()),
+            "--max-ply" => args.options.max_ply = value.parse().map_err(|_| bad())?,
+            "--min-games" => args.options.min_games = value.parse().map_err(|_| bad())?,
+            "--min-share" => args.options.min_share = value.parse().map_err(|_| bad())?,
+            "--score-depth" => args.options.score_depth = value.parse().map_err(|_| bad())?,
+            "--score-margin" => args.options.score_margin = value.parse().map_err(|_| bad())?,
+            _ => return Err(format!("unbekannte Option {}", arg)),
+        }
+    }
+    if args.inputs.is_empty() {
+        return Err("keine PGN-Datei angegeben".to_string());
+    }
+    if args.json.is_none() && args.bin.is_none() && args.positions.is_none() {
+        return Err("--json, --bin und/oder --positions angeben".to_string());
+    }
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut builder = BookBuilder::new(args.options.clone());
+    let mut positions = PositionBook::default();
+    for path in &args.inputs {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        };
+        if path.ends_with(".json") {
+            match positions.merge_history_json(&String::from_utf8_lossy(&bytes)) {
+                Ok(merged) => eprintln!("{}: {} Buchstellungen", path, merged),
+                Err(err) => {
+                    eprintln!("{}: {}", path, err);
+                    return ExitCode::FAILURE;
+                }
+            }
+            continue;
+        }
+        // PGN-Sammlungen sind oft Latin-1; Namen in Tags sind für das Buch egal.
+        let (added, failed) = builder.add_pgn(&String::from_utf8_lossy(&bytes));
+        eprintln!("{}: {} Partien, {} fehlerhaft übersprungen", path, added, failed);
+    }
+
+    if args.options.score_depth > 0 {
+        eprintln!("bewerte Buchzüge mit Tiefe {} ...", args.options.score_depth);
+        builder.score_moves(&SystemClock);
+    }
+
+    if let Some(path) = &args.json {
+        if let Err(err) = std::fs::write(path, builder.to_history_json()) {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+    }
+    if let Some(path) = &args.bin {
+        let bytes = builder.to_polyglot();
+        if let Err(err) = std::fs::write(path, &bytes) {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("{}: {} Einträge", path, bytes.len() / 16);
+    }
+    if let Some(path) = &args.positions {
+        if builder.games() > 0 {
+            // Das eigene JSON ist immer gültig.
+            positions.merge_history_json(&builder.to_history_json()).expect("book-builder JSON");
+        }
+        if let Err(err) = std::fs::write(path, positions.to_bytes()) {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("{}: {} Stellungen, {} Einträge", path, positions.positions(), positions.len());
+    }
+    if args.inputs.iter().any(|path| !path.ends_with(".json")) {
+        eprintln!("{} Partien, {} Buchstellungen", builder.games(), builder.positions());
+    }
+    ExitCode::SUCCESS
+}
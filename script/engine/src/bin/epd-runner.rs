@@ -0,0 +1,135 @@
+// =====================================================================================
+// EPD-Testsuiten-Runner
+// -------------------------------------------------------------------------------------
+// Sucht jede Stellung einer EPD-Suite (WAC, STS, ECM, ...) mit festem Budget und meldet
+// je Stellung den gefundenen Zug, ob er `bm`/`am` erfüllt, die Time-to-Solve und die
+// Punkte; am Ende gelöste Stellungen und die STS-Punktsumme.
+//
+//   epd-runner [Optionen] suite.epd [weitere.epd ...]
+//     --time <ms>      Zeit je Stellung (Standard 1000, wenn weder --nodes noch --depth)
+//     --nodes <n>      Knotenbudget je Stellung
+//     --depth <n>      feste Suchtiefe
+//     --hash <mb>      TT-Größe (Standard 64)
+//     --threads <n>    Such-Threads (Lazy SMP)
+//     --fresh          TT und Heuristiken vor jeder Stellung leeren
+// =====================================================================================
+
+use std::process::ExitCode;
+
+use chess_engine::{parse_epd_suite, solve_epd, Engine, SearchLimits, SystemClock, MAX_THREADS, MAX_TT_MB};
+
+const DEFAULT_TIME_MS: u32 = 1000;
+const DEFAULT_HASH_MB: u32 = 64;
+
+struct Args {
+    limits: SearchLimits,
+    hash_mb: u32,
+    fresh: bool,
+    inputs: Vec<String>,
+}
+
+const USAGE: &str =
+    "usage: epd-runner [--time MS] [--nodes N] [--depth N] [--hash MB] [--threads N] [--fresh] suite.epd...";
+
+fn parse_args(mut raw: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args =
+        Args { limits: SearchLimits::default(), hash_mb: DEFAULT_HASH_MB, fresh: false, inputs: Vec::new() };
+    while let Some(arg) = raw.next() {
+        if arg == "--fresh" {
+            args.fresh = true;
+            continue;
+        }
+        if !arg.starts_with("--") {
+            args.inputs.push(arg);
+            continue;
+        }
+        let value = raw.next().ok_or_else(|| format!("{} braucht einen Wert", arg))?;
+        let bad = || format!("ungültiger Wert für {}: {}", arg, value);
+        match arg.as_str() {
+            "--time" => args.limits.time_ms = value.parse().map_err(|_| bad())?,
+            "--nodes" => args.limits.nodes = value.parse().map_err(|_| bad())?,
+            "--depth" => args.limits.depth = value.parse().map_err(|_| bad())?,
+            "--hash" => args.hash_mb = value.parse::<u32>().map_err(|_| bad())?.clamp(1, MAX_TT_MB),
+            "--threads" => args.limits.threads = value.parse::<u32>().map_err(|_| bad())?.clamp(1, MAX_THREADS),
+            _ => return Err(format!("unbekannte Option {}", arg)),
+        }
+    }
+    if args.inputs.is_empty() {
+        return Err("keine EPD-Datei angegeben".to_string());
+    }
+    if args.limits.time_ms == 0 && args.limits.nodes == 0 && args.limits.depth == 0 {
+        args.limits.time_ms = DEFAULT_TIME_MS;
+    }
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut records = Vec::new();
+    for path in &args.inputs {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        };
+        for (index, record) in parse_epd_suite(&text).into_iter().enumerate() {
+            match record {
+                Ok(record) => records.push(record),
+                Err(err) => eprintln!("{} Stellung {}: {}", path, index + 1, err),
+            }
+        }
+    }
+
+    let mut engine = Engine::new(args.hash_mb);
+    let (mut solved, mut points, mut max_points) = (0usize, 0u32, 0u32);
+    let mut solve_times = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        if args.fresh {
+            engine.new_game();
+        }
+        let outcome = solve_epd(&mut engine, record, &args.limits, &SystemClock);
+        let expected = if record.best_moves.is_empty() {
+            format!("am {}", record.avoid_moves.join(" "))
+        } else {
+            format!("bm {}", record.best_moves.join(" "))
+        };
+        let tts = match (outcome.solve_time_ms, outcome.solve_depth) {
+            (Some(ms), Some(depth)) => format!("{} ms (d{}, {} nodes)", ms, depth, outcome.solve_nodes.unwrap_or(0)),
+            _ => "-".to_string(),
+        };
+        println!(
+            "{:>4} {:<16} {:<20} found {:<6} {:<4} {:>2}/{:<2} tts {}",
+            i + 1,
+            record.id.as_deref().unwrap_or("-"),
+            expected,
+            outcome.best.as_deref().unwrap_or("-"),
+            if outcome.solved { "ok" } else { "FAIL" },
+            outcome.points,
+            record.max_points(),
+            tts,
+        );
+        solved += usize::from(outcome.solved);
+        points += outcome.points;
+        max_points += record.max_points();
+        solve_times.extend(outcome.solve_time_ms);
+    }
+
+    let total = records.len().max(1);
+    println!();
+    println!("solved {}/{} ({:.1}%)", solved, records.len(), 100.0 * solved as f64 / total as f64);
+    println!("points {}/{} ({:.1}%)", points, max_points, 100.0 * points as f64 / max_points.max(1) as f64);
+    if !solve_times.is_empty() {
+        let avg = solve_times.iter().sum::<u64>() as f64 / solve_times.len() as f64;
+        println!("average time-to-solve {:.0} ms", avg);
+    }
+    ExitCode::SUCCESS
+}
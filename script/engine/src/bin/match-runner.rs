@@ -0,0 +1,248 @@
+// =====================================================================================
+// Selbstspiel-Match mit SPRT
+// -------------------------------------------------------------------------------------
+// Lässt zwei Konfigurationen der Engine gegeneinander spielen und entscheidet per SPRT,
+// ob A mindestens `elo1` stärker ist als B (H1) oder höchstens `elo0` (H0). Jede Eröffnung
+// wird zweimal mit getauschten Farben gespielt; nach jeder Partie erscheinen Stand,
+// Elo-Schätzung (±95%) und LLR mit Grenzen.
+//
+//   match-runner [Optionen]
+//     --a key=value    Einstellung für A (mehrfach): hash, threads, skill oder ein
+//     --b key=value    Tuning-Parameter aus `TUNE_PARAMS` (z. B. ASP_WINDOW=40)
+//     --tc BASE+INC    Partieuhr in ms (Standard 10000+100)
+//     --movetime <ms>  feste Zeit je Zug statt Partieuhr
+//     --nodes <n>      Knotenbudget je Zug
+//     --depth <n>      feste Suchtiefe je Zug
+//     --games <n>      Höchstzahl Partien (Standard 200)
+//     --openings FILE  Startstellungen (EPD/FEN je Zeile); sonst eingebaute Eröffnungen
+//     --sprt E0,E1     SPRT-Hypothesen in Elo (Standard 0,5)
+// =====================================================================================
+
+use std::process::ExitCode;
+
+use chess_engine::{
+    make_move_in_place, parse_epd, parse_fen, parse_uci_move, play_game, position_to_fen, GameResult,
+    MatchStats, Player, PlayerConfig, Sprt, SprtDecision, SystemClock, TimeControl, MAX_SKILL_LEVEL,
+    MAX_THREADS, MAX_TT_MB, START_FEN,
+};
+
+const DEFAULT_GAMES: u32 = 200;
+const DEFAULT_TC: TimeControl = TimeControl::Clock { base_ms: 10_000, inc_ms: 100 };
+
+// Kurze, ausgeglichene Eröffnungen (UCI ab Grundstellung), falls keine Datei angegeben ist.
+const OPENINGS: &[&str] = &[
+    "e2e4 e7e5 g1f3 b8c6 f1b5",
+    "e2e4 c7c5 g1f3 d7d6",
+    "e2e4 e7e6 d2d4 d7d5",
+    "e2e4 c7c6 d2d4 d7d5",
+    "d2d4 d7d5 c2c4 e7e6",
+    "d2d4 g8f6 c2c4 g7g6",
+    "d2d4 g8f6 c2c4 e7e6 g1f3",
+    "c2c4 e7e5 b1c3",
+    "g1f3 d7d5 g2g3",
+    "e2e4 e7e5 g1f3 g8f6",
+    "d2d4 d7d5 c2c4 c7c6",
+    "e2e4 d7d6 d2d4 g8f6 b1c3",
+];
+
+struct Args {
+    a: PlayerConfig,
+    b: PlayerConfig,
+    tc: TimeControl,
+    games: u32,
+    openings: Option<String>,
+    sprt: Sprt,
+}
+
+const USAGE: &str = "usage: match-runner [--a key=value]... [--b key=value]... \
+[--tc BASE+INC | --movetime MS | --nodes N | --depth N] [--games N] [--openings FILE] [--sprt E0,E1]";
+
+// `key=value` für eine Konfiguration; unbekannte Schlüssel sind ein Fehler.
+fn apply_setting(config: &mut PlayerConfig, setting: &str) -> Result<(), String> {
+    let (key, value) = setting.split_once('=').ok_or_else(|| format!("erwartet key=value: {}", setting))?;
+    let bad = || format!("ungültiger Wert für {}: {}", key, value);
+    match key.to_ascii_lowercase().as_str() {
+        "hash" => config.hash_mb = value.parse::<u32>().map_err(|_| bad())?.clamp(1, MAX_TT_MB),
+        "threads" => config.threads = value.parse::<u32>().map_err(|_| bad())?.clamp(1, MAX_THREADS),
+        "skill" => config.skill_level = Some(value.parse::<u32>().map_err(|_| bad())?.min(MAX_SKILL_LEVEL)),
+        _ => {
+            let value: i32 = value.parse().map_err(|_| bad())?;
+            if !config.tune.set(key, value) {
+                return Err(format!("unbekannter Parameter {}", key));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_args(mut raw: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Args {
+        a: PlayerConfig::new("A"),
+        b: PlayerConfig::new("B"),
+        tc: DEFAULT_TC,
+        games: DEFAULT_GAMES,
+        openings: None,
+        sprt: Sprt::new(0.0, 5.0),
+    };
+    while let Some(arg) = raw.next() {
+        let value = raw.next().ok_or_else(|| format!("{} braucht einen Wert", arg))?;
+        let bad = || format!("ungültiger Wert für {}: {}", arg, value);
+        match arg.as_str() {
+            "--a" => apply_setting(&mut args.a, &value)?,
+            "--b" => apply_setting(&mut args.b, &value)?,
+            "--tc" => {
+                let (base, inc) = value.split_once('+').unwrap_or((value.as_str(), "0"));
+                args.tc = TimeControl::Clock {
+                    base_ms: base.parse().map_err(|_| bad())?,
+                    inc_ms: inc.parse().map_err(|_| bad())?,
+                };
+            }
+            "--movetime" => args.tc = TimeControl::MoveTime(value.parse().map_err(|_| bad())?),
+            "--nodes" => args.tc = TimeControl::Nodes(value.parse().map_err(|_| bad())?),
+            "--depth" => args.tc = TimeControl::Depth(value.parse().map_err(|_| bad())?),
+            "--games" => args.games = value.parse().map_err(|_| bad())?,
+            "--openings" => args.openings = Some(value),
+            "--sprt" => {
+                let (e0, e1) = value.split_once(',').ok_or_else(bad)?;
+                let (e0, e1) = (e0.trim().parse().map_err(|_| bad())?, e1.trim().parse().map_err(|_| bad())?);
+                if e0 >= e1 {
+                    return Err(bad());
+                }
+                args.sprt = Sprt::new(e0, e1);
+            }
+            _ => return Err(format!("unbekannte Option {}", arg)),
+        }
+    }
+    if args.games == 0 {
+        return Err("--games muss mindestens 1 sein".to_string());
+    }
+    Ok(args)
+}
+
+// Startstellungen: Datei (EPD oder FEN je Zeile, `#` = Kommentar) oder eingebaute Eröffnungen.
+fn load_openings(path: Option<&str>) -> Result<Vec<String>, String> {
+    let Some(path) = path else {
+        return Ok(OPENINGS.iter().map(|line| play_opening(line)).collect());
+    };
+    let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    let mut fens = Vec::new();
+    for (index, line) in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')).enumerate() {
+        let fen = match parse_fen(line) {
+            Some(pos) => position_to_fen(&pos),
+            None => parse_epd(line).map_err(|err| format!("{} Stellung {}: {}", path, index + 1, err))?.fen,
+        };
+        fens.push(fen);
+    }
+    if fens.is_empty() {
+        return Err(format!("{}: keine Stellungen", path));
+    }
+    Ok(fens)
+}
+
+fn play_opening(moves: &str) -> String {
+    let mut pos = parse_fen(START_FEN).expect("Grundstellung");
+    for uci in moves.split_whitespace() {
+        let (mv, promo) = parse_uci_move(&mut pos, uci).expect("Eröffnungszug");
+        let _ = make_move_in_place(&mut pos, mv, promo);
+    }
+    position_to_fen(&pos)
+}
+
+fn describe(config: &PlayerConfig) -> String {
+    let mut parts = vec![format!("hash={}", config.hash_mb), format!("threads={}", config.threads)];
+    if let Some(level) = config.skill_level {
+        parts.push(format!("skill={}", level));
+    }
+    parts.extend(config.tune.changed().into_iter().map(|(name, value)| format!("{}={}", name, value)));
+    format!("{}: {}", config.name, parts.join(" "))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let openings = match load_openings(args.openings.as_deref()) {
+        Ok(openings) => openings,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{}", describe(&args.a));
+    println!("{}", describe(&args.b));
+    let (lower, upper) = args.sprt.bounds();
+    println!(
+        "SPRT elo0={} elo1={} alpha={} beta={} bounds [{:.2}, {:.2}]",
+        args.sprt.elo0, args.sprt.elo1, args.sprt.alpha, args.sprt.beta, lower, upper
+    );
+
+    let mut a = Player::new(args.a.clone());
+    let mut b = Player::new(args.b.clone());
+    let mut stats = MatchStats::default();
+    let mut decision = None;
+    for game in 0..args.games {
+        let fen = &openings[(game / 2) as usize % openings.len()];
+        let a_is_white = game % 2 == 0;
+        let record = if a_is_white {
+            play_game(&mut a, &mut b, fen, args.tc, &SystemClock)
+        } else {
+            play_game(&mut b, &mut a, fen, args.tc, &SystemClock)
+        };
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                eprintln!("Partie {}: {}", game + 1, err);
+                return ExitCode::FAILURE;
+            }
+        };
+        stats.record(record.result, a_is_white);
+        let result = match record.result {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+        };
+        println!(
+            "game {:>4} {} {:<7} {:<20} {:>3} plies | +{} ={} -{} | elo {:+.1} ±{:.1} | llr {:.2} [{:.2}, {:.2}]",
+            game + 1,
+            if a_is_white { "A-B" } else { "B-A" },
+            result,
+            record.reason,
+            record.moves.len(),
+            stats.wins,
+            stats.draws,
+            stats.losses,
+            stats.elo(),
+            stats.elo_error(),
+            stats.llr(args.sprt.elo0, args.sprt.elo1),
+            lower,
+            upper,
+        );
+        decision = args.sprt.decision(&stats);
+        if decision.is_some() {
+            break;
+        }
+    }
+
+    println!();
+    println!(
+        "{} games: +{} ={} -{}, score {:.1}%, elo {:+.1} ±{:.1}",
+        stats.games(),
+        stats.wins,
+        stats.draws,
+        stats.losses,
+        100.0 * stats.score(),
+        stats.elo(),
+        stats.elo_error()
+    );
+    match decision {
+        Some(SprtDecision::AcceptH1) => println!("SPRT: H1 accepted (A is at least {} Elo stronger)", args.sprt.elo1),
+        Some(SprtDecision::AcceptH0) => println!("SPRT: H0 accepted (A gains at most {} Elo)", args.sprt.elo0),
+        None => println!("SPRT: inconclusive after {} games", stats.games()),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,609 @@
+// =====================================================================================
+// UCI-Frontend
+// -------------------------------------------------------------------------------------
+// Native Binary, das die Engine über das UCI-Protokoll anspricht, damit sie in
+// cutechess-cli, Arena oder anderen GUIs laufen kann.
+//
+// Aufbau:
+// - Haupt-Thread liest stdin und beantwortet `uci`, `isready`, `stop`, `quit` sofort.
+// - Ein einziger Such-Thread besitzt die `Engine` und führt ihre Suchen aus. Er lebt über
+//   die ganze Sitzung, damit TT/Killer/History zwischen Zügen erhalten bleiben.
+// - Jedes `go` bekommt eigene AtomicBools für Stop und Ponderhit, die die Suche in
+//   `should_stop` liest. `stop` setzt die Stop-Flags aller noch nicht beendeten Suchen;
+//   ein späteres `go` kann so keinen Stop verschlucken, der einer früheren Suche galt.
+// - `go ponder` startet eine Ponder-Suche, `ponderhit` setzt ihr Ponderhit-Flag, ab dem
+//   die Suche mit ihrem Zeitbudget als normale Suche weiterläuft.
+// - `SyzygyPath` lädt Endspieldatenbanken; jede Suche bekommt sie über `SearchLimits`.
+// - `BookFile` lädt ein Polyglot-Buch; mit `OwnBook` antwortet `go` aus dem Buch, solange
+//   die Stellung darin steht.
+// - Die Tuning-Parameter aus `TUNE_PARAMS` erscheinen als Spin-Optionen unter ihrem Namen.
+// =====================================================================================
+
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chess_engine::{
+    generate_legal_moves, make_move_in_place, mate_in_moves, move_to_uci, parse_fen, parse_uci_move,
+    position_to_fen, skill_level_elo, skill_level_for_elo, solve_mate, BookSelection, Engine,
+    Color, GameClock, MateResult, PolyglotBook, ProgressSink, ScoreBound, SearchInfo, SearchLimits,
+    SystemClock, Tablebases, TuneValues, MAX_SKILL_LEVEL, MAX_THREADS, MAX_TT_MB, MOVE_OVERHEAD_MS,
+    START_FEN, TUNE_PARAMS,
+};
+
+const ENGINE_NAME: &str = "Curlov Chess Nova";
+const ENGINE_AUTHOR: &str = "Curlov";
+const DEFAULT_HASH_MB: u32 = 32;
+const MAX_MULTIPV: u32 = 64;
+// Knoten je Millisekunde, mit denen `Deterministic` Zeitvorgaben in ein Knotenlimit umrechnet.
+const DETERMINISTIC_NODES_PER_MS: u64 = 1000;
+
+// Auftrag an den Such-Thread.
+enum Job {
+    Search {
+        fen: String,
+        history: String,
+        limits: SearchLimits,
+    },
+    // `go mate N`: beweist ein Matt in höchstens `moves` Zügen.
+    Mate {
+        fen: String,
+        moves: u32,
+        limits: SearchLimits,
+    },
+    // Buchzug: wird ohne Suche als `bestmove` gemeldet.
+    BookMove(String),
+    NewGame,
+    ClearHash,
+    SetHash(u32),
+}
+
+// Geparste Parameter von `go`.
+#[derive(Default)]
+struct GoParams {
+    depth: u32,
+    movetime: u64,
+    wtime: Option<u64>,
+    btime: Option<u64>,
+    winc: u64,
+    binc: u64,
+    movestogo: Option<u64>,
+    nodes: u64,
+    mate: u32,
+    infinite: bool,
+    ponder: bool,
+    searchmoves: Vec<String>,
+}
+
+// Sitzungszustand des Frontends (aktuelle Stellung + Optionen).
+struct UciState {
+    fen: String,
+    // FENs der Stellungen vor `fen` (für Repetitionserkennung), zeilengetrennt
+    // wie bei `search_with_history`.
+    history: Vec<String>,
+    hash_mb: u32,
+    multipv: u32,
+    // Such-Threads (Lazy SMP) inklusive Haupt-Thread.
+    threads: u32,
+    // `Skill Level`; greift nur, wenn `UCI_LimitStrength` aus ist.
+    skill_level: u32,
+    limit_strength: bool,
+    elo: u32,
+    // Option `Deterministic`: reproduzierbare Suchen (nur `depth`/`nodes` begrenzen;
+    // `movetime` und Uhr werden zu Knoten, siehe `DETERMINISTIC_NODES_PER_MS`).
+    deterministic: bool,
+    // Geladene Syzygy-Tabellen (Option `SyzygyPath`).
+    tablebases: Option<Arc<Tablebases>>,
+    // Polyglot-Buch (Option `BookFile`), benutzt nur mit `OwnBook`.
+    book: Option<PolyglotBook>,
+    own_book: bool,
+    // Tuning-Parameter (Spin-Optionen aus `TUNE_PARAMS`), gehen mit jeder Suche mit.
+    tune: TuneValues,
+    // Stop-Flags der Suchen, die der Such-Thread noch hält (laufend oder in der Warteschlange).
+    stops: Vec<Arc<AtomicBool>>,
+    // Ponderhit-Flag der zuletzt gestarteten Suche.
+    ponderhit: Arc<AtomicBool>,
+    jobs: Sender<Job>,
+}
+
+fn main() {
+    let (jobs, rx) = mpsc::channel::<Job>();
+    let worker = thread::spawn(move || search_worker(rx));
+
+    let mut state = UciState {
+        fen: START_FEN.to_string(),
+        history: Vec::new(),
+        hash_mb: DEFAULT_HASH_MB,
+        multipv: 1,
+        threads: 1,
+        skill_level: MAX_SKILL_LEVEL,
+        limit_strength: false,
+        elo: skill_level_elo(MAX_SKILL_LEVEL),
+        deterministic: false,
+        tablebases: None,
+        book: None,
+        own_book: false,
+        tune: TuneValues::default(),
+        stops: Vec::new(),
+        ponderhit: Arc::new(AtomicBool::new(false)),
+        jobs,
+    };
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some(&cmd) = tokens.first() else { continue };
+        match cmd {
+            "uci" => {
+                println!("id name {}", ENGINE_NAME);
+                println!("id author {}", ENGINE_AUTHOR);
+                println!(
+                    "option name Hash type spin default {} min 1 max {}",
+                    DEFAULT_HASH_MB, MAX_TT_MB
+                );
+                println!("option name Threads type spin default 1 min 1 max {}", MAX_THREADS);
+                println!("option name MultiPV type spin default 1 min 1 max {}", MAX_MULTIPV);
+                println!("option name Clear Hash type button");
+                println!("option name Ponder type check default false");
+                println!("option name Deterministic type check default false");
+                println!("option name SyzygyPath type string default <empty>");
+                println!("option name OwnBook type check default false");
+                println!("option name BookFile type string default <empty>");
+                println!(
+                    "option name Skill Level type spin default {} min 0 max {}",
+                    MAX_SKILL_LEVEL, MAX_SKILL_LEVEL
+                );
+                println!("option name UCI_LimitStrength type check default false");
+                println!(
+                    "option name UCI_Elo type spin default {} min {} max {}",
+                    skill_level_elo(MAX_SKILL_LEVEL),
+                    skill_level_elo(0),
+                    skill_level_elo(MAX_SKILL_LEVEL)
+                );
+                for param in &TUNE_PARAMS {
+                    println!(
+                        "option name {} type spin default {} min {} max {}",
+                        param.name, param.default, param.min, param.max
+                    );
+                }
+                println!("uciok");
+            }
+            "isready" => println!("readyok"),
+            "ucinewgame" => {
+                state.fen = START_FEN.to_string();
+                state.history.clear();
+                let _ = state.jobs.send(Job::NewGame);
+            }
+            "position" => handle_position(&mut state, &tokens[1..]),
+            "go" => handle_go(&mut state, &tokens[1..]),
+            "stop" => stop_searches(&state),
+            "ponderhit" => state.ponderhit.store(true, Ordering::Relaxed),
+            "setoption" => handle_setoption(&mut state, &tokens[1..]),
+            "quit" => break,
+            _ => {}
+        }
+    }
+
+    stop_searches(&state);
+    drop(state.jobs);
+    let _ = worker.join();
+}
+
+// Such-Thread: arbeitet Aufträge sequenziell ab und schreibt `info`/`bestmove`.
+fn search_worker(rx: Receiver<Job>) {
+    let mut engine = Engine::new(DEFAULT_HASH_MB);
+    for job in rx {
+        match job {
+            Job::NewGame => engine.new_game(),
+            Job::ClearHash => engine.clear_hash(),
+            Job::SetHash(mb) => engine.set_hash_size(mb),
+            Job::BookMove(uci) => println!("bestmove {}", uci),
+            Job::Search { fen, history, limits } => {
+                let infinite = limits.infinite;
+                let ponder = limits.ponder;
+                let result = engine.search(&fen, &history, &limits, &SystemClock, &mut InfoPrinter);
+
+                // Bei `go infinite` darf `bestmove` erst nach `stop` kommen (beim Pondern nach
+                // `stop` oder `ponderhit`), auch wenn die Suche vorher fertig war.
+                let set = |flag: &Option<Arc<AtomicBool>>| flag.as_ref().is_some_and(|f| f.load(Ordering::Relaxed));
+                while (infinite || (ponder && !set(&limits.ponderhit))) && !set(&limits.stop) {
+                    thread::sleep(Duration::from_millis(2));
+                }
+
+                let ponder_move = result.as_ref().and_then(|r| r.pv.get(1).cloned());
+                let best = result
+                    .and_then(|r| r.best)
+                    .or_else(|| fallback_move(&fen))
+                    .unwrap_or_else(|| "0000".to_string());
+                match ponder_move {
+                    Some(reply) => println!("bestmove {} ponder {}", best, reply),
+                    None => println!("bestmove {}", best),
+                }
+            }
+            Job::Mate { fen, moves, limits } => {
+                let result = solve_mate(&fen, moves, &limits, &SystemClock, &mut ());
+                let best = match result {
+                    Some(r) => {
+                        print_mate(&r, moves);
+                        r.line.first().cloned()
+                    }
+                    None => None,
+                };
+                let best = best
+                    .or_else(|| fallback_move(&fen))
+                    .unwrap_or_else(|| "0000".to_string());
+                println!("bestmove {}", best);
+            }
+        }
+    }
+}
+
+// Was: Setzt die Stop-Flags aller Suchen, die der Such-Thread noch hält.
+// Warum: `stop` gilt der laufenden Suche, auch wenn schon ein weiteres `go` wartet.
+// Kosten: Ein Store je offener Suche.
+fn stop_searches(state: &UciState) {
+    for flag in &state.stops {
+        flag.store(true, Ordering::Relaxed);
+    }
+}
+
+// Meldet das Ergebnis von `go mate` als `info`-Zeile bzw. "kein Matt" als `info string`.
+fn print_mate(result: &MateResult, moves: u32) {
+    match result.mate_in {
+        Some(n) => println!(
+            "info depth {} score mate {} nodes {} time {} pv {}",
+            2 * n - 1,
+            n,
+            result.nodes,
+            result.time_ms,
+            result.line.join(" ")
+        ),
+        None if result.complete => println!("info string no mate in {}", moves),
+        None => println!("info string no mate in {} (searched {} moves)", moves, result.searched),
+    }
+}
+
+// Gibt jede abgeschlossene Iteration als UCI-`info`-Zeile aus.
+struct InfoPrinter;
+
+impl ProgressSink for InfoPrinter {
+    fn iteration(&mut self, info: &SearchInfo) {
+        print_info(info);
+    }
+}
+
+// Formatiert eine Iteration als UCI-`info`-Zeile, im Multi-PV-Modus eine Zeile
+// je Variante mit `multipv k`. Aspiration-Fehlschläge tragen `lowerbound`/`upperbound`.
+fn print_info(info: &SearchInfo) {
+    let bound = match info.bound {
+        ScoreBound::Exact => "",
+        ScoreBound::Lower => " lowerbound",
+        ScoreBound::Upper => " upperbound",
+    };
+    if info.lines.len() <= 1 {
+        println!(
+            "info depth {} seldepth {} score {}{} nodes {} nps {} hashfull {} tbhits {} time {} pv {}",
+            info.depth,
+            info.seldepth,
+            uci_score(info.score),
+            bound,
+            info.nodes,
+            info.nps,
+            info.hashfull,
+            info.tbhits,
+            info.time_ms,
+            info.pv.join(" ")
+        );
+        return;
+    }
+    for line in &info.lines {
+        println!(
+            "info depth {} seldepth {} multipv {} score {} nodes {} nps {} hashfull {} tbhits {} time {} pv {}",
+            line.depth,
+            info.seldepth,
+            line.multipv,
+            uci_score(line.score),
+            info.nodes,
+            info.nps,
+            info.hashfull,
+            info.tbhits,
+            info.time_ms,
+            line.pv.join(" ")
+        );
+    }
+}
+
+fn uci_score(score: i32) -> String {
+    match mate_in_moves(score) {
+        Some(n) => format!("mate {}", n),
+        None => format!("cp {}", score),
+    }
+}
+
+// Wird die Suche vor Abschluss der ersten Iteration gestoppt, gibt es keinen
+// Bestzug. UCI verlangt trotzdem einen legalen Zug, falls einer existiert.
+fn fallback_move(fen: &str) -> Option<String> {
+    let mut pos = parse_fen(fen)?;
+    generate_legal_moves(&mut pos)
+        .first()
+        .map(|&(mv, promo)| move_to_uci(mv, promo))
+}
+
+// `position [startpos | fen <fen>] [moves <m1> <m2> ...]`
+fn handle_position(state: &mut UciState, args: &[&str]) {
+    let moves_idx = args.iter().position(|&t| t == "moves");
+    let setup = &args[..moves_idx.unwrap_or(args.len())];
+    let base_fen = match setup.first() {
+        Some(&"startpos") => START_FEN.to_string(),
+        Some(&"fen") if setup.len() > 1 => setup[1..].join(" "),
+        _ => return,
+    };
+    let Some(mut pos) = parse_fen(&base_fen) else {
+        println!("info string invalid fen: {}", base_fen);
+        return;
+    };
+
+    let mut history = Vec::new();
+    if let Some(idx) = moves_idx {
+        for uci in &args[idx + 1..] {
+            let Some((mv, promo)) = parse_uci_move(&mut pos, uci) else {
+                println!("info string illegal move: {}", uci);
+                break;
+            };
+            history.push(position_to_fen(&pos));
+            let _ = make_move_in_place(&mut pos, mv, promo);
+        }
+    }
+
+    state.fen = position_to_fen(&pos);
+    state.history = history;
+}
+
+// Schlüsselwörter von `go`; beenden die Zugliste von `searchmoves`.
+const GO_KEYWORDS: [&str; 12] = [
+    "searchmoves", "ponder", "wtime", "btime", "winc", "binc", "movestogo", "depth", "nodes", "mate",
+    "movetime", "infinite",
+];
+
+// `go [searchmoves M..] [ponder] [depth N] [movetime N] [wtime N] [btime N] [winc N] [binc N] [movestogo N] [nodes N] [mate N] [infinite]`
+fn handle_go(state: &mut UciState, args: &[&str]) {
+    let mut go = GoParams::default();
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1).and_then(|v| v.parse::<u64>().ok());
+        match args[i] {
+            "depth" => go.depth = value.unwrap_or(0) as u32,
+            "movetime" => go.movetime = value.unwrap_or(0),
+            "wtime" => go.wtime = value,
+            "btime" => go.btime = value,
+            "winc" => go.winc = value.unwrap_or(0),
+            "binc" => go.binc = value.unwrap_or(0),
+            "movestogo" => go.movestogo = value,
+            "nodes" => go.nodes = value.unwrap_or(0),
+            "mate" => go.mate = value.unwrap_or(0) as u32,
+            "infinite" => {
+                go.infinite = true;
+                i += 1;
+                continue;
+            }
+            "ponder" => {
+                go.ponder = true;
+                i += 1;
+                continue;
+            }
+            // Alle folgenden Token bis zum nächsten Schlüsselwort sind Züge.
+            "searchmoves" => {
+                i += 1;
+                while i < args.len() && !GO_KEYWORDS.contains(&args[i]) {
+                    go.searchmoves.push(args[i].to_string());
+                    i += 1;
+                }
+                continue;
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+
+    // Ein `go` ohne jedes Limit (auch nur mit `searchmoves`) sucht wie `go infinite` bis `stop`.
+    let limited = go.depth > 0
+        || go.movetime > 0
+        || go.wtime.is_some()
+        || go.btime.is_some()
+        || go.nodes > 0
+        || go.mate > 0
+        || go.ponder;
+    go.infinite |= !limited;
+
+    // `movetime` ist ein festes Limit; sonst teilt das Zeitmanagement der Engine die Uhr ein.
+    let time_ms = if go.movetime > 0 && !go.infinite {
+        go.movetime.saturating_sub(MOVE_OVERHEAD_MS).max(1)
+    } else {
+        0
+    };
+    let game_clock = (go.wtime.is_some() || go.btime.is_some()).then(|| GameClock {
+        wtime: go.wtime.unwrap_or(0),
+        btime: go.btime.unwrap_or(0),
+        winc: go.winc,
+        binc: go.binc,
+        movestogo: go.movestogo.unwrap_or(0).min(u32::MAX as u64) as u32,
+    });
+
+    // Deterministisch zählt keine Uhr: Zeitvorgaben werden zu einem festen Knotenbudget.
+    let mut nodes = go.nodes;
+    if state.deterministic && nodes == 0 && !go.infinite {
+        let side = parse_fen(&state.fen).map_or(Color::White, |pos| pos.side_to_move);
+        let budget_ms = match game_clock {
+            _ if time_ms > 0 => time_ms,
+            Some(clock) => clock.limits(side).soft_ms,
+            None => 0,
+        };
+        nodes = budget_ms.saturating_mul(DETERMINISTIC_NODES_PER_MS);
+    }
+
+    if !(go.infinite || go.ponder || go.mate > 0 || state.deterministic) && go.searchmoves.is_empty() {
+        if let Some(uci) = book_move(state) {
+            let _ = state.jobs.send(Job::BookMove(uci));
+            return;
+        }
+    }
+    // Frische Flags je Suche; beendete Suchen halten ihre Flags nicht mehr.
+    let stop = Arc::new(AtomicBool::new(false));
+    state.stops.retain(|flag| Arc::strong_count(flag) > 1);
+    state.stops.push(Arc::clone(&stop));
+    state.ponderhit = Arc::new(AtomicBool::new(false));
+    let limits = SearchLimits {
+        depth: go.depth,
+        time_ms: time_ms.min(u32::MAX as u64) as u32,
+        nodes,
+        infinite: go.infinite,
+        tt_mb: state.hash_mb,
+        multipv: state.multipv,
+        stop: Some(stop),
+        skill_level: effective_skill_level(state),
+        ponder: go.ponder,
+        ponderhit: Some(Arc::clone(&state.ponderhit)),
+        game_clock,
+        searchmoves: go.searchmoves,
+        deterministic: state.deterministic,
+        seed: 0,
+        threads: state.threads,
+        tablebases: state.tablebases.clone(),
+        tune: state.tune,
+    };
+    let job = if go.mate > 0 {
+        Job::Mate { fen: state.fen.clone(), moves: go.mate, limits }
+    } else {
+        Job::Search {
+            fen: state.fen.clone(),
+            history: state.history.join("\n"),
+            limits,
+        }
+    };
+    let _ = state.jobs.send(job);
+}
+
+// Gewichteter Zufallszug aus dem Buch, falls `OwnBook` an ist und die Stellung darin steht.
+fn book_move(state: &UciState) -> Option<String> {
+    let book = state.book.as_ref().filter(|_| state.own_book)?;
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+    book.book_move(&state.fen, BookSelection::Weighted, seed)
+}
+
+// Stufe für die nächste Suche: `UCI_Elo` hat bei `UCI_LimitStrength` Vorrang vor
+// `Skill Level`; die Höchststufe heißt volle Stärke (`None`).
+fn effective_skill_level(state: &UciState) -> Option<u32> {
+    let level = if state.limit_strength {
+        skill_level_for_elo(state.elo)
+    } else {
+        state.skill_level
+    };
+    (level < MAX_SKILL_LEVEL).then_some(level)
+}
+
+// `setoption name <Name> [value <Wert>]`
+fn handle_setoption(state: &mut UciState, args: &[&str]) {
+    let value_idx = args.iter().position(|&t| t == "value");
+    let name = args
+        .get(1..value_idx.unwrap_or(args.len()))
+        .map(|parts| parts.join(" "))
+        .unwrap_or_default();
+    let value = value_idx.map(|idx| args[idx + 1..].join(" ")).unwrap_or_default();
+
+    match name.to_ascii_lowercase().as_str() {
+        "hash" => {
+            if let Ok(mb) = value.parse::<u32>() {
+                state.hash_mb = mb.clamp(1, MAX_TT_MB);
+                let _ = state.jobs.send(Job::SetHash(state.hash_mb));
+            }
+        }
+        "threads" => {
+            if let Ok(n) = value.parse::<u32>() {
+                state.threads = n.clamp(1, MAX_THREADS);
+            }
+        }
+        "multipv" => {
+            if let Ok(n) = value.parse::<u32>() {
+                state.multipv = n.clamp(1, MAX_MULTIPV);
+            }
+        }
+        "skill level" => {
+            if let Ok(level) = value.parse::<u32>() {
+                state.skill_level = level.min(MAX_SKILL_LEVEL);
+            }
+        }
+        "uci_limitstrength" => state.limit_strength = value.eq_ignore_ascii_case("true"),
+        "deterministic" => state.deterministic = value.eq_ignore_ascii_case("true"),
+        "uci_elo" => {
+            if let Ok(elo) = value.parse::<u32>() {
+                state.elo = elo.clamp(skill_level_elo(0), skill_level_elo(MAX_SKILL_LEVEL));
+            }
+        }
+        // Pondern steuert die GUI über `go ponder`; die Option dient nur der Ankündigung.
+        "ponder" => {}
+        "clear hash" => {
+            let _ = state.jobs.send(Job::ClearHash);
+        }
+        "syzygypath" => state.tablebases = load_tablebases(&value),
+        "ownbook" => state.own_book = value.eq_ignore_ascii_case("true"),
+        "bookfile" => state.book = load_book(&value),
+        // Tuning-Parameter (für SPSA-Tuner und `match-runner`-Gegenproben).
+        _ => match value.parse::<i32>() {
+            Ok(v) if state.tune.set(&name, v) => {}
+            _ => println!("info string unknown option: {}", name),
+        },
+    }
+}
+
+// Was: Lädt alle Tabellen aus `paths` (Verzeichnisse, getrennt durch `:` bzw. `;` unter
+// Windows); leer oder `<empty>` entlädt sie.
+// Kosten: Ein Verzeichnis-Scan; die Dateien selbst werden erst bei Proben gelesen.
+fn load_tablebases(paths: &str) -> Option<Arc<Tablebases>> {
+    let separator = if cfg!(windows) { ';' } else { ':' };
+    let mut tablebases = Tablebases::new();
+    for dir in paths.split(separator).map(str::trim).filter(|d| !d.is_empty() && *d != "<empty>") {
+        if let Err(err) = tablebases.add_directory(dir) {
+            println!("info string cannot read syzygy path {}: {}", dir, err);
+        }
+    }
+    if tablebases.wdl_count() == 0 {
+        return None;
+    }
+    println!(
+        "info string found {} wdl and {} dtz tablebases (up to {} pieces)",
+        tablebases.wdl_count(),
+        tablebases.dtz_count(),
+        tablebases.max_pieces()
+    );
+    Some(Arc::new(tablebases))
+}
+
+// Was: Lädt das Polyglot-Buch `path`; leer oder `<empty>` entlädt es.
+// Kosten: Liest die ganze Datei (16 Byte je Eintrag) in den Speicher.
+fn load_book(path: &str) -> Option<PolyglotBook> {
+    let path = path.trim();
+    if path.is_empty() || path == "<empty>" {
+        return None;
+    }
+    match PolyglotBook::open(path) {
+        Ok(book) if !book.is_empty() => {
+            println!("info string loaded book {} ({} entries)", path, book.len());
+            Some(book)
+        }
+        Ok(_) => {
+            println!("info string book {} is empty", path);
+            None
+        }
+        Err(err) => {
+            println!("info string cannot read book {}: {}", path, err);
+            None
+        }
+    }
+}
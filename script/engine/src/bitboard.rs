@@ -0,0 +1,572 @@
+// =====================================================================================
+// Bitboard-Grundlagen
+// -------------------------------------------------------------------------------------
+// Bit-Helfer (`bb`, `pop_lsb`, ...), Ray-/Springer-/Königstabellen als `const`,
+// Datei-/Reihen-/Freibauernmasken und die Slider-Angriffe per Ray-Clipping.
+// Keine Abhängigkeit auf `Position`: reine Funktionen über `u64`.
+// =====================================================================================
+
+#[inline]
+// Erzeugt ein Bitboard mit genau einem gesetzten Bit.
+// `sq` ist das Feld 0..63 (a1=0, h8=63).
+// Wird für schnelle Maskenbildung in allen Routinen genutzt.
+// Was: Führt `bb` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn bb(sq: u8) -> u64 {
+    1u64 << sq
+}
+
+#[inline]
+// Liefert den Index des niederwertigsten gesetzten Bits.
+// Erwartet x != 0, ansonsten Debug-Assertion.
+// Wird für Ray-Clipping und Bitboard-Iteration verwendet.
+// Was: Führt `lsb_idx` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn lsb_idx(x: u64) -> usize {
+    debug_assert!(x != 0);
+    x.trailing_zeros() as usize
+}
+
+#[inline]
+// Liefert den Index des höchstwertigsten gesetzten Bits.
+// Erwartet x != 0, ansonsten Debug-Assertion.
+// Hilfreich für das Abschneiden von Rays "nach hinten".
+// Was: Führt `msb_idx` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn msb_idx(x: u64) -> usize {
+    debug_assert!(x != 0);
+    (63 - x.leading_zeros()) as usize
+}
+
+#[inline]
+// Was: Führt `popcnt` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn popcnt(x: u64) -> i32 {
+    x.count_ones() as i32
+}
+
+#[inline]
+// Was: Führt `pop_lsb` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn pop_lsb(bb: &mut u64) -> u8 {
+    let lsb = *bb & bb.wrapping_neg();
+    let idx = lsb.trailing_zeros() as u8;
+    *bb ^= lsb;
+    idx
+}
+
+#[inline]
+// Was: Führt `mirror_sq` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn mirror_sq(sq: u8) -> u8 {
+    sq ^ 56
+}
+
+#[inline]
+// Was: Führt `shift_east` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn shift_east(bb: u64) -> u64 {
+    (bb << 1) & !FILE_A
+}
+
+#[inline]
+// Was: Führt `shift_west` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn shift_west(bb: u64) -> u64 {
+    (bb >> 1) & !FILE_H
+}
+
+// ----------------------
+// Ray-Generatoren (const)
+// ----------------------
+// Baut einen Ray nach Nord-Ost ab Feld `sq` (ohne Startfeld).
+// Alle Felder bis zum Brettrand sind enthalten.
+// Compile-time Funktion für Lookup-Tabellen.
+const fn ray_ne_from(sq: u8) -> u64 {
+    let mut r = (sq / 8) as i8;
+    let mut f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while r < 7 && f < 7 {
+        r += 1;
+        f += 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Nord-West ab Feld `sq` (ohne Startfeld).
+// Wird für Läufer/Queen-Angriffe genutzt.
+const fn ray_nw_from(sq: u8) -> u64 {
+    let mut r = (sq / 8) as i8;
+    let mut f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while r < 7 && f > 0 {
+        r += 1;
+        f -= 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Süd-Ost ab Feld `sq` (ohne Startfeld).
+// Für diagonale Angriffe der weißen/schwarzen Läufer.
+const fn ray_se_from(sq: u8) -> u64 {
+    let mut r = (sq / 8) as i8;
+    let mut f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while r > 0 && f < 7 {
+        r -= 1;
+        f += 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Süd-West ab Feld `sq` (ohne Startfeld).
+// Dient für diagonale Sliding-Angriffe.
+const fn ray_sw_from(sq: u8) -> u64 {
+    let mut r = (sq / 8) as i8;
+    let mut f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while r > 0 && f > 0 {
+        r -= 1;
+        f -= 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Norden (gleiche Datei) ab Feld `sq`.
+// Enthält alle Felder bis zur 8. Reihe, Startfeld ausgeschlossen.
+const fn ray_n_from(sq: u8) -> u64 {
+    let mut r = (sq / 8) as i8;
+    let f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while r < 7 {
+        r += 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Süden (gleiche Datei) ab Feld `sq`.
+// Enthält alle Felder bis zur 1. Reihe, Startfeld ausgeschlossen.
+const fn ray_s_from(sq: u8) -> u64 {
+    let mut r = (sq / 8) as i8;
+    let f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while r > 0 {
+        r -= 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Osten (gleiche Reihe) ab Feld `sq`.
+// Enthält alle Felder bis zur h-Linie.
+const fn ray_e_from(sq: u8) -> u64 {
+    let r = (sq / 8) as i8;
+    let mut f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while f < 7 {
+        f += 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// Baut einen Ray nach Westen (gleiche Reihe) ab Feld `sq`.
+// Enthält alle Felder bis zur a-Linie.
+const fn ray_w_from(sq: u8) -> u64 {
+    let r = (sq / 8) as i8;
+    let mut f = (sq % 8) as i8;
+    let mut ray = 0u64;
+    while f > 0 {
+        f -= 1;
+        ray |= 1u64 << ((r as u8) * 8 + (f as u8));
+    }
+    ray
+}
+
+// ---------------------------
+// Const-Tabellen
+// ---------------------------
+// Erstellt die Ray-Tabelle für Nord-Ost (pro Startfeld).
+// Wird einmalig als const-Array erzeugt.
+const fn build_ne() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_ne_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Nord-West (pro Startfeld).
+// Erlaubt schnellen Zugriff ohne Laufzeitkosten.
+const fn build_nw() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_nw_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Süd-Ost (pro Startfeld).
+// Grundlage für Sliding-Angriffe.
+const fn build_se() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_se_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Süd-West (pro Startfeld).
+// Wird für Läufer/Dame benötigt.
+const fn build_sw() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_sw_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Norden (pro Startfeld).
+// Für Turm/Queen-Angriffe entlang der Datei.
+const fn build_n() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_n_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Süden (pro Startfeld).
+// Gegenrichtung zu build_n.
+const fn build_s() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_s_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Osten (pro Startfeld).
+// Für horizontale Sliding-Züge.
+const fn build_e() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_e_from(i as u8);
+        i += 1;
+    }
+    a
+}
+// Erstellt die Ray-Tabelle für Westen (pro Startfeld).
+// Ergänzt die horizontalen Richtungen.
+const fn build_w() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = ray_w_from(i as u8);
+        i += 1;
+    }
+    a
+}
+
+const RAY_NE: [u64; 64] = build_ne();
+const RAY_NW: [u64; 64] = build_nw();
+const RAY_SE: [u64; 64] = build_se();
+const RAY_SW: [u64; 64] = build_sw();
+const RAY_N: [u64; 64] = build_n();
+const RAY_S: [u64; 64] = build_s();
+const RAY_E: [u64; 64] = build_e();
+const RAY_W: [u64; 64] = build_w();
+
+const KNIGHT_DELTAS: [(i8, i8); 8] = [
+    (-2, -1), (-2, 1),
+    (-1, -2), (-1, 2),
+    (1, -2),  (1, 2),
+    (2, -1),  (2, 1),
+];
+
+// Liefert alle Königsschläge von `sq` (max. 8 Nachbarfelder).
+// Wird als Lookup-Tabelle vorkalkuliert.
+const fn king_attack_from(sq: u8) -> u64 {
+    let r = (sq / 8) as i8;
+    let f = (sq % 8) as i8;
+    let mut attacks = 0u64;
+    let mut dr = -1;
+    while dr <= 1 {
+        let mut df = -1;
+        while df <= 1 {
+            if !(dr == 0 && df == 0) {
+                let rr = r + dr;
+                let ff = f + df;
+                if rr >= 0 && rr < 8 && ff >= 0 && ff < 8 {
+                    attacks |= 1u64 << ((rr as u8) * 8 + (ff as u8));
+                }
+            }
+            df += 1;
+        }
+        dr += 1;
+    }
+    attacks
+}
+
+// Liefert alle Springer-Schläge von `sq` (max. 8 Ziele).
+// Nutzt vordefinierte Deltas für Reihen/Dateien.
+const fn knight_attack_from(sq: u8) -> u64 {
+    let r = (sq / 8) as i8;
+    let f = (sq % 8) as i8;
+    let mut attacks = 0u64;
+    let mut i = 0;
+    while i < 8 {
+        let (dr, df) = KNIGHT_DELTAS[i];
+        let rr = r + dr;
+        let ff = f + df;
+        if rr >= 0 && rr < 8 && ff >= 0 && ff < 8 {
+            attacks |= 1u64 << ((rr as u8) * 8 + (ff as u8));
+        }
+        i += 1;
+    }
+    attacks
+}
+
+// Baut die komplette König-Table (64 Felder).
+// Zugriff später per KING_ATTACKS[index].
+const fn build_king() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = king_attack_from(i as u8);
+        i += 1;
+    }
+    a
+}
+
+// Baut die komplette Springer-Table (64 Felder).
+// Spart Laufzeitberechnungen pro Zug.
+const fn build_knight() -> [u64; 64] {
+    let mut a = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        a[i] = knight_attack_from(i as u8);
+        i += 1;
+    }
+    a
+}
+
+pub(crate) const KING_ATTACKS: [u64; 64] = build_king();
+pub(crate) const KNIGHT_ATTACKS: [u64; 64] = build_knight();
+
+// ---------------------------
+// Zusätzliche Masken / Flags
+// ---------------------------
+pub(crate) const FILE_A: u64 = 0x0101_0101_0101_0101;
+pub(crate) const FILE_H: u64 = 0x8080_8080_8080_8080;
+
+#[allow(dead_code)]
+const RANK_1: u64 = 0x0000_0000_0000_00FF;
+pub(crate) const RANK_2: u64 = 0x0000_0000_0000_FF00;
+pub(crate) const RANK_3: u64 = 0x0000_0000_00FF_0000;
+pub(crate) const RANK_4: u64 = 0x0000_0000_FF00_0000;
+pub(crate) const RANK_5: u64 = 0x0000_00FF_0000_0000;
+pub(crate) const RANK_6: u64 = 0x0000_FF00_0000_0000;
+pub(crate) const RANK_7: u64 = 0x00FF_0000_0000_0000;
+#[allow(dead_code)]
+const RANK_8: u64 = 0xFF00_0000_0000_0000;
+
+const fn build_file_masks() -> [u64; 8] {
+    let mut a = [0u64; 8];
+    let mut f = 0;
+    while f < 8 {
+        a[f] = FILE_A << f;
+        f += 1;
+    }
+    a
+}
+
+const fn build_adj_file_masks() -> [u64; 8] {
+    let mut a = [0u64; 8];
+    let mut f = 0;
+    while f < 8 {
+        let mut mask = 0u64;
+        if f > 0 {
+            mask |= FILE_A << (f - 1);
+        }
+        if f < 7 {
+            mask |= FILE_A << (f + 1);
+        }
+        a[f] = mask;
+        f += 1;
+    }
+    a
+}
+
+pub(crate) const FILE_MASKS: [u64; 8] = build_file_masks();
+pub(crate) const ADJ_FILE_MASKS: [u64; 8] = build_adj_file_masks();
+
+const fn build_passed_masks_white() -> [u64; 64] {
+    let mut a = [0u64; 64];
+    let mut sq = 0;
+    while sq < 64 {
+        let file = (sq % 8) as i8;
+        let rank = (sq / 8) as i8;
+        let mut mask = 0u64;
+        let mut r = rank + 1;
+        while r <= 7 {
+            let mut f = file - 1;
+            while f <= file + 1 {
+                if f >= 0 && f <= 7 {
+                    let idx = (r as u8) * 8 + (f as u8);
+                    mask |= 1u64 << idx;
+                }
+                f += 1;
+            }
+            r += 1;
+        }
+        a[sq as usize] = mask;
+        sq += 1;
+    }
+    a
+}
+
+const fn build_passed_masks_black() -> [u64; 64] {
+    let mut a = [0u64; 64];
+    let mut sq = 0;
+    while sq < 64 {
+        let file = (sq % 8) as i8;
+        let rank = (sq / 8) as i8;
+        let mut mask = 0u64;
+        let mut r = rank - 1;
+        while r >= 0 {
+            let mut f = file - 1;
+            while f <= file + 1 {
+                if f >= 0 && f <= 7 {
+                    let idx = (r as u8) * 8 + (f as u8);
+                    mask |= 1u64 << idx;
+                }
+                f += 1;
+            }
+            r -= 1;
+        }
+        a[sq as usize] = mask;
+        sq += 1;
+    }
+    a
+}
+
+pub(crate) const PASSED_MASKS_WHITE: [u64; 64] = build_passed_masks_white();
+pub(crate) const PASSED_MASKS_BLACK: [u64; 64] = build_passed_masks_black();
+
+const fn build_king_zone() -> [u64; 64] {
+    let mut a = [0u64; 64];
+    let mut sq = 0;
+    while sq < 64 {
+        let mut zone = KING_ATTACKS[sq] | (1u64 << sq);
+        let ring = KING_ATTACKS[sq];
+        let mut i = 0;
+        while i < 64 {
+            if ((ring >> i) & 1) != 0 {
+                zone |= KING_ATTACKS[i];
+            }
+            i += 1;
+        }
+        a[sq] = zone;
+        sq += 1;
+    }
+    a
+}
+
+pub(crate) const KING_ZONE: [u64; 64] = build_king_zone();
+
+// ---------------------------
+// Clipping (erste Blockerbox)
+// ---------------------------
+#[inline]
+// Schneidet einen Ray an der ersten Blocker-Figur ab (vorwärts Richtung).
+// `occ_on_ray` sind nur die Belegungen auf diesem Ray.
+// Das Blockerfeld bleibt enthalten, dahinter wird abgeschnitten.
+// Was: Führt `clip_forward` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn clip_forward(ray_from_sq: u64, occ_on_ray: u64, table: &[u64; 64]) -> u64 {
+    if occ_on_ray == 0 {
+        return ray_from_sq;
+    }
+    let b = lsb_idx(occ_on_ray);
+    ray_from_sq ^ table[b]
+}
+
+#[inline]
+// Schneidet einen Ray an der ersten Blocker-Figur ab (rückwärts Richtung).
+// Nutzt MSB, um den nächsten Blocker „hinter“ dem Start zu finden.
+// Das Blockerfeld bleibt enthalten.
+// Was: Führt `clip_backward` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn clip_backward(ray_from_sq: u64, occ_on_ray: u64, table: &[u64; 64]) -> u64 {
+    if occ_on_ray == 0 {
+        return ray_from_sq;
+    }
+    let b = msb_idx(occ_on_ray);
+    ray_from_sq ^ table[b]
+}
+
+// ---------------------------
+// Angriffs-Masken
+// ---------------------------
+#[inline]
+// Ermittelt Läufer-Angriffe von `sq` unter Berücksichtigung der Belegung.
+// Verwendet Rays + Clipping für diagonale Richtungen.
+// Was: Führt `bishop_attacks` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn bishop_attacks(sq: u8, occ: u64) -> u64 {
+    let i = sq as usize;
+    let ne = clip_forward(RAY_NE[i], occ & RAY_NE[i], &RAY_NE);
+    let nw = clip_forward(RAY_NW[i], occ & RAY_NW[i], &RAY_NW);
+    let se = clip_backward(RAY_SE[i], occ & RAY_SE[i], &RAY_SE);
+    let sw = clip_backward(RAY_SW[i], occ & RAY_SW[i], &RAY_SW);
+    ne | nw | se | sw
+}
+
+#[inline]
+// Ermittelt Turm-Angriffe von `sq` unter Berücksichtigung der Belegung.
+// Verwendet Rays + Clipping für horizontale/vertikale Richtungen.
+// Was: Führt `rook_attacks` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn rook_attacks(sq: u8, occ: u64) -> u64 {
+    let i = sq as usize;
+    let n = clip_forward(RAY_N[i], occ & RAY_N[i], &RAY_N);
+    let e = clip_forward(RAY_E[i], occ & RAY_E[i], &RAY_E);
+    let s = clip_backward(RAY_S[i], occ & RAY_S[i], &RAY_S);
+    let w = clip_backward(RAY_W[i], occ & RAY_W[i], &RAY_W);
+    n | e | s | w
+}
+
+#[inline]
+// Ermittelt Damen-Angriffe als Kombination aus Läufer und Turm.
+// Benötigt nur die Belegung des Boards.
+// Was: Führt `queen_attacks` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn queen_attacks(sq: u8, occ: u64) -> u64 {
+    bishop_attacks(sq, occ) | rook_attacks(sq, occ)
+}
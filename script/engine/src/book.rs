@@ -0,0 +1,214 @@
+// =====================================================================================
+// Polyglot-Eröffnungsbuch
+// -------------------------------------------------------------------------------------
+// Liest Bücher im Polyglot-Format (`.bin`): 16-Byte-Einträge (Big Endian) aus
+// Schlüssel (u64), Zug (u16), Gewicht (u16) und Lernwert (u32), nach Schlüssel sortiert.
+// Schlüssel sind die festen Polyglot-Zobrist-Werte (`polyglot_key`), nicht die der TT.
+//
+// Zugcodierung: Bits 0..5 Ziel, 6..11 Start, 12..14 Umwandlung (0 keine, 1 S .. 4 D).
+// Rochaden stehen als "König schlägt eigenen Turm" (e1h1) im Buch und werden auf den
+// UCI-Zug (e1g1) abgebildet. Einträge, die in der Stellung nicht legal sind, entfallen.
+// =====================================================================================
+
+use crate::movegen::*;
+use crate::position::*;
+use crate::skill::*;
+use crate::zobrist::*;
+
+const ENTRY_SIZE: usize = 16;
+
+// Ein Eintrag der Buchdatei.
+#[derive(Copy, Clone)]
+struct PolyglotEntry {
+    key: u64,
+    mv: u16,
+    weight: u16,
+    learn: u32,
+}
+
+// Buchzug einer Stellung mit seinem Gewicht.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookMove {
+    pub uci: String,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+// Auswahl unter den Buchzügen einer Stellung.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BookSelection {
+    // Immer der Zug mit dem höchsten Gewicht.
+    Best,
+    // Zufällig, Wahrscheinlichkeit proportional zum Gewicht.
+    Weighted,
+    // Zufällig, alle Buchzüge gleich wahrscheinlich.
+    Random,
+}
+
+impl BookSelection {
+    // `"best"`, `"weighted"`, `"random"` (Groß-/Kleinschreibung egal).
+    pub fn parse(name: &str) -> Option<BookSelection> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "best" => Some(BookSelection::Best),
+            "weighted" => Some(BookSelection::Weighted),
+            "random" => Some(BookSelection::Random),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PolyglotBook {
+    entries: Vec<PolyglotEntry>,
+}
+
+impl PolyglotBook {
+    // Was: Liest alle Einträge aus dem Dateiinhalt; ein unvollständiger Rest wird ignoriert.
+    // Kosten: Linear in der Dateigröße (plus Sortierung, falls die Datei unsortiert ist).
+    pub fn from_bytes(bytes: &[u8]) -> PolyglotBook {
+        let mut entries: Vec<PolyglotEntry> = bytes
+            .chunks_exact(ENTRY_SIZE)
+            .map(|c| PolyglotEntry {
+                key: u64::from_be_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]),
+                mv: u16::from_be_bytes([c[8], c[9]]),
+                weight: u16::from_be_bytes([c[10], c[11]]),
+                learn: u32::from_be_bytes([c[12], c[13], c[14], c[15]]),
+            })
+            .collect();
+        if !entries.windows(2).all(|w| w[0].key <= w[1].key) {
+            entries.sort_by_key(|e| e.key);
+        }
+        PolyglotBook { entries }
+    }
+
+    // Was: Lädt eine `.bin`-Datei vollständig in den Speicher.
+    #[cfg(any(unix, windows))]
+    pub fn open(path: impl AsRef<std::path::Path>) -> std::io::Result<PolyglotBook> {
+        Ok(PolyglotBook::from_bytes(&std::fs::read(path)?))
+    }
+
+    // Anzahl der Einträge (Stellung/Zug-Paare).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Was: Alle legalen Buchzüge der Stellung, nach Gewicht absteigend (`None` bei ungültiger FEN).
+    // Kosten: Binärsuche im Buch plus eine Zuggenerierung.
+    pub fn probe(&self, fen: &str) -> Option<Vec<BookMove>> {
+        let mut pos = parse_fen(fen)?;
+        Some(self.probe_position(&mut pos))
+    }
+
+    fn probe_position(&self, pos: &mut Position) -> Vec<BookMove> {
+        let key = polyglot_key(pos);
+        let start = self.entries.partition_point(|e| e.key < key);
+        let found = self.entries[start..].iter().take_while(|e| e.key == key);
+
+        let legal: Vec<String> = generate_legal_moves(pos).into_iter().map(|(mv, promo)| move_to_uci(mv, promo)).collect();
+        let mut moves: Vec<BookMove> = Vec::new();
+        for entry in found {
+            let uci = decode_move(pos, entry.mv);
+            if !legal.contains(&uci) || moves.iter().any(|m| m.uci == uci) {
+                continue;
+            }
+            moves.push(BookMove { uci, weight: entry.weight, learn: entry.learn });
+        }
+        moves.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        moves
+    }
+
+    // Was: Wählt einen Buchzug für die Stellung (UCI) oder `None`, wenn sie nicht im Buch ist.
+    // Warum: `seed` macht die Zufallsauswahl reproduzierbar; gleicher Seed, gleicher Zug.
+    // Kosten: Wie `probe`.
+    pub fn book_move(&self, fen: &str, selection: BookSelection, seed: u64) -> Option<String> {
+        let moves = self.probe(fen)?;
+        pick_book_move(&moves, selection, seed)
+    }
+}
+
+// Auswahl aus den (nach Gewicht sortierten) Buchzügen. Gewicht 0 heißt "nie spielen",
+// außer alle Züge haben Gewicht 0.
+pub(crate) fn pick_book_move(moves: &[BookMove], selection: BookSelection, seed: u64) -> Option<String> {
+    let playable: Vec<&BookMove> = if moves.iter().any(|m| m.weight > 0) {
+        moves.iter().filter(|m| m.weight > 0).collect()
+    } else {
+        moves.iter().collect()
+    };
+    let first = playable.first()?;
+    let mut rng = seed;
+    let pick = match selection {
+        BookSelection::Best => first,
+        BookSelection::Random => playable[next_below(&mut rng, playable.len() as u64) as usize],
+        BookSelection::Weighted => {
+            let total: u64 = playable.iter().map(|m| m.weight.max(1) as u64).sum();
+            let mut roll = next_below(&mut rng, total);
+            playable
+                .iter()
+                .find(|m| {
+                    let w = m.weight.max(1) as u64;
+                    if roll < w {
+                        return true;
+                    }
+                    roll -= w;
+                    false
+                })
+                .unwrap_or(first)
+        }
+    };
+    Some(pick.uci.clone())
+}
+
+// Was: UCI -> Polyglot-Zug (Gegenstück zu `decode_move`); Rochade als "König schlägt Turm".
+pub(crate) fn encode_move(pos: &Position, uci: &str) -> Option<u16> {
+    let from = lan_to_field(uci.get(0..2)?)?;
+    let to = lan_to_field(uci.get(2..4)?)?;
+    let promo: u16 = match uci[4..].chars().next() {
+        None => 0,
+        Some('n') => 1,
+        Some('b') => 2,
+        Some('r') => 3,
+        Some('q') => 4,
+        Some(_) => return None,
+    };
+    let to = match (pos.board[from as usize], from, to) {
+        (Some('K'), 4, 6) => 7,
+        (Some('K'), 4, 2) => 0,
+        (Some('k'), 60, 62) => 63,
+        (Some('k'), 60, 58) => 56,
+        _ => to,
+    };
+    Some(promo << 12 | (from as u16) << 6 | to as u16)
+}
+
+// Hängt einen 16-Byte-Eintrag im Dateiformat an.
+pub(crate) fn write_entry(out: &mut Vec<u8>, key: u64, mv: u16, weight: u16, learn: u32) {
+    out.extend_from_slice(&key.to_be_bytes());
+    out.extend_from_slice(&mv.to_be_bytes());
+    out.extend_from_slice(&weight.to_be_bytes());
+    out.extend_from_slice(&learn.to_be_bytes());
+}
+
+// Was: Polyglot-Zug -> UCI; Rochade "König schlägt Turm" wird zum Königszug.
+fn decode_move(pos: &Position, mv: u16) -> String {
+    let to = (mv & 63) as u8;
+    let from = ((mv >> 6) & 63) as u8;
+    let promo = match (mv >> 12) & 7 {
+        1 => Some('n'),
+        2 => Some('b'),
+        3 => Some('r'),
+        4 => Some('q'),
+        _ => None,
+    };
+    let to = match (pos.board[from as usize], from, to) {
+        (Some('K'), 4, 7) => 6,
+        (Some('K'), 4, 0) => 2,
+        (Some('k'), 60, 63) => 62,
+        (Some('k'), 60, 56) => 58,
+        _ => to,
+    };
+    move_to_uci(Move { from, to, kind: MoveKind::Normal }, promo)
+}
@@ -0,0 +1,266 @@
+// =====================================================================================
+// Eröffnungsbuch-Generator
+// -------------------------------------------------------------------------------------
+// Sammelt aus PGN-Partien Zugstatistiken je Stellung (Partien, Siege/Remis/Niederlagen
+// aus Sicht der ziehenden Seite, optional ein Engine-Score) und schreibt daraus
+//   - das history-basierte JSON der Browser-Bücher (`opening_book_*.json`):
+//     `{"e2e4 e7e5": {"moves": {"g1f3": 0.8396, ...}}, ...}` mit Zuganteilen 0..1,
+//   - ein Polyglot-Buch (`.bin`) mit Gewicht 2 x Siege + Remis je Zug.
+// Das JSON enthält nur Partien ab der Grundstellung (Schlüssel ist die Zugfolge),
+// das Polyglot-Buch alle Partien (Schlüssel ist die Stellung, Zugumstellungen fallen zusammen).
+// =====================================================================================
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::book::*;
+use crate::engine::*;
+use crate::pgn::*;
+use crate::platform::*;
+use crate::position::*;
+use crate::search::*;
+use crate::zobrist::*;
+
+// Grenzen und Filter des Generators.
+#[derive(Clone, Debug)]
+pub struct BookBuilderOptions {
+    // Nur Stellungen vor diesem Halbzug kommen ins Buch.
+    pub max_ply: usize,
+    // Mindestzahl an Partien, in denen ein Zug gespielt wurde.
+    pub min_games: u32,
+    // Mindestanteil eines Zugs an den Partien seiner Stellung (0..1).
+    pub min_share: f64,
+    // Suchtiefe der Engine-Bewertung je Buchzug; 0 = keine Bewertung.
+    pub score_depth: u32,
+    // Bewertete Züge, die mehr als so viele Centipawns hinter dem besten liegen, entfallen.
+    pub score_margin: i32,
+}
+
+impl Default for BookBuilderOptions {
+    fn default() -> Self {
+        BookBuilderOptions { max_ply: 20, min_games: 2, min_share: 0.0, score_depth: 0, score_margin: 100 }
+    }
+}
+
+// Statistik eines Zugs in einer Stellung, Ergebnisse aus Sicht der ziehenden Seite.
+#[derive(Copy, Clone, Default)]
+struct MoveStats {
+    games: u32,
+    wins: u32,
+    draws: u32,
+    losses: u32,
+    // Engine-Score (Centipawns, Sicht der ziehenden Seite), falls bewertet.
+    score: Option<i32>,
+}
+
+// Eine Stellung mit ihren gespielten Zügen (sortiert für reproduzierbare Ausgabe).
+struct BookNode {
+    fen: String,
+    games: u32,
+    moves: BTreeMap<String, MoveStats>,
+}
+
+impl BookNode {
+    fn new(fen: &str) -> BookNode {
+        BookNode { fen: fen.to_string(), games: 0, moves: BTreeMap::new() }
+    }
+
+    // Was: Züge, die alle Filter bestehen, nach Häufigkeit absteigend.
+    fn kept_moves(&self, options: &BookBuilderOptions) -> Vec<(&String, &MoveStats)> {
+        let mut kept: Vec<(&String, &MoveStats)> = self
+            .moves
+            .iter()
+            .filter(|(_, s)| s.games >= options.min_games)
+            .filter(|(_, s)| s.games as f64 >= options.min_share * self.games as f64)
+            .collect();
+        if let Some(best) = kept.iter().filter_map(|(_, s)| s.score).max() {
+            kept.retain(|(_, s)| s.score.is_none_or(|score| score >= best - options.score_margin));
+        }
+        kept.sort_by(|a, b| b.1.games.cmp(&a.1.games).then_with(|| a.0.cmp(b.0)));
+        kept
+    }
+}
+
+pub struct BookBuilder {
+    options: BookBuilderOptions,
+    // Schlüssel: Zugfolge ab der Grundstellung (UCI, leerzeichengetrennt).
+    by_history: HashMap<String, BookNode>,
+    // Schlüssel: Polyglot-Hash der Stellung.
+    by_position: HashMap<u64, BookNode>,
+    games: usize,
+}
+
+impl BookBuilder {
+    pub fn new(options: BookBuilderOptions) -> BookBuilder {
+        BookBuilder { options, by_history: HashMap::new(), by_position: HashMap::new(), games: 0 }
+    }
+
+    // Anzahl der übernommenen Partien.
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    // Anzahl der Stellungen mit mindestens einem Buchzug (nach allen Filtern).
+    pub fn positions(&self) -> usize {
+        self.by_position.values().filter(|n| !n.kept_moves(&self.options).is_empty()).count()
+    }
+
+    // Was: Übernimmt die ersten `max_ply` Züge einer Partie in die Statistik.
+    // Kosten: Ein FEN-Parse plus Polyglot-Hash je Halbzug.
+    pub fn add_game(&mut self, game: &PgnGame) {
+        // 1 = Weiß gewinnt, -1 = Schwarz gewinnt, 0 = Remis, None = offen.
+        let white_result = match game.result.as_str() {
+            "1-0" => Some(1),
+            "0-1" => Some(-1),
+            "1/2-1/2" => Some(0),
+            _ => None,
+        };
+        let from_start = game.start_fen == START_FEN;
+        let mut history = String::new();
+        for (ply, uci) in game.moves.iter().enumerate().take(self.options.max_ply) {
+            let fen = &game.fens[ply];
+            let Some(pos) = parse_fen(fen) else { break };
+            let sign = if pos.side_to_move == Color::White { 1 } else { -1 };
+            let result = white_result.map(|r| r * sign);
+
+            let node = self.by_position.entry(polyglot_key(&pos)).or_insert_with(|| BookNode::new(fen));
+            record(node, uci, result);
+            if from_start {
+                let node = self.by_history.entry(history.clone()).or_insert_with(|| BookNode::new(fen));
+                record(node, uci, result);
+                if !history.is_empty() {
+                    history.push(' ');
+                }
+                history.push_str(uci);
+            }
+        }
+        self.games += 1;
+    }
+
+    // Was: Liest alle Partien aus PGN-Text; liefert (übernommen, fehlerhaft).
+    pub fn add_pgn(&mut self, text: &str) -> (usize, usize) {
+        let mut added = 0;
+        let mut failed = 0;
+        for game in parse_pgn_games(text) {
+            match game {
+                Ok(game) => {
+                    self.add_game(&game);
+                    added += 1;
+                }
+                Err(_) => failed += 1,
+            }
+        }
+        (added, failed)
+    }
+
+    // Was: Bewertet alle Züge, die die Häufigkeitsfilter bestehen, mit einer Suche der Tiefe
+    // `score_depth`; die Scores gelten für beide Ausgaben.
+    // Warum: Häufig gespielt heißt nicht gut; schwache Züge fallen über `score_margin` heraus.
+    // Kosten: Eine Mehrfach-Wurzelsuche je Buchstellung (dominiert die Laufzeit des Generators).
+    pub fn score_moves(&mut self, clock: &dyn Clock) {
+        if self.options.score_depth == 0 {
+            return;
+        }
+        let limits = SearchLimits {
+            depth: self.options.score_depth,
+            tt_mb: 16,
+            deterministic: true,
+            ..SearchLimits::default()
+        };
+        let mut engine = Engine::new(limits.tt_mb);
+        for node in self.by_position.values_mut() {
+            let candidates: Vec<String> =
+                node.kept_moves(&self.options).into_iter().map(|(uci, _)| uci.clone()).collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            let Some(result) = engine.score_root_moves(&node.fen, "", &candidates, &limits, clock, &mut ()) else {
+                continue;
+            };
+            for line in &result.lines {
+                if let Some(stats) = line.pv.first().and_then(|uci| node.moves.get_mut(uci)) {
+                    stats.score = Some(line.score);
+                }
+            }
+        }
+        // History-Knoten übernehmen die Scores ihrer Stellung.
+        for node in self.by_history.values_mut() {
+            let Some(pos) = parse_fen(&node.fen) else { continue };
+            let Some(scored) = self.by_position.get(&polyglot_key(&pos)) else { continue };
+            for (uci, stats) in node.moves.iter_mut() {
+                stats.score = scored.moves.get(uci).and_then(|s| s.score);
+            }
+        }
+    }
+
+    // Was: History-basiertes JSON im Format der Browser-Bücher; Anteile auf 4 Stellen gerundet.
+    // Bewertete Züge stehen zusätzlich unter `"scores"` (Centipawns, Sicht der ziehenden Seite).
+    // Kosten: Linear in der Zahl der Buchstellungen (plus Sortierung nach Zugfolge).
+    pub fn to_history_json(&self) -> String {
+        let mut histories: Vec<&String> = self.by_history.keys().collect();
+        histories.sort_by_key(|h| (h.split_whitespace().count(), h.as_str()));
+
+        let mut items = Vec::new();
+        for history in histories {
+            let node = &self.by_history[history];
+            let kept = node.kept_moves(&self.options);
+            if kept.is_empty() {
+                continue;
+            }
+            let shares: Vec<String> = kept
+                .iter()
+                .map(|(uci, s)| {
+                    let share = (s.games as f64 / node.games as f64 * 10_000.0).round() / 10_000.0;
+                    format!("\"{}\":{}", uci, share)
+                })
+                .collect();
+            let scores: Vec<String> =
+                kept.iter().filter_map(|(uci, s)| s.score.map(|score| format!("\"{}\":{}", uci, score))).collect();
+            let scores =
+                if scores.is_empty() { String::new() } else { format!(",\"scores\":{{{}}}", scores.join(",")) };
+            items.push(format!("\"{}\":{{\"moves\":{{{}}}{}}}", history, shares.join(","), scores));
+        }
+        format!("{{{}}}", items.join(","))
+    }
+
+    // Was: Polyglot-Buch aller Stellungen, nach Schlüssel sortiert, je Stellung nach Gewicht.
+    // Gewicht 2 x Siege + Remis (wie bei Polyglot üblich), je Stellung auf u16 skaliert;
+    // Züge mit Gewicht 0 (nur verloren oder ohne Ergebnis) werden nicht geschrieben.
+    // Kosten: Linear in der Zahl der Buchzüge plus Sortierung.
+    pub fn to_polyglot(&self) -> Vec<u8> {
+        let mut entries: Vec<(u64, u16, u16)> = Vec::new();
+        for (&key, node) in &self.by_position {
+            let Some(pos) = parse_fen(&node.fen) else { continue };
+            let kept = node.kept_moves(&self.options);
+            let raw: Vec<u64> = kept.iter().map(|(_, s)| 2 * s.wins as u64 + s.draws as u64).collect();
+            let max = raw.iter().copied().max().unwrap_or(0);
+            for ((uci, _), weight) in kept.iter().zip(raw) {
+                let weight = if max > u16::MAX as u64 { weight * u16::MAX as u64 / max } else { weight };
+                if weight == 0 {
+                    continue;
+                }
+                if let Some(mv) = encode_move(&pos, uci) {
+                    entries.push((key, mv, weight as u16));
+                }
+            }
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.cmp(&a.2)).then(a.1.cmp(&b.1)));
+
+        let mut out = Vec::with_capacity(entries.len() * 16);
+        for (key, mv, weight) in entries {
+            write_entry(&mut out, key, mv, weight, 0);
+        }
+        out
+    }
+}
+
+fn record(node: &mut BookNode, uci: &str, result: Option<i32>) {
+    node.games += 1;
+    let stats = node.moves.entry(uci.to_string()).or_default();
+    stats.games += 1;
+    match result {
+        Some(1) => stats.wins += 1,
+        Some(0) => stats.draws += 1,
+        Some(_) => stats.losses += 1,
+        None => {}
+    }
+}
@@ -0,0 +1,321 @@
+// =====================================================================================
+// Spezialisierte Endspiel-Bewertung
+// -------------------------------------------------------------------------------------
+// Für Grundendspiele reichen Material + PST nicht: K+L+S-K findet die Mattecke nicht,
+// K+L-K gilt als Vorteil, K+L+Randbauer-K mit falschem Läufer als Gewinn. Hier liegt
+// eine Registry nach Materialsignatur (`"KBNvK"`: starke Seite zuerst), deren Funktionen
+// die generische Bewertung ersetzen. KPK (König+Bauer gegen König) entscheidet eine
+// Bitbase, die zur Compile-Zeit per Retro-Analyse als `const` entsteht (keine Laufzeit-
+// oder Ladekosten).
+//
+// Alle Scores hier sind aus Sicht der starken Seite; `endgame_score` liefert Weiß-Sicht.
+// =====================================================================================
+
+use crate::bitboard::*;
+use crate::position::*;
+
+// Sicher gewonnenes Endspiel: klar über jedem Materialvorteil, klar unter Matt/Tablebase.
+pub(crate) const ENDGAME_WIN: i32 = 10_000;
+
+// Mehr Steine hat keine Signatur der Registry (K+L+3B-K).
+const MAX_ENDGAME_PIECES: i32 = 6;
+
+// Endspielwerte der Figuren (wie `EG_VALUES` der Bewertung).
+const PAWN_VALUE: i32 = 120;
+const KNIGHT_VALUE: i32 = 300;
+const BISHOP_VALUE: i32 = 320;
+const ROOK_VALUE: i32 = 510;
+const QUEEN_VALUE: i32 = 900;
+
+// Helle Felder: (Datei + Reihe) ungerade, a1 ist dunkel.
+const LIGHT_SQUARES: u64 = 0x55AA_55AA_55AA_55AA;
+
+// ---------------------------
+// KPK-Bitbase
+// ---------------------------
+
+// Bauer auf a2..d7 -> 0..23 (Spiegelung an der d/e-Linie deckt die e..h-Linien ab).
+const fn pawn_index(p: usize) -> usize {
+    (p / 8 - 1) * 4 + p % 8
+}
+
+// Felder, die ein weißer Bauer auf `p` angreift.
+const fn pawn_attacks(p: usize) -> u64 {
+    let pawn = 1u64 << p;
+    ((pawn & !FILE_A) << 7) | ((pawn & !FILE_H) << 9)
+}
+
+// Vereinigung der Königsfelder aller Felder in `s`.
+const fn king_spread(s: u64) -> u64 {
+    let v = s | (s << 8) | (s >> 8);
+    (s << 8) | (s >> 8) | ((v & !FILE_H) << 1) | ((v & !FILE_A) >> 1)
+}
+
+// Gewinnmenge (weiße Königsfelder) mit Weiß am Zug für Bauer `p`, schwarzen König `bk`.
+const fn kpk_white_wins(win: &[[[u64; 64]; 24]; 2], p: usize, bk: usize) -> u64 {
+    let pawn = 1u64 << p;
+    let king = 1u64 << bk;
+    // Schwarz im Schach mit Weiß am Zug ist illegal.
+    if bk == p || pawn_attacks(p) & king != 0 {
+        return 0;
+    }
+    let legal = !(KING_ATTACKS[bk] | king | pawn);
+
+    let mut wins = king_spread(win[1][pawn_index(p)][bk]);
+    let q = p + 8;
+    let queen_sq = 1u64 << q;
+    if p / 8 == 6 {
+        // Umwandlung gewinnt, wenn das Feld frei ist und nicht verloren geht.
+        wins |= if (KING_ATTACKS[bk] | king) & queen_sq == 0 { !queen_sq } else { KING_ATTACKS[q] & !queen_sq };
+    } else if bk != q {
+        wins |= win[1][pawn_index(q)][bk] & !queen_sq;
+        if p / 8 == 1 && bk != q + 8 {
+            wins |= win[1][pawn_index(q + 8)][bk] & !queen_sq;
+        }
+    }
+    wins & legal
+}
+
+// Gewinnmenge mit Schwarz am Zug: jeder legale Königszug muss verlieren.
+const fn kpk_black_wins(win: &[[[u64; 64]; 24]; 2], p: usize, bk: usize) -> u64 {
+    if bk == p {
+        return 0;
+    }
+    let legal = !(KING_ATTACKS[bk] | (1u64 << bk) | (1u64 << p));
+
+    let mut all = !0u64;
+    let mut any = 0u64;
+    let mut targets = KING_ATTACKS[bk] & !pawn_attacks(p);
+    if targets & (1u64 << p) != 0 {
+        // Ungedeckter Bauer wird geschlagen: remis.
+        all &= KING_ATTACKS[p];
+        targets &= !(1u64 << p);
+    }
+    while targets != 0 {
+        let t = targets.trailing_zeros() as usize;
+        targets &= targets - 1;
+        // Zielfeld neben dem weißen König ist kein legaler Zug.
+        all &= KING_ATTACKS[t] | win[0][pawn_index(p)][t];
+        any |= !KING_ATTACKS[t];
+    }
+    // Ohne legalen Zug: Patt (ein Bauernmatt gibt es in KPK praktisch nicht).
+    all & any & legal
+}
+
+// Was: Retro-Analyse aller KPK-Stellungen bis zum Fixpunkt der Gewinnmengen.
+// Warum: Läuft zur Compile-Zeit; je Bauernfeld und schwarzem König ein Bitboard weißer Königsfelder.
+// Kosten: Compile-Zeit, einige zehn Iterationen über 2 x 24 x 64 Einträge; zur Laufzeit 24 KB.
+const fn build_kpk() -> [[[u64; 64]; 24]; 2] {
+    // [0] = Weiß am Zug, [1] = Schwarz am Zug.
+    let mut win = [[[0u64; 64]; 24]; 2];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        // Von der 7. Reihe abwärts: Bauernzüge erreichen schon aktualisierte Einträge.
+        let mut rank = 6;
+        while rank >= 1 {
+            let mut file = 0;
+            while file < 4 {
+                let p = rank * 8 + file;
+                let mut bk = 0;
+                while bk < 64 {
+                    let w = kpk_white_wins(&win, p, bk);
+                    if w != win[0][pawn_index(p)][bk] {
+                        win[0][pawn_index(p)][bk] = w;
+                        changed = true;
+                    }
+                    let b = kpk_black_wins(&win, p, bk);
+                    if b != win[1][pawn_index(p)][bk] {
+                        win[1][pawn_index(p)][bk] = b;
+                        changed = true;
+                    }
+                    bk += 1;
+                }
+                file += 1;
+            }
+            rank -= 1;
+        }
+    }
+    win
+}
+
+static KPK_BITBASE: [[[u64; 64]; 24]; 2] = build_kpk();
+
+// Was: Ob Weiß (König `wk`, Bauer `p` auf a..d) gegen König `bk` gewinnt.
+// Kosten: Ein Tabellenzugriff.
+pub(crate) fn kpk_probe(wk: u8, p: u8, bk: u8, white_to_move: bool) -> bool {
+    let stm = if white_to_move { 0 } else { 1 };
+    KPK_BITBASE[stm][pawn_index(p as usize)][bk as usize] & bb(wk) != 0
+}
+
+// ---------------------------
+// Registry
+// ---------------------------
+
+// Bewertung aus Sicht von `strong`; `None` überlässt die Stellung der normalen Bewertung.
+type EndgameFn = fn(&Position, Color) -> Option<i32>;
+
+struct Endgame {
+    strong: u32,
+    weak: u32,
+    eval: EndgameFn,
+}
+
+// Figurenzahlen einer Seite, je 4 Bit: Bauer, Springer, Läufer, Turm, Dame.
+const fn signature_side(name: &[u8], mut i: usize, end: usize) -> u32 {
+    let mut key = 0u32;
+    while i < end {
+        let shift = match name[i] {
+            b'P' => 0,
+            b'N' => 4,
+            b'B' => 8,
+            b'R' => 12,
+            b'Q' => 16,
+            b'K' => {
+                i += 1;
+                continue;
+            }
+            _ => panic!("ungültige Endspiel-Signatur"),
+        };
+        key += 1 << shift;
+        i += 1;
+    }
+    key
+}
+
+// Signatur `"KBNvK"` -> Schlüssel beider Seiten (Fehler brechen den Build ab).
+const fn endgame(name: &str, eval: EndgameFn) -> Endgame {
+    let bytes = name.as_bytes();
+    let mut split = 0;
+    while bytes[split] != b'v' {
+        split += 1;
+    }
+    Endgame { strong: signature_side(bytes, 0, split), weak: signature_side(bytes, split + 1, bytes.len()), eval }
+}
+
+static ENDGAMES: [Endgame; 10] = [
+    endgame("KPvK", eval_kpk),
+    endgame("KRvK", eval_kxk),
+    endgame("KQvK", eval_kxk),
+    endgame("KBNvK", eval_kbnk),
+    endgame("KBvK", eval_draw),
+    endgame("KNvK", eval_draw),
+    endgame("KNNvK", eval_draw),
+    endgame("KBPvK", eval_kbpsk),
+    endgame("KBPPvK", eval_kbpsk),
+    endgame("KBPPPvK", eval_kbpsk),
+];
+
+fn side_signature(pos: &Position, color: Color) -> u32 {
+    let b = &pos.bb;
+    let (p, n, bs, r, q) = match color {
+        Color::White => (b.wp, b.wn, b.wb, b.wr, b.wq),
+        Color::Black => (b.bp, b.bn, b.bb, b.br, b.bq),
+    };
+    popcnt(p) as u32 | (popcnt(n) as u32) << 4 | (popcnt(bs) as u32) << 8 | (popcnt(r) as u32) << 12 | (popcnt(q) as u32) << 16
+}
+
+// Was: Sucht die Materialsignatur in der Registry und bewertet mit der Spezialfunktion.
+// Warum: Ersetzt die generische Bewertung dort, wo sie Grundendspiele falsch einschätzt.
+// Kosten: Ein Popcount für die meisten Stellungen; sonst Signatur + Suche über 10 Einträge.
+pub(crate) fn endgame_score(pos: &Position) -> Option<i32> {
+    if popcnt(pos.bb.occ) > MAX_ENDGAME_PIECES {
+        return None;
+    }
+    let white = side_signature(pos, Color::White);
+    let black = side_signature(pos, Color::Black);
+    for e in &ENDGAMES {
+        if e.strong == white && e.weak == black {
+            return (e.eval)(pos, Color::White);
+        }
+        if e.strong == black && e.weak == white {
+            return (e.eval)(pos, Color::Black).map(|s| -s);
+        }
+    }
+    None
+}
+
+// ---------------------------
+// Bewertungsfunktionen
+// ---------------------------
+
+fn king_sq(pos: &Position, color: Color) -> u8 {
+    if color == Color::White { pos.bb.white_king_sq } else { pos.bb.black_king_sq }
+}
+
+fn distance(a: u8, b: u8) -> i32 {
+    let df = ((a & 7) as i32 - (b & 7) as i32).abs();
+    let dr = ((a >> 3) as i32 - (b >> 3) as i32).abs();
+    df.max(dr)
+}
+
+// Belohnt den schwachen König am Rand (Ecke am höchsten).
+fn push_to_edge(sq: u8) -> i32 {
+    let f = (sq & 7) as i32;
+    let r = (sq >> 3) as i32;
+    20 * ((3 - f).max(f - 4) + (3 - r).max(r - 4))
+}
+
+// Belohnt Nähe der Könige (Abstand 1..7).
+fn push_close(a: u8, b: u8) -> i32 {
+    140 - 20 * distance(a, b)
+}
+
+fn eval_draw(_pos: &Position, _strong: Color) -> Option<i32> {
+    Some(0)
+}
+
+// K+T-K, K+D-K: gegnerischen König an den Rand treiben, eigenen König heranführen.
+fn eval_kxk(pos: &Position, strong: Color) -> Option<i32> {
+    let b = &pos.bb;
+    let material = popcnt(b.wr | b.br) * ROOK_VALUE + popcnt(b.wq | b.bq) * QUEEN_VALUE;
+    let (sk, wk) = (king_sq(pos, strong), king_sq(pos, strong.opposite()));
+    Some(ENDGAME_WIN + material + push_to_edge(wk) + push_close(sk, wk))
+}
+
+// K+L+S-K: Matt geht nur in einer Ecke der Läuferfarbe, dorthin wird getrieben.
+fn eval_kbnk(pos: &Position, strong: Color) -> Option<i32> {
+    let b = &pos.bb;
+    let (sk, wk) = (king_sq(pos, strong), king_sq(pos, strong.opposite()));
+    let corners: [u8; 2] = if (b.wb | b.bb) & LIGHT_SQUARES != 0 { [7, 56] } else { [0, 63] };
+    let manhattan = |a: u8, c: u8| ((a & 7) as i32 - (c & 7) as i32).abs() + ((a >> 3) as i32 - (c >> 3) as i32).abs();
+    let corner_dist = manhattan(wk, corners[0]).min(manhattan(wk, corners[1]));
+    Some(ENDGAME_WIN + KNIGHT_VALUE + BISHOP_VALUE + 30 * (14 - corner_dist) + push_close(sk, wk))
+}
+
+// KPK: exakt per Bitbase; Gewinne nach Bauernfortschritt gestaffelt.
+fn eval_kpk(pos: &Position, strong: Color) -> Option<i32> {
+    // Auf Weiß als starke Seite und Bauer auf a..d normieren.
+    let flip = if strong == Color::White { 0 } else { 56 };
+    let pawn = (pos.bb.wp | pos.bb.bp).trailing_zeros() as u8 ^ flip;
+    let mirror = if pawn & 7 > 3 { 7 } else { 0 };
+    let wk = king_sq(pos, strong) ^ flip ^ mirror;
+    let bk = king_sq(pos, strong.opposite()) ^ flip ^ mirror;
+    let p = pawn ^ mirror;
+    if !kpk_probe(wk, p, bk, pos.side_to_move == strong) {
+        return Some(0);
+    }
+    Some(ENDGAME_WIN + PAWN_VALUE + 10 * (p >> 3) as i32)
+}
+
+// K+L+Randbauern-K: falscher Läufer und König in der Umwandlungsecke halten remis.
+fn eval_kbpsk(pos: &Position, strong: Color) -> Option<i32> {
+    let b = &pos.bb;
+    let pawns = b.wp | b.bp;
+    let file = if pawns & !FILE_A == 0 {
+        0
+    } else if pawns & !FILE_H == 0 {
+        7
+    } else {
+        return None;
+    };
+    let queen_sq = if strong == Color::White { 56 + file } else { file };
+    let bishop_light = (b.wb | b.bb) & LIGHT_SQUARES != 0;
+    if bishop_light == (bb(queen_sq) & LIGHT_SQUARES != 0) {
+        return None;
+    }
+    if distance(king_sq(pos, strong.opposite()), queen_sq) <= 1 {
+        return Some(0);
+    }
+    None
+}
@@ -0,0 +1,111 @@
+// =====================================================================================
+// Engine-Instanzen
+// -------------------------------------------------------------------------------------
+// `Engine` besitzt den gesamten Suchzustand (TT, Killer, History-Heuristik, History-Cache).
+// Mehrere Instanzen im selben Thread (z. B. Partie und Analysebrett) beeinflussen sich
+// nicht. Die freien Funktionen `run_search` und `score_root_moves` suchen mit einer
+// frischen Instanz je Aufruf und behalten nichts über den Aufruf hinaus.
+// =====================================================================================
+
+use crate::platform::*;
+use crate::search::*;
+
+// Eine unabhängige Engine mit eigener Transposition Table und eigenen Heuristiken.
+pub struct Engine {
+    state: SearchState,
+}
+
+impl Engine {
+    // Was: Neue Engine mit einer TT von `hash_mb` MB (0 = ohne TT).
+    // Kosten: Allokation der TT.
+    pub fn new(hash_mb: u32) -> Self {
+        Engine { state: SearchState::new(hash_mb) }
+    }
+
+    pub fn hash_size(&self) -> u32 {
+        self.state.tt_mb()
+    }
+
+    // Promille der TT, die die letzte Suche belegt hat (Stichprobe, wie UCI `hashfull`).
+    pub fn hashfull(&self) -> u32 {
+        self.state.hashfull()
+    }
+
+    // Was: Ändert die TT-Größe; die Tabelle wird dabei geleert, gleiche Größe ist ein No-op.
+    // Kosten: Neuallokation der TT.
+    pub fn set_hash_size(&mut self, hash_mb: u32) {
+        self.state.set_tt_size(hash_mb);
+    }
+
+    // Was: Leert nur die TT; Killer und History-Heuristik bleiben erhalten.
+    // Warum: UCI `Clear Hash` – frische Tabelle ohne Neustart der Partie.
+    // Kosten: Linear in der TT-Größe.
+    pub fn clear_hash(&mut self) {
+        self.state.clear_tt();
+    }
+
+    // Was: Verwirft TT, Killer, History-Heuristik und History-Cache.
+    // Warum: UCI `ucinewgame` – alte Partien beeinflussen die nächste Suche nicht.
+    // Kosten: Linear in der TT-Größe.
+    pub fn new_game(&mut self) {
+        self.state.reset();
+    }
+
+    // Was: Suche wie `run_search`, aber auf dem Zustand dieser Instanz.
+    // Warum: `limits.tt_mb` wird ignoriert; die Größe setzt `new`/`set_hash_size`.
+    // Kosten: Siehe `run_search`.
+    pub fn search(
+        &mut self,
+        fen: &str,
+        history: &str,
+        limits: &SearchLimits,
+        clock: &dyn Clock,
+        sink: &mut dyn ProgressSink,
+    ) -> Option<SearchResult> {
+        search_position(&mut self.state, fen, history, limits, clock, sink)
+    }
+
+    // Was: Bewertung einzelner Wurzelzüge wie `score_root_moves` auf dieser Instanz.
+    // Kosten: Siehe `score_root_moves`.
+    pub fn score_root_moves(
+        &mut self,
+        fen: &str,
+        history: &str,
+        moves: &[String],
+        limits: &SearchLimits,
+        clock: &dyn Clock,
+        sink: &mut dyn ProgressSink,
+    ) -> Option<SearchResult> {
+        score_root_moves_in(&mut self.state, fen, history, moves, limits, clock, sink)
+    }
+}
+
+// Was: Iterative Deepening mit Aspiration Windows für eine FEN plus FEN-History.
+// Warum: Gemeinsamer Einstieg für die WASM-Exports (JSON) und native Frontends ohne eigene
+// `Engine`; sucht mit einer frischen Engine mit TT-Größe `limits.tt_mb`.
+// Kosten: Allokation der TT, dann dominiert von den einzelnen Iterationen; `sink.iteration`
+// wird nur einmal pro Iteration gerufen.
+pub fn run_search(
+    fen: &str,
+    history: &str,
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+    sink: &mut dyn ProgressSink,
+) -> Option<SearchResult> {
+    Engine::new(limits.tt_mb).search(fen, history, limits, clock, sink)
+}
+
+// Was: Bewertet jeden Zug aus `moves` einzeln; `lines` enthält je legalem Zug eine Variante
+// mit eigenem Score und PV, sortiert vom besten zum schlechtesten (leer ohne legalen Zug).
+// Warum: "Was wäre, wenn ich das spiele?"-Analyse für mehrere Kandidaten auf einmal.
+// Kosten: Wie Multi-PV mit `moves.len()` Varianten (ein Root-Durchlauf je Zug und Iteration).
+pub fn score_root_moves(
+    fen: &str,
+    history: &str,
+    moves: &[String],
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+    sink: &mut dyn ProgressSink,
+) -> Option<SearchResult> {
+    Engine::new(limits.tt_mb).score_root_moves(fen, history, moves, limits, clock, sink)
+}
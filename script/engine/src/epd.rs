@@ -0,0 +1,232 @@
+// =====================================================================================
+// EPD-Testsuiten
+// -------------------------------------------------------------------------------------
+// Liest EPD-Zeilen (WAC, STS, ECM, ...): vier FEN-Felder, danach Operationen
+// `opcode operand ...;`. Ausgewertet werden `bm` (beste Züge), `am` (zu vermeidende
+// Züge), `id` und `c0`. Züge stehen in SAN und werden in UCI umgerechnet.
+//
+// STS-Suiten tragen in `c0` eine Punktetabelle (`"f5=10, Be5+=2, Bf2=3"`); ohne Tabelle
+// bringt eine gelöste Stellung 10 Punkte. `solve_epd` sucht eine Stellung und misst,
+// ab wann der gefundene Zug bis zum Ende der Suche richtig bleibt (Time-to-Solve).
+// =====================================================================================
+
+use crate::engine::*;
+use crate::movegen::*;
+use crate::platform::*;
+use crate::position::*;
+use crate::san::*;
+use crate::search::*;
+
+// Punkte einer gelösten Stellung ohne `c0`-Tabelle (Maximum bei STS).
+pub const EPD_FULL_POINTS: u32 = 10;
+
+// Eine Stellung der Testsuite.
+#[derive(Clone, Debug, Default)]
+pub struct EpdRecord {
+    // Vollständige FEN (Halbzug- und Zugzähler aus `hmvc`/`fmvn` oder `0 1`).
+    pub fen: String,
+    pub id: Option<String>,
+    // `bm` in UCI.
+    pub best_moves: Vec<String>,
+    // `am` in UCI.
+    pub avoid_moves: Vec<String>,
+    pub comment: Option<String>,
+    // Punktetabelle aus `c0` (UCI, Punkte), leer ohne Tabelle.
+    pub points: Vec<(String, u32)>,
+}
+
+impl EpdRecord {
+    // Was: Gilt die Stellung mit `uci` als gelöst? Mit `bm` muss der Zug darunter sein,
+    // mit `am` darf er nicht darunter sein (beides zusammen: beide Bedingungen).
+    pub fn is_solved_by(&self, uci: &str) -> bool {
+        let best_ok = self.best_moves.is_empty() || self.best_moves.iter().any(|m| m == uci);
+        let avoid_ok = !self.avoid_moves.iter().any(|m| m == uci);
+        (!self.best_moves.is_empty() || !self.avoid_moves.is_empty()) && best_ok && avoid_ok
+    }
+
+    // Was: Punkte für `uci`: aus der `c0`-Tabelle, sonst `EPD_FULL_POINTS` bei gelöster Stellung.
+    pub fn points_for(&self, uci: &str) -> u32 {
+        if self.points.is_empty() {
+            return if self.is_solved_by(uci) { EPD_FULL_POINTS } else { 0 };
+        }
+        self.points.iter().find(|(m, _)| m == uci).map_or(0, |(_, p)| *p)
+    }
+
+    // Höchstmögliche Punktzahl der Stellung.
+    pub fn max_points(&self) -> u32 {
+        self.points.iter().map(|(_, p)| *p).max().unwrap_or(EPD_FULL_POINTS)
+    }
+}
+
+// Was: Liest eine EPD-Zeile.
+// Kosten: Linear in der Zeilenlänge plus eine SAN-Auflösung je Zug in `bm`/`am`/`c0`.
+pub fn parse_epd(line: &str) -> Result<EpdRecord, String> {
+    let mut rest = line.trim();
+    let mut board = Vec::new();
+    while board.len() < 4 && !rest.is_empty() {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        board.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    if board.len() < 4 {
+        return Err(format!("epd: zu wenige FEN-Felder: {}", line.trim()));
+    }
+
+    let ops = parse_operations(rest)?;
+    let operand = |code: &str| ops.iter().find(|(c, _)| c == code).map(|(_, v)| v);
+    let counter = |code: &str, default: &str| {
+        operand(code).and_then(|v| v.first()).cloned().unwrap_or_else(|| default.to_string())
+    };
+    let fen = format!("{} {}", board.join(" "), [counter("hmvc", "0"), counter("fmvn", "1")].join(" "));
+    let mut pos = parse_fen(&fen).ok_or_else(|| format!("epd: ungültige Stellung: {}", fen))?;
+
+    let mut record = EpdRecord { fen: fen.clone(), ..EpdRecord::default() };
+    record.id = operand("id").map(|v| v.join(" "));
+    record.comment = operand("c0").map(|v| v.join(" "));
+    for (code, target) in [("bm", &mut record.best_moves), ("am", &mut record.avoid_moves)] {
+        for san in operand(code).into_iter().flatten() {
+            let uci = san_to_uci(&mut pos, san).ok_or_else(|| format!("epd: {} {} in {}", code, san, fen))?;
+            target.push(uci);
+        }
+    }
+    if let Some(comment) = &record.comment {
+        record.points = parse_points(&mut pos, comment);
+    }
+    Ok(record)
+}
+
+// Was: Liest alle Zeilen einer EPD-Datei; leere Zeilen und `#`-Kommentare entfallen.
+// Warum: Eine defekte Zeile soll die übrigen Stellungen der Suite nicht verwerfen.
+pub fn parse_epd_suite(text: &str) -> Vec<Result<EpdRecord, String>> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(parse_epd)
+        .collect()
+}
+
+// Ergebnis einer gesuchten Stellung.
+#[derive(Clone, Debug)]
+pub struct EpdOutcome {
+    // Gespielter Zug (UCI) oder `None` ohne legalen Zug.
+    pub best: Option<String>,
+    pub solved: bool,
+    pub points: u32,
+    pub depth: u32,
+    pub nodes: u64,
+    pub time_ms: u64,
+    // Ab dieser Iteration blieb der Zug bis zum Ende richtig (nur bei `solved`).
+    pub solve_time_ms: Option<u64>,
+    pub solve_nodes: Option<u64>,
+    pub solve_depth: Option<u32>,
+}
+
+// Merkt sich die erste Iteration, ab der der beste Zug durchgehend richtig ist.
+struct SolveTracker<'a> {
+    record: &'a EpdRecord,
+    since: Option<(u64, u64, u32)>,
+}
+
+impl ProgressSink for SolveTracker<'_> {
+    fn iteration(&mut self, info: &SearchInfo) {
+        let solved = info.best.as_deref().is_some_and(|best| self.record.is_solved_by(best));
+        if !solved {
+            self.since = None;
+        } else if self.since.is_none() {
+            self.since = Some((info.time_ms, info.nodes, info.depth));
+        }
+    }
+}
+
+// Was: Sucht die Stellung mit `limits` auf `engine` und bewertet den gefundenen Zug.
+// Warum: Gemeinsamer Kern für `epd-runner` und Tests; die TT bleibt zwischen Stellungen
+// erhalten, sofern der Aufrufer nicht `new_game` ruft.
+// Kosten: Eine Suche.
+pub fn solve_epd(
+    engine: &mut Engine,
+    record: &EpdRecord,
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+) -> EpdOutcome {
+    let mut tracker = SolveTracker { record, since: None };
+    let result = engine.search(&record.fen, "", limits, clock, &mut tracker);
+    let best = result.as_ref().and_then(|r| r.best.clone());
+    let solved = best.as_deref().is_some_and(|b| record.is_solved_by(b));
+    let since = tracker.since.filter(|_| solved);
+    EpdOutcome {
+        points: best.as_deref().map_or(0, |b| record.points_for(b)),
+        best,
+        solved,
+        depth: result.as_ref().map_or(0, |r| r.depth),
+        nodes: result.as_ref().map_or(0, |r| r.nodes),
+        time_ms: result.as_ref().map_or(0, |r| r.time_ms),
+        solve_time_ms: since.map(|s| s.0),
+        solve_nodes: since.map(|s| s.1),
+        solve_depth: since.map(|s| s.2),
+    }
+}
+
+// Zerlegt `opcode operand ...;` in (opcode, operanden); Strings in `"` dürfen `;` enthalten.
+fn parse_operations(text: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let mut ops = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => {
+                if !tokens.is_empty() {
+                    let code = tokens.remove(0);
+                    ops.push((code, std::mem::take(&mut tokens)));
+                }
+            }
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => s.push(c),
+                        None => return Err(format!("epd: offener String in {}", text)),
+                    }
+                }
+                tokens.push(s);
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut s = c.to_string();
+                while let Some(&n) = chars.peek() {
+                    if n.is_whitespace() || n == ';' || n == '"' {
+                        break;
+                    }
+                    s.push(n);
+                    chars.next();
+                }
+                tokens.push(s);
+            }
+        }
+    }
+    // Letzte Operation ohne abschließendes `;` (kommt in der Praxis vor).
+    if !tokens.is_empty() {
+        let code = tokens.remove(0);
+        ops.push((code, tokens));
+    }
+    Ok(ops)
+}
+
+// SAN (oder bereits UCI) -> UCI in `pos`.
+fn san_to_uci(pos: &mut Position, text: &str) -> Option<String> {
+    let (mv, promo) = parse_san(pos, text).or_else(|| parse_uci_move(pos, text))?;
+    Some(move_to_uci(mv, promo))
+}
+
+// STS-Punktetabelle `"f5=10, Be5+=2, Bf2=3"`; leer, wenn `c0` keine gültige Tabelle ist.
+fn parse_points(pos: &mut Position, comment: &str) -> Vec<(String, u32)> {
+    let mut points = Vec::new();
+    for item in comment.split(',') {
+        let Some((san, value)) = item.rsplit_once('=') else { return Vec::new() };
+        let (Some(uci), Ok(value)) = (san_to_uci(pos, san.trim()), value.trim().parse()) else {
+            return Vec::new();
+        };
+        points.push((uci, value));
+    }
+    points
+}
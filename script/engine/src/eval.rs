@@ -0,0 +1,535 @@
+// =====================================================================================
+// Bewertung
+// -------------------------------------------------------------------------------------
+// Tapered Eval (MG/EG) aus Material, PST, Bauernstruktur und King Safety;
+// Grundendspiele bewertet `endgame` gesondert.
+// Scores sind stets aus Sicht von `side_to_move`.
+// =====================================================================================
+
+use crate::bitboard::*;
+use crate::endgame::*;
+use crate::position::*;
+use crate::tune::*;
+
+const MAX_PHASE: i32 = 24;
+
+// ---------------------------
+// Eval: Material + PST (MG/EG)
+// ---------------------------
+const MG_VALUES: [i32; 6] = [100, 320, 330, 500, 900, 0];
+const EG_VALUES: [i32; 6] = [120, 300, 320, 510, 900, 0];
+const PHASE_VALUES: [i32; 6] = [0, 1, 1, 2, 4, 0];
+
+const MG_PST_PAWN: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10,-10,-10, 10, 10,  5,
+     5,  5, 10, 15, 15, 10,  5,  5,
+     0,  0, 10, 20, 20, 10,  0,  0,
+     5,  5, 10, 25, 25, 10,  5,  5,
+    10, 10, 20, 30, 30, 20, 10, 10,
+    50, 50, 50, 50, 50, 50, 50, 50,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+const EG_PST_PAWN: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5,  5,  5,  5,  5,  5,  5,  5,
+    10, 10, 10, 10, 10, 10, 10, 10,
+    20, 20, 20, 20, 20, 20, 20, 20,
+    40, 40, 40, 40, 40, 40, 40, 40,
+    70, 70, 70, 70, 70, 70, 70, 70,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+const MG_PST_KNIGHT: [i32; 64] = [
+   -50,-40,-30,-30,-30,-30,-40,-50,
+   -40,-20,  0,  0,  0,  0,-20,-40,
+   -30,  0, 10, 15, 15, 10,  0,-30,
+   -30,  5, 15, 20, 20, 15,  5,-30,
+   -30,  0, 15, 20, 20, 15,  0,-30,
+   -30,  5, 10, 15, 15, 10,  5,-30,
+   -40,-20,  0,  5,  5,  0,-20,-40,
+   -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+const EG_PST_KNIGHT: [i32; 64] = [
+   -40,-30,-20,-20,-20,-20,-30,-40,
+   -30,-10,  0,  0,  0,  0,-10,-30,
+   -20,  0, 10, 10, 10, 10,  0,-20,
+   -20,  5, 10, 15, 15, 10,  5,-20,
+   -20,  0, 10, 15, 15, 10,  0,-20,
+   -20,  5, 10, 10, 10, 10,  5,-20,
+   -30,-10,  0,  5,  5,  0,-10,-30,
+   -40,-30,-20,-20,-20,-20,-30,-40,
+];
+
+const MG_PST_BISHOP: [i32; 64] = [
+   -20,-10,-10,-10,-10,-10,-10,-20,
+   -10,  0,  0,  0,  0,  0,  0,-10,
+   -10,  0,  5, 10, 10,  5,  0,-10,
+   -10,  5,  5, 10, 10,  5,  5,-10,
+   -10,  0, 10, 10, 10, 10,  0,-10,
+   -10, 10, 10, 10, 10, 10, 10,-10,
+   -10,  5,  0,  0,  0,  0,  5,-10,
+   -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+const EG_PST_BISHOP: [i32; 64] = [
+   -10, -5, -5, -5, -5, -5, -5,-10,
+    -5,  5,  0,  0,  0,  0,  5, -5,
+    -5,  0, 10, 10, 10, 10,  0, -5,
+    -5,  5, 10, 15, 15, 10,  5, -5,
+    -5,  0, 10, 15, 15, 10,  0, -5,
+    -5,  5, 10, 10, 10, 10,  5, -5,
+    -5,  5,  0,  0,  0,  0,  5, -5,
+   -10, -5, -5, -5, -5, -5, -5,-10,
+];
+
+const MG_PST_ROOK: [i32; 64] = [
+     0,  0,  5, 10, 10,  5,  0,  0,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     5, 10, 10, 10, 10, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+const EG_PST_ROOK: [i32; 64] = [
+     0,  0,  5, 10, 10,  5,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+     0,  0,  5,  5,  5,  5,  0,  0,
+     0,  0,  5,  5,  5,  5,  0,  0,
+     0,  0,  5,  5,  5,  5,  0,  0,
+     0,  0,  5,  5,  5,  5,  0,  0,
+     0,  0,  5, 10, 10,  5,  0,  0,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+const MG_PST_QUEEN: [i32; 64] = [
+   -20,-10,-10, -5, -5,-10,-10,-20,
+   -10,  0,  0,  0,  0,  0,  0,-10,
+   -10,  0,  5,  5,  5,  5,  0,-10,
+    -5,  0,  5,  5,  5,  5,  0, -5,
+     0,  0,  5,  5,  5,  5,  0, -5,
+   -10,  5,  5,  5,  5,  5,  0,-10,
+   -10,  0,  5,  0,  0,  0,  0,-10,
+   -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+const EG_PST_QUEEN: [i32; 64] = [
+   -10, -5, -5, -5, -5, -5, -5,-10,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  5,  5,  5,  5,  0, -5,
+    -5,  0,  5,  5,  5,  5,  0, -5,
+    -5,  0,  5,  5,  5,  5,  0, -5,
+    -5,  0,  5,  5,  5,  5,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+   -10, -5, -5, -5, -5, -5, -5,-10,
+];
+
+const MG_PST_KING: [i32; 64] = [
+   -30,-40,-40,-50,-50,-40,-40,-30,
+   -30,-40,-40,-50,-50,-40,-40,-30,
+   -40,-50,-50,-60,-60,-50,-50,-40,
+   -50,-60,-60,-70,-70,-60,-60,-50,
+   -50,-60,-60,-70,-70,-60,-60,-50,
+   -40,-50,-50,-60,-60,-50,-50,-40,
+   -30,-40,-40,-50,-50,-40,-40,-30,
+   -30,-40,-40,-50,-50,-40,-40,-30,
+];
+
+const EG_PST_KING: [i32; 64] = [
+   -10, -5,  0,  5,  5,  0, -5,-10,
+    -5,  5, 10, 15, 15, 10,  5, -5,
+     0, 10, 20, 25, 25, 20, 10,  0,
+     5, 15, 25, 30, 30, 25, 15,  5,
+     5, 15, 25, 30, 30, 25, 15,  5,
+     0, 10, 20, 25, 25, 20, 10,  0,
+    -5,  5, 10, 15, 15, 10,  5, -5,
+   -10, -5,  0,  5,  5,  0, -5,-10,
+];
+
+const MG_PST: [[i32; 64]; 6] = [
+    MG_PST_PAWN,
+    MG_PST_KNIGHT,
+    MG_PST_BISHOP,
+    MG_PST_ROOK,
+    MG_PST_QUEEN,
+    MG_PST_KING,
+];
+
+const EG_PST: [[i32; 64]; 6] = [
+    EG_PST_PAWN,
+    EG_PST_KNIGHT,
+    EG_PST_BISHOP,
+    EG_PST_ROOK,
+    EG_PST_QUEEN,
+    EG_PST_KING,
+];
+
+// Was: Führt `piece_value` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn piece_value(piece: char) -> i32 {
+    match piece.to_ascii_lowercase() {
+        'p' => 100,
+        'n' => 320,
+        'b' => 330,
+        'r' => 500,
+        'q' => 900,
+        'k' => 0,
+        _ => 0,
+    }
+}
+
+// Was: Führt `blend` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn blend(mg: i32, eg: i32, phase: i32) -> i32 {
+    (mg * phase + eg * (MAX_PHASE - phase)) / MAX_PHASE
+}
+
+// Was: Führt `compute_phase` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn compute_phase(pos: &Position) -> i32 {
+    let mut phase = 0;
+    phase += popcnt(pos.bb.wn) * PHASE_VALUES[1];
+    phase += popcnt(pos.bb.bn) * PHASE_VALUES[1];
+    phase += popcnt(pos.bb.wb) * PHASE_VALUES[2];
+    phase += popcnt(pos.bb.bb) * PHASE_VALUES[2];
+    phase += popcnt(pos.bb.wr) * PHASE_VALUES[3];
+    phase += popcnt(pos.bb.br) * PHASE_VALUES[3];
+    phase += popcnt(pos.bb.wq) * PHASE_VALUES[4];
+    phase += popcnt(pos.bb.bq) * PHASE_VALUES[4];
+    if phase > MAX_PHASE { MAX_PHASE } else { phase }
+}
+
+// Was: Addiert Material- und PST-Beiträge einer Figurenmenge in Midgame/Endgame-Summen.
+// Warum: Entkoppelt die wiederkehrende Stücklisten-Auswertung aus der Haupt-Evaluation und reduziert Duplikate.
+// Kosten: Linear in der Anzahl gesetzter Bits des übergebenen Bitboards (`O(popcnt(bb))`).
+fn add_piece_scores(
+    material_mg: &mut i32,
+    material_eg: &mut i32,
+    pst_mg: &mut i32,
+    pst_eg: &mut i32,
+    mut bb: u64,
+    piece_idx: usize,
+    is_white: bool,
+) {
+    let sign = if is_white { 1 } else { -1 };
+    while bb != 0 {
+        let sq = pop_lsb(&mut bb);
+        let psq = if is_white { sq } else { mirror_sq(sq) };
+        *material_mg += sign * MG_VALUES[piece_idx];
+        *material_eg += sign * EG_VALUES[piece_idx];
+        *pst_mg += sign * MG_PST[piece_idx][psq as usize];
+        *pst_eg += sign * EG_PST[piece_idx][psq as usize];
+    }
+}
+
+const DOUBLED_PAWN_MG: i32 = -12;
+const DOUBLED_PAWN_EG: i32 = -8;
+const ISOLATED_PAWN_MG: i32 = -15;
+const ISOLATED_PAWN_EG: i32 = -10;
+const CONNECTED_PASSED_MG: i32 = 8;
+const CONNECTED_PASSED_EG: i32 = 15;
+const SPACE_PAWN_MG: i32 = 5;
+
+const PASSED_BONUS_MG: [i32; 8] = [0, 5, 10, 20, 30, 40, 60, 0];
+const PASSED_BONUS_EG: [i32; 8] = [0, 10, 20, 40, 60, 80, 120, 0];
+
+// Was: Führt `pawn_features` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn pawn_features(pos: &Position, color: Color) -> (i32, i32) {
+    let pawns = if color == Color::White { pos.bb.wp } else { pos.bb.bp };
+    let enemy_pawns = if color == Color::White { pos.bb.bp } else { pos.bb.wp };
+
+    let mut mg = 0;
+    let mut eg = 0;
+
+    // Doubled / isolated
+    for file in 0..8 {
+        let file_mask = FILE_MASKS[file];
+        let pawns_on_file = pawns & file_mask;
+        let count = popcnt(pawns_on_file);
+        if count > 1 {
+            let extra = count - 1;
+            mg += extra * DOUBLED_PAWN_MG;
+            eg += extra * DOUBLED_PAWN_EG;
+        }
+        if pawns_on_file != 0 {
+            let adj = pawns & ADJ_FILE_MASKS[file];
+            if adj == 0 {
+                mg += count * ISOLATED_PAWN_MG;
+                eg += count * ISOLATED_PAWN_EG;
+            }
+        }
+    }
+
+    // Passed pawns + connected passed
+    let mut passed = 0u64;
+    let mut pawns_bb = pawns;
+    while pawns_bb != 0 {
+        let sq = pop_lsb(&mut pawns_bb);
+        let mask = if color == Color::White {
+            PASSED_MASKS_WHITE[sq as usize]
+        } else {
+            PASSED_MASKS_BLACK[sq as usize]
+        };
+        if (enemy_pawns & mask) == 0 {
+            passed |= bb(sq);
+            let rank = (sq / 8) as i32;
+            let r = if color == Color::White { rank } else { 7 - rank };
+            let idx = r.clamp(0, 7) as usize;
+            mg += PASSED_BONUS_MG[idx];
+            eg += PASSED_BONUS_EG[idx];
+        }
+    }
+
+    let connected = passed & (shift_east(passed) | shift_west(passed));
+    let connected_count = popcnt(connected);
+    mg += connected_count * CONNECTED_PASSED_MG;
+    eg += connected_count * CONNECTED_PASSED_EG;
+
+    // Space bonus (small, MG only)
+    let space_mask = if color == Color::White { RANK_5 | RANK_6 } else { RANK_4 | RANK_3 };
+    let space_count = popcnt(pawns & space_mask);
+    mg += space_count * SPACE_PAWN_MG;
+
+    (mg, eg)
+}
+
+// Was: Führt `pawn_structure_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn pawn_structure_score(pos: &Position) -> (i32, i32) {
+    let (w_mg, w_eg) = pawn_features(pos, Color::White);
+    let (b_mg, b_eg) = pawn_features(pos, Color::Black);
+    (w_mg - b_mg, w_eg - b_eg)
+}
+
+// KING_PRESSURE_MG/EG und PAWN_SHIELD_MG/EG sind Tuning-Parameter (siehe `tune`).
+const PAWN_FILE_HALF_OPEN_MG: i32 = 6;
+const PAWN_FILE_OPEN_MG: i32 = 10;
+
+// Was: Führt `attacks_for_color` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn attacks_for_color(pos: &Position, color: Color) -> u64 {
+    let occ = pos.bb.occ;
+    let (pawns, knights, bishops, rooks, queens, king_sq) = match color {
+        Color::White => (pos.bb.wp, pos.bb.wn, pos.bb.wb, pos.bb.wr, pos.bb.wq, pos.bb.white_king_sq),
+        Color::Black => (pos.bb.bp, pos.bb.bn, pos.bb.bb, pos.bb.br, pos.bb.bq, pos.bb.black_king_sq),
+    };
+
+    let pawn_attacks = match color {
+        Color::White => ((pawns & !FILE_H) << 9) | ((pawns & !FILE_A) << 7),
+        Color::Black => ((pawns & !FILE_A) >> 9) | ((pawns & !FILE_H) >> 7),
+    };
+
+    let mut attacks = pawn_attacks | KING_ATTACKS[king_sq as usize];
+
+    let mut bb = knights;
+    while bb != 0 {
+        let sq = pop_lsb(&mut bb) as usize;
+        attacks |= KNIGHT_ATTACKS[sq];
+    }
+
+    let mut bb = bishops;
+    while bb != 0 {
+        let sq = pop_lsb(&mut bb);
+        attacks |= bishop_attacks(sq, occ);
+    }
+
+    let mut bb = rooks;
+    while bb != 0 {
+        let sq = pop_lsb(&mut bb);
+        attacks |= rook_attacks(sq, occ);
+    }
+
+    let mut bb = queens;
+    while bb != 0 {
+        let sq = pop_lsb(&mut bb);
+        attacks |= queen_attacks(sq, occ);
+    }
+
+    attacks
+}
+
+// Was: Führt `king_safety_for` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn king_safety_for(pos: &Position, color: Color, tune: &TuneValues) -> (i32, i32) {
+    let king_sq = if color == Color::White { pos.bb.white_king_sq } else { pos.bb.black_king_sq };
+    let enemy_attacks = attacks_for_color(pos, color.opposite());
+    let pressure = popcnt(enemy_attacks & KING_ZONE[king_sq as usize]);
+    let mut mg = -pressure * tune.value(KING_PRESSURE_MG);
+    let mut eg = -pressure * tune.value(KING_PRESSURE_EG);
+
+    // Pawn shield
+    let pawns = if color == Color::White { pos.bb.wp } else { pos.bb.bp };
+    let enemy_pawns = if color == Color::White { pos.bb.bp } else { pos.bb.wp };
+    let file = (king_sq % 8) as usize;
+    let shield_files = FILE_MASKS[file] | ADJ_FILE_MASKS[file];
+    let shield_ranks = if color == Color::White { RANK_2 | RANK_3 } else { RANK_7 | RANK_6 };
+    let shield_mask = shield_files & shield_ranks;
+    let expected = if file == 0 || file == 7 { 2 } else { 3 };
+    let shield_count = popcnt(pawns & shield_mask);
+    let missing = expected - shield_count;
+    if missing > 0 {
+        mg -= missing * tune.value(PAWN_SHIELD_MG);
+        eg -= missing * tune.value(PAWN_SHIELD_EG);
+    }
+
+    // Open / half-open files near king
+    for df in [-1i32, 0, 1] {
+        let f = file as i32 + df;
+        if !(0..=7).contains(&f) {
+            continue;
+        }
+        let fmask = FILE_MASKS[f as usize];
+        if (pawns & fmask) == 0 {
+            if (enemy_pawns & fmask) == 0 {
+                mg -= PAWN_FILE_OPEN_MG;
+            } else {
+                mg -= PAWN_FILE_HALF_OPEN_MG;
+            }
+        }
+    }
+
+    (mg, eg)
+}
+
+// Was: Führt `king_safety_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn king_safety_score(pos: &Position, tune: &TuneValues) -> (i32, i32) {
+    let (w_mg, w_eg) = king_safety_for(pos, Color::White, tune);
+    let (b_mg, b_eg) = king_safety_for(pos, Color::Black, tune);
+    (w_mg - b_mg, w_eg - b_eg)
+}
+
+// Einzelterme der Bewertung; nur der Root-Eval-Debug des `wasm`-Features liest sie aus.
+#[derive(Copy, Clone)]
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+pub(crate) struct EvalBreakdown {
+    pub(crate) material: i32,
+    pub(crate) pst: i32,
+    pub(crate) pawn: i32,
+    pub(crate) king: i32,
+    pub(crate) misc: i32,
+    pub(crate) total: i32,
+}
+
+// Was: Führt `negate_breakdown` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+pub(crate) fn negate_breakdown(bd: EvalBreakdown) -> EvalBreakdown {
+    EvalBreakdown {
+        material: -bd.material,
+        pst: -bd.pst,
+        pawn: -bd.pawn,
+        king: -bd.king,
+        misc: -bd.misc,
+        total: -bd.total,
+    }
+}
+
+// Was: Führt `evaluate_breakdown` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn evaluate_breakdown(pos: &Position, tune: &TuneValues) -> EvalBreakdown {
+    let mut material_mg = 0;
+    let mut material_eg = 0;
+    let mut pst_mg = 0;
+    let mut pst_eg = 0;
+
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wp, 0, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wn, 1, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wb, 2, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wr, 3, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wq, 4, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wk, 5, true);
+
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bp, 0, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bn, 1, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bb, 2, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.br, 3, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bq, 4, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bk, 5, false);
+
+    let (pawn_mg, pawn_eg) = pawn_structure_score(pos);
+    let (king_mg, king_eg) = king_safety_score(pos, tune);
+
+    let phase = compute_phase(pos);
+    let material = blend(material_mg, material_eg, phase);
+    let pst = blend(pst_mg, pst_eg, phase);
+    let pawn = blend(pawn_mg, pawn_eg, phase);
+    let king = blend(king_mg, king_eg, phase);
+    // Spezialbewertung eines Grundendspiels ersetzt die Summe der übrigen Terme.
+    let misc = match endgame_score(pos) {
+        Some(score) => score - (material + pst + pawn + king),
+        None => 0,
+    };
+
+    let mut total = material + pst + pawn + king + misc;
+    let sign = if pos.side_to_move == Color::White { 1 } else { -1 };
+    total *= sign;
+
+    EvalBreakdown {
+        material: material * sign,
+        pst: pst * sign,
+        pawn: pawn * sign,
+        king: king * sign,
+        misc: misc * sign,
+        total,
+    }
+}
+
+// Was: Führt `evaluate_fast` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn evaluate_fast(pos: &Position) -> i32 {
+    let sign = if pos.side_to_move == Color::White { 1 } else { -1 };
+    if let Some(score) = endgame_score(pos) {
+        return score * sign;
+    }
+
+    let mut material_mg = 0;
+    let mut material_eg = 0;
+    let mut pst_mg = 0;
+    let mut pst_eg = 0;
+
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wp, 0, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wn, 1, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wb, 2, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wr, 3, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wq, 4, true);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.wk, 5, true);
+
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bp, 0, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bn, 1, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bb, 2, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.br, 3, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bq, 4, false);
+    add_piece_scores(&mut material_mg, &mut material_eg, &mut pst_mg, &mut pst_eg, pos.bb.bk, 5, false);
+
+    let phase = compute_phase(pos);
+    let material = blend(material_mg, material_eg, phase);
+    let pst = blend(pst_mg, pst_eg, phase);
+
+    (material + pst) * sign
+}
+
+// Was: Führt `evaluate` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn evaluate(pos: &Position, tune: &TuneValues) -> i32 {
+    evaluate_breakdown(pos, tune).total
+}
@@ -0,0 +1,162 @@
+// =====================================================================================
+// Mattlöser
+// -------------------------------------------------------------------------------------
+// Beweist "Matt in N" für die Seite am Zug. `negamax` läuft dazu im Mattmodus
+// (`SearchContext::mate_mode`): alle Verteidigungen auf voller Tiefe, keine Reduktionen,
+// Blätter nur "matt" oder 0. Findet die vollständige Suche kein Matt, ist das ein Beweis.
+// =====================================================================================
+
+use crate::movegen::*;
+use crate::platform::*;
+use crate::position::*;
+use crate::search::*;
+use crate::tt::*;
+use crate::zobrist::*;
+
+// TT-Größe, wenn `SearchLimits::tt_mb` 0 ist; ohne TT explodieren Mattnetze mit Zugumstellungen.
+const DEFAULT_MATE_TT_MB: u32 = 16;
+
+// Ergebnis von `solve_mate`.
+#[derive(Clone, Debug)]
+pub struct MateResult {
+    // Kürzestes erzwungenes Matt in Zügen des Angreifers; None = keines innerhalb der Grenze.
+    pub mate_in: Option<u32>,
+    // Mattführung in UCI; der Verteidiger wählt jeweils die längste Gegenwehr.
+    pub line: Vec<String>,
+    // false, wenn Zeit-/Knotenlimit oder Stop die Suche beendet haben. Dann ist
+    // "kein Matt" nur bis `searched` Züge bewiesen.
+    pub complete: bool,
+    // Vollständig durchsuchte Zugtiefe (Angreiferzüge).
+    pub searched: u32,
+    pub nodes: u64,
+    pub time_ms: u64,
+}
+
+// Was: Sucht das kürzeste erzwungene Matt der Seite am Zug in höchstens `moves` Zügen.
+// Warum: Puzzle-Sammlungen brauchen einen Beweis samt Lösungszug statt eines hohen Scores;
+// "kein Matt in N" ist bei `complete` definitiv.
+// Kosten: Iterativ 1, 3, .., 2N-1 Halbzüge ohne Pruning außer Alpha-Beta (exponentiell in N);
+// `limits` liefert TT-Größe, Zeit-/Knotenlimit und Stop-Flag; Tiefe, Multi-PV und Tablebases
+// werden ignoriert.
+pub fn solve_mate(
+    fen: &str,
+    moves: u32,
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+    sink: &mut dyn ProgressSink,
+) -> Option<MateResult> {
+    let mut pos = parse_fen(fen)?;
+    let tt_mb = if limits.tt_mb > 0 { limits.tt_mb } else { DEFAULT_MATE_TT_MB };
+    let tt = TT::new(tt_mb);
+    let max_plies = moves.saturating_mul(2).saturating_sub(1);
+
+    Some(ZOBRIST_TABLE.with(|zob| {
+        let hash = compute_hash(&pos, zob);
+        let mut ctx = SearchContext::new(clock, sink, limits, max_plies as usize + 1, vec![hash]);
+        ctx.mate_mode = true;
+        // Tablebase-Werte sind keine Mattdistanzen und würden den Beweis abkürzen: nie proben.
+        ctx.tb_cardinality = 0;
+        // Ohne festes `time_ms` begrenzt die Partieuhr die Mattsuche (hartes Limit).
+        if let Some(game_clock) = limits.game_clock.filter(|_| limits.time_ms == 0) {
+            ctx.set_time_limit(game_clock.limits(pos.side_to_move).hard_ms as f64);
+        }
+        let mut result = MateResult {
+            mate_in: None,
+            line: Vec::new(),
+            complete: true,
+            searched: 0,
+            nodes: 0,
+            time_ms: 0,
+        };
+
+        for k in 1..=moves {
+            let plies = 2 * k - 1;
+            let (score, _) = best_reply(&mut pos, plies, 0, &mut ctx, &tt, zob, hash);
+            if ctx.stop {
+                result.complete = false;
+                break;
+            }
+            result.searched = k;
+            if score >= mate_score(plies as i32) {
+                result.mate_in = Some(k);
+                result.line = mate_line(&mut pos, plies, &mut ctx, &tt, zob, hash);
+                result.complete = !ctx.stop;
+                break;
+            }
+        }
+
+        result.nodes = ctx.nodes;
+        result.time_ms = ctx.elapsed_ms() as u64;
+        result
+    }))
+}
+
+// Was: Bester Zug für die Seite am Zug mit Restiefe `depth` (Halbzüge) bei voller Fensterbreite.
+// Warum: Liefert exakte Mattdistanzen; für den Verteidiger ist das die längste Gegenwehr.
+// Kosten: Eine Mattmodus-Suche je Wurzelzug; auf dem letzten Angreiferzug nur Schachgebote.
+fn best_reply(
+    pos: &mut Position,
+    depth: u32,
+    ply: i32,
+    ctx: &mut SearchContext,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+) -> (i32, Option<(Move, Option<char>)>) {
+    let mut best_score = -INF_SCORE;
+    let mut best = None;
+    for (mv, promo) in generate_legal_moves(pos) {
+        let Some(undo) = make_move_in_place(pos, mv, promo) else { continue };
+        if depth == 1 && !is_in_check(pos, pos.side_to_move) {
+            unmake_move_in_place(pos, mv, promo, undo);
+            continue;
+        }
+        let next_hash = update_hash_after_move(hash, zob, &undo, pos, mv);
+        history_push(ctx, next_hash);
+        let score = -negamax(pos, depth - 1, -INF_SCORE, -best_score, ctx, tt, zob, next_hash, ply + 1);
+        history_pop(ctx);
+        unmake_move_in_place(pos, mv, promo, undo);
+        if ctx.stop {
+            break;
+        }
+        if best.is_none() || score > best_score {
+            best_score = score;
+            best = Some((mv, promo));
+        }
+    }
+    (best_score, best)
+}
+
+// Was: Spielt die Lösung Zug für Zug aus, bis der Verteidiger matt ist.
+// Warum: TT-PVs können nach Ersetzungen lückenhaft sein; erneutes Suchen je Halbzug
+// liefert immer eine vollständige, legale Mattführung.
+// Kosten: Eine `best_reply`-Suche pro Halbzug, großteils aus der TT beantwortet.
+fn mate_line(
+    pos: &mut Position,
+    plies: u32,
+    ctx: &mut SearchContext,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+) -> Vec<String> {
+    let mut line = Vec::new();
+    let mut played = Vec::new();
+    let mut cur_hash = hash;
+    for ply in 0..plies {
+        let (_, best) = best_reply(pos, plies - ply, ply as i32, ctx, tt, zob, cur_hash);
+        let Some((mv, promo)) = best else { break };
+        if ctx.stop {
+            break;
+        }
+        let Some(undo) = make_move_in_place(pos, mv, promo) else { break };
+        line.push(move_to_uci(mv, promo));
+        cur_hash = update_hash_after_move(cur_hash, zob, &undo, pos, mv);
+        history_push(ctx, cur_hash);
+        played.push((mv, promo, undo));
+    }
+    while let Some((mv, promo, undo)) = played.pop() {
+        history_pop(ctx);
+        unmake_move_in_place(pos, mv, promo, undo);
+    }
+    line
+}
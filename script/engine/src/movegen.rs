@@ -0,0 +1,935 @@
+// =====================================================================================
+// Zuggenerierung
+// -------------------------------------------------------------------------------------
+// Angriffs-/Schachprüfung, Pseudo- und Legal-Zuggenerierung, Make/Unmake
+// und die Umwandlung zwischen `Move` und UCI-Notation.
+// =====================================================================================
+
+use crate::bitboard::*;
+use crate::position::*;
+use crate::zobrist::*;
+
+// ---------------------------
+// Angriffs- und Schachprüfung
+// ---------------------------
+// Prüft, ob ein Feld `sq` von der Farbe `by` angegriffen wird.
+// Mischt Pawn-, Knight-, King- und Sliding-Angriffe.
+// Wird für Schach, Rochade und Legalitätsprüfung verwendet.
+// Was: Führt `is_square_attacked` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn is_square_attacked(pos: &Position, sq: u8, by: Color) -> bool {
+    let occ = pos.bb.occ;
+    let i = sq as usize;
+
+    // Bauernangriffe über Bitboards
+    let pawn_attacks = match by {
+        Color::White => {
+            ((pos.bb.wp & !FILE_H) << 9) | ((pos.bb.wp & !FILE_A) << 7)
+        }
+        Color::Black => {
+            ((pos.bb.bp & !FILE_A) >> 9) | ((pos.bb.bp & !FILE_H) >> 7)
+        }
+    };
+    if (pawn_attacks & bb(sq)) != 0 {
+        return true;
+    }
+
+    // Springer
+    let knights = match by {
+        Color::White => pos.bb.wn,
+        Color::Black => pos.bb.bn,
+    };
+    if (KNIGHT_ATTACKS[i] & knights) != 0 {
+        return true;
+    }
+
+    // König
+    let king = match by {
+        Color::White => pos.bb.wk,
+        Color::Black => pos.bb.bk,
+    };
+    if (KING_ATTACKS[i] & king) != 0 {
+        return true;
+    }
+
+    // Läufer/Dame
+    let bishops = match by {
+        Color::White => pos.bb.wb | pos.bb.wq,
+        Color::Black => pos.bb.bb | pos.bb.bq,
+    };
+    if (bishop_attacks(sq, occ) & bishops) != 0 {
+        return true;
+    }
+
+    // Türme/Dame
+    let rooks = match by {
+        Color::White => pos.bb.wr | pos.bb.wq,
+        Color::Black => pos.bb.br | pos.bb.bq,
+    };
+    if (rook_attacks(sq, occ) & rooks) != 0 {
+        return true;
+    }
+
+    false
+}
+
+// Prüft, ob der König der gegebenen Farbe aktuell im Schach steht.
+// Nutzt die vorab gespeicherte König-Position aus Bitboards.
+// Was: Führt `is_in_check` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn is_in_check(pos: &Position, color: Color) -> bool {
+    let king_sq = match color {
+        Color::White => pos.bb.white_king_sq,
+        Color::Black => pos.bb.black_king_sq,
+    };
+    is_square_attacked(pos, king_sq, color.opposite())
+}
+
+// ---------------------------
+// Zugerzeugung (Pseudo + Legal)
+// ---------------------------
+// Fügt alle gesetzten Bits als Standardzüge hinzu.
+// `from` ist das Startfeld der Figur, `bb` enthält Ziel-Felder.
+// Spezialzüge (Rochade/EP/Promotion) werden separat behandelt.
+// Was: Führt `push_moves_from_bb` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn push_moves_from_bb(moves: &mut Vec<Move>, from: u8, mut bb: u64) {
+    while bb != 0 {
+        let lsb = bb & bb.wrapping_neg();
+        let idx = lsb.trailing_zeros() as u8;
+        moves.push(Move { from, to: idx, kind: MoveKind::Normal });
+        bb ^= lsb;
+    }
+}
+
+// Prüft, ob eine kurze Rochade zulässig ist.
+// Bedingungen: Rechte vorhanden, König/Turm korrekt, Felder frei,
+// und keine der König-Durchlauf-Felder im Schach.
+// Was: Führt `can_castle_kingside` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn can_castle_kingside(pos: &Position, color: Color, from: u8) -> bool {
+    match color {
+        Color::White => {
+            // König muss auf e1 stehen und Rochaderecht vorhanden
+            if from != 4 { return false; }
+            if (pos.castling & CASTLE_WK) == 0 { return false; }
+            if pos.board[7] != Some('R') { return false; }
+            if pos.board[5].is_some() || pos.board[6].is_some() { return false; }
+            if is_square_attacked(pos, 4, Color::Black) { return false; }
+            if is_square_attacked(pos, 5, Color::Black) { return false; }
+            if is_square_attacked(pos, 6, Color::Black) { return false; }
+            true
+        }
+        Color::Black => {
+            // König muss auf e8 stehen und Rochaderecht vorhanden
+            if from != 60 { return false; }
+            if (pos.castling & CASTLE_BK) == 0 { return false; }
+            if pos.board[63] != Some('r') { return false; }
+            if pos.board[61].is_some() || pos.board[62].is_some() { return false; }
+            if is_square_attacked(pos, 60, Color::White) { return false; }
+            if is_square_attacked(pos, 61, Color::White) { return false; }
+            if is_square_attacked(pos, 62, Color::White) { return false; }
+            true
+        }
+    }
+}
+
+// Prüft, ob eine lange Rochade zulässig ist.
+// Zusätzliche Bedingung: b-Feld darf belegt sein, aber nicht die Durchlauf-Felder.
+// (Hier prüfen wir: b, c, d müssen leer sein).
+// Was: Führt `can_castle_queenside` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn can_castle_queenside(pos: &Position, color: Color, from: u8) -> bool {
+    match color {
+        Color::White => {
+            // König muss auf e1 stehen und Rochaderecht vorhanden
+            if from != 4 { return false; }
+            if (pos.castling & CASTLE_WQ) == 0 { return false; }
+            if pos.board[0] != Some('R') { return false; }
+            if pos.board[1].is_some() || pos.board[2].is_some() || pos.board[3].is_some() { return false; }
+            if is_square_attacked(pos, 4, Color::Black) { return false; }
+            if is_square_attacked(pos, 3, Color::Black) { return false; }
+            if is_square_attacked(pos, 2, Color::Black) { return false; }
+            true
+        }
+        Color::Black => {
+            // König muss auf e8 stehen und Rochaderecht vorhanden
+            if from != 60 { return false; }
+            if (pos.castling & CASTLE_BQ) == 0 { return false; }
+            if pos.board[56] != Some('r') { return false; }
+            if pos.board[57].is_some() || pos.board[58].is_some() || pos.board[59].is_some() { return false; }
+            if is_square_attacked(pos, 60, Color::White) { return false; }
+            if is_square_attacked(pos, 59, Color::White) { return false; }
+            if is_square_attacked(pos, 58, Color::White) { return false; }
+            true
+        }
+    }
+}
+
+// Erzeugt alle Bauern-Pseudozüge von einem Feld.
+// Enthält Vorwärtszüge, Doppelzug, Schläge, Promotion und En-passant.
+// Legalitätsprüfung passiert später.
+// Was: Führt `gen_pawn_moves` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn gen_pawn_moves(pos: &Position, from: u8, color: Color, moves: &mut Vec<Move>) {
+    let occ = pos.bb.occ;
+    let from_i = from as i16;
+    let file = (from % 8) as i16;
+    let rank = (from / 8) as i16;
+
+    let dir: i16 = match color {
+        Color::White => 8,
+        Color::Black => -8,
+    };
+
+    let promotion_rank: i16 = match color {
+        Color::White => 7,
+        Color::Black => 0,
+    };
+
+    // 1 Schritt vorwärts
+    let one = from_i + dir;
+    if (0..64).contains(&one) {
+        let one_sq = one as u8;
+        if (occ & bb(one_sq)) == 0 {
+            let one_rank = (one_sq / 8) as i16;
+            let kind = if one_rank == promotion_rank { MoveKind::Promotion } else { MoveKind::Normal };
+            moves.push(Move { from, to: one_sq, kind });
+
+            // 2 Schritte (Startreihe)
+            let start_rank = if color == Color::White { 1 } else { 6 };
+            if rank == start_rank {
+                let two = from_i + (dir * 2);
+                if (0..64).contains(&two) {
+                    let two_sq = two as u8;
+                    if (occ & bb(two_sq)) == 0 {
+                        moves.push(Move { from, to: two_sq, kind: MoveKind::Normal });
+                    }
+                }
+            }
+        }
+    }
+
+    // Schlagen (Diagonalen)
+    let (cap_left, cap_right) = match color {
+        Color::White => (from_i + 7, from_i + 9),
+        Color::Black => (from_i - 9, from_i - 7),
+    };
+
+    if file > 0 && (0..64).contains(&cap_left) {
+        let cap_sq = cap_left as u8;
+        let is_enemy = match color {
+            Color::White => (pos.bb.black_occ & bb(cap_sq)) != 0,
+            Color::Black => (pos.bb.white_occ & bb(cap_sq)) != 0,
+        };
+        if is_enemy {
+            let cap_rank = (cap_sq / 8) as i16;
+            let kind = if cap_rank == promotion_rank { MoveKind::Promotion } else { MoveKind::Normal };
+            moves.push(Move { from, to: cap_sq, kind });
+        }
+    }
+
+    if file < 7 && (0..64).contains(&cap_right) {
+        let cap_sq = cap_right as u8;
+        let is_enemy = match color {
+            Color::White => (pos.bb.black_occ & bb(cap_sq)) != 0,
+            Color::Black => (pos.bb.white_occ & bb(cap_sq)) != 0,
+        };
+        if is_enemy {
+            let cap_rank = (cap_sq / 8) as i16;
+            let kind = if cap_rank == promotion_rank { MoveKind::Promotion } else { MoveKind::Normal };
+            moves.push(Move { from, to: cap_sq, kind });
+        }
+    }
+
+    // En passant (nur wenn Datei-Grenzen passen, kein Wrap-around)
+    if let Some(ep_sq) = pos.ep {
+        let allow = (file > 0 && ep_sq as i16 == cap_left) || (file < 7 && ep_sq as i16 == cap_right);
+
+        if allow {
+            let cap_field = if color == Color::White {
+                (ep_sq as i16 - 8) as u8
+            } else {
+                (ep_sq as i16 + 8) as u8
+            };
+            let has_enemy_pawn = match color {
+                Color::White => (pos.bb.bp & bb(cap_field)) != 0,
+                Color::Black => (pos.bb.wp & bb(cap_field)) != 0,
+            };
+            if has_enemy_pawn {
+                moves.push(Move { from, to: ep_sq, kind: MoveKind::EnPassant });
+            }
+        }
+    }
+}
+
+// Erzeugt alle Pseudozüge einer Figur auf `from` in `out`.
+// Filtert auf „side to move“ und ergänzt Rochade beim König.
+// Legalitätsprüfung erfolgt separat.
+// Was: Führt `generate_moves_for_piece_into` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn generate_moves_for_piece_into(pos: &Position, from: u8, out: &mut Vec<Move>) {
+    out.clear();
+    let idx = from as usize;
+    let Some(piece) = pos.board[idx] else { return; };
+    let color = piece_color(piece);
+
+    // Nur Züge der Seite am Zug
+    if color != pos.side_to_move {
+        return;
+    }
+
+    let own_occ = match color {
+        Color::White => pos.bb.white_occ,
+        Color::Black => pos.bb.black_occ,
+    };
+
+    match piece.to_ascii_lowercase() {
+        'p' => {
+            gen_pawn_moves(pos, from, color, out);
+        }
+        'n' => {
+            let attacks = KNIGHT_ATTACKS[idx] & !own_occ;
+            push_moves_from_bb(out, from, attacks);
+        }
+        'b' => {
+            let attacks = bishop_attacks(from, pos.bb.occ) & !own_occ;
+            push_moves_from_bb(out, from, attacks);
+        }
+        'r' => {
+            let attacks = rook_attacks(from, pos.bb.occ) & !own_occ;
+            push_moves_from_bb(out, from, attacks);
+        }
+        'q' => {
+            let attacks = queen_attacks(from, pos.bb.occ) & !own_occ;
+            push_moves_from_bb(out, from, attacks);
+        }
+        'k' => {
+            let attacks = KING_ATTACKS[idx] & !own_occ;
+            push_moves_from_bb(out, from, attacks);
+
+            if can_castle_kingside(pos, color, from) {
+                let to = if color == Color::White { 6 } else { 62 };
+                out.push(Move { from, to, kind: MoveKind::Castle });
+            }
+            if can_castle_queenside(pos, color, from) {
+                let to = if color == Color::White { 2 } else { 58 };
+                out.push(Move { from, to, kind: MoveKind::Castle });
+            }
+        }
+        _ => {}
+    }
+}
+
+// Wendet einen Zug direkt auf das Board an.
+// Behandelt Sonderzüge (Rochade, En-passant, Promotion).
+// Macht keine Legalitätsprüfung.
+// Was: Führt `apply_move_to_board` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn apply_move_to_board(board: &mut [Option<char>; 64], mv: Move, color: Color, promotion: Option<char>) {
+    let from = mv.from as usize;
+    let to = mv.to as usize;
+    let Some(piece) = board[from] else { return; };
+
+    // Board-Update ohne weitere Validierung
+    match mv.kind {
+        MoveKind::Normal => {
+            board[to] = Some(piece);
+            board[from] = None;
+        }
+        MoveKind::Promotion => {
+            let promo = promotion.unwrap_or('q').to_ascii_lowercase();
+            let mut placed = match promo {
+                'q' | 'r' | 'b' | 'n' => promo,
+                _ => 'q',
+            };
+            if piece.is_ascii_uppercase() {
+                placed = placed.to_ascii_uppercase();
+            }
+            board[to] = Some(placed);
+            board[from] = None;
+        }
+        MoveKind::EnPassant => {
+            board[to] = Some(piece);
+            board[from] = None;
+            let cap_sq = if color == Color::White { mv.to - 8 } else { mv.to + 8 };
+            board[cap_sq as usize] = None;
+        }
+        MoveKind::Castle => {
+            board[to] = Some(piece);
+            board[from] = None;
+
+            if color == Color::White {
+                if mv.to == 6 {
+                    board[5] = board[7];
+                    board[7] = None;
+                } else if mv.to == 2 {
+                    board[3] = board[0];
+                    board[0] = None;
+                }
+            } else {
+                if mv.to == 62 {
+                    board[61] = board[63];
+                    board[63] = None;
+                } else if mv.to == 58 {
+                    board[59] = board[56];
+                    board[56] = None;
+                }
+            }
+        }
+    }
+}
+
+// Prüft die Legalität eines Zuges:
+// Zug simulieren und sicherstellen, dass der eigene König nicht im Schach steht.
+// Weitere Regeln (z.B. Rochade-Bedingungen) sind bereits im Pseudozug enthalten.
+// Was: Führt `is_move_legal` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn is_move_legal(pos: &mut Position, mv: Move, color: Color) -> bool {
+    if color != pos.side_to_move {
+        return false;
+    }
+    let Some(undo) = make_move_in_place(pos, mv, None) else { return false; };
+    let in_check = is_in_check(pos, color);
+    unmake_move_in_place(pos, mv, None, undo);
+    !in_check
+}
+
+// Findet einen legalen Zug von `from` nach `to` (inkl. Sonderzugtyp).
+// Wird für `apply_move` genutzt, damit nur gültige Züge übernommen werden.
+// Was: Führt `find_legal_move` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn find_legal_move(pos: &mut Position, from: u8, to: u8) -> Option<Move> {
+    let mut moves: Vec<Move> = Vec::with_capacity(32);
+    generate_moves_for_piece_into(pos, from, &mut moves);
+    moves.into_iter().find(|&mv| mv.to == to && is_move_legal(pos, mv, pos.side_to_move))
+}
+
+// Parst einen Zug in UCI-Notation ("e2e4", "e7e8q") gegen die aktuelle Position.
+// Liefert nur legale Züge; bei Promotionen ohne Suffix wird die Dame gewählt.
+// Was: Übersetzt Frontend-Züge (UCI-Protokoll, Buch, PGN-Replay) in interne Züge.
+// Warum: Alle Textzüge laufen so über dieselbe Legalitätsprüfung wie `apply_move`.
+// Kosten: Pseudozüge einer Figur plus Legalitätsprüfung je Kandidat.
+pub fn parse_uci_move(pos: &mut Position, uci: &str) -> Option<(Move, Option<char>)> {
+    let uci = uci.trim();
+    if !uci.is_ascii() || (uci.len() != 4 && uci.len() != 5) {
+        return None;
+    }
+    let from = lan_to_field(&uci[0..2])?;
+    let to = lan_to_field(&uci[2..4])?;
+    let promo = uci[4..].chars().next().map(|c| c.to_ascii_lowercase());
+    let mv = find_legal_move(pos, from, to)?;
+    match mv.kind {
+        MoveKind::Promotion => {
+            let p = promo.unwrap_or('q');
+            if !matches!(p, 'q' | 'r' | 'b' | 'n') {
+                return None;
+            }
+            Some((mv, Some(p)))
+        }
+        _ if promo.is_some() => None,
+        _ => Some((mv, None)),
+    }
+}
+
+// Was: Führt `generate_legal_moves_into` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn generate_legal_moves_into(pos: &mut Position, out: &mut Vec<(Move, Option<char>)>) {
+    // Erst pseudo-legale Züge je Figur erzeugen, dann per `is_move_legal`
+    // auf echte legale Züge filtern. Promotionen werden in 4 Varianten
+    // (q/r/b/n) explizit aufgefächert.
+    out.clear();
+    let mut piece_moves: Vec<Move> = Vec::with_capacity(32);
+    for from in 0u8..64u8 {
+        generate_moves_for_piece_into(pos, from, &mut piece_moves);
+        for mv in piece_moves.iter().copied() {
+            if is_move_legal(pos, mv, pos.side_to_move) {
+                if let MoveKind::Promotion = mv.kind {
+                    for promo in ['q', 'r', 'b', 'n'] {
+                        out.push((mv, Some(promo)));
+                    }
+                } else {
+                    out.push((mv, None));
+                }
+            }
+        }
+    }
+}
+
+// Was: Führt `generate_legal_moves` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn generate_legal_moves(pos: &mut Position) -> Vec<(Move, Option<char>)> {
+    let mut out = Vec::new();
+    generate_legal_moves_into(pos, &mut out);
+    out
+}
+
+// Was: Wendet einen Zug funktional (ohne Seiteneffekte am Original) auf Position + Hash an.
+// Warum: Erlaubt sichere Probe-Berechnungen (z. B. Hilfsroutinen/Validierung), ohne den Suchzustand zu verändern.
+// Kosten: Konstant pro Zug mit mehreren kleinen Zweigen für Spezialfälle (En Passant, Rochade, Promotion).
+#[allow(dead_code)]
+fn apply_move_to_position(
+    pos: &Position,
+    mv: Move,
+    promotion: Option<char>,
+    hash: u64,
+    zob: &Zobrist,
+) -> Option<(Position, u64)> {
+    let mut board = pos.board;
+    let piece = board[mv.from as usize]?;
+    let color = piece_color(piece);
+    if color != pos.side_to_move {
+        return None;
+    }
+
+    let mut cap_sq = None;
+    let captured_piece = match mv.kind {
+        MoveKind::EnPassant => {
+            let sq = if color == Color::White { mv.to - 8 } else { mv.to + 8 };
+            cap_sq = Some(sq);
+            board[sq as usize]
+        }
+        _ => {
+            let cap = board[mv.to as usize];
+            if cap.is_some() {
+                cap_sq = Some(mv.to);
+            }
+            cap
+        }
+    };
+
+    let mut placed = piece;
+    if let MoveKind::Promotion = mv.kind {
+        let promo = promotion.unwrap_or('q').to_ascii_lowercase();
+        let mut placed_piece = match promo {
+            'q' | 'r' | 'b' | 'n' => promo,
+            _ => 'q',
+        };
+        if piece.is_ascii_uppercase() {
+            placed_piece = placed_piece.to_ascii_uppercase();
+        }
+        placed = placed_piece;
+    }
+
+    let mut bb = pos.bb;
+    remove_piece(&mut bb, piece, mv.from);
+    if let (Some(cap), Some(sq)) = (captured_piece, cap_sq) {
+        remove_piece(&mut bb, cap, sq);
+    }
+
+    apply_move_to_board(&mut board, mv, color, promotion);
+    add_piece(&mut bb, placed, mv.to);
+
+    if mv.kind == MoveKind::Castle {
+        let (rook_from, rook_to) = if color == Color::White {
+            if mv.to == 6 { (7u8, 5u8) } else { (0u8, 3u8) }
+        } else {
+            if mv.to == 62 { (63u8, 61u8) } else { (56u8, 59u8) }
+        };
+        let rook_piece = if color == Color::White { 'R' } else { 'r' };
+        remove_piece(&mut bb, rook_piece, rook_from);
+        add_piece(&mut bb, rook_piece, rook_to);
+    }
+
+    let mut castling = pos.castling;
+    if piece.eq_ignore_ascii_case(&'k') {
+        if color == Color::White {
+            castling &= !(CASTLE_WK | CASTLE_WQ);
+        } else {
+            castling &= !(CASTLE_BK | CASTLE_BQ);
+        }
+    }
+    if piece.eq_ignore_ascii_case(&'r') {
+        match mv.from {
+            0 => castling &= !CASTLE_WQ,
+            7 => castling &= !CASTLE_WK,
+            56 => castling &= !CASTLE_BQ,
+            63 => castling &= !CASTLE_BK,
+            _ => {}
+        }
+    }
+    if let Some(cap) = captured_piece {
+        if cap.eq_ignore_ascii_case(&'r') {
+            match mv.to {
+                0 => castling &= !CASTLE_WQ,
+                7 => castling &= !CASTLE_WK,
+                56 => castling &= !CASTLE_BQ,
+                63 => castling &= !CASTLE_BK,
+                _ => {}
+            }
+        }
+    }
+
+    let mut new_ep = None;
+    if piece.eq_ignore_ascii_case(&'p') {
+        let from_rank = mv.from / 8;
+        let to_rank = mv.to / 8;
+        if color == Color::White && from_rank == 1 && to_rank == 3 {
+            new_ep = Some(mv.from + 8);
+        } else if color == Color::Black && from_rank == 6 && to_rank == 4 {
+            new_ep = Some(mv.from - 8);
+        }
+    }
+
+    let mut halfmove = pos.halfmove;
+    let is_capture = match mv.kind {
+        MoveKind::EnPassant => true,
+        _ => captured_piece.is_some(),
+    };
+    if piece.eq_ignore_ascii_case(&'p') || is_capture {
+        halfmove = 0;
+    } else {
+        halfmove += 1;
+    }
+
+    let mut fullmove = pos.fullmove;
+    if pos.side_to_move == Color::Black {
+        fullmove += 1;
+    }
+
+    let mut new_hash = hash;
+    new_hash ^= zob.side;
+    new_hash ^= zob.castle[(pos.castling & 0x0F) as usize] ^ zob.castle[(castling & 0x0F) as usize];
+    if let Some(ep) = pos.ep {
+        let file = (ep % 8) as usize;
+        new_hash ^= zob.ep_file[file + 1];
+    }
+    if let Some(ep) = new_ep {
+        let file = (ep % 8) as usize;
+        new_hash ^= zob.ep_file[file + 1];
+    }
+
+    if let Some(idx) = piece_index(piece) {
+        new_hash ^= zob.piece_sq[idx][mv.from as usize];
+    }
+    if let (Some(cap), Some(sq)) = (captured_piece, cap_sq) {
+        if let Some(idx) = piece_index(cap) {
+            new_hash ^= zob.piece_sq[idx][sq as usize];
+        }
+    }
+    if let Some(idx) = piece_index(placed) {
+        new_hash ^= zob.piece_sq[idx][mv.to as usize];
+    }
+    if mv.kind == MoveKind::Castle {
+        let (rook_from, rook_to) = if color == Color::White {
+            if mv.to == 6 { (7usize, 5usize) } else { (0usize, 3usize) }
+        } else {
+            if mv.to == 62 { (63usize, 61usize) } else { (56usize, 59usize) }
+        };
+        let rook_piece = if color == Color::White { 'R' } else { 'r' };
+        if let Some(idx) = piece_index(rook_piece) {
+            new_hash ^= zob.piece_sq[idx][rook_from];
+            new_hash ^= zob.piece_sq[idx][rook_to];
+        }
+    }
+
+    Some((Position {
+        board,
+        side_to_move: pos.side_to_move.opposite(),
+        castling,
+        ep: new_ep,
+        halfmove,
+        fullmove,
+        bb,
+    }, new_hash))
+}
+
+// Undo-Paket für make/unmake:
+// enthält exakt die Informationen, die zur verlustfreien Rücknahme nötig sind.
+pub struct Undo {
+    pub(crate) captured: Option<char>,
+    pub(crate) captured_sq: Option<u8>,
+    pub(crate) prev_castling: u8,
+    pub(crate) prev_ep: Option<u8>,
+    pub(crate) prev_halfmove: u32,
+    pub(crate) prev_fullmove: u32,
+    pub(crate) moved_piece: char,
+}
+
+// Führt einen Zug in-place aus und liefert Undo-Daten für die Rücknahme.
+// Zentral für Suchknoten, da hier keine kompletten Positionskopien entstehen.
+// Was: Führt `make_move_in_place` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn make_move_in_place(pos: &mut Position, mv: Move, promotion: Option<char>) -> Option<Undo> {
+    // Warum in-place + Undo?
+    // In der Suche wird diese Funktion millionenfach aufgerufen.
+    // In-place vermeidet Heap-/Copy-Overhead und ist damit deutlich schneller
+    // als pro Knoten eine komplette Position zu klonen.
+    let piece = pos.board[mv.from as usize]?;
+    let color = piece_color(piece);
+    if color != pos.side_to_move {
+        return None;
+    }
+
+    let mut cap_sq = None;
+    let captured = match mv.kind {
+        MoveKind::EnPassant => {
+            let sq = if color == Color::White { mv.to - 8 } else { mv.to + 8 };
+            cap_sq = Some(sq);
+            pos.board[sq as usize]
+        }
+        _ => {
+            let cap = pos.board[mv.to as usize];
+            if cap.is_some() {
+                cap_sq = Some(mv.to);
+            }
+            cap
+        }
+    };
+
+    let undo = Undo {
+        captured,
+        captured_sq: cap_sq,
+        prev_castling: pos.castling,
+        prev_ep: pos.ep,
+        prev_halfmove: pos.halfmove,
+        prev_fullmove: pos.fullmove,
+        moved_piece: piece,
+    };
+
+    remove_piece(&mut pos.bb, piece, mv.from);
+    if let (Some(cap), Some(sq)) = (captured, cap_sq) {
+        remove_piece(&mut pos.bb, cap, sq);
+    }
+
+    apply_move_to_board(&mut pos.board, mv, color, promotion);
+
+    let mut placed = piece;
+    if let MoveKind::Promotion = mv.kind {
+        let promo = promotion.unwrap_or('q').to_ascii_lowercase();
+        let mut placed_piece = match promo {
+            'q' | 'r' | 'b' | 'n' => promo,
+            _ => 'q',
+        };
+        if piece.is_ascii_uppercase() {
+            placed_piece = placed_piece.to_ascii_uppercase();
+        }
+        placed = placed_piece;
+    }
+    add_piece(&mut pos.bb, placed, mv.to);
+
+    if mv.kind == MoveKind::Castle {
+        let (rook_from, rook_to) = if color == Color::White {
+            if mv.to == 6 { (7u8, 5u8) } else { (0u8, 3u8) }
+        } else {
+            if mv.to == 62 { (63u8, 61u8) } else { (56u8, 59u8) }
+        };
+        let rook_piece = if color == Color::White { 'R' } else { 'r' };
+        remove_piece(&mut pos.bb, rook_piece, rook_from);
+        add_piece(&mut pos.bb, rook_piece, rook_to);
+    }
+
+    // Warum Castling-Rechte hier sofort aktualisieren?
+    // Damit Folgeknoten (insb. TT-Hash und Legality) immer den korrekten
+    // Zustand sehen und keine impliziten Sonderfälle benötigen.
+    let mut castling = pos.castling;
+    if piece.eq_ignore_ascii_case(&'k') {
+        if color == Color::White {
+            castling &= !(CASTLE_WK | CASTLE_WQ);
+        } else {
+            castling &= !(CASTLE_BK | CASTLE_BQ);
+        }
+    }
+    if piece.eq_ignore_ascii_case(&'r') {
+        match mv.from {
+            0 => castling &= !CASTLE_WQ,
+            7 => castling &= !CASTLE_WK,
+            56 => castling &= !CASTLE_BQ,
+            63 => castling &= !CASTLE_BK,
+            _ => {}
+        }
+    }
+    if let Some(cap) = captured {
+        if cap.eq_ignore_ascii_case(&'r') {
+            match mv.to {
+                0 => castling &= !CASTLE_WQ,
+                7 => castling &= !CASTLE_WK,
+                56 => castling &= !CASTLE_BQ,
+                63 => castling &= !CASTLE_BK,
+                _ => {}
+            }
+        }
+    }
+
+    // Warum EP-Feld nur bei Doppelzug setzen?
+    // Nur dann ist im unmittelbar nächsten Halbzug ein EP-Capture legal.
+    let mut new_ep = None;
+    if piece.eq_ignore_ascii_case(&'p') {
+        let from_rank = mv.from / 8;
+        let to_rank = mv.to / 8;
+        if color == Color::White && from_rank == 1 && to_rank == 3 {
+            new_ep = Some(mv.from + 8);
+        } else if color == Color::Black && from_rank == 6 && to_rank == 4 {
+            new_ep = Some(mv.from - 8);
+        }
+    }
+
+    // Warum halfmove hier pflegen?
+    // Für 50-Züge-Regel und Repetition-Einordnung muss der Counter
+    // auf jedem Knoten korrekt fortgeführt werden.
+    let mut halfmove = pos.halfmove;
+    let is_capture = match mv.kind {
+        MoveKind::EnPassant => true,
+        _ => captured.is_some(),
+    };
+    if piece.eq_ignore_ascii_case(&'p') || is_capture {
+        halfmove = 0;
+    } else {
+        halfmove += 1;
+    }
+
+    let mut fullmove = pos.fullmove;
+    if pos.side_to_move == Color::Black {
+        fullmove += 1;
+    }
+
+    pos.castling = castling;
+    pos.ep = new_ep;
+    pos.halfmove = halfmove;
+    pos.fullmove = fullmove;
+    pos.side_to_move = pos.side_to_move.opposite();
+
+    Some(undo)
+}
+
+// Nimmt einen zuvor ausgeführten Zug per Undo deterministisch zurück.
+// Was: Führt `unmake_move_in_place` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn unmake_move_in_place(pos: &mut Position, mv: Move, _promotion: Option<char>, undo: Undo) {
+    // Warum deterministisches Unmake?
+    // Suchknoten dürfen keine schleichenden Seiteneffekte hinterlassen.
+    // Exaktes Rücksetzen ist Grundlage für korrekte Bewertung/TT.
+    pos.side_to_move = pos.side_to_move.opposite();
+    pos.castling = undo.prev_castling;
+    pos.ep = undo.prev_ep;
+    pos.halfmove = undo.prev_halfmove;
+    pos.fullmove = undo.prev_fullmove;
+
+    let color = pos.side_to_move;
+    let to = mv.to as usize;
+    let from = mv.from as usize;
+
+    if let Some(p) = pos.board[to] {
+        remove_piece(&mut pos.bb, p, mv.to);
+    }
+    pos.board[to] = None;
+
+    if mv.kind == MoveKind::Castle {
+        let (rook_from, rook_to) = if color == Color::White {
+            if mv.to == 6 { (7u8, 5u8) } else { (0u8, 3u8) }
+        } else {
+            if mv.to == 62 { (63u8, 61u8) } else { (56u8, 59u8) }
+        };
+        let rook_piece = if color == Color::White { 'R' } else { 'r' };
+        if let Some(p) = pos.board[rook_to as usize] {
+            remove_piece(&mut pos.bb, p, rook_to);
+        }
+        pos.board[rook_to as usize] = None;
+        pos.board[rook_from as usize] = Some(rook_piece);
+        add_piece(&mut pos.bb, rook_piece, rook_from);
+    }
+
+    if let (Some(cap), Some(sq)) = (undo.captured, undo.captured_sq) {
+        pos.board[sq as usize] = Some(cap);
+        add_piece(&mut pos.bb, cap, sq);
+    }
+
+    pos.board[from] = Some(undo.moved_piece);
+    add_piece(&mut pos.bb, undo.moved_piece, mv.from);
+}
+
+// Interne Zugrepräsentation in UCI-Text umwandeln (z. B. "e2e4", "a7a8q").
+// Was: Führt `move_to_uci` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn move_to_uci(mv: Move, promotion: Option<char>) -> String {
+    let mut out = String::new();
+    out.push_str(&field_to_lan(mv.from));
+    out.push_str(&field_to_lan(mv.to));
+    if let Some(p) = promotion {
+        out.push(p.to_ascii_lowercase());
+    }
+    out
+}
+
+// Liefert bei Capture-Zügen das tatsächlich geschlagene Feld/Piece
+// (inkl. korrektem EP-Capture-Feld).
+// Was: Führt `capture_info` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn capture_info(pos: &Position, mv: Move) -> Option<(u8, char)> {
+    match mv.kind {
+        MoveKind::EnPassant => {
+            let cap_sq = if pos.side_to_move == Color::White {
+                mv.to - 8
+            } else {
+                mv.to + 8
+            };
+            pos.board[cap_sq as usize].map(|p| (cap_sq, p))
+        }
+        _ => pos.board[mv.to as usize].map(|p| (mv.to, p)),
+    }
+}
+
+// Vereinfachter Capture-Check über `capture_info`.
+// Was: Führt `move_is_capture` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn move_is_capture(pos: &Position, mv: Move) -> bool {
+    capture_info(pos, mv).is_some()
+}
+
+// Erzeugt nur "taktische" Züge für Quiescence:
+// Captures + Promotions (keine ruhigen Züge).
+// Was: Führt `generate_tactical_moves_into` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn generate_tactical_moves_into(pos: &mut Position, out: &mut Vec<(Move, Option<char>)>) {
+    out.clear();
+    let mut piece_moves: Vec<Move> = Vec::with_capacity(32);
+    for from in 0u8..64u8 {
+        generate_moves_for_piece_into(pos, from, &mut piece_moves);
+        for mv in piece_moves.iter().copied() {
+            if !is_move_legal(pos, mv, pos.side_to_move) {
+                continue;
+            }
+            if move_is_capture(pos, mv) {
+                if let MoveKind::Promotion = mv.kind {
+                    for promo in ['q', 'r', 'b', 'n'] {
+                        out.push((mv, Some(promo)));
+                    }
+                } else {
+                    out.push((mv, None));
+                }
+            } else if let MoveKind::Promotion = mv.kind {
+                for promo in ['q', 'r', 'b', 'n'] {
+                    out.push((mv, Some(promo)));
+                }
+            }
+        }
+    }
+}
+
+// Was: Führt `generate_tactical_moves` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+#[allow(dead_code)]
+fn generate_tactical_moves(pos: &mut Position) -> Vec<(Move, Option<char>)> {
+    let mut out = Vec::new();
+    generate_tactical_moves_into(pos, &mut out);
+    out
+}
@@ -0,0 +1,64 @@
+// =====================================================================================
+// Perft
+// -------------------------------------------------------------------------------------
+// Zählt alle Blattknoten des legalen Zugbaums bis zu einer festen Tiefe. Die Werte
+// lassen sich mit bekannten Referenzzahlen vergleichen und prüfen damit Zug-
+// generierung, Make/Unmake sowie Rochade-, En-passant- und Promotionszweige.
+// =====================================================================================
+
+use crate::movegen::*;
+use crate::position::*;
+
+// Was: Zählt die Blattknoten ab `pos` bis `depth` Halbzüge tief.
+// Warum: Referenzwert für die Korrektheit der Zuggenerierung (Tests, Debug-Hook im UI).
+// Kosten: Exponentiell in `depth`; auf Tiefe 1 wird nur die Zugliste gezählt (Bulk Counting).
+pub fn perft(pos: &mut Position, depth: u32) -> u64 {
+    let mut bufs: Vec<Vec<(Move, Option<char>)>> = (0..depth).map(|_| Vec::with_capacity(64)).collect();
+    perft_inner(pos, depth, &mut bufs)
+}
+
+// Was: Perft je Wurzelzug, sortiert nach UCI-Notation.
+// Warum: Beim Abweichen von einer Referenz zeigt `divide` den fehlerhaften Teilbaum.
+// Kosten: Wie `perft`, zusätzlich ein String pro Wurzelzug.
+pub fn perft_divide(pos: &mut Position, depth: u32) -> Vec<(String, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+    let mut bufs: Vec<Vec<(Move, Option<char>)>> = (0..depth).map(|_| Vec::with_capacity(64)).collect();
+    let moves = generate_legal_moves(pos);
+    let mut out = Vec::with_capacity(moves.len());
+    for (mv, promo) in moves {
+        let Some(undo) = make_move_in_place(pos, mv, promo) else {
+            continue;
+        };
+        let nodes = perft_inner(pos, depth - 1, &mut bufs);
+        unmake_move_in_place(pos, mv, promo, undo);
+        out.push((move_to_uci(mv, promo), nodes));
+    }
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+// Rekursionskern; `bufs[d - 1]` ist der Zugpuffer für Resttiefe `d`, damit pro
+// Knoten keine neue Liste alloziert wird.
+fn perft_inner(pos: &mut Position, depth: u32, bufs: &mut [Vec<(Move, Option<char>)>]) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let (rest, cur) = bufs.split_at_mut(depth as usize - 1);
+    let moves = &mut cur[0];
+    generate_legal_moves_into(pos, moves);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    let mut nodes = 0u64;
+    for &(mv, promo) in moves.iter() {
+        let Some(undo) = make_move_in_place(pos, mv, promo) else {
+            continue;
+        };
+        nodes += perft_inner(pos, depth - 1, rest);
+        unmake_move_in_place(pos, mv, promo, undo);
+    }
+    nodes
+}
@@ -0,0 +1,308 @@
+// =====================================================================================
+// PGN
+// -------------------------------------------------------------------------------------
+// Import: Tag-Paare und SAN-Zugtext (Kommentare, NAGs, Varianten werden übersprungen)
+// werden über `make_move_in_place` nachgespielt und liefern UCI-Züge plus FEN-Folge.
+// Export: Startstellung + UCI-Züge als PGN mit Seven Tag Roster und SetUp/FEN-Tags.
+// =====================================================================================
+
+use crate::movegen::*;
+use crate::position::*;
+use crate::san::*;
+use crate::status::*;
+
+// Eine nachgespielte Partie.
+#[derive(Clone, Debug, Default)]
+pub struct PgnGame {
+    // Tag-Paare in Dateireihenfolge.
+    pub tags: Vec<(String, String)>,
+    pub start_fen: String,
+    // Hauptvariante in UCI-Notation.
+    pub moves: Vec<String>,
+    // Startstellung plus Stellung nach jedem Zug (`moves.len() + 1` Einträge).
+    pub fens: Vec<String>,
+    // `1-0`, `0-1`, `1/2-1/2` oder `*`.
+    pub result: String,
+}
+
+impl PgnGame {
+    // Wert eines Tags (Groß-/Kleinschreibung wie im PGN-Standard exakt).
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+const PGN_LINE_WIDTH: usize = 79;
+
+// Was: Liest die erste Partie aus `text`.
+// Warum: Häufigster Fall im UI (eine eingefügte Partie).
+// Kosten: Linear in der Textlänge plus eine SAN-Auflösung pro Zug.
+pub fn parse_pgn(text: &str) -> Result<PgnGame, String> {
+    parse_pgn_games(text)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| Err("empty pgn".to_string()))
+}
+
+// Was: Liest alle Partien einer PGN-Datei; jede Partie wird unabhängig ausgewertet.
+// Warum: Datenbanken (Buch-Generierung, Testsets) enthalten viele Partien, eine
+// defekte Partie soll die übrigen nicht verwerfen.
+// Kosten: Linear in der Textlänge plus eine SAN-Auflösung pro Zug.
+pub fn parse_pgn_games(text: &str) -> Vec<Result<PgnGame, String>> {
+    let mut games = Vec::new();
+    let mut tags: Vec<(String, String)> = Vec::new();
+    let mut movetext = String::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        // `%` am Zeilenanfang ist laut Standard eine Escape-Zeile.
+        if trimmed.starts_with('%') {
+            continue;
+        }
+        if trimmed.starts_with('[') && !in_open_comment(&movetext) {
+            // Ein Tag nach Zugtext beginnt die nächste Partie.
+            if !movetext.trim().is_empty() {
+                games.push(replay(std::mem::take(&mut tags), &movetext));
+                movetext.clear();
+            }
+            if let Some(tag) = parse_tag_line(trimmed) {
+                tags.push(tag);
+            }
+            continue;
+        }
+        movetext.push_str(line);
+        movetext.push('\n');
+        // Ergebnis-Token am Zeilenende schließt die Partie auch ohne folgende Tags ab.
+        let ends_game = trimmed
+            .rsplit(char::is_whitespace)
+            .next()
+            .is_some_and(|last| RESULTS.contains(&last));
+        if ends_game && !in_open_comment(&movetext) {
+            games.push(replay(std::mem::take(&mut tags), &movetext));
+            movetext.clear();
+        }
+    }
+    if !movetext.trim().is_empty() || !tags.is_empty() {
+        games.push(replay(tags, &movetext));
+    }
+    games
+}
+
+// Was: Erzeugt PGN aus Startstellung, UCI-Zügen und optionalen Tags.
+// Warum: Speichern/Exportieren von Partien; SetUp/FEN wird bei abweichender
+// Startstellung automatisch gesetzt, das Ergebnis bei Partieende aus `game_status`.
+// Kosten: Eine SAN-Formatierung pro Zug (zwei legale Zuggenerierungen).
+pub fn write_pgn(start_fen: &str, moves: &[String], tags: &[(String, String)]) -> Result<String, String> {
+    let mut pos = parse_fen(start_fen).ok_or_else(|| "invalid fen".to_string())?;
+    let start = position_to_fen(&pos);
+
+    let mut sans = Vec::with_capacity(moves.len());
+    let mut history = Vec::with_capacity(moves.len());
+    let first_fullmove = pos.fullmove;
+    let black_first = pos.side_to_move == Color::Black;
+    for uci in moves {
+        let (mv, promo) = parse_uci_move(&mut pos, uci).ok_or_else(|| format!("illegal move {}", uci))?;
+        sans.push(move_to_san(&mut pos, mv, promo));
+        history.push(position_to_fen(&pos));
+        make_move_in_place(&mut pos, mv, promo).ok_or_else(|| format!("illegal move {}", uci))?;
+    }
+
+    let given = |name: &str| tags.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str());
+    let result = match given("Result") {
+        Some(r) if RESULTS.contains(&r) => r.to_string(),
+        _ => result_from_status(game_status(&mut pos, &history.join("\n"))).to_string(),
+    };
+
+    let mut out = String::new();
+    for (name, default) in SEVEN_TAG_ROSTER {
+        let value = if name == "Result" { result.as_str() } else { given(name).unwrap_or(default) };
+        push_tag(&mut out, name, value);
+    }
+    if start != START_FEN {
+        push_tag(&mut out, "SetUp", "1");
+        push_tag(&mut out, "FEN", &start);
+    }
+    for (name, value) in tags {
+        let reserved = SEVEN_TAG_ROSTER.iter().any(|(n, _)| n == name) || name == "SetUp" || name == "FEN";
+        if !reserved {
+            push_tag(&mut out, name, value);
+        }
+    }
+    out.push('\n');
+
+    // Zugtext mit Zugnummern, umgebrochen auf `PGN_LINE_WIDTH`.
+    let mut tokens = Vec::with_capacity(sans.len() * 3 / 2 + 1);
+    for (i, san) in sans.iter().enumerate() {
+        let ply = i + usize::from(black_first);
+        let number = first_fullmove as usize + ply / 2;
+        if ply % 2 == 0 {
+            tokens.push(format!("{}.", number));
+        } else if i == 0 {
+            tokens.push(format!("{}...", number));
+        }
+        tokens.push(san.clone());
+    }
+    tokens.push(result);
+
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > PGN_LINE_WIDTH {
+            out.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            out.push(' ');
+            line_len += 1;
+        }
+        line_len += token.len();
+        out.push_str(&token);
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+// Ergebnis-Token für einen Partiestatus; reklamierbare Remis bleiben offen.
+fn result_from_status(status: GameStatus) -> &'static str {
+    match status {
+        GameStatus::Checkmate { winner: Color::White } => "1-0",
+        GameStatus::Checkmate { winner: Color::Black } => "0-1",
+        s if s.is_game_over() => "1/2-1/2",
+        _ => "*",
+    }
+}
+
+fn push_tag(out: &mut String, name: &str, value: &str) {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    out.push_str(&format!("[{} \"{}\"]\n", name, escaped));
+}
+
+// `[Name "Wert"]` mit `\"`/`\\`-Escapes.
+fn parse_tag_line(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.trim_end().strip_suffix(']')?;
+    let (name, rest) = inner.trim().split_once(char::is_whitespace)?;
+    let quoted = rest.trim().strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next()),
+            '"' => return Some((name.to_string(), value)),
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+// Liegt das Textende innerhalb eines `{...}`-Kommentars? Dann gehört eine Zeile
+// mit `[` noch zum Kommentar und ist kein Tag.
+fn in_open_comment(movetext: &str) -> bool {
+    movetext.rfind('{').is_some_and(|open| movetext.rfind('}').is_none_or(|close| close < open))
+}
+
+// Was: Spielt den Zugtext einer Partie nach.
+// Warum: Nur legale Züge übernehmen; Fehler nennen Zugnummer und SAN.
+// Kosten: Eine SAN-Auflösung und ein `make_move_in_place` pro Zug.
+fn replay(tags: Vec<(String, String)>, movetext: &str) -> Result<PgnGame, String> {
+    let start_fen = tags
+        .iter()
+        .find(|(k, _)| k == "FEN")
+        .map(|(_, v)| v.clone())
+        .unwrap_or_else(|| START_FEN.to_string());
+    let mut pos = parse_fen(&start_fen).ok_or_else(|| format!("invalid FEN tag: {}", start_fen))?;
+
+    let mut game = PgnGame {
+        result: tags
+            .iter()
+            .find(|(k, _)| k == "Result")
+            .map(|(_, v)| v.clone())
+            .unwrap_or_else(|| "*".to_string()),
+        fens: vec![position_to_fen(&pos)],
+        start_fen: position_to_fen(&pos),
+        tags,
+        moves: Vec::new(),
+    };
+
+    for token in movetext_tokens(movetext) {
+        if RESULTS.contains(&token.as_str()) {
+            game.result = token;
+            break;
+        }
+        let (mv, promo) = parse_san(&mut pos, &token)
+            .ok_or_else(|| format!("illegal move {} after {} plies", token, game.moves.len()))?;
+        make_move_in_place(&mut pos, mv, promo).ok_or_else(|| format!("illegal move {}", token))?;
+        game.moves.push(move_to_uci(mv, promo));
+        game.fens.push(position_to_fen(&pos));
+    }
+    Ok(game)
+}
+
+// Zerlegt Zugtext in SAN- und Ergebnis-Token. Kommentare (`{}` und `;`),
+// Varianten (`()`, auch verschachtelt), NAGs (`$n`) und Zugnummern entfallen.
+fn movetext_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0u32;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                flush(&mut current, &mut tokens);
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                flush(&mut current, &mut tokens);
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' => {
+                flush(&mut current, &mut tokens);
+                depth += 1;
+            }
+            ')' => {
+                current.clear();
+                depth = depth.saturating_sub(1);
+            }
+            _ if depth > 0 => {}
+            c if c.is_whitespace() => flush(&mut current, &mut tokens),
+            // `12.e4` bzw. `12...Nf6`: Zugnummer direkt vor dem Zug.
+            '.' => {
+                current.clear();
+                while chars.peek() == Some(&'.') {
+                    chars.next();
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    flush(&mut current, &mut tokens);
+    tokens
+}
+
+// Übernimmt das laufende Token, sofern es weder Zugnummer noch NAG ist.
+fn flush(current: &mut String, tokens: &mut Vec<String>) {
+    if current.is_empty() {
+        return;
+    }
+    let token = std::mem::take(current);
+    let is_number = token.chars().all(|c| c.is_ascii_digit());
+    if !is_number && !token.starts_with('$') {
+        tokens.push(token);
+    }
+}
@@ -0,0 +1,57 @@
+// =====================================================================================
+// Plattform-Abstraktionen
+// -------------------------------------------------------------------------------------
+// Die Suche fragt weder `Date.now` noch JS-Hooks direkt ab, sondern geht über
+// `Clock` und `ProgressSink`. So läuft derselbe Kern im Browser (Feature `wasm`),
+// in nativen Binaries und in Tests.
+// =====================================================================================
+
+use crate::search::SearchInfo;
+
+// Zeitquelle der Suche in Millisekunden. Nur Differenzen werden ausgewertet,
+// der Nullpunkt ist daher beliebig.
+pub trait Clock {
+    fn now_ms(&self) -> f64;
+}
+
+// Empfänger für Zwischenstände der Suche. Alle Methoden haben leere
+// Default-Implementierungen, damit Frontends nur das Nötige überschreiben.
+pub trait ProgressSink {
+    // Gedrosselter Fortschritt während einer Iteration (Browser-Timebar).
+    fn progress(&mut self, _depth: u32, _nodes_completed: u64, _nodes_total: u64, _elapsed_ms: u32) {}
+
+    // Einmal pro vollständig abgeschlossener Iteration (z. B. UCI `info`).
+    fn iteration(&mut self, _info: &SearchInfo) {}
+
+    // Wird wie `SearchLimits::stop` periodisch abgefragt. Für Frontends ohne
+    // `Arc<AtomicBool>`, z. B. den Browser-Worker mit SharedArrayBuffer-Flag.
+    fn stop_requested(&mut self) -> bool {
+        false
+    }
+
+    // Wie `SearchLimits::ponderhit`: true beendet den Ponder-Modus einer Suche mit
+    // `SearchLimits::ponder`.
+    fn ponderhit(&mut self) -> bool {
+        false
+    }
+}
+
+// Kein Fortschritt gewünscht (Tests, Batch-Läufe).
+impl ProgressSink for () {}
+
+// Systemuhr für native Builds. Auf wasm32 ohne WASI gibt es keine Systemzeit,
+// dort liefert das `wasm`-Feature eine `Date.now`-basierte Uhr.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct SystemClock;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    // Was: Millisekunden seit dem ersten Aufruf im Prozess (Gleitkomma, wie `Date.now`).
+    // Warum: Gleiche Einheit wie im Browser, damit Zeitlimits identisch wirken; `Instant`
+    // ist monoton, ein Verstellen der Systemuhr (NTP, Hand) verschiebt keine Zeitlimits.
+    // Kosten: Ein Systemaufruf; wird von der Suche nur periodisch abgefragt.
+    fn now_ms(&self) -> f64 {
+        static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        START.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
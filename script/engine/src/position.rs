@@ -0,0 +1,457 @@
+// =====================================================================================
+// Position / FEN
+// -------------------------------------------------------------------------------------
+// Grundtypen (`Color`, `Move`, `Position`), inkrementelle Figuren-Bitboards
+// sowie FEN-Parser und -Serialisierung.
+// =====================================================================================
+
+use crate::bitboard::*;
+
+pub(crate) const CASTLE_WK: u8 = 1;
+pub(crate) const CASTLE_WQ: u8 = 2;
+pub(crate) const CASTLE_BK: u8 = 4;
+pub(crate) const CASTLE_BQ: u8 = 8;
+
+// ---------------------------
+// Grundtypen
+// ---------------------------
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    #[inline]
+    // Liefert die Gegenseite der aktuellen Farbe.
+    // Hilfreich für Schachprüfungen und Zugrechtwechsel.
+    // Was: Führt `opposite` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+    // Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+    // Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+    pub fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MoveKind {
+    Normal,
+    EnPassant,
+    Castle,
+    Promotion,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub from: u8,
+    pub to: u8,
+    pub kind: MoveKind,
+}
+
+// Bitboards + Kingsquare (für schnelle Schachprüfung)
+#[derive(Copy, Clone)]
+pub(crate) struct Bitboards {
+    pub(crate) white_occ: u64,
+    pub(crate) black_occ: u64,
+    pub(crate) occ: u64,
+    pub(crate) wp: u64,
+    pub(crate) wn: u64,
+    pub(crate) wb: u64,
+    pub(crate) wr: u64,
+    pub(crate) wq: u64,
+    pub(crate) wk: u64,
+    pub(crate) bp: u64,
+    pub(crate) bn: u64,
+    pub(crate) bb: u64,
+    pub(crate) br: u64,
+    pub(crate) bq: u64,
+    pub(crate) bk: u64,
+    pub(crate) white_king_sq: u8,
+    pub(crate) black_king_sq: u8,
+}
+
+#[inline]
+// Was: Führt `remove_piece` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn remove_piece(bits: &mut Bitboards, piece: char, sq: u8) {
+    let mask = bb(sq);
+    match piece {
+        'P' => bits.wp &= !mask,
+        'N' => bits.wn &= !mask,
+        'B' => bits.wb &= !mask,
+        'R' => bits.wr &= !mask,
+        'Q' => bits.wq &= !mask,
+        'K' => bits.wk &= !mask,
+        'p' => bits.bp &= !mask,
+        'n' => bits.bn &= !mask,
+        'b' => bits.bb &= !mask,
+        'r' => bits.br &= !mask,
+        'q' => bits.bq &= !mask,
+        'k' => bits.bk &= !mask,
+        _ => {}
+    }
+    if piece.is_ascii_uppercase() {
+        bits.white_occ &= !mask;
+    } else {
+        bits.black_occ &= !mask;
+    }
+    bits.occ &= !mask;
+}
+
+#[inline]
+// Was: Führt `add_piece` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn add_piece(bits: &mut Bitboards, piece: char, sq: u8) {
+    let mask = bb(sq);
+    match piece {
+        'P' => bits.wp |= mask,
+        'N' => bits.wn |= mask,
+        'B' => bits.wb |= mask,
+        'R' => bits.wr |= mask,
+        'Q' => bits.wq |= mask,
+        'K' => { bits.wk |= mask; bits.white_king_sq = sq; },
+        'p' => bits.bp |= mask,
+        'n' => bits.bn |= mask,
+        'b' => bits.bb |= mask,
+        'r' => bits.br |= mask,
+        'q' => bits.bq |= mask,
+        'k' => { bits.bk |= mask; bits.black_king_sq = sq; },
+        _ => {}
+    }
+    if piece.is_ascii_uppercase() {
+        bits.white_occ |= mask;
+    } else {
+        bits.black_occ |= mask;
+    }
+    bits.occ |= mask;
+}
+
+// Position aus FEN: Board + Metadaten + Bitboards
+pub struct Position {
+    pub board: [Option<char>; 64],
+    pub side_to_move: Color,
+    pub castling: u8,
+    pub ep: Option<u8>,
+    pub halfmove: u32,
+    pub fullmove: u32,
+    pub(crate) bb: Bitboards,
+}
+
+#[inline]
+// Was: Führt `clone_position` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn clone_position(pos: &Position) -> Position {
+    Position {
+        board: pos.board,
+        side_to_move: pos.side_to_move,
+        castling: pos.castling,
+        ep: pos.ep,
+        halfmove: pos.halfmove,
+        fullmove: pos.fullmove,
+        bb: pos.bb,
+    }
+}
+
+// ---------------------------
+// Position / FEN
+// ---------------------------
+#[inline]
+// Bestimmt die Farbe eines Pieces anhand der Groß-/Kleinschreibung.
+// Großbuchstaben = Weiß, Kleinbuchstaben = Schwarz.
+// Wird überall für Farblogik genutzt.
+// Was: Führt `piece_color` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn piece_color(ch: char) -> Color {
+    if ch.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    }
+}
+
+// Erzeugt alle Bitboards aus dem Board-Array.
+// Setzt zusätzlich die King-Squares für schnelle Schachprüfungen.
+// Gibt None zurück, wenn ein König fehlt (ungültige Position).
+// Was: Führt `build_bitboards` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn build_bitboards(board: &[Option<char>; 64]) -> Option<Bitboards> {
+    let mut bits = Bitboards {
+        white_occ: 0,
+        black_occ: 0,
+        occ: 0,
+        wp: 0,
+        wn: 0,
+        wb: 0,
+        wr: 0,
+        wq: 0,
+        wk: 0,
+        bp: 0,
+        bn: 0,
+        bb: 0,
+        br: 0,
+        bq: 0,
+        bk: 0,
+        white_king_sq: 64,
+        black_king_sq: 64,
+    };
+
+    for (i, p) in board.iter().enumerate() {
+        let Some(ch) = p else { continue };
+        let sq = i as u8;
+        let mask = bb(sq);
+        match ch {
+            'P' => { bits.wp |= mask; bits.white_occ |= mask; }
+            'N' => { bits.wn |= mask; bits.white_occ |= mask; }
+            'B' => { bits.wb |= mask; bits.white_occ |= mask; }
+            'R' => { bits.wr |= mask; bits.white_occ |= mask; }
+            'Q' => { bits.wq |= mask; bits.white_occ |= mask; }
+            'K' => { bits.wk |= mask; bits.white_occ |= mask; bits.white_king_sq = sq; }
+            'p' => { bits.bp |= mask; bits.black_occ |= mask; }
+            'n' => { bits.bn |= mask; bits.black_occ |= mask; }
+            'b' => { bits.bb |= mask; bits.black_occ |= mask; }
+            'r' => { bits.br |= mask; bits.black_occ |= mask; }
+            'q' => { bits.bq |= mask; bits.black_occ |= mask; }
+            'k' => { bits.bk |= mask; bits.black_occ |= mask; bits.black_king_sq = sq; }
+            _ => {}
+        }
+    }
+
+    bits.occ = bits.white_occ | bits.black_occ;
+
+    if bits.white_king_sq >= 64 || bits.black_king_sq >= 64 {
+        return None;
+    }
+
+    Some(bits)
+}
+
+// Parst eine komplette FEN in unsere Position-Struktur.
+// Erwartet mind. 4 Felder (Brett, Zugrecht, Rochade, En-passant).
+// Halbzug/Vollzug werden mit Defaults versehen.
+// Was: Führt `parse_fen` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn parse_fen(fen: &str) -> Option<Position> {
+    let parts: Vec<&str> = fen.split_whitespace().collect();
+    if parts.len() < 4 {
+        return None;
+    }
+
+    // Brett, Zugrecht, Rochade, En-passant, Halbzug, Vollzug
+    let board = fen_board_to_array(parts[0])?;
+
+    let side_to_move = match parts[1] {
+        "w" => Color::White,
+        "b" => Color::Black,
+        _ => return None,
+    };
+
+    let castling = parse_castling(parts[2]);
+    let ep = parse_ep(parts[3]);
+    let halfmove = parts.get(4).and_then(|s| s.parse::<u32>().ok()).unwrap_or(0);
+    let fullmove = parts.get(5).and_then(|s| s.parse::<u32>().ok()).unwrap_or(1);
+
+    let bb = build_bitboards(&board)?;
+
+    Some(Position {
+        board,
+        side_to_move,
+        castling,
+        ep,
+        halfmove,
+        fullmove,
+        bb,
+    })
+}
+
+// ---------------------------
+// FEN Helper
+// ---------------------------
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+// Serialisiert eine komplette Position zurück in eine FEN.
+// Gegenstück zu `parse_fen` (inkl. Halbzug-/Vollzugzähler).
+pub fn position_to_fen(pos: &Position) -> String {
+    let side = if pos.side_to_move == Color::White { "w" } else { "b" };
+    let ep = match pos.ep {
+        Some(sq) => field_to_lan(sq),
+        None => "-".to_string(),
+    };
+    format!(
+        "{} {} {} {} {} {}",
+        board_to_fen(&pos.board),
+        side,
+        castling_to_string(pos.castling),
+        ep,
+        pos.halfmove,
+        pos.fullmove
+    )
+}
+
+// Parst den Rochade-String der FEN in eine Bitmaske.
+// Unterstützt KQkq oder "-" für keine Rechte.
+// Was: Führt `parse_castling` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn parse_castling(s: &str) -> u8 {
+    if s == "-" {
+        return 0;
+    }
+    let mut mask = 0u8;
+    for ch in s.chars() {
+        match ch {
+            'K' => mask |= CASTLE_WK,
+            'Q' => mask |= CASTLE_WQ,
+            'k' => mask |= CASTLE_BK,
+            'q' => mask |= CASTLE_BQ,
+            _ => {}
+        }
+    }
+    mask
+}
+
+// Wandelt die Rochade-Bitmaske zurück in die FEN-Repräsentation.
+// Gibt "-" aus, wenn keine Rechte vorhanden sind.
+// Was: Führt `castling_to_string` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn castling_to_string(mask: u8) -> String {
+    if mask == 0 {
+        return "-".to_string();
+    }
+    let mut out = String::new();
+    if (mask & CASTLE_WK) != 0 { out.push('K'); }
+    if (mask & CASTLE_WQ) != 0 { out.push('Q'); }
+    if (mask & CASTLE_BK) != 0 { out.push('k'); }
+    if (mask & CASTLE_BQ) != 0 { out.push('q'); }
+    out
+}
+
+// Wandelt algebraische Notation (z.B. "e4") in Feldindex 0..63.
+// Erwartet exakt zwei Zeichen und gültige Range.
+// Was: Führt `lan_to_field` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn lan_to_field(s: &str) -> Option<u8> {
+    let bytes = s.as_bytes();
+    if bytes.len() != 2 {
+        return None;
+    }
+    let file = bytes[0] as char;
+    let rank = bytes[1] as char;
+    if !('a'..='h').contains(&file) {
+        return None;
+    }
+    if !('1'..='8').contains(&rank) {
+        return None;
+    }
+    let file_idx = file as u8 - b'a';
+    let rank_idx = rank as u8 - b'1';
+    Some(rank_idx * 8 + file_idx)
+}
+
+// Wandelt Feldindex 0..63 in algebraische Notation (z.B. "e4").
+// Nutzt a1=0, h8=63 Konvention.
+// Was: Führt `field_to_lan` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn field_to_lan(sq: u8) -> String {
+    let file = sq % 8;
+    let rank = sq / 8 + 1;
+    let file_char = (b'a' + file) as char;
+    format!("{}{}", file_char, rank)
+}
+
+// Parst das En-passant-Feld der FEN.
+// "-" bedeutet: kein EP-Ziel vorhanden.
+// Was: Führt `parse_ep` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn parse_ep(s: &str) -> Option<u8> {
+    if s == "-" {
+        return None;
+    }
+    lan_to_field(s)
+}
+
+// Parst nur den Brett-Teil der FEN in ein Array.
+// a1 entspricht Index 0, h8 entspricht Index 63.
+// Gibt None zurück bei ungültiger Struktur.
+// Was: Führt `fen_board_to_array` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn fen_board_to_array(board_part: &str) -> Option<[Option<char>; 64]> {
+    let ranks: Vec<&str> = board_part.split('/').collect();
+    if ranks.len() != 8 {
+        return None;
+    }
+
+    let mut board: [Option<char>; 64] = [None; 64];
+    let mut row: i8 = 7;
+
+    for rank in ranks {
+        let mut col: i8 = 0;
+        for ch in rank.chars() {
+            if ch.is_ascii_digit() {
+                let n = ch.to_digit(10)? as i8;
+                col += n;
+            } else {
+                if !(0..=7).contains(&col) {
+                    return None;
+                }
+                let idx = (row as usize) * 8 + (col as usize);
+                board[idx] = Some(ch);
+                col += 1;
+            }
+        }
+        if col != 8 {
+            return None;
+        }
+        row -= 1;
+    }
+
+    Some(board)
+}
+
+// Serialisiert das Board-Array zurück in den Brett-Teil der FEN.
+// Komprimiert leere Felder als Zahlen.
+// Was: Führt `board_to_fen` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn board_to_fen(board: &[Option<char>; 64]) -> String {
+    let mut ranks = Vec::with_capacity(8);
+
+    for row in (0..8).rev() {
+        let mut empty = 0;
+        let mut rank = String::new();
+        for col in 0..8 {
+            let idx = row * 8 + col;
+            match board[idx] {
+                Some(ch) => {
+                    if empty > 0 {
+                        rank.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    rank.push(ch);
+                }
+                None => {
+                    empty += 1;
+                }
+            }
+        }
+        if empty > 0 {
+            rank.push_str(&empty.to_string());
+        }
+        ranks.push(rank);
+    }
+
+    ranks.join("/")
+}
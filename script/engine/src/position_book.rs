@@ -0,0 +1,372 @@
+// =====================================================================================
+// Stellungsbasiertes Eröffnungsbuch
+// -------------------------------------------------------------------------------------
+// Buchzüge je Stellung, indiziert über den Zobrist-Hash der Engine (`compute_hash`).
+// Zugumstellungen landen dadurch automatisch im selben Eintrag; zur Laufzeit ist keine
+// Zugfolge mehr nachzuspielen.
+//
+// Binärformat (Little Endian): Magic "CPB1", Anzahl (u32), dann je Eintrag 12 Byte
+// Schlüssel (u64), Zug (u16: Bits 0..5 Ziel, 6..11 Start, 12..14 Umwandlung 1 S .. 4 D)
+// und Gewicht (u16), nach Schlüssel und Zug sortiert.
+//
+// Das Gewicht ist der Zuganteil der history-basierten JSON-Bücher in 1/10000. Mehrere
+// Bücher und Zugumstellungen addieren sich (wie zuvor im Worker), begrenzt auf u16::MAX.
+// =====================================================================================
+
+use std::collections::HashMap;
+
+use crate::book::*;
+use crate::movegen::*;
+use crate::position::*;
+use crate::zobrist::*;
+
+const MAGIC: &[u8; 4] = b"CPB1";
+const HEADER_SIZE: usize = 8;
+const ENTRY_SIZE: usize = 12;
+const WEIGHT_SCALE: f64 = 10_000.0;
+
+#[derive(Copy, Clone)]
+struct PositionEntry {
+    key: u64,
+    mv: u16,
+    weight: u16,
+}
+
+#[derive(Default)]
+pub struct PositionBook {
+    entries: Vec<PositionEntry>,
+}
+
+impl PositionBook {
+    // Was: Liest ein Buch im Binärformat (`to_bytes`); Fehler bei falscher Magic oder Länge.
+    // Kosten: Linear in der Dateigröße.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PositionBook, String> {
+        if bytes.len() < HEADER_SIZE || &bytes[..4] != MAGIC {
+            return Err("kein Stellungsbuch (Magic fehlt)".to_string());
+        }
+        let count = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+        let body = &bytes[HEADER_SIZE..];
+        if body.len() != count * ENTRY_SIZE {
+            return Err(format!("{} Einträge angekündigt, {} Byte Daten", count, body.len()));
+        }
+        let mut entries: Vec<PositionEntry> = body
+            .chunks_exact(ENTRY_SIZE)
+            .map(|c| PositionEntry {
+                key: u64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]),
+                mv: u16::from_le_bytes([c[8], c[9]]),
+                weight: u16::from_le_bytes([c[10], c[11]]),
+            })
+            .collect();
+        if !entries.windows(2).all(|w| (w[0].key, w[0].mv) <= (w[1].key, w[1].mv)) {
+            entries.sort_by_key(|e| (e.key, e.mv));
+        }
+        Ok(PositionBook { entries })
+    }
+
+    // Was: Serialisiert das Buch im Binärformat.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_SIZE + self.entries.len() * ENTRY_SIZE);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for e in &self.entries {
+            out.extend_from_slice(&e.key.to_le_bytes());
+            out.extend_from_slice(&e.mv.to_le_bytes());
+            out.extend_from_slice(&e.weight.to_le_bytes());
+        }
+        out
+    }
+
+    // Was: Übernimmt ein history-basiertes JSON-Buch (`{"e2e4 e7e5": {"moves": {"g1f3": 0.84}}}`)
+    // und liefert die Zahl der übernommenen Stellungen.
+    // Warum: Ersetzt `buildPositionBook` des Workers; jede Zugfolge wird genau einmal ab der
+    // Stellung ihres Vorgängers nachgespielt. Fehlt der Vorgänger im Buch, wird wie früher in
+    // `resolveHistoryFen` die ganze Zugfolge ab der Grundstellung nachgespielt.
+    // Kosten: Ein Zug je Buchstellung (ohne Vorgänger die ganze Zugfolge); gehalten werden nur
+    // die Stellungen eines Halbzugs.
+    pub fn merge_history_json(&mut self, json: &str) -> Result<usize, String> {
+        let mut book = parse_history_book(json)?;
+        book.sort_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+        let mut merged = 0;
+        let mut previous: HashMap<Vec<String>, Position> = HashMap::new();
+        let mut current: HashMap<Vec<String>, Position> = HashMap::new();
+        let mut ply = 0;
+        for (history, moves) in book {
+            if history.len() != ply {
+                previous = std::mem::take(&mut current);
+                ply = history.len();
+            }
+            let pos = match history.split_last() {
+                None => parse_fen(START_FEN),
+                Some((last, parent)) => match previous.get(parent) {
+                    Some(parent_pos) => replay(clone_position(parent_pos), std::slice::from_ref(last)),
+                    None => parse_fen(START_FEN).and_then(|pos| replay(pos, &history)),
+                },
+            };
+            let Some(mut pos) = pos else { continue };
+
+            let key = position_key(&pos);
+            for (uci, share) in moves {
+                let Some((mv, promo)) = parse_uci_move(&mut pos, &uci) else { continue };
+                let weight = (share * WEIGHT_SCALE).round().min(u16::MAX as f64);
+                if weight >= 1.0 {
+                    self.entries.push(PositionEntry { key, mv: encode(mv, promo), weight: weight as u16 });
+                }
+            }
+            current.insert(history, pos);
+            merged += 1;
+        }
+        self.normalize();
+        Ok(merged)
+    }
+
+    // Was: Konvertiert ein JSON-Buch direkt (siehe `merge_history_json`).
+    pub fn from_history_json(json: &str) -> Result<PositionBook, String> {
+        let mut book = PositionBook::default();
+        book.merge_history_json(json)?;
+        Ok(book)
+    }
+
+    // Anzahl der Einträge (Stellung/Zug-Paare).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Anzahl der Stellungen mit mindestens einem Buchzug.
+    pub fn positions(&self) -> usize {
+        let changes = self.entries.windows(2).filter(|w| w[0].key != w[1].key).count();
+        changes + usize::from(!self.entries.is_empty())
+    }
+
+    // Was: Alle legalen Buchzüge der Stellung, nach Gewicht absteigend (`None` bei ungültiger FEN).
+    // Kosten: Binärsuche im Buch plus eine Zuggenerierung.
+    pub fn probe(&self, fen: &str) -> Option<Vec<BookMove>> {
+        let mut pos = parse_fen(fen)?;
+        let key = position_key(&pos);
+        let start = self.entries.partition_point(|e| e.key < key);
+        let found = self.entries[start..].iter().take_while(|e| e.key == key);
+
+        let legal: Vec<String> =
+            generate_legal_moves(&mut pos).into_iter().map(|(mv, promo)| move_to_uci(mv, promo)).collect();
+        let mut moves: Vec<BookMove> = found
+            .map(|e| BookMove { uci: decode(e.mv), weight: e.weight, learn: 0 })
+            .filter(|m| legal.contains(&m.uci))
+            .collect();
+        moves.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        Some(moves)
+    }
+
+    // Was: Wählt einen Buchzug wie `PolyglotBook::book_move`.
+    // Kosten: Wie `probe`.
+    pub fn book_move(&self, fen: &str, selection: BookSelection, seed: u64) -> Option<String> {
+        let moves = self.probe(fen)?;
+        pick_book_move(&moves, selection, seed)
+    }
+
+    // Sortiert nach (Schlüssel, Zug) und fasst doppelte Einträge zusammen.
+    fn normalize(&mut self) {
+        self.entries.sort_by_key(|e| (e.key, e.mv));
+        self.entries.dedup_by(|next, kept| {
+            if (next.key, next.mv) != (kept.key, kept.mv) {
+                return false;
+            }
+            kept.weight = kept.weight.saturating_add(next.weight);
+            true
+        });
+    }
+}
+
+// Spielt UCI-Züge nach; `None` beim ersten ungültigen oder illegalen Zug.
+fn replay(mut pos: Position, moves: &[String]) -> Option<Position> {
+    for uci in moves {
+        let (mv, promo) = parse_uci_move(&mut pos, uci)?;
+        make_move_in_place(&mut pos, mv, promo)?;
+    }
+    Some(pos)
+}
+
+// Was: Zobrist-Hash der Stellung; ein En-passant-Feld zählt nur, wenn es schlagbar ist.
+// Warum: FENs aus UI und Engine unterscheiden sich sonst nach jedem Doppelschritt.
+fn position_key(pos: &Position) -> u64 {
+    let mut pos = clone_position(pos);
+    if !ep_capture_possible(&pos) {
+        pos.ep = None;
+    }
+    ZOBRIST_TABLE.with(|zob| compute_hash(&pos, zob))
+}
+
+fn encode(mv: Move, promo: Option<char>) -> u16 {
+    let promo: u16 = match promo.map(|c| c.to_ascii_lowercase()) {
+        Some('n') => 1,
+        Some('b') => 2,
+        Some('r') => 3,
+        Some('q') => 4,
+        _ => 0,
+    };
+    promo << 12 | (mv.from as u16) << 6 | mv.to as u16
+}
+
+fn decode(mv: u16) -> String {
+    let promo = match (mv >> 12) & 7 {
+        1 => Some('n'),
+        2 => Some('b'),
+        3 => Some('r'),
+        4 => Some('q'),
+        _ => None,
+    };
+    move_to_uci(Move { from: ((mv >> 6) & 63) as u8, to: (mv & 63) as u8, kind: MoveKind::Normal }, promo)
+}
+
+// -------------------------------------------------------------------------------------
+// Minimaler JSON-Leser für das Buchformat: Objekt aus Zugfolge -> {"moves": {uci: Anteil}}.
+// Andere Felder (z. B. "scores") werden übersprungen.
+// -------------------------------------------------------------------------------------
+
+type HistoryBook = Vec<(Vec<String>, Vec<(String, f64)>)>;
+
+fn parse_history_book(json: &str) -> Result<HistoryBook, String> {
+    let mut p = JsonReader { s: json.as_bytes(), i: 0 };
+    let mut book = Vec::new();
+    p.object(|p, history| {
+        let mut moves = Vec::new();
+        p.object(|p, field| {
+            if field != "moves" {
+                return p.skip_value();
+            }
+            p.object(|p, uci| {
+                moves.push((uci, p.number()?));
+                Ok(())
+            })
+        })?;
+        book.push((history.split_whitespace().map(str::to_string).collect(), moves));
+        Ok(())
+    })?;
+    p.ws();
+    if p.i != p.s.len() {
+        return Err(p.error("Daten nach dem Buch"));
+    }
+    Ok(book)
+}
+
+struct JsonReader<'a> {
+    s: &'a [u8],
+    i: usize,
+}
+
+impl JsonReader<'_> {
+    fn error(&self, what: &str) -> String {
+        format!("JSON: {} an Position {}", what, self.i)
+    }
+
+    fn ws(&mut self) {
+        while self.s.get(self.i).is_some_and(|c| c.is_ascii_whitespace()) {
+            self.i += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        self.ws();
+        if self.s.get(self.i) != Some(&c) {
+            return Err(self.error(&format!("'{}' erwartet", c as char)));
+        }
+        self.i += 1;
+        Ok(())
+    }
+
+    // Ruft `field` für jedes Feld eines Objekts mit dem Reader vor dem Wert auf.
+    fn object(&mut self, mut field: impl FnMut(&mut Self, String) -> Result<(), String>) -> Result<(), String> {
+        self.expect(b'{')?;
+        self.ws();
+        if self.s.get(self.i) == Some(&b'}') {
+            self.i += 1;
+            return Ok(());
+        }
+        loop {
+            let name = self.string()?;
+            self.expect(b':')?;
+            field(self, name)?;
+            self.ws();
+            match self.s.get(self.i) {
+                Some(b',') => self.i += 1,
+                Some(b'}') => {
+                    self.i += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error("',' oder '}' erwartet")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut out = Vec::new();
+        loop {
+            match self.s.get(self.i) {
+                None => return Err(self.error("offener String")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    // Buchschlüssel sind ASCII; Escapes werden nur übersprungen.
+                    out.push(*self.s.get(self.i + 1).ok_or_else(|| self.error("offener String"))?);
+                    self.i += 2;
+                }
+                Some(&c) => {
+                    out.push(c);
+                    self.i += 1;
+                }
+            }
+        }
+        self.i += 1;
+        String::from_utf8(out).map_err(|_| self.error("ungültiges UTF-8"))
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        self.ws();
+        let start = self.i;
+        while self.s.get(self.i).is_some_and(|c| c.is_ascii_digit() || b"+-.eE".contains(c)) {
+            self.i += 1;
+        }
+        std::str::from_utf8(&self.s[start..self.i])
+            .ok()
+            .and_then(|t| t.parse().ok())
+            .ok_or_else(|| self.error("Zahl erwartet"))
+    }
+
+    fn skip_value(&mut self) -> Result<(), String> {
+        self.ws();
+        match self.s.get(self.i) {
+            Some(b'{') => self.object(|p, _| p.skip_value()),
+            Some(b'"') => self.string().map(|_| ()),
+            Some(b'[') => {
+                self.i += 1;
+                self.ws();
+                if self.s.get(self.i) == Some(&b']') {
+                    self.i += 1;
+                    return Ok(());
+                }
+                loop {
+                    self.skip_value()?;
+                    self.ws();
+                    match self.s.get(self.i) {
+                        Some(b',') => self.i += 1,
+                        Some(b']') => {
+                            self.i += 1;
+                            return Ok(());
+                        }
+                        _ => return Err(self.error("',' oder ']' erwartet")),
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                while self.s.get(self.i).is_some_and(|c| c.is_ascii_alphabetic()) {
+                    self.i += 1;
+                }
+                Ok(())
+            }
+            _ => self.number().map(|_| ()),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// =====================================================================================
+// SAN (Standard Algebraic Notation)
+// -------------------------------------------------------------------------------------
+// Formatieren und Parsen von Zügen wie `Nbd7`, `exd6`, `O-O-O`, `e8=Q+` oder `Qh4#`.
+// Beides baut auf der legalen Zugliste auf, damit Mehrdeutigkeiten und Schach-/Matt-
+// Zusätze exakt den Regeln entsprechen.
+// =====================================================================================
+
+use crate::movegen::*;
+use crate::position::*;
+
+// Was: Formatiert einen legalen Zug in `pos` als SAN inklusive `+`/`#`.
+// Warum: Notation für Zugliste, PGN und Analyseanzeige ohne JS-Nachbau.
+// Kosten: Eine legale Zuggenerierung (Disambiguierung) plus eine nach dem Zug (Matt-Test).
+pub fn move_to_san(pos: &mut Position, mv: Move, promotion: Option<char>) -> String {
+    let mut out = san_without_suffix(pos, mv, promotion);
+    if let Some(undo) = make_move_in_place(pos, mv, promotion) {
+        let them = pos.side_to_move;
+        if is_in_check(pos, them) {
+            out.push(if generate_legal_moves(pos).is_empty() { '#' } else { '+' });
+        }
+        unmake_move_in_place(pos, mv, promotion, undo);
+    }
+    out
+}
+
+// Was: Sucht den legalen Zug zu `san` in `pos`.
+// Warum: Eingaben aus PGN/UI sind oft unsauber; akzeptiert werden fehlendes `x`,
+// überflüssige Disambiguierung, `0-0`, Promotion ohne `=` (ohne Figur: Dame wie bei
+// `parse_uci_move`) sowie Anmerkungen (`!?`, `+`, `#`).
+// Kosten: Eine legale Zuggenerierung; None bei ungültiger oder mehrdeutiger Eingabe.
+pub fn parse_san(pos: &mut Position, san: &str) -> Option<(Move, Option<char>)> {
+    let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+    let moves = generate_legal_moves(pos);
+
+    if matches!(text, "O-O" | "0-0" | "O-O-O" | "0-0-0") {
+        let long = text.len() == 5;
+        return moves.into_iter().find(|(mv, _)| {
+            matches!(mv.kind, MoveKind::Castle) && ((mv.to % 8) < (mv.from % 8)) == long
+        });
+    }
+
+    let mut chars: Vec<char> = text.chars().filter(|&c| c != 'x' && c != ':' && c != '-').collect();
+
+    // Promotion am Ende: `e8=Q`, `e8Q` oder `e8q`.
+    let mut promotion = None;
+    if let Some(&last) = chars.last() {
+        if matches!(last.to_ascii_lowercase(), 'q' | 'r' | 'b' | 'n') && chars.len() >= 3 {
+            let before = chars[chars.len() - 2];
+            if before == '=' || before.is_ascii_digit() {
+                promotion = Some(last.to_ascii_lowercase());
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+            }
+        }
+    }
+
+    // Figur: Großbuchstabe am Anfang, sonst Bauer.
+    let piece = match chars.first() {
+        Some(&c) if matches!(c, 'K' | 'Q' | 'R' | 'B' | 'N' | 'P') => {
+            chars.remove(0);
+            c
+        }
+        _ => 'P',
+    };
+
+    if chars.len() < 2 {
+        return None;
+    }
+    let to_str: String = chars[chars.len() - 2..].iter().collect();
+    let to = lan_to_field(&to_str)?;
+    let mut from_file = None;
+    let mut from_rank = None;
+    for &c in &chars[..chars.len() - 2] {
+        match c {
+            'a'..='h' => from_file = Some(c as u8 - b'a'),
+            '1'..='8' => from_rank = Some(c as u8 - b'1'),
+            _ => return None,
+        }
+    }
+
+    let mut found = None;
+    for (mv, promo) in moves {
+        let Some(moved) = pos.board[mv.from as usize] else {
+            continue;
+        };
+        if mv.to != to
+            || moved.to_ascii_uppercase() != piece
+            || matches!(mv.kind, MoveKind::Castle)
+            || from_file.is_some_and(|f| mv.from % 8 != f)
+            || from_rank.is_some_and(|r| mv.from / 8 != r)
+            || promo != promotion.or(promo.and(Some('q')))
+        {
+            continue;
+        }
+        if found.is_some() {
+            return None;
+        }
+        found = Some((mv, promo));
+    }
+    found
+}
+
+// SAN ohne Schach-/Matt-Zusatz.
+fn san_without_suffix(pos: &mut Position, mv: Move, promotion: Option<char>) -> String {
+    if let MoveKind::Castle = mv.kind {
+        return if mv.to % 8 > mv.from % 8 { "O-O".to_string() } else { "O-O-O".to_string() };
+    }
+    let Some(piece) = pos.board[mv.from as usize] else {
+        return move_to_uci(mv, promotion);
+    };
+    let kind = piece.to_ascii_uppercase();
+    let capture = move_is_capture(pos, mv);
+    let target = field_to_lan(mv.to);
+    let mut out = String::new();
+
+    if kind == 'P' {
+        if capture {
+            out.push((b'a' + mv.from % 8) as char);
+            out.push('x');
+        }
+        out.push_str(&target);
+        if let Some(p) = promotion {
+            out.push('=');
+            out.push(p.to_ascii_uppercase());
+        }
+        return out;
+    }
+
+    out.push(kind);
+    // Andere gleichartige Figuren, die dasselbe Zielfeld legal erreichen.
+    let rivals: Vec<u8> = generate_legal_moves(pos)
+        .into_iter()
+        .filter(|(other, _)| {
+            other.to == mv.to && other.from != mv.from && pos.board[other.from as usize] == Some(piece)
+        })
+        .map(|(other, _)| other.from)
+        .collect();
+    if !rivals.is_empty() {
+        let same_file = rivals.iter().any(|&sq| sq % 8 == mv.from % 8);
+        let same_rank = rivals.iter().any(|&sq| sq / 8 == mv.from / 8);
+        if !same_file {
+            out.push((b'a' + mv.from % 8) as char);
+        } else if !same_rank {
+            out.push((b'1' + mv.from / 8) as char);
+        } else {
+            out.push_str(&field_to_lan(mv.from));
+        }
+    }
+    if capture {
+        out.push('x');
+    }
+    out.push_str(&target);
+    out
+}
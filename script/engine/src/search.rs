@@ -0,0 +1,2069 @@
+// =====================================================================================
+// Suche
+// -------------------------------------------------------------------------------------
+// Iterative Deepening mit Aspiration Windows, PVS-Negamax (Null-Move, LMR,
+// Killer, History), Quiescence mit SEE und der Suchkern `search_position` für `Engine`.
+// Syzygy-Tabellen filtern die Wurzelzüge (DTZ) und schneiden im Baum per WDL ab.
+// Zeit und Fortschritt laufen über `Clock`/`ProgressSink` aus `platform`.
+// =====================================================================================
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::bitboard::*;
+use crate::position::*;
+use crate::movegen::*;
+use crate::eval::*;
+use crate::zobrist::*;
+use crate::tt::*;
+use crate::platform::*;
+use crate::skill::*;
+use crate::smp::*;
+use crate::syzygy::*;
+use crate::timeman::*;
+use crate::tune::*;
+
+const TIME_CHECK_NODE_INTERVAL: u64 = 256;
+const PROGRESS_EMIT_INTERVAL_MS: f64 = 250.0;
+
+pub(crate) const MATE_SCORE: i32 = 30000;
+const MATE_THRESHOLD: i32 = 29000;
+const MATE_EARLY_STOP_PLIES: i32 = 10;
+pub(crate) const INF_SCORE: i32 = 32000;
+
+#[inline]
+// Was: Führt `mate_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn mate_score(ply: i32) -> i32 {
+    MATE_SCORE - ply
+}
+
+#[inline]
+// Was: Führt `is_mate_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn is_mate_score(score: i32) -> bool {
+    score.abs() >= MATE_THRESHOLD
+}
+
+// Rechnet einen Mate-Score in "Matt in N Zügen" um (UCI `score mate N`).
+// Positiv = Seite am Zug setzt matt, negativ = Seite am Zug wird matt gesetzt.
+// Liefert None für normale Centipawn-Scores.
+pub fn mate_in_moves(score: i32) -> Option<i32> {
+    if !is_mate_score(score) {
+        return None;
+    }
+    let plies = MATE_SCORE - score.abs();
+    let moves = (plies + 1) / 2;
+    Some(if score > 0 { moves } else { -moves })
+}
+
+#[inline]
+// Was: Führt `clamp_eval` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn clamp_eval(score: i32) -> i32 {
+    score.clamp(-MATE_THRESHOLD + 1, MATE_THRESHOLD - 1)
+}
+
+#[inline]
+// Was: Führt `tt_store_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn tt_store_score(score: i32, ply: i32) -> i32 {
+    if score >= MATE_THRESHOLD {
+        score + ply
+    } else if score <= -MATE_THRESHOLD {
+        score - ply
+    } else {
+        score
+    }
+}
+
+#[inline]
+// Was: Führt `tt_probe_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn tt_probe_score(score: i32, ply: i32) -> i32 {
+    if score >= MATE_THRESHOLD {
+        score - ply
+    } else if score <= -MATE_THRESHOLD {
+        score + ply
+    } else {
+        score
+    }
+}
+
+// Kompakter Key für Killer-/History-Heuristiken.
+// Was: Führt `move_key` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn move_key(mv: Move, promo: Option<char>) -> (u8, u8, u8) {
+    (mv.from, mv.to, encode_promo(promo))
+}
+
+#[inline]
+// Was: Führt `see_piece_value` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn see_piece_value(piece: char) -> i32 {
+    match piece.to_ascii_lowercase() {
+        'p' => 100,
+        'n' => 320,
+        'b' => 330,
+        'r' => 500,
+        'q' => 900,
+        'k' => 20_000,
+        _ => 0,
+    }
+}
+
+#[inline]
+// Was: Führt `pawn_attackers_to` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn pawn_attackers_to(sq: u8, side: Color) -> u64 {
+    let target = bb(sq);
+    match side {
+        Color::White => ((target & !FILE_A) >> 9) | ((target & !FILE_H) >> 7),
+        Color::Black => ((target & !FILE_H) << 9) | ((target & !FILE_A) << 7),
+    }
+}
+
+#[inline]
+// Was: Führt `attackers_to_square` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn attackers_to_square(pos: &Position, sq: u8, occ: u64, side: Color) -> u64 {
+    let (pawns, knights, bishops, rooks, queens, king) = match side {
+        Color::White => (pos.bb.wp, pos.bb.wn, pos.bb.wb, pos.bb.wr, pos.bb.wq, pos.bb.wk),
+        Color::Black => (pos.bb.bp, pos.bb.bn, pos.bb.bb, pos.bb.br, pos.bb.bq, pos.bb.bk),
+    };
+
+    let mut attackers = 0u64;
+    attackers |= pawn_attackers_to(sq, side) & pawns;
+    attackers |= KNIGHT_ATTACKS[sq as usize] & knights;
+    attackers |= bishop_attacks(sq, occ) & (bishops | queens);
+    attackers |= rook_attacks(sq, occ) & (rooks | queens);
+    attackers |= KING_ATTACKS[sq as usize] & king;
+    attackers
+}
+
+#[inline]
+// Was: Führt `least_valuable_attacker` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn least_valuable_attacker(pos: &Position, sq: u8, occ: u64, side: Color) -> Option<(u8, i32)> {
+    let attackers = attackers_to_square(pos, sq, occ, side);
+    if attackers == 0 {
+        return None;
+    }
+
+    let (pawns, knights, bishops, rooks, queens, kings) = match side {
+        Color::White => (pos.bb.wp, pos.bb.wn, pos.bb.wb, pos.bb.wr, pos.bb.wq, pos.bb.wk),
+        Color::Black => (pos.bb.bp, pos.bb.bn, pos.bb.bb, pos.bb.br, pos.bb.bq, pos.bb.bk),
+    };
+
+    let mut bb_attackers = attackers & pawns & occ;
+    if bb_attackers != 0 {
+        let sq = pop_lsb(&mut bb_attackers);
+        return Some((sq, see_piece_value('p')));
+    }
+    bb_attackers = attackers & knights & occ;
+    if bb_attackers != 0 {
+        let sq = pop_lsb(&mut bb_attackers);
+        return Some((sq, see_piece_value('n')));
+    }
+    bb_attackers = attackers & bishops & occ;
+    if bb_attackers != 0 {
+        let sq = pop_lsb(&mut bb_attackers);
+        return Some((sq, see_piece_value('b')));
+    }
+    bb_attackers = attackers & rooks & occ;
+    if bb_attackers != 0 {
+        let sq = pop_lsb(&mut bb_attackers);
+        return Some((sq, see_piece_value('r')));
+    }
+    bb_attackers = attackers & queens & occ;
+    if bb_attackers != 0 {
+        let sq = pop_lsb(&mut bb_attackers);
+        return Some((sq, see_piece_value('q')));
+    }
+    bb_attackers = attackers & kings & occ;
+    if bb_attackers != 0 {
+        let sq = pop_lsb(&mut bb_attackers);
+        return Some((sq, see_piece_value('k')));
+    }
+
+    None
+}
+
+// Was: Führt `see` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn see(pos: &Position, mv: Move, promo: Option<char>) -> i32 {
+    // SEE (Static Exchange Evaluation):
+    // simuliert rein materialbasiert die Schlagabfolge auf dem Zielfeld,
+    // um "schlechte" Captures im Move-Ordering/pruning zu erkennen.
+    let from = mv.from;
+    let to = mv.to;
+    let Some(moved_piece) = pos.board[from as usize] else {
+        return 0;
+    };
+
+    let captured = capture_info(pos, mv);
+    let mut gain = [0i32; 32];
+    let captured_value = captured.map(|(_, p)| see_piece_value(p)).unwrap_or(0);
+    let promoted_piece = if matches!(mv.kind, MoveKind::Promotion) {
+        match promo.map(|c| c.to_ascii_lowercase()) {
+            Some('q') => 'q',
+            Some('r') => 'r',
+            Some('b') => 'b',
+            Some('n') => 'n',
+            _ => 'q',
+        }
+    } else {
+        moved_piece.to_ascii_lowercase()
+    };
+    let promotion_gain = if matches!(mv.kind, MoveKind::Promotion) {
+        see_piece_value(promoted_piece) - see_piece_value('p')
+    } else {
+        0
+    };
+    gain[0] = captured_value + promotion_gain;
+
+    let mut occ = pos.bb.occ;
+    occ &= !bb(from);
+    if let (MoveKind::EnPassant, Some((cap_sq, _))) = (mv.kind, captured) {
+        occ &= !bb(cap_sq);
+    }
+    occ |= bb(to);
+
+    let mut side = pos.side_to_move.opposite();
+    let mut last_piece_value = see_piece_value(promoted_piece);
+    let mut depth = 0usize;
+
+    while let Some((attacker_sq, attacker_val)) = least_valuable_attacker(pos, to, occ, side) {
+        depth += 1;
+        if depth >= gain.len() {
+            break;
+        }
+        gain[depth] = last_piece_value - gain[depth - 1];
+        if gain[depth].max(-gain[depth - 1]) < 0 {
+            break;
+        }
+        occ &= !bb(attacker_sq);
+        side = side.opposite();
+        last_piece_value = attacker_val;
+    }
+
+    while depth > 0 {
+        depth -= 1;
+        gain[depth] = -std::cmp::max(-gain[depth], gain[depth + 1]);
+    }
+
+    gain[0]
+}
+
+// Was: Führt `is_quiet_move` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn is_quiet_move(pos: &Position, mv: Move, promo: Option<char>) -> bool {
+    if let MoveKind::Promotion = mv.kind {
+        return false;
+    }
+    if promo.is_some() {
+        return false;
+    }
+    !move_is_capture(pos, mv)
+}
+
+// Killer-Heuristik: merkt sich je Ply die besten fail-high Quiet-Moves.
+// Was: Führt `update_killers` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn update_killers(ctx: &mut SearchContext, ply: i32, key: (u8, u8, u8)) {
+    if ply < 0 {
+        return;
+    }
+    let idx = ply as usize;
+    if idx >= ctx.killers.len() {
+        return;
+    }
+    if ctx.killers[idx][0] == Some(key) {
+        return;
+    }
+    ctx.killers[idx][1] = ctx.killers[idx][0];
+    ctx.killers[idx][0] = Some(key);
+}
+
+// History-Heuristik: verstärkt Quiet-Moves, die auf tieferen Ebenen gut waren.
+// Was: Führt `update_history_heur` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn update_history_heur(ctx: &mut SearchContext, color: Color, from: u8, to: u8, depth: u32) {
+    let c = if color == Color::White { 0 } else { 1 };
+    let entry = &mut ctx.history_heur[c][from as usize][to as usize];
+    let inc = (depth as i32).saturating_mul(depth as i32);
+    *entry = entry.saturating_add(inc);
+}
+
+#[inline]
+// Was: Führt `has_non_pawn_material` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn has_non_pawn_material(pos: &Position, color: Color) -> bool {
+    match color {
+        Color::White => (pos.bb.wn | pos.bb.wb | pos.bb.wr | pos.bb.wq) != 0,
+        Color::Black => (pos.bb.bn | pos.bb.bb | pos.bb.br | pos.bb.bq) != 0,
+    }
+}
+
+#[inline]
+// Was: Führt `lmr_reduction` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn lmr_reduction(depth: u32, move_index: usize) -> u32 {
+    // Warum LMR stufenweise?
+    // Späte, ruhige Züge sind statistisch seltener best. Daher dort
+    // aggressiver reduzieren, aber bei kleinen Tiefen konservativ bleiben.
+    let mut r = 1u32;
+    if depth >= 5 && move_index > 4 {
+        r += 1;
+    }
+    if depth >= 8 && move_index > 8 {
+        r += 1;
+    }
+    if depth >= 12 && move_index > 16 {
+        r += 1;
+    }
+    if depth >= 16 && move_index > 24 {
+        r += 1;
+    }
+    r.min(depth.saturating_sub(1))
+}
+
+// Zwei Killer-Slots pro Ply (Key aus `move_key`).
+type KillerSlots = [Option<(u8, u8, u8)>; 2];
+
+struct MoveOrderScratch {
+    captures: Vec<(i32, Move, Option<char>)>,
+    quiet: Vec<(i32, Move, Option<char>)>,
+    rest: Vec<(Move, Option<char>)>,
+}
+
+impl MoveOrderScratch {
+    // Was: Führt `new` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+    // Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+    // Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+    fn new() -> Self {
+        MoveOrderScratch {
+            captures: Vec::new(),
+            quiet: Vec::new(),
+            rest: Vec::new(),
+        }
+    }
+}
+
+// Was: Führt `capture_score` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn capture_score(pos: &Position, mv: Move, promo: Option<char>) -> i32 {
+    // Warum SEE in der Capture-Bewertung?
+    // MVV-LVA allein überschätzt oft "giftige" Captures.
+    // SEE hilft, taktisch schlechte Schläge früh nach hinten zu sortieren.
+    let mut score = 0;
+    if let Some(p) = promo {
+        score += 5000 + piece_value(p.to_ascii_lowercase());
+    } else if let MoveKind::Promotion = mv.kind {
+        score += 5000;
+    }
+    if let Some((_, cap)) = capture_info(pos, mv) {
+        let mover = pos.board[mv.from as usize].unwrap_or('P');
+        score += 3000 + piece_value(cap) - piece_value(mover);
+    }
+    let see_gain = see(pos, mv, promo);
+    if see_gain >= 0 {
+        20_000 + score + see_gain
+    } else {
+        score + see_gain - 4_000
+    }
+}
+
+// Was: Sortiert Züge nach Hash/PV, Captures (SEE), Killer-Moves und History-Heuristik.
+// Warum: Gute Zugreihenfolge erhöht die Wahrscheinlichkeit früher Alpha-Beta-Cutoffs und senkt die Knotenzahl.
+// Kosten: Dominiert von den lokalen Sortierungen; pro Knoten moderat und durch bessere Pruning-Quote meist amortisiert.
+fn order_moves_in_place(
+    pos: &Position,
+    moves: &mut Vec<(Move, Option<char>)>,
+    pv_move: Option<(u8, u8, Option<char>)>,
+    tt_entry: Option<TTEntry>,
+    killers: Option<&KillerSlots>,
+    history_heur: Option<&[[[i32; 64]; 64]; 2]>,
+    scratch: &mut MoveOrderScratch,
+) {
+    // Warum diese Reihenfolge?
+    // 1) Hash/PV-Move zuerst -> oft sofort guter Alpha-Boost oder Cutoff.
+    // 2) Taktische Züge (SEE-sortiert)
+    // 3) Killer-Moves
+    // 4) Quiet-History
+    // Ergebnis: maximaler Cutoff-Effekt bei minimalem Sortieraufwand.
+    let pv_best = pv_move;
+    let tt_best = tt_entry.and_then(entry_best_move);
+    let hash_best = if tt_best.is_some() { tt_best } else { pv_best };
+    let mut hash_move: Option<(Move, Option<char>)> = None;
+    let mut killer_moves: [Option<(Move, Option<char>)>; 2] = [None, None];
+    scratch.captures.clear();
+    scratch.quiet.clear();
+    scratch.rest.clear();
+
+    for (mv, promo) in moves.drain(..) {
+        if let Some((bf, bt, bp)) = hash_best {
+            if mv.from == bf && mv.to == bt && promo == bp {
+                hash_move = Some((mv, promo));
+                continue;
+            }
+        }
+
+        let is_capture = move_is_capture(pos, mv) || matches!(mv.kind, MoveKind::Promotion) || promo.is_some();
+        if is_capture {
+            scratch.captures.push((capture_score(pos, mv, promo), mv, promo));
+            continue;
+        }
+
+        let key = move_key(mv, promo);
+        if let Some(k) = killers {
+            if k[0] == Some(key) {
+                killer_moves[0] = Some((mv, promo));
+                continue;
+            }
+            if k[1] == Some(key) {
+                killer_moves[1] = Some((mv, promo));
+                continue;
+            }
+        }
+
+        if let Some(hist) = history_heur {
+            if is_quiet_move(pos, mv, promo) {
+                let c = if pos.side_to_move == Color::White { 0 } else { 1 };
+                scratch.quiet.push((hist[c][mv.from as usize][mv.to as usize], mv, promo));
+                continue;
+            }
+        }
+
+        scratch.rest.push((mv, promo));
+    }
+
+    scratch.captures.sort_by_key(|e| std::cmp::Reverse(e.0));
+
+    // Warum Partial-Sort bei Quiets?
+    // Vollsortierung aller ruhigen Züge kostet Zeit, bringt aber wenig.
+    // Für Alpha-Beta reichen die vorderen Kandidaten meist aus.
+    const QUIET_SORT_LIMIT: usize = 12;
+    if scratch.quiet.len() > 1 {
+        if scratch.quiet.len() > QUIET_SORT_LIMIT {
+            scratch.quiet.select_nth_unstable_by(QUIET_SORT_LIMIT, |a, b| b.0.cmp(&a.0));
+            scratch.quiet[..QUIET_SORT_LIMIT].sort_by_key(|e| std::cmp::Reverse(e.0));
+        } else {
+            scratch.quiet.sort_by_key(|e| std::cmp::Reverse(e.0));
+        }
+    }
+
+    moves.clear();
+    if let Some(mv) = hash_move {
+        moves.push(mv);
+    }
+    for (_, mv, promo) in scratch.captures.drain(..) {
+        moves.push((mv, promo));
+    }
+    if let Some(mv) = killer_moves[0] {
+        moves.push(mv);
+    }
+    if let Some(mv) = killer_moves[1] {
+        moves.push(mv);
+    }
+    for (_, mv, promo) in scratch.quiet.drain(..) {
+        moves.push((mv, promo));
+    }
+    moves.append(&mut scratch.rest);
+}
+
+// Suchzustand einer Engine-Instanz, der über Suchen hinweg wiederverwendet wird
+// (TT, Killer, History-Heuristik, gecachte History-Hashes). Gehört genau einer `Engine`.
+pub(crate) struct SearchState {
+    mb: u32,
+    gen: u8,
+    table: Option<TT>,
+    killers: Vec<[Option<(u8, u8, u8)>; 2]>,
+    history_heur: [[[i32; 64]; 64]; 2],
+    history_cache_raw: String,
+    history_cache_hashes: Vec<u64>,
+}
+
+impl SearchState {
+    // Was: Leerer Zustand mit einer TT von `tt_mb` MB (0 = ohne TT).
+    // Warum: Jede Engine-Instanz startet mit eigener, frischer Tabelle.
+    // Kosten: Allokation der TT.
+    pub(crate) fn new(tt_mb: u32) -> Self {
+        SearchState {
+            mb: tt_mb,
+            gen: 0,
+            table: TT::new(tt_mb),
+            killers: Vec::new(),
+            history_heur: [[[0i32; 64]; 64]; 2],
+            history_cache_raw: String::new(),
+            history_cache_hashes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn tt_mb(&self) -> u32 {
+        self.mb
+    }
+
+    // Was: Ersetzt die TT durch eine leere Tabelle der neuen Größe; gleiche Größe ist ein No-op.
+    // Warum: Größenwechsel verwirft die Einträge ohnehin, Heuristiken bleiben erhalten.
+    // Kosten: Neuallokation der TT.
+    pub(crate) fn set_tt_size(&mut self, tt_mb: u32) {
+        if self.mb != tt_mb {
+            self.table = TT::new(tt_mb);
+            self.mb = tt_mb;
+            self.gen = 0;
+        }
+    }
+
+    // Belegung der TT durch die letzte Suche in Promille (0 ohne TT).
+    pub(crate) fn hashfull(&self) -> u32 {
+        self.table.as_ref().map_or(0, |table| table.hashfull(self.gen))
+    }
+
+    // Was: Leert nur die TT und setzt ihre Generation zurück.
+    // Kosten: Linear in der TT-Größe.
+    pub(crate) fn clear_tt(&mut self) {
+        if let Some(table) = self.table.as_ref() {
+            table.clear();
+        }
+        self.gen = 0;
+    }
+
+    // Was: Leert TT, Killer und History-Heuristik und setzt die TT-Generation zurück.
+    // Warum: Deterministische Suchen und neue Partien starten unabhängig von vorherigen Suchen.
+    // Kosten: Linear in der TT-Größe.
+    pub(crate) fn reset_heuristics(&mut self) {
+        self.clear_tt();
+        self.killers.fill([None; 2]);
+        self.history_heur = [[[0; 64]; 64]; 2];
+    }
+
+    // Was: Wie `reset_heuristics`, verwirft zusätzlich den History-Cache.
+    // Warum: `ucinewgame` bzw. `Engine::new_game` – nichts aus der alten Partie wirkt nach.
+    // Kosten: Linear in der TT-Größe.
+    pub(crate) fn reset(&mut self) {
+        self.reset_heuristics();
+        self.history_cache_raw.clear();
+        self.history_cache_hashes.clear();
+    }
+}
+
+// Laufender Suchkontext pro Root-Search.
+// Enthält Knoten-/Zeitstände, Heuristiken, temporäre Buffers sowie Uhr und
+// Fortschrittskanal des aufrufenden Frontends.
+pub(crate) struct SearchContext<'a> {
+    clock: &'a dyn Clock,
+    sink: &'a mut dyn ProgressSink,
+    pub(crate) nodes: u64,
+    completed_nodes: u64,
+    start_ms: f64,
+    // Beginn des Zeitbudgets: Suchstart bzw. Zeitpunkt des Ponderhits.
+    budget_start_ms: f64,
+    time_limit_ms: f64,
+    time_check_interval_ms: f64,
+    last_time_check_ms: f64,
+    last_progress_emit_ms: f64,
+    progress_emit_interval_ms: f64,
+    current_depth: u32,
+    completed_depth: u32,
+    // Größte erreichte Ply der laufenden Iteration (UCI `seldepth`).
+    seldepth: u32,
+    pub(crate) stop: bool,
+    // Mattsuche (`solve_mate`): keine Reduktionen/Null-Move/Verlängerungen, Blätter ohne
+    // Quiescence, damit "kein Matt" ein Beweis ist.
+    pub(crate) mate_mode: bool,
+    // Solange true, zählt das Zeitlimit nicht (siehe `poll_ponderhit`).
+    pondering: bool,
+    ponderhit_flag: Option<Arc<AtomicBool>>,
+    node_limit: u64,
+    stop_flag: Option<Arc<AtomicBool>>,
+    // Skill-Rauschen auf der statischen Bewertung (Amplitude in cp, 0 = aus).
+    eval_noise: i32,
+    noise_seed: u64,
+    pub(crate) tt_gen: u8,
+    history: Vec<u64>,
+    killers: Vec<[Option<(u8, u8, u8)>; 2]>,
+    pub(crate) history_heur: [[[i32; 64]; 64]; 2],
+    move_buf: Vec<Vec<(Move, Option<char>)>>,
+    order_scratch: MoveOrderScratch,
+    // Aspiration-Fehlschläge der Hauptvariante in der laufenden Iteration (Zeitmanagement).
+    aspiration_fails: u32,
+    // Erlaubte Wurzelzüge (`searchmoves`); leer = alle legalen Züge.
+    pub(crate) root_moves: Vec<(Move, Option<char>)>,
+    tablebases: Option<Arc<Tablebases>>,
+    // Höchste Steinzahl für WDL-Proben im Baum (0 = keine Proben).
+    pub(crate) tb_cardinality: u32,
+    pub(crate) tb_hits: u64,
+    // Tuning-Parameter dieser Suche (Eval und Aspiration).
+    tune: TuneValues,
+}
+
+impl<'a> SearchContext<'a> {
+    // Was: Kontext mit leeren Heuristiken und Zeit-/Knoten-/Stop-Limits aus `limits`.
+    // Warum: Gemeinsamer Startzustand für `search_position` (übernimmt danach Killer/History
+    // aus dem Engine-Zustand), die Lazy-SMP-Helfer und `solve_mate`.
+    // Kosten: Allokation der Killer-Tabelle mit `max_ply` Einträgen.
+    pub(crate) fn new(
+        clock: &'a dyn Clock,
+        sink: &'a mut dyn ProgressSink,
+        limits: &SearchLimits,
+        max_ply: usize,
+        history: Vec<u64>,
+    ) -> Self {
+        let start_ms = clock.now_ms();
+        let mut ctx = SearchContext {
+            clock,
+            sink,
+            nodes: 0,
+            completed_nodes: 0,
+            start_ms,
+            budget_start_ms: start_ms,
+            time_limit_ms: 0.0,
+            time_check_interval_ms: 0.0,
+            last_time_check_ms: start_ms,
+            last_progress_emit_ms: start_ms - PROGRESS_EMIT_INTERVAL_MS,
+            progress_emit_interval_ms: PROGRESS_EMIT_INTERVAL_MS,
+            current_depth: 0,
+            completed_depth: 0,
+            seldepth: 0,
+            stop: false,
+            mate_mode: false,
+            pondering: limits.ponder,
+            ponderhit_flag: limits.ponderhit.clone(),
+            node_limit: limits.nodes,
+            stop_flag: limits.stop.clone(),
+            eval_noise: 0,
+            noise_seed: 0,
+            tt_gen: 1,
+            history,
+            killers: vec![[None; 2]; max_ply],
+            history_heur: [[[0; 64]; 64]; 2],
+            move_buf: Vec::with_capacity(max_ply),
+            order_scratch: MoveOrderScratch::new(),
+            aspiration_fails: 0,
+            root_moves: Vec::new(),
+            tablebases: limits.tablebases.clone(),
+            tb_cardinality: limits.tablebases.as_ref().map_or(0, |tb| tb.max_pieces()),
+            tb_hits: 0,
+            tune: limits.tune,
+        };
+        ctx.set_time_limit(limits.time_ms as f64);
+        ctx
+    }
+
+    // Hartes Zeitlimit in ms (0 = keins), geprüft in `should_stop`.
+    pub(crate) fn set_time_limit(&mut self, time_limit_ms: f64) {
+        self.time_limit_ms = time_limit_ms;
+        // Zeitlimit häufiger prüfen, damit UI-Timer und Engine-Ende nicht stark auseinanderlaufen.
+        // Vorher: bei langen Suchen bis zu 2000ms Prüfintervall -> sichtbar "leere Timebar, aber kein Zug".
+        self.time_check_interval_ms = if time_limit_ms <= 0.0 {
+            0.0
+        } else {
+            time_limit_ms.min(250.0)
+        };
+    }
+
+    // Verstrichene Zeit seit Suchbeginn in ms.
+    pub(crate) fn elapsed_ms(&self) -> f64 {
+        (self.clock.now_ms() - self.start_ms).max(0.0)
+    }
+}
+
+#[inline]
+// Skill-Rauschen für die Stellung `hash`; 0 bei voller Stärke.
+fn skill_noise(ctx: &SearchContext, hash: u64) -> i32 {
+    if ctx.eval_noise == 0 {
+        0
+    } else {
+        eval_noise(hash, ctx.noise_seed, ctx.eval_noise)
+    }
+}
+
+#[inline]
+// Was: Führt `history_count` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn history_count(ctx: &SearchContext, hash: u64, halfmove: u32) -> u8 {
+    let len = ctx.history.len();
+    if len == 0 {
+        return 0;
+    }
+    let max_back = (halfmove as usize).min(len.saturating_sub(1));
+    let mut count = 0u8;
+    let mut idx = len - 1;
+    let mut back = 0usize;
+
+    loop {
+        if ctx.history[idx] == hash {
+            count = count.saturating_add(1);
+            if count >= 3 {
+                return count;
+            }
+        }
+        if back + 2 > max_back || idx < 2 {
+            break;
+        }
+        idx -= 2;
+        back += 2;
+    }
+
+    count
+}
+
+#[inline]
+// Was: Führt `history_push` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn history_push(ctx: &mut SearchContext, hash: u64) {
+    ctx.history.push(hash);
+}
+
+#[inline]
+// Was: Führt `history_pop` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn history_pop(ctx: &mut SearchContext) {
+    let _ = ctx.history.pop();
+}
+
+#[inline]
+// Was: Führt `emit_progress_at` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn emit_progress_at(ctx: &mut SearchContext, now: f64, force: bool) {
+    if !force && (now - ctx.last_progress_emit_ms) < ctx.progress_emit_interval_ms {
+        return;
+    }
+    ctx.last_progress_emit_ms = now;
+    let elapsed_ms = (now - ctx.budget_start_ms).max(0.0) as u32;
+    let depth_for_display = ctx.current_depth.max(ctx.completed_depth);
+    ctx.sink.progress(depth_for_display, ctx.completed_nodes, ctx.nodes, elapsed_ms);
+}
+
+#[inline]
+// Was: Führt `emit_progress` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn emit_progress(ctx: &mut SearchContext, force: bool) {
+    let now = ctx.clock.now_ms();
+    emit_progress_at(ctx, now, force);
+}
+
+// Was: Beendet den Ponder-Modus, sobald ein Ponderhit gemeldet wird; liefert, ob noch
+// gepondert wird.
+// Warum: Nach dem Ponderhit läuft dieselbe Suche als normale Suche weiter, mit Zeitbudget
+// ab diesem Moment; TT, Killer und History bleiben dabei einfach erhalten.
+// Kosten: Ein Atomic-Load bzw. Sink-Aufruf, nur solange gepondert wird.
+fn poll_ponderhit(ctx: &mut SearchContext) -> bool {
+    if !ctx.pondering {
+        return false;
+    }
+    let hit = ctx.ponderhit_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed));
+    if hit || ctx.sink.ponderhit() {
+        let now = ctx.clock.now_ms();
+        ctx.pondering = false;
+        ctx.budget_start_ms = now;
+        ctx.last_time_check_ms = now;
+    }
+    ctx.pondering
+}
+
+#[inline]
+// Was: Führt `should_stop` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn should_stop(ctx: &mut SearchContext) -> bool {
+    // Zeitprüfung nur in Intervallen, um Overhead gering zu halten.
+    // Gleichzeitig in diesem Takt Progress-Events zur UI schicken.
+    if ctx.stop {
+        return true;
+    }
+    if ctx.node_limit > 0 && ctx.nodes >= ctx.node_limit {
+        ctx.stop = true;
+        return true;
+    }
+    // Warum knotenbasiertes Intervall?
+    // Systemzeit und Stop-Flags abzufragen ist teuer; daher nur periodisch prüfen.
+    if (ctx.nodes & (TIME_CHECK_NODE_INTERVAL - 1)) != 0 {
+        return false;
+    }
+    // Externer Abbruch (UCI `stop` aus dem Eingabe-Thread, Stop-Knopf im Browser).
+    let flag_set = ctx.stop_flag.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed));
+    if flag_set || ctx.sink.stop_requested() {
+        ctx.stop = true;
+        return true;
+    }
+    if poll_ponderhit(ctx) || ctx.time_limit_ms <= 0.0 {
+        return false;
+    }
+    let now = ctx.clock.now_ms();
+    if now - ctx.last_time_check_ms < ctx.time_check_interval_ms {
+        return false;
+    }
+    ctx.last_time_check_ms = now;
+    emit_progress_at(ctx, now, false);
+    if now - ctx.budget_start_ms >= ctx.time_limit_ms {
+        ctx.stop = true;
+        return true;
+    }
+    false
+}
+
+// Was: Führt `take_move_buf` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn take_move_buf(ctx: &mut SearchContext, ply: i32) -> Vec<(Move, Option<char>)> {
+    let idx = if ply < 0 { 0 } else { ply as usize };
+    if idx >= ctx.move_buf.len() {
+        ctx.move_buf.resize_with(idx + 1, Vec::new);
+    }
+    std::mem::take(&mut ctx.move_buf[idx])
+}
+
+// Was: Führt `restore_move_buf` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn restore_move_buf(ctx: &mut SearchContext, ply: i32, buf: Vec<(Move, Option<char>)>) {
+    let idx = if ply < 0 { 0 } else { ply as usize };
+    if idx >= ctx.move_buf.len() {
+        ctx.move_buf.resize_with(idx + 1, Vec::new);
+    }
+    ctx.move_buf[idx] = buf;
+}
+
+// Was: Führt die Quiescence-Suche aus (nur taktische Fortsetzungen statt voller Breite).
+// Warum: Verhindert Horizon-Effekte, indem instabile Blattstellungen vor der finalen Bewertung "beruhigt" werden.
+// Kosten: Variabel je nach Taktikdichte; typischerweise deutlich geringer als eine volle Tiefe-Erweiterung.
+#[allow(clippy::too_many_arguments)]
+fn quiescence(
+    pos: &mut Position,
+    mut alpha: i32,
+    beta: i32,
+    ctx: &mut SearchContext,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+    ply: i32,
+) -> i32 {
+    // Quiescence-Suche:
+    // erweitert Blätter mit taktischen Zügen, damit "noisy" Positionen
+    // nicht mit unrealistischem Stand-Pat bewertet werden.
+    if ctx.stop {
+        return 0;
+    }
+    if pos.halfmove >= 100 {
+        return 0;
+    }
+    if history_count(ctx, hash, pos.halfmove) >= 3 {
+        return 0;
+    }
+
+    ctx.nodes += 1;
+    ctx.seldepth = ctx.seldepth.max(ply as u32);
+    if should_stop(ctx) {
+        return 0;
+    }
+
+    if let Some(table) = tt.as_ref() {
+        if let Some(entry) = table.probe(hash) {
+            if let Some(val) = tt_cutoff_value(entry, 0, alpha, beta, ply) {
+                return val;
+            }
+        }
+    }
+
+    let in_check = is_in_check(pos, pos.side_to_move);
+    let mut stand_pat = -INF_SCORE;
+    if !in_check {
+        stand_pat = clamp_eval(evaluate_fast(pos) + skill_noise(ctx, hash));
+        if stand_pat >= beta {
+            return beta;
+        }
+        if stand_pat > alpha {
+            alpha = stand_pat;
+        }
+    }
+
+    let mut moves = take_move_buf(ctx, ply);
+    if in_check {
+        generate_legal_moves_into(pos, &mut moves);
+    } else {
+        generate_tactical_moves_into(pos, &mut moves);
+    }
+
+    if moves.is_empty() {
+        if in_check {
+            restore_move_buf(ctx, ply, moves);
+            return -mate_score(ply);
+        }
+        restore_move_buf(ctx, ply, moves);
+        return stand_pat;
+    }
+
+    order_moves_in_place(pos, &mut moves, None, None, None, Some(&ctx.history_heur), &mut ctx.order_scratch);
+    const QS_SEE_PRUNE_MARGIN: i32 = 80;
+    for (mv, promo) in moves.iter().copied() {
+        if ctx.stop {
+            break;
+        }
+        if !in_check
+            && move_is_capture(pos, mv)
+            && !matches!(mv.kind, MoveKind::Promotion)
+            && promo.is_none()
+            && see(pos, mv, promo) < -QS_SEE_PRUNE_MARGIN
+        {
+            continue;
+        }
+        let Some(undo) = make_move_in_place(pos, mv, promo) else { continue; };
+        let next_hash = update_hash_after_move(hash, zob, &undo, pos, mv);
+        history_push(ctx, next_hash);
+        let score = -quiescence(pos, -beta, -alpha, ctx, tt, zob, next_hash, ply + 1);
+        history_pop(ctx);
+        unmake_move_in_place(pos, mv, promo, undo);
+        if ctx.stop {
+            break;
+        }
+        if score >= beta {
+            restore_move_buf(ctx, ply, moves);
+            return beta;
+        }
+        if score > alpha {
+            alpha = score;
+        }
+    }
+
+    restore_move_buf(ctx, ply, moves);
+    alpha
+}
+
+// Syzygy-Proben im Baum erst ab dieser Resttiefe, wenn die Stellung die volle Steinzahl hat.
+const TB_PROBE_DEPTH: u32 = 1;
+
+// Was: Score und TT-Bound einer WDL-Probe im Baum.
+// Warum: Cursed Wins/Blessed Losses sind wegen der 50-Züge-Regel Remis (±2 cp als Tendenz);
+// echte Gewinne liegen knapp unter `TB_WIN_SCORE`, kürzere Wege zählen mehr.
+// Kosten: Konstant.
+fn tb_wdl_value(wdl: i32, ply: i32) -> (i32, u8) {
+    if wdl < -1 {
+        (-TB_WIN_SCORE + ply, TT_BOUND_UPPER)
+    } else if wdl > 1 {
+        (TB_WIN_SCORE - ply, TT_BOUND_LOWER)
+    } else {
+        (2 * wdl, TT_BOUND_EXACT)
+    }
+}
+
+// Was: Kern der Alpha-Beta-Suche im Negamax-Format inklusive TT, Null-Move, LMR und Heuristiken.
+// Warum: Bündelt die leistungsrelevante Hauptsuche in einer rekursiven Routine mit konsistenter Score-Perspektive.
+// Kosten: Exponentiell im Worst Case, in der Praxis stark reduziert durch Pruning und Move-Ordering.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn negamax(
+    pos: &mut Position,
+    depth: u32,
+    mut alpha: i32,
+    beta: i32,
+    ctx: &mut SearchContext,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+    ply: i32,
+) -> i32 {
+    // Hauptsuchroutine (Alpha-Beta im Negamax-Format) mit:
+    // - TT-Probe/Store
+    // - Null-Move-Pruning
+    // - LMR (Late Move Reductions)
+    // - Killer/History-Heuristik
+    if ctx.stop {
+        return 0;
+    }
+    if pos.halfmove >= 100 {
+        return 0;
+    }
+    if history_count(ctx, hash, pos.halfmove) >= 3 {
+        return 0;
+    }
+
+    ctx.nodes += 1;
+    ctx.seldepth = ctx.seldepth.max(ply as u32);
+    if should_stop(ctx) {
+        return 0;
+    }
+
+    let tt_entry = {
+        if let Some(table) = tt.as_ref() {
+            table.probe(hash)
+        } else {
+            None
+        }
+    };
+    if let Some(entry) = tt_entry {
+        if let Some(val) = tt_cutoff_value(entry, depth, alpha, beta, ply) {
+            return val;
+        }
+    }
+
+    // Syzygy-WDL direkt nach Schlag/Bauernzug (dann stimmt der 50-Züge-Zähler der Tabelle).
+    if ply > 0 && ctx.tb_cardinality > 0 && pos.halfmove == 0 && pos.castling == 0 {
+        let pieces = popcnt(pos.bb.occ) as u32;
+        if pieces < ctx.tb_cardinality || (pieces == ctx.tb_cardinality && depth >= TB_PROBE_DEPTH) {
+            let probe = ctx.tablebases.as_ref().and_then(|tb| tb.wdl(pos));
+            if let Some(wdl) = probe {
+                ctx.tb_hits += 1;
+                let (value, bound) = tb_wdl_value(wdl, ply);
+                let cutoff = match bound {
+                    TT_BOUND_LOWER => value >= beta,
+                    TT_BOUND_UPPER => value <= alpha,
+                    _ => true,
+                };
+                if cutoff {
+                    if let Some(table) = tt.as_ref() {
+                        table.store(hash, depth + 6, value, bound, None, ctx.tt_gen);
+                    }
+                    return value;
+                }
+            }
+        }
+    }
+
+    // Hinweis: Quiescence wird aus negamax(depth==0) aufgerufen.
+    if depth == 0 {
+        if ctx.mate_mode {
+            // Blatt der Mattsuche: nur "matt" oder "kein Matt gefunden" (0).
+            return if is_in_check(pos, pos.side_to_move) && generate_legal_moves(pos).is_empty() {
+                -mate_score(ply)
+            } else {
+                0
+            };
+        }
+        return quiescence(pos, alpha, beta, ctx, tt, zob, hash, ply);
+    }
+
+    let in_check = is_in_check(pos, pos.side_to_move);
+    let is_pv = alpha.saturating_add(1) < beta;
+
+    // Warum Null-Move-Pruning nur unter Bedingungen?
+    // In Schachnähe/PV oder ohne Restmaterial ist Null-Move unzuverlässiger.
+    if depth >= 3
+        && !ctx.mate_mode
+        && !is_pv
+        && !in_check
+        && ply > 0
+        && has_non_pawn_material(pos, pos.side_to_move)
+        && beta.abs() < MATE_THRESHOLD
+    {
+        let null_reduction = if depth >= 8 { 3 } else { 2 };
+        let null_depth = depth.saturating_sub(1 + null_reduction);
+        let prev_side = pos.side_to_move;
+        let prev_ep = pos.ep;
+        let prev_halfmove = pos.halfmove;
+        let prev_fullmove = pos.fullmove;
+
+        pos.side_to_move = prev_side.opposite();
+        pos.ep = None;
+        pos.halfmove = pos.halfmove.saturating_add(1);
+        if prev_side == Color::Black {
+            pos.fullmove = pos.fullmove.saturating_add(1);
+        }
+
+        let mut null_hash = hash ^ zob.side;
+        if let Some(ep) = prev_ep {
+            let file = (ep % 8) as usize;
+            null_hash ^= zob.ep_file[file + 1];
+        }
+
+        history_push(ctx, null_hash);
+        let zw_alpha = -beta;
+        let zw_beta = zw_alpha.saturating_add(1);
+        let score = -negamax(pos, null_depth, zw_alpha, zw_beta, ctx, tt, zob, null_hash, ply + 1);
+        history_pop(ctx);
+
+        pos.side_to_move = prev_side;
+        pos.ep = prev_ep;
+        pos.halfmove = prev_halfmove;
+        pos.fullmove = prev_fullmove;
+
+        if ctx.stop {
+            return 0;
+        }
+        if score >= beta {
+            return beta;
+        }
+    }
+
+    let mut moves = take_move_buf(ctx, ply);
+    generate_legal_moves_into(pos, &mut moves);
+    if moves.is_empty() {
+        restore_move_buf(ctx, ply, moves);
+        return if is_in_check(pos, pos.side_to_move) {
+            -mate_score(ply)
+        } else {
+            0
+        };
+    }
+    let killers = if ply < 0 { None } else { ctx.killers.get(ply as usize) };
+    let history_heur = Some(&ctx.history_heur);
+    order_moves_in_place(pos, &mut moves, None, tt_entry, killers, history_heur, &mut ctx.order_scratch);
+
+    let orig_alpha = alpha;
+    let mut best = -INF_SCORE;
+    let mut best_move: Option<(Move, Option<char>)> = None;
+    // Warum PVS (Principal Variation Search)?
+    // Erster Zug mit vollem Fenster, Folgezüge zunächst im Nullfenster.
+    // Spart deutlich Suchaufwand, wenn Ordnung gut ist.
+    let mut first = true;
+    let mut move_index: usize = 0;
+    for (mv, promo) in moves.iter().copied() {
+        move_index = move_index.saturating_add(1);
+        if ctx.stop {
+            break;
+        }
+        let is_quiet = is_quiet_move(pos, mv, promo);
+        let Some(undo) = make_move_in_place(pos, mv, promo) else { continue; };
+        let gives_check = is_in_check(pos, pos.side_to_move);
+        // Mattsuche: der letzte Zug des Angreifers kann nur mit Schach matt setzen.
+        if ctx.mate_mode && depth == 1 && !gives_check {
+            unmake_move_in_place(pos, mv, promo, undo);
+            continue;
+        }
+        let next_hash = update_hash_after_move(hash, zob, &undo, pos, mv);
+        history_push(ctx, next_hash);
+        let extend = gives_check && depth <= 3 && !ctx.mate_mode;
+        let base_depth = depth - 1 + if extend { 1 } else { 0 };
+        // Warum LMR nur für späte ruhige Züge?
+        // Harte/taktische Züge und frühe Kandidaten behalten Volltiefe.
+        let use_lmr = !first
+            && !ctx.mate_mode
+            && ply > 0
+            && base_depth >= 3
+            && move_index > 2
+            && is_quiet
+            && !in_check
+            && !gives_check;
+
+        let score = if first {
+            -negamax(pos, base_depth, -beta, -alpha, ctx, tt, zob, next_hash, ply + 1)
+        } else if use_lmr {
+            let narrow = alpha.saturating_add(1);
+            let reduced_depth = base_depth.saturating_sub(lmr_reduction(base_depth, move_index));
+            let mut sc = -negamax(pos, reduced_depth, -narrow, -alpha, ctx, tt, zob, next_hash, ply + 1);
+            if sc > alpha {
+                sc = -negamax(pos, base_depth, -narrow, -alpha, ctx, tt, zob, next_hash, ply + 1);
+                if sc > alpha && sc < beta {
+                    sc = -negamax(pos, base_depth, -beta, -alpha, ctx, tt, zob, next_hash, ply + 1);
+                }
+            }
+            sc
+        } else {
+            let narrow = alpha.saturating_add(1);
+            let mut sc = -negamax(pos, base_depth, -narrow, -alpha, ctx, tt, zob, next_hash, ply + 1);
+            if sc > alpha && sc < beta {
+                sc = -negamax(pos, base_depth, -beta, -alpha, ctx, tt, zob, next_hash, ply + 1);
+            }
+            sc
+        };
+        history_pop(ctx);
+        unmake_move_in_place(pos, mv, promo, undo);
+        first = false;
+        if ctx.stop {
+            break;
+        }
+        if score > best {
+            best = score;
+            best_move = Some((mv, promo));
+        }
+        if score > alpha {
+            alpha = score;
+        }
+        if alpha >= beta {
+            if is_quiet {
+                let key = move_key(mv, promo);
+                update_killers(ctx, ply, key);
+                update_history_heur(ctx, pos.side_to_move, mv.from, mv.to, depth);
+            }
+            break;
+        }
+    }
+
+    if best == -INF_SCORE {
+        best = if ctx.mate_mode { 0 } else { clamp_eval(evaluate(pos, &ctx.tune) + skill_noise(ctx, hash)) };
+    }
+
+    if !ctx.stop {
+        let bound = if best <= orig_alpha {
+            TT_BOUND_UPPER
+        } else if best >= beta {
+            TT_BOUND_LOWER
+        } else {
+            TT_BOUND_EXACT
+        };
+        if let Some(table) = tt.as_ref() {
+            table.store(hash, depth, tt_store_score(best, ply), bound, best_move, ctx.tt_gen);
+        }
+    }
+
+    restore_move_buf(ctx, ply, moves);
+    best
+}
+
+// Root-Ergebnis einer Tiefe: Score, gewählter Zug, "repetition avoided"-Flag.
+type RootResult = (i32, Option<(Move, Option<char>)>, bool);
+
+// Was: Führt die Root-Suche für eine feste Iterationstiefe durch und liefert besten Zug plus Score.
+// Warum: Trennt Root-spezifische Logik (Wiederholungsvermeidung, Reporting, PV-Seed) von der allgemeinen Rekursion.
+// Kosten: Entspricht der vollständigen Suche auf der jeweiligen Iterationstiefe und dominiert die Gesamtzeit.
+#[allow(clippy::too_many_arguments)]
+fn search_depth(
+    pos: &mut Position,
+    depth: u32,
+    ctx: &mut SearchContext,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+    alpha: i32,
+    beta: i32,
+    pv_move: Option<(u8, u8, Option<char>)>,
+    excluded: &[(Move, Option<char>)],
+) -> RootResult {
+    // Root-Suche für eine fixe Tiefe.
+    // `excluded` enthält im Multi-PV-Modus die Züge bereits gefundener Varianten.
+    // Liefert Score, besten Zug und ggf. "repetition avoided"-Flag.
+    if pos.halfmove >= 100 {
+        return (0, None, false);
+    }
+    if depth == 0 {
+        let score = quiescence(pos, -INF_SCORE, INF_SCORE, ctx, tt, zob, hash, 0);
+        return (score, None, false);
+    }
+
+    let tt_entry = {
+        if let Some(table) = tt.as_ref() {
+            table.probe(hash)
+        } else {
+            None
+        }
+    };
+
+    let mut moves = take_move_buf(ctx, 0);
+    generate_legal_moves_into(pos, &mut moves);
+    if moves.is_empty() {
+        let score = if is_in_check(pos, pos.side_to_move) { -mate_score(0) } else { 0 };
+        restore_move_buf(ctx, 0, moves);
+        return (score, None, false);
+    }
+    let killers = ctx.killers.first();
+    let history_heur = Some(&ctx.history_heur);
+    order_moves_in_place(pos, &mut moves, pv_move, tt_entry, killers, history_heur, &mut ctx.order_scratch);
+
+    let mut alpha = alpha;
+    let orig_alpha = alpha;
+    let mut best = None;
+    let mut best_score = -INF_SCORE;
+    // Root-spezifisch: Wir merken uns, ob der aktuell beste Zug
+    // in eine 3-fach Wiederholung führt.
+    let mut best_is_rep = false;
+    let mut best_non_rep_non_losing: Option<(Move, Option<char>)> = None;
+    let mut best_non_rep_non_losing_score = -INF_SCORE;
+    let mut rep_avoid_used = false;
+
+    let mut first = true;
+    for (mv, promo) in moves.iter().copied() {
+        if ctx.stop {
+            break;
+        }
+        if should_stop(ctx) {
+            break;
+        }
+        if excluded.contains(&(mv, promo)) {
+            continue;
+        }
+        if !ctx.root_moves.is_empty() && !ctx.root_moves.contains(&(mv, promo)) {
+            continue;
+        }
+        let Some(undo) = make_move_in_place(pos, mv, promo) else { continue; };
+        let next_hash = update_hash_after_move(hash, zob, &undo, pos, mv);
+        history_push(ctx, next_hash);
+        // Warum Repetition schon am Root auswerten?
+        // Damit wir bei Bedarf nicht verlierende Alternativen bevorzugen können.
+        let is_rep_draw = history_count(ctx, next_hash, pos.halfmove) >= 3;
+        let score = if first {
+            -negamax(pos, depth - 1, -beta, -alpha, ctx, tt, zob, next_hash, 1)
+        } else {
+            let narrow = alpha.saturating_add(1);
+            let mut sc = -negamax(pos, depth - 1, -narrow, -alpha, ctx, tt, zob, next_hash, 1);
+            if sc > alpha && sc < beta {
+                sc = -negamax(pos, depth - 1, -beta, -alpha, ctx, tt, zob, next_hash, 1);
+            }
+            sc
+        };
+        history_pop(ctx);
+        unmake_move_in_place(pos, mv, promo, undo);
+        first = false;
+        if ctx.stop {
+            break;
+        }
+        if score > best_score {
+            best_score = score;
+            best = Some((mv, promo));
+            best_is_rep = is_rep_draw;
+        }
+        if score > alpha {
+            alpha = score;
+        }
+        if !is_rep_draw && score >= 0 && score > best_non_rep_non_losing_score {
+            best_non_rep_non_losing_score = score;
+            best_non_rep_non_losing = Some((mv, promo));
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    // Ohne die ausgeschlossenen bzw. nicht erlaubten Züge ist der Score keine Schranke
+    // für die Stellung.
+    if !ctx.stop && excluded.is_empty() && ctx.root_moves.is_empty() {
+        let bound = if alpha <= orig_alpha {
+            TT_BOUND_UPPER
+        } else if alpha >= beta {
+            TT_BOUND_LOWER
+        } else {
+            TT_BOUND_EXACT
+        };
+        if let Some(table) = tt.as_ref() {
+            table.store(hash, depth, tt_store_score(alpha, 0), bound, best, ctx.tt_gen);
+        }
+    }
+
+    let mut chosen_score = best_score;
+    let mut chosen_move = best;
+    // Warum "rep_avoid"?
+    // Falls bester Zug nur Remis durch Wiederholung liefert, nehmen wir
+    // optional einen nicht-verlierenden Nicht-Rep-Zug für menschlicheres Spiel.
+    if !ctx.stop && best_is_rep {
+        if let Some(mv) = best_non_rep_non_losing {
+            chosen_score = best_non_rep_non_losing_score;
+            chosen_move = Some(mv);
+            rep_avoid_used = true;
+        }
+    }
+
+    // Warum leichter Root-Contempt?
+    // Verhindert in ausgeglichenen Lagen ein zu frühes "Einrasten" in Remislinien.
+    const ROOT_CONTEMPT: i32 = 10;
+    const ROOT_CONTEMPT_THRESHOLD: i32 = 15;
+    if !ctx.stop && best_is_rep && chosen_score.abs() < ROOT_CONTEMPT_THRESHOLD {
+        chosen_score -= ROOT_CONTEMPT;
+    }
+
+    restore_move_buf(ctx, 0, moves);
+    (chosen_score, chosen_move, rep_avoid_used)
+}
+
+const USE_ASPIRATION: bool = true;
+// Startbreite des Fensters: Tuning-Parameter ASP_WINDOW aus `SearchLimits::tune`.
+const ASP_MAX_ITERS: u32 = 6;
+
+// Was: Sucht eine Iterationstiefe mit Aspiration-Window um `center` (Fallback: Vollfenster).
+// Warum: Im Multi-PV-Modus läuft dieselbe Fenster-Logik einmal pro Variante mit deren eigenem Vorscore.
+// Kosten: Ein bis `ASP_MAX_ITERS + 1` Aufrufe von `search_depth`; None bei Abbruch mitten in der Tiefe.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn search_iteration(
+    pos: &mut Position,
+    depth: u32,
+    ctx: &mut SearchContext,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+    center: i32,
+    pv_move: Option<(u8, u8, Option<char>)>,
+    excluded: &[(Move, Option<char>)],
+) -> Option<RootResult> {
+    if !USE_ASPIRATION || depth <= 1 {
+        let result = search_depth(pos, depth, ctx, tt, zob, hash, -INF_SCORE, INF_SCORE, pv_move, excluded);
+        return if ctx.stop { None } else { Some(result) };
+    }
+
+    // Aspiration-Window um den letzten Score:
+    // schneller bei stabilen Positionen, fallback auf Vollfenster.
+    let mut window = ctx.tune.value(ASP_WINDOW);
+    let mut alpha = (center - window).max(-INF_SCORE);
+    let mut beta = (center + window).min(INF_SCORE);
+    let mut attempts = 0;
+
+    loop {
+        let (score, mv, rep_avoid) = search_depth(pos, depth, ctx, tt, zob, hash, alpha, beta, pv_move, excluded);
+        if ctx.stop {
+            return None;
+        }
+
+        let bound = if score <= alpha {
+            alpha = (alpha - window).max(-INF_SCORE);
+            ScoreBound::Upper
+        } else if score >= beta {
+            beta = (beta + window).min(INF_SCORE);
+            ScoreBound::Lower
+        } else {
+            return Some((score, mv, rep_avoid));
+        };
+        window = window.saturating_mul(2);
+        // Zwischenstand nur für die Hauptvariante; weitere Multi-PV-Zeilen melden erst am Ende.
+        if excluded.is_empty() {
+            ctx.aspiration_fails += 1;
+            let info = search_info(ctx, pos, tt, zob, hash, depth, score, bound, mv, Vec::new());
+            ctx.sink.iteration(&info);
+        }
+
+        attempts += 1;
+        if attempts >= ASP_MAX_ITERS {
+            let result = search_depth(pos, depth, ctx, tt, zob, hash, -INF_SCORE, INF_SCORE, pv_move, excluded);
+            return if ctx.stop { None } else { Some(result) };
+        }
+    }
+}
+
+// Was: Baut den Info-Event einer Iteration bzw. eines Aspiration-Fehlschlags.
+// Warum: Gleiche Felder (Seldepth, Bound, Bestzug, PV, NPS, Hashfull) für beide Fälle.
+// Kosten: Eine PV-Rekonstruktion, falls `lines` leer ist, plus TT-Stichprobe für `hashfull`.
+#[allow(clippy::too_many_arguments)]
+fn search_info(
+    ctx: &SearchContext,
+    pos: &Position,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+    depth: u32,
+    score: i32,
+    bound: ScoreBound,
+    best_move: Option<(Move, Option<char>)>,
+    lines: Vec<PvLine>,
+) -> SearchInfo {
+    let elapsed_ms = (ctx.clock.now_ms() - ctx.start_ms).max(0.0);
+    let pv = match lines.first() {
+        Some(line) => line.pv.clone(),
+        None => build_pv_line(pos, tt, zob, hash, depth, best_move),
+    };
+    SearchInfo {
+        depth,
+        seldepth: ctx.seldepth.max(depth),
+        score,
+        bound,
+        best: pv.first().cloned(),
+        nodes: ctx.nodes,
+        time_ms: elapsed_ms as u64,
+        nps: nodes_per_second(ctx.nodes, elapsed_ms),
+        hashfull: tt.as_ref().map_or(0, |table| table.hashfull(ctx.tt_gen)),
+        tbhits: ctx.tb_hits,
+        pv,
+        lines,
+    }
+}
+
+// Baut die Hash-Historie aus FEN-Text mit kleinem Cache,
+// damit Repetitionsprüfung bei wiederholten Suchen günstiger bleibt.
+// Was: Führt `build_history_cached` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn build_history_cached(state: &mut SearchState, history: &str, zob: &Zobrist, root_hash: u64) -> Vec<u64> {
+    let mut cache_updated = false;
+    if state.history_cache_raw != history {
+        if !state.history_cache_raw.is_empty() && history.starts_with(&state.history_cache_raw) {
+            let mut rest = &history[state.history_cache_raw.len()..];
+            if rest.starts_with('\n') {
+                rest = &rest[1..];
+            }
+            for line in rest.lines() {
+                let fen = line.trim();
+                if fen.is_empty() {
+                    continue;
+                }
+                if let Some(pos) = parse_fen(fen) {
+                    state.history_cache_hashes.push(compute_hash(&pos, zob));
+                }
+            }
+            state.history_cache_raw.clear();
+            state.history_cache_raw.push_str(history);
+            cache_updated = true;
+        }
+
+        if !cache_updated {
+            state.history_cache_raw.clear();
+            state.history_cache_raw.push_str(history);
+            state.history_cache_hashes.clear();
+            for line in history.lines() {
+                let fen = line.trim();
+                if fen.is_empty() {
+                    continue;
+                }
+                if let Some(pos) = parse_fen(fen) {
+                    state.history_cache_hashes.push(compute_hash(&pos, zob));
+                }
+            }
+        }
+    }
+
+    let mut out = if state.history_cache_hashes.len() > 128 {
+        let start = state.history_cache_hashes.len() - 128;
+        state.history_cache_hashes[start..].to_vec()
+    } else {
+        state.history_cache_hashes.clone()
+    };
+    out.push(root_hash);
+    out
+}
+
+// Was: Baut aus TT-Bestzügen eine PV-Zeile (Principal Variation) für Anzeige/Debug-Ausgabe.
+// Warum: Macht den aktuellen Suchpfad transparent und hilft bei Konsistenzprüfung zwischen Search und UI/Console.
+// Kosten: Linear in `max_len` mit zusätzlichen TT-Probes und legalitätsgeprüften Probezügen.
+fn build_pv_line(
+    pos: &Position,
+    tt: &Option<TT>,
+    zob: &Zobrist,
+    hash: u64,
+    max_len: u32,
+    first_move: Option<(Move, Option<char>)>,
+) -> Vec<String> {
+    if max_len == 0 {
+        return Vec::new();
+    }
+
+    let mut pos = clone_position(pos);
+    let mut cur_hash = hash;
+    let mut line: Vec<String> = Vec::new();
+    let mut seen: Vec<u64> = Vec::new();
+    let mut next_move = first_move;
+
+    for _ in 0..max_len {
+        if seen.contains(&cur_hash) {
+            break;
+        }
+        seen.push(cur_hash);
+
+        let (mv, promo) = if let Some(mv) = next_move.take() {
+            mv
+        } else {
+            let table = match tt.as_ref() {
+                Some(t) => t,
+                None => break,
+            };
+            let entry = match table.probe(cur_hash) {
+                Some(e) => e,
+                None => break,
+            };
+            let (from, to, promo_hint) = match entry_best_move(entry) {
+                Some(m) => m,
+                None => break,
+            };
+            let mv = match find_legal_move(&mut pos, from, to) {
+                Some(m) => m,
+                None => break,
+            };
+            let promo = if let MoveKind::Promotion = mv.kind {
+                Some(promo_hint.unwrap_or('q').to_ascii_lowercase())
+            } else {
+                None
+            };
+            (mv, promo)
+        };
+
+        line.push(move_to_uci(mv, promo));
+
+        let Some(undo) = make_move_in_place(&mut pos, mv, promo) else { break; };
+        let next_hash = update_hash_after_move(cur_hash, zob, &undo, &pos, mv);
+        cur_hash = next_hash;
+    }
+
+    line
+}
+
+// Suchgrenzen für `run_search` bzw. `Engine::search`.
+// `depth == 0` heißt: keine feste Tiefe, solange Zeit-/Knotenlimit oder `infinite`
+// das Ende bestimmen (sonst nur Tiefe 1).
+#[derive(Clone, Default)]
+pub struct SearchLimits {
+    pub depth: u32,
+    pub time_ms: u32,
+    pub nodes: u64,
+    pub infinite: bool,
+    // TT-Größe für `run_search` (frische Engine je Aufruf); `Engine::search` nutzt
+    // stattdessen die Größe der Instanz.
+    pub tt_mb: u32,
+    // Anzahl der Hauptvarianten (Multi-PV); 0 und 1 bedeuten nur den besten Zug.
+    pub multipv: u32,
+    // Externer Abbruch, wird in `should_stop` periodisch gelesen.
+    pub stop: Option<Arc<AtomicBool>>,
+    // Spielstärke 0..=20 (siehe `skill`); `None` ist volle Stärke.
+    pub skill_level: Option<u32>,
+    // Ponder-Suche: läuft wie `infinite`, bis `ponderhit` gesetzt wird (bzw.
+    // `ProgressSink::ponderhit` true liefert); ab dann gilt `time_ms`.
+    pub ponder: bool,
+    pub ponderhit: Option<Arc<AtomicBool>>,
+    // Partieuhr statt festem `time_ms`: Zeitmanagement mit weichem/hartem Limit.
+    // Wird ignoriert, wenn `time_ms` gesetzt ist.
+    pub game_clock: Option<GameClock>,
+    // Nur diese Wurzelzüge (UCI, z. B. "e2e4") untersuchen; leer oder ohne legalen
+    // Eintrag = alle Züge.
+    pub searchmoves: Vec<String>,
+    // Bit-identische Ergebnisse für gleiche FEN, History und Limits: keine Zeitlimits
+    // (nur `depth`/`nodes`; `time_ms` und `game_clock` werden ignoriert, ohne beide gilt
+    // Tiefe 1), leere TT sowie Killer/History-Heuristik bei jedem Start.
+    pub deterministic: bool,
+    // Zufallssaat für Skill-Rauschen und Zugwahl; 0 = aus der Uhr ableiten.
+    pub seed: u64,
+    // Such-Threads inklusive Haupt-Thread (Lazy SMP, siehe `smp`); 0 und 1 = einsträngig.
+    // Wirkt nicht bei `deterministic` und Skill-Stufen; auf wasm32 nur im Thread-Build mit
+    // angemeldeten Helfer-Workern (siehe `smp`).
+    pub threads: u32,
+    // Syzygy-Endspieldatenbanken; `None` = keine Proben.
+    pub tablebases: Option<Arc<Tablebases>>,
+    // Eval-/Suchkonstanten (siehe `tune`); Standard sind die eingebauten Werte.
+    pub tune: TuneValues,
+}
+
+// Eine Hauptvariante im Multi-PV-Modus; `multipv` zählt ab 1 (1 = bester Zug).
+#[derive(Clone, Debug)]
+pub struct PvLine {
+    pub multipv: u32,
+    pub depth: u32,
+    pub score: i32,
+    pub pv: Vec<String>,
+}
+
+// Art des Scores in `SearchInfo`: exakt oder Schranke nach Aspiration-Fehlschlag.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ScoreBound {
+    Exact,
+    // Fail-High: tatsächlicher Wert ist mindestens `score`.
+    Lower,
+    // Fail-Low: tatsächlicher Wert ist höchstens `score`.
+    Upper,
+}
+
+impl ScoreBound {
+    // Kurzname für JSON/JS.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScoreBound::Exact => "exact",
+            ScoreBound::Lower => "lower",
+            ScoreBound::Upper => "upper",
+        }
+    }
+}
+
+// Zwischenstand nach jeder abgeschlossenen Iteration sowie bei Fail-High/-Low des
+// Aspiration-Windows (dann `bound != Exact`, nur für die erste Variante).
+#[derive(Clone, Debug)]
+pub struct SearchInfo {
+    pub depth: u32,
+    pub seldepth: u32,
+    pub score: i32,
+    pub bound: ScoreBound,
+    pub best: Option<String>,
+    pub nodes: u64,
+    pub time_ms: u64,
+    pub nps: u64,
+    // Belegung der TT in Promille (0 ohne TT).
+    pub hashfull: u32,
+    // Syzygy-Treffer (Haupt-Thread, inklusive Wurzelproben).
+    pub tbhits: u64,
+    pub pv: Vec<String>,
+    // Alle Varianten der Iteration (bei `multipv <= 1` genau die beste).
+    pub lines: Vec<PvLine>,
+}
+
+// Ergebnis der letzten vollständig abgeschlossenen Iteration.
+pub struct SearchResult {
+    pub depth: u32,
+    pub score: i32,
+    pub best: Option<String>,
+    pub pv: Vec<String>,
+    pub nodes: u64,
+    pub nodes_completed: u64,
+    pub time_ms: u64,
+    pub nps: u64,
+    pub rep_avoid: bool,
+    pub tbhits: u64,
+    pub lines: Vec<PvLine>,
+}
+
+// Was: Iterative Deepening mit Aspiration Windows über `search_depth` für eine FEN plus FEN-History.
+// Warum: Gemeinsamer Suchkern aller Engine-Instanzen; `limits.tt_mb` wird hier ignoriert,
+// die TT-Größe gehört zu `state`.
+// Kosten: Dominiert von den einzelnen Iterationen; `sink.iteration` wird nur einmal pro Iteration gerufen.
+pub(crate) fn search_position(
+    state: &mut SearchState,
+    fen: &str,
+    history: &str,
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+    sink: &mut dyn ProgressSink,
+) -> Option<SearchResult> {
+    let mut pos = parse_fen(fen)?;
+
+    let time_limit_ms = if limits.deterministic { 0.0 } else { limits.time_ms as f64 };
+    let mut time_manager = limits
+        .game_clock
+        .filter(|_| limits.time_ms == 0 && !limits.infinite && !limits.deterministic)
+        .map(|c| TimeManager::new(c.limits(pos.side_to_move)));
+    // Skill-Stufe begrenzt Tiefe und Knoten zusätzlich zu den Limits des Aufrufers;
+    // die Analyse (`infinite`) läuft immer mit voller Stärke.
+    let skill = limits.skill_level.filter(|_| !limits.infinite).map(skill_params);
+    let mut node_limit = limits.nodes;
+    if let Some(p) = skill.filter(|p| p.max_nodes > 0) {
+        node_limit = if node_limit == 0 { p.max_nodes } else { node_limit.min(p.max_nodes) };
+    }
+    // Deterministisch zählen Zeitvorgaben nicht (sie wären ohnehin wirkungslos), also auch
+    // nicht als Grund, bis Tiefe 64 zu suchen; Frontends rechnen sie vorher in Knoten um.
+    let timed = !limits.deterministic && (limits.time_ms > 0 || time_manager.is_some());
+    let open_ended = timed || node_limit > 0 || limits.infinite || limits.ponder;
+    let mut max_depth = if limits.depth > 0 {
+        limits.depth
+    } else if open_ended {
+        64
+    } else {
+        1
+    };
+    if let Some(p) = skill.filter(|p| p.max_depth > 0) {
+        max_depth = max_depth.min(p.max_depth);
+    }
+    let max_ply = (max_depth as usize).saturating_add(8);
+
+    Some(ZOBRIST_TABLE.with(|zob| {
+        let root_hash = compute_hash(&pos, zob);
+        if limits.deterministic {
+            state.reset_heuristics();
+        }
+        state.gen = state.gen.wrapping_add(1);
+        if state.gen == 0 {
+            state.gen = 1;
+        }
+        let tt_gen = state.gen;
+
+        let mut killers = std::mem::take(&mut state.killers);
+        if killers.len() < max_ply {
+            killers.resize(max_ply, [None; 2]);
+        }
+        let history_heur = state.history_heur;
+
+        let history = build_history_cached(state, history, zob, root_hash);
+        // Verrauschte Bewertungen bleiben in einer eigenen TT (siehe `skill`).
+        let skill_table = skill.filter(|p| p.eval_noise > 0).and_then(|_| TT::new(SKILL_TT_MB));
+        let table = if skill_table.is_some() { &skill_table } else { &state.table };
+        let threads = search_threads(limits);
+        let helper_history = if threads > 1 { history.clone() } else { Vec::new() };
+        let mut ctx = SearchContext::new(clock, sink, limits, max_ply, history);
+        ctx.tt_gen = tt_gen;
+        ctx.killers = killers;
+        ctx.history_heur = history_heur;
+        ctx.node_limit = node_limit;
+        ctx.root_moves = resolve_root_moves(&mut pos, &limits.searchmoves);
+        if let Some(tm) = time_manager.as_ref() {
+            ctx.set_time_limit(tm.hard_ms());
+        }
+        if limits.deterministic {
+            ctx.set_time_limit(0.0);
+        }
+        let mut rng = match limits.seed {
+            0 if limits.deterministic => root_hash,
+            0 => clock.now_ms().to_bits() ^ root_hash,
+            seed => seed,
+        };
+        if let Some(p) = skill.as_ref() {
+            ctx.eval_noise = p.eval_noise;
+            ctx.noise_seed = rng;
+        }
+
+        let mut best_move: Option<(Move, Option<char>)> = None;
+        let mut best_score = 0;
+        let mut completed_depth = 0;
+        let mut rep_avoid_used = false;
+        let mut pv_move_hint: Option<(u8, u8, Option<char>)> = None;
+        let mut last_score = 0;
+        // Skill-Kandidaten sind interne Varianten; gemeldet werden nur die angeforderten.
+        let reported_lines = limits.multipv.max(1) as usize;
+        let wanted_lines = (reported_lines as u32).max(skill.map_or(1, |p| p.candidates)) as usize;
+        // Varianten der letzten abgeschlossenen Iteration (Score-Zentrum und Zug-Hinweis je Variante).
+        let mut prev_lines: Vec<(i32, (Move, Option<char>))> = Vec::new();
+        let mut lines: Vec<PvLine> = Vec::new();
+        // Multi-PV und Skill-Kandidaten brauchen alle Züge; dort nur Proben im Baum.
+        let tb_root_score =
+            if wanted_lines == 1 { tablebase_root_filter(&mut ctx, &mut pos, zob, root_hash) } else { None };
+
+        let setup = HelperSetup {
+            pos: clone_position(&pos),
+            tt: table,
+            zob,
+            root_hash,
+            history: helper_history,
+            root_moves: ctx.root_moves.clone(),
+            history_heur: ctx.history_heur,
+            tt_gen,
+            max_depth,
+            max_ply,
+            stop: Arc::new(AtomicBool::new(false)),
+            tablebases: limits.tablebases.clone(),
+            tb_cardinality: ctx.tb_cardinality,
+            tune: limits.tune,
+        };
+        let ((), helper_nodes) = run_with_helpers(threads, &setup, || {
+            for d in 1..=max_depth {
+                ctx.current_depth = d;
+                ctx.seldepth = 0;
+                ctx.aspiration_fails = 0;
+                let mut score = 0;
+                let mut mv: Option<(Move, Option<char>)> = None;
+                let mut rep_avoid = false;
+                let mut found: Vec<(i32, (Move, Option<char>))> = Vec::with_capacity(wanted_lines);
+                let mut excluded: Vec<(Move, Option<char>)> = Vec::with_capacity(wanted_lines);
+
+                for k in 0..wanted_lines {
+                    let (center, hint) = match prev_lines.get(k) {
+                        Some(&(s, (m, p))) if k > 0 => (s, Some((m.from, m.to, p))),
+                        _ => (last_score, pv_move_hint),
+                    };
+                    let Some((s, m, r)) =
+                        search_iteration(&mut pos, d, &mut ctx, table, zob, root_hash, center, hint, &excluded)
+                    else {
+                        break;
+                    };
+                    if k == 0 {
+                        score = s;
+                        mv = m;
+                        rep_avoid = r;
+                    }
+                    // Keine weiteren legalen Root-Züge.
+                    let Some(line_move) = m else { break };
+                    excluded.push(line_move);
+                    found.push((s, line_move));
+                }
+
+                if ctx.stop {
+                    break;
+                }
+
+                best_score = score;
+                best_move = mv;
+                completed_depth = d;
+                ctx.completed_depth = d;
+                ctx.completed_nodes = ctx.nodes;
+                rep_avoid_used = rep_avoid;
+                last_score = best_score;
+                pv_move_hint = best_move.map(|(mv, promo)| (mv.from, mv.to, promo));
+                lines = found
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &(score, line_move))| PvLine {
+                        multipv: k as u32 + 1,
+                        depth: d,
+                        score,
+                        pv: build_pv_line(&pos, table, zob, root_hash, d, Some(line_move)),
+                    })
+                    .collect();
+                prev_lines = found;
+                if let Some(line) = lines.first_mut() {
+                    line.score = tb_display_score(tb_root_score, line.score);
+                }
+                emit_progress(&mut ctx, true);
+
+                let info = search_info(
+                    &ctx,
+                    &pos,
+                    table,
+                    zob,
+                    root_hash,
+                    d,
+                    tb_display_score(tb_root_score, best_score),
+                    ScoreBound::Exact,
+                    best_move,
+                    lines[..lines.len().min(reported_lines)].to_vec(),
+                );
+                ctx.sink.iteration(&info);
+
+                // Im Analysemodus (`infinite`) und beim Pondern entscheidet nur der Stop
+                // (bzw. der Ponderhit) über das Ende.
+                let pondering = poll_ponderhit(&mut ctx);
+                if !limits.infinite && !pondering && best_score >= MATE_SCORE - MATE_EARLY_STOP_PLIES {
+                    break;
+                }
+
+                let elapsed_ms = clock.now_ms() - ctx.budget_start_ms;
+                let out_of_time = match time_manager.as_mut() {
+                    Some(tm) => {
+                        tm.update(pv_move_hint, best_score, ctx.aspiration_fails);
+                        tm.out_of_time(elapsed_ms)
+                    }
+                    None => time_limit_ms > 0.0 && elapsed_ms >= time_limit_ms,
+                };
+                if out_of_time && !pondering {
+                    break;
+                }
+            }
+        });
+        emit_progress(&mut ctx, true);
+
+        // Schwache Stufen spielen nicht zwingend den besten Kandidaten.
+        if let Some(p) = skill.as_ref() {
+            let pick = pick_root_line(&lines, p, &mut rng);
+            if let Some(&(score, line_move)) = prev_lines.get(pick).filter(|_| pick > 0) {
+                best_score = score;
+                best_move = Some(line_move);
+                rep_avoid_used = false;
+                // Der gespielte Zug steht als Variante 1 vorn.
+                lines[..=pick].rotate_right(1);
+                for (k, line) in lines.iter_mut().enumerate() {
+                    line.multipv = k as u32 + 1;
+                }
+            }
+        }
+        lines.truncate(reported_lines);
+
+        let elapsed_ms = (clock.now_ms() - ctx.start_ms).max(0.0);
+        let best = best_move.map(|(mv, promo)| move_to_uci(mv, promo));
+        let pv = match best.as_ref() {
+            None => Vec::new(),
+            Some(best_str) => {
+                let line = build_pv_line(&pos, table, zob, root_hash, completed_depth, best_move);
+                if line.is_empty() { vec![best_str.clone()] } else { line }
+            }
+        };
+
+        state.killers = ctx.killers;
+        state.history_heur = ctx.history_heur;
+
+        SearchResult {
+            depth: completed_depth,
+            score: tb_display_score(tb_root_score, best_score),
+            best,
+            pv,
+            nodes: ctx.nodes + helper_nodes,
+            nodes_completed: ctx.completed_nodes,
+            time_ms: elapsed_ms as u64,
+            nps: nodes_per_second(ctx.nodes + helper_nodes, elapsed_ms),
+            rep_avoid: rep_avoid_used,
+            tbhits: ctx.tb_hits,
+            lines,
+        }
+    }))
+}
+
+// Was: Beschränkt die Wurzelzüge auf die tablebase-optimalen Züge und liefert deren
+// Anzeige-Score; `None`, wenn die Stellung nicht in den geladenen Tabellen liegt.
+// Warum: Lange Gewinnwege findet die Suche allein oft nicht; mit DTZ bleibt jeder gespielte
+// Zug ein Gewinn innerhalb der 50-Züge-Regel. Danach sind Proben im Baum unnötig (DTZ)
+// bzw. nur noch bei Gewinn sinnvoll (nur WDL geladen).
+// Kosten: Eine DTZ-Probe je Wurzelzug.
+fn tablebase_root_filter(ctx: &mut SearchContext, pos: &mut Position, zob: &Zobrist, root_hash: u64) -> Option<i32> {
+    let tb = ctx.tablebases.clone()?;
+    if !tb.covers(pos) {
+        return None;
+    }
+    let moves = if ctx.root_moves.is_empty() { generate_legal_moves(pos) } else { ctx.root_moves.clone() };
+    if moves.is_empty() {
+        return None;
+    }
+    let mut repetition_draws = Vec::with_capacity(moves.len());
+    for &(mv, promo) in &moves {
+        let Some(undo) = make_move_in_place(pos, mv, promo) else {
+            repetition_draws.push(false);
+            continue;
+        };
+        let next_hash = update_hash_after_move(root_hash, zob, &undo, pos, mv);
+        history_push(ctx, next_hash);
+        repetition_draws.push(history_count(ctx, next_hash, pos.halfmove) >= 3);
+        history_pop(ctx);
+        unmake_move_in_place(pos, mv, promo, undo);
+    }
+    let repeated = has_repeated(ctx, pos.halfmove);
+
+    let (ranks, dtz) = tb.rank_root_moves(pos, &moves, &repetition_draws, repeated)?;
+    ctx.tb_hits += moves.len() as u64;
+    let best = ranks.iter().map(|&(rank, _)| rank).max()?;
+    let score = ranks.iter().find(|&&(rank, _)| rank == best)?.1;
+    ctx.root_moves = moves.iter().zip(&ranks).filter(|(_, &(rank, _))| rank == best).map(|(&m, _)| m).collect();
+    if dtz || score <= 0 {
+        ctx.tb_cardinality = 0;
+    }
+    Some(score)
+}
+
+// Ob sich seit dem letzten Schlag/Bauernzug eine Stellung der Partie wiederholt hat.
+fn has_repeated(ctx: &SearchContext, halfmove: u32) -> bool {
+    let start = ctx.history.len().saturating_sub(halfmove as usize + 1);
+    let recent = &ctx.history[start..];
+    recent.iter().enumerate().any(|(i, hash)| recent[i + 1..].contains(hash))
+}
+
+// Anzeige-Score mit Wurzel in der Datenbank: Tabellenwert statt Suchwert, außer bei Matt.
+#[inline]
+fn tb_display_score(tb_score: Option<i32>, score: i32) -> i32 {
+    match tb_score {
+        Some(tb) if !is_mate_score(score) => tb,
+        _ => score,
+    }
+}
+
+// Was: Wandelt die `searchmoves`-Liste in legale Wurzelzüge um (ohne Duplikate).
+// Warum: Ungültige Einträge werden ignoriert; bleibt keiner übrig, sucht die Engine wie
+// UCI-üblich alle Züge statt ohne Zug zu enden.
+// Kosten: Eine Legalitätsprüfung pro Listeneintrag.
+fn resolve_root_moves(pos: &mut Position, searchmoves: &[String]) -> Vec<(Move, Option<char>)> {
+    let mut moves = Vec::with_capacity(searchmoves.len());
+    for uci in searchmoves {
+        if let Some(mv) = parse_uci_move(pos, uci) {
+            if !moves.contains(&mv) {
+                moves.push(mv);
+            }
+        }
+    }
+    moves
+}
+
+// Was: Kern von `score_root_moves`: Multi-PV über genau die legalen Züge aus `moves`.
+// Warum: Jede Variante bekommt ihren eigenen Score statt nur einer Schranke.
+// Kosten: Wie Multi-PV mit `moves.len()` Varianten (ein Root-Durchlauf je Zug und Iteration).
+pub(crate) fn score_root_moves_in(
+    state: &mut SearchState,
+    fen: &str,
+    history: &str,
+    moves: &[String],
+    limits: &SearchLimits,
+    clock: &dyn Clock,
+    sink: &mut dyn ProgressSink,
+) -> Option<SearchResult> {
+    let legal = resolve_root_moves(&mut parse_fen(fen)?, moves);
+    let limits = SearchLimits {
+        searchmoves: moves.to_vec(),
+        multipv: legal.len().max(1) as u32,
+        skill_level: None,
+        ..limits.clone()
+    };
+    let mut result = search_position(state, fen, history, &limits, clock, sink)?;
+    if legal.is_empty() {
+        result.lines.clear();
+    }
+    Some(result)
+}
+
+#[inline]
+fn nodes_per_second(nodes: u64, elapsed_ms: f64) -> u64 {
+    if elapsed_ms > 0.0 {
+        (nodes as f64 * 1000.0 / elapsed_ms) as u64
+    } else {
+        0
+    }
+}
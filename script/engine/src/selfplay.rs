@@ -0,0 +1,298 @@
+// =====================================================================================
+// Selbstspiel und Match-Statistik
+// -------------------------------------------------------------------------------------
+// Spielt Partien zwischen zwei Engine-Konfigurationen (Hash, Threads, Skill-Stufe,
+// Tuning-Parameter aus `tune`) und wertet Matches aus: Elo-Differenz mit 95%-Intervall
+// und SPRT (Sequential Probability Ratio Test) mit H0: Elo <= elo0, H1: Elo >= elo1.
+//
+// Partieende entscheidet `game_status` (Matt, Patt, Remisregeln, tote Stellungen);
+// dazu kommen Zeitüberschreitung und ein Remis nach `MAX_GAME_PLIES` Halbzügen.
+// Jede Seite sucht mit ihren eigenen Tuning-Werten (`SearchLimits::tune`); Partien
+// dürfen daher auch parallel im selben Prozess laufen.
+// =====================================================================================
+
+use crate::engine::*;
+use crate::movegen::*;
+use crate::platform::*;
+use crate::position::*;
+use crate::search::*;
+use crate::status::*;
+use crate::timeman::*;
+use crate::tune::*;
+
+// Sicherheitsnetz gegen endlose Partien (jenseits der 75-Züge-Regel kaum erreichbar).
+pub const MAX_GAME_PLIES: usize = 600;
+
+// Eine Engine-Konfiguration im Match.
+#[derive(Clone, Debug)]
+pub struct PlayerConfig {
+    pub name: String,
+    pub hash_mb: u32,
+    pub threads: u32,
+    pub skill_level: Option<u32>,
+    // Tuning-Parameter (siehe `TUNE_PARAMS`); nicht gesetzte stehen auf Standard.
+    pub tune: TuneValues,
+}
+
+impl PlayerConfig {
+    pub fn new(name: &str) -> PlayerConfig {
+        PlayerConfig { name: name.to_string(), hash_mb: 16, threads: 1, skill_level: None, tune: TuneValues::default() }
+    }
+}
+
+// Bedenkzeit je Partie bzw. je Zug.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimeControl {
+    // Partieuhr: Grundzeit und Inkrement je Zug (ms); Zeitüberschreitung verliert.
+    Clock { base_ms: u64, inc_ms: u64 },
+    MoveTime(u32),
+    Nodes(u64),
+    Depth(u32),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+// Eine gespielte Partie.
+#[derive(Clone, Debug)]
+pub struct GameRecord {
+    pub start_fen: String,
+    pub moves: Vec<String>,
+    pub result: GameResult,
+    // `GameStatus::reason` oder "time_forfeit", "no_move", "max_plies".
+    pub reason: String,
+}
+
+// Eine Seite im Match: Konfiguration plus eigene Engine (TT bleibt über die Partie erhalten).
+pub struct Player {
+    pub config: PlayerConfig,
+    engine: Engine,
+}
+
+impl Player {
+    pub fn new(config: PlayerConfig) -> Player {
+        let engine = Engine::new(config.hash_mb);
+        Player { config, engine }
+    }
+}
+
+// Was: Spielt eine Partie ab `start_fen`; beide Engines beginnen mit leerem Suchzustand.
+// Warum: Kern von `match-runner`; Tests nutzen Tiefen- oder Knotenlimits.
+// Kosten: Eine Suche je Halbzug plus `game_status` (linear in der Partielänge).
+pub fn play_game(
+    white: &mut Player,
+    black: &mut Player,
+    start_fen: &str,
+    tc: TimeControl,
+    clock: &dyn Clock,
+) -> Result<GameRecord, String> {
+    let mut pos = parse_fen(start_fen).ok_or_else(|| format!("ungültige FEN: {}", start_fen))?;
+    white.engine.new_game();
+    black.engine.new_game();
+
+    let mut record = GameRecord {
+        start_fen: start_fen.to_string(),
+        moves: Vec::new(),
+        result: GameResult::Draw,
+        reason: String::new(),
+    };
+    let mut history: Vec<String> = Vec::new();
+    let (mut wtime, mut btime) = match tc {
+        TimeControl::Clock { base_ms, .. } => (base_ms as f64, base_ms as f64),
+        _ => (0.0, 0.0),
+    };
+
+    loop {
+        let status = game_status(&mut pos, &history.join("\n"));
+        if status != GameStatus::Ongoing {
+            record.result = match status {
+                GameStatus::Checkmate { winner: Color::White } => GameResult::WhiteWins,
+                GameStatus::Checkmate { winner: Color::Black } => GameResult::BlackWins,
+                _ => GameResult::Draw,
+            };
+            record.reason = status.reason().to_string();
+            return Ok(record);
+        }
+        if record.moves.len() >= MAX_GAME_PLIES {
+            record.reason = "max_plies".to_string();
+            return Ok(record);
+        }
+
+        let side = pos.side_to_move;
+        let player = if side == Color::White { &mut *white } else { &mut *black };
+        let mut limits = SearchLimits {
+            threads: player.config.threads,
+            skill_level: player.config.skill_level,
+            tune: player.config.tune,
+            ..SearchLimits::default()
+        };
+        match tc {
+            TimeControl::Clock { inc_ms, .. } => {
+                limits.game_clock = Some(GameClock {
+                    wtime: wtime.max(0.0) as u64,
+                    btime: btime.max(0.0) as u64,
+                    winc: inc_ms,
+                    binc: inc_ms,
+                    movestogo: 0,
+                });
+            }
+            TimeControl::MoveTime(ms) => limits.time_ms = ms,
+            TimeControl::Nodes(n) => limits.nodes = n,
+            TimeControl::Depth(d) => limits.depth = d,
+        }
+
+        let fen = position_to_fen(&pos);
+        let started = clock.now_ms();
+        let result = player.engine.search(&fen, &history.join("\n"), &limits, clock, &mut ());
+        let elapsed = clock.now_ms() - started;
+
+        let loser_wins = if side == Color::White { GameResult::BlackWins } else { GameResult::WhiteWins };
+        if let TimeControl::Clock { inc_ms, .. } = tc {
+            let remaining = if side == Color::White { &mut wtime } else { &mut btime };
+            *remaining -= elapsed;
+            if *remaining < 0.0 {
+                record.result = loser_wins;
+                record.reason = "time_forfeit".to_string();
+                return Ok(record);
+            }
+            *remaining += inc_ms as f64;
+        }
+
+        let parsed = result.and_then(|r| r.best).and_then(|uci| {
+            let (mv, promo) = parse_uci_move(&mut pos, &uci)?;
+            Some((uci, mv, promo))
+        });
+        let Some((uci, mv, promo)) = parsed else {
+            // Ohne legalen Zug hätte `game_status` das Spiel schon beendet; sonst Engine-Fehler.
+            record.result = loser_wins;
+            record.reason = "no_move".to_string();
+            return Ok(record);
+        };
+        history.push(fen);
+        let _ = make_move_in_place(&mut pos, mv, promo);
+        record.moves.push(uci);
+    }
+}
+
+// Match-Stand aus Sicht der ersten Konfiguration.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MatchStats {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MatchStats {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    // Trägt eine Partie ein; `first_is_white` gibt die Farbe der ersten Konfiguration an.
+    pub fn record(&mut self, result: GameResult, first_is_white: bool) {
+        match (result, first_is_white) {
+            (GameResult::Draw, _) => self.draws += 1,
+            (GameResult::WhiteWins, true) | (GameResult::BlackWins, false) => self.wins += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    // Punktanteil 0..1 (0.5 ohne Partien).
+    pub fn score(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / self.games() as f64
+    }
+
+    // Varianz des Punktergebnisses einer Partie (Trinomialmodell).
+    fn variance(&self) -> f64 {
+        let n = self.games() as f64;
+        if n == 0.0 {
+            return 0.0;
+        }
+        let (w, d) = (self.wins as f64 / n, self.draws as f64 / n);
+        let s = self.score();
+        (w + 0.25 * d - s * s).max(0.0)
+    }
+
+    // Was: Elo-Differenz aus dem Punktanteil (logistisches Modell), begrenzt auf ±1000.
+    pub fn elo(&self) -> f64 {
+        score_to_elo(self.score())
+    }
+
+    // Was: Halbe Breite des 95%-Intervalls der Elo-Differenz.
+    // Kosten: Konstant.
+    pub fn elo_error(&self) -> f64 {
+        let n = self.games() as f64;
+        if n == 0.0 {
+            return f64::INFINITY;
+        }
+        let margin = 1.959964 * (self.variance() / n).sqrt();
+        let s = self.score();
+        (score_to_elo(s + margin) - score_to_elo(s - margin)) / 2.0
+    }
+
+    // Was: Log-Likelihood-Ratio von H1 (Elo = elo1) gegen H0 (Elo = elo0), Normalapproximation.
+    // Warum: Standard-SPRT der Engine-Tests (Fishtest/cutechess); 0 solange die Varianz 0 ist.
+    pub fn llr(&self, elo0: f64, elo1: f64) -> f64 {
+        let var = self.variance();
+        if var <= 0.0 {
+            return 0.0;
+        }
+        let (s0, s1) = (elo_to_score(elo0), elo_to_score(elo1));
+        let n = self.games() as f64;
+        (s1 - s0) * (2.0 * self.score() - s0 - s1) / (2.0 * var / n)
+    }
+}
+
+fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+fn score_to_elo(score: f64) -> f64 {
+    let s = score.clamp(1e-6, 1.0 - 1e-6);
+    (400.0 * (s / (1.0 - s)).log10()).clamp(-1000.0, 1000.0)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SprtDecision {
+    AcceptH0,
+    AcceptH1,
+}
+
+// SPRT-Parameter: H0 Elo = elo0, H1 Elo = elo1, Fehler 1./2. Art alpha/beta.
+#[derive(Copy, Clone, Debug)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Sprt {
+    pub fn new(elo0: f64, elo1: f64) -> Sprt {
+        Sprt { elo0, elo1, alpha: 0.05, beta: 0.05 }
+    }
+
+    // (untere, obere) LLR-Grenze.
+    pub fn bounds(&self) -> (f64, f64) {
+        ((self.beta / (1.0 - self.alpha)).ln(), ((1.0 - self.beta) / self.alpha).ln())
+    }
+
+    // Entscheidung, sobald die LLR eine Grenze erreicht; sonst weiterspielen.
+    pub fn decision(&self, stats: &MatchStats) -> Option<SprtDecision> {
+        let llr = stats.llr(self.elo0, self.elo1);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            Some(SprtDecision::AcceptH1)
+        } else if llr <= lower {
+            Some(SprtDecision::AcceptH0)
+        } else {
+            None
+        }
+    }
+}
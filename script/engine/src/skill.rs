@@ -0,0 +1,147 @@
+// =====================================================================================
+// Spielstärke (Skill Level / UCI_Elo)
+// -------------------------------------------------------------------------------------
+// Schwächt die Suche gezielt statt nur flacher: Tiefen- und Knotenlimit, Rauschen auf
+// der statischen Bewertung, Zufallswahl unter fast gleich guten Wurzelzügen und
+// gelegentliche Patzer aus der Multi-PV-Kandidatenliste. Stufe 20 ist volle Stärke.
+// Schwache Stufen suchen auf einer eigenen kleinen TT (`SKILL_TT_MB`), damit verrauschte
+// Werte nicht in der TT der Engine landen und spätere Suchen mit voller Stärke verfälschen.
+// =====================================================================================
+
+use crate::search::PvLine;
+
+pub const MAX_SKILL_LEVEL: u32 = 20;
+
+// TT-Größe der Suchen mit Bewertungsrauschen; reicht für das Knotenlimit jeder Stufe.
+pub(crate) const SKILL_TT_MB: u32 = 8;
+
+// Parameter einer Stufe. `0` bei `max_depth`/`max_nodes` heißt unbegrenzt.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct SkillParams {
+    // Elo der Stufe (Kalibrierung siehe `SKILL_LEVELS`).
+    pub(crate) elo: u32,
+    pub(crate) max_depth: u32,
+    pub(crate) max_nodes: u64,
+    // Anzahl der Wurzelkandidaten (Multi-PV), aus denen gewählt wird.
+    pub(crate) candidates: u32,
+    // Amplitude des Bewertungsrauschens in Centipawns.
+    pub(crate) eval_noise: i32,
+    // Züge bis zu diesem Abstand zum besten Score gelten als gleichwertig.
+    pub(crate) pick_margin: i32,
+    // Wahrscheinlichkeit (Promille), einen beliebigen Kandidaten zu spielen.
+    pub(crate) blunder_permille: u32,
+}
+
+const fn level(
+    elo: u32,
+    max_depth: u32,
+    max_nodes: u64,
+    candidates: u32,
+    eval_noise: i32,
+    pick_margin: i32,
+    blunder_permille: u32,
+) -> SkillParams {
+    SkillParams { elo, max_depth, max_nodes, candidates, eval_noise, pick_margin, blunder_permille }
+}
+
+// Stufe -> Parameter. Kalibrierung der Elo-Werte mit dem Match-Runner (Selbstspiel,
+// eingebaute Eröffnungen, SPRT mit `--sprt 0,0.01` praktisch abgeschaltet):
+// - Stufen 0..12 paarweise benachbart, je 100 Partien, `--nodes 50000` (liegt über dem
+//   Knotenlimit jeder dieser Stufen, begrenzt sie also nicht zusätzlich).
+// - Stufen 12..20 in Zweierschritten, je 40 Partien, `--nodes` = Knotenlimit der stärkeren
+//   Stufe (Stufe 20: 512000); ungerade Stufen liegen mittig zwischen den Nachbarn.
+// Gemessene Abstände (Elo, 95-%-Fehler ±67..±349): 424 330 252 200 263 177 127 186 143 323 85 53
+// (0->1 ... 11->12), 338 436 269 319 (12->14 ... 18->20), zusammen 3926. Selbstspiel
+// überzeichnet Abstände stark; deshalb sind nur die relativen Abstände gemessen, die
+// Skala ist an Stufe 0 = 800 und Stufe 20 = 2300 verankert (geschätzt, nicht gegen
+// Menschen gemessen) und auf 5 gerundet. Nach Änderungen an Suche oder Parametern neu
+// messen, z. B. `match-runner --a skill=8 --b skill=7 --nodes 50000 --games 100 --sprt 0,0.01`.
+const SKILL_LEVELS: [SkillParams; (MAX_SKILL_LEVEL + 1) as usize] = [
+    level(800, 1, 500, 6, 200, 250, 250),
+    level(960, 1, 800, 6, 180, 220, 220),
+    level(1090, 2, 1_200, 5, 160, 200, 190),
+    level(1185, 2, 2_000, 5, 140, 180, 160),
+    level(1260, 2, 3_000, 5, 120, 160, 140),
+    level(1360, 3, 4_000, 4, 110, 140, 120),
+    level(1430, 3, 6_000, 4, 100, 120, 100),
+    level(1480, 3, 8_000, 4, 90, 100, 85),
+    level(1550, 4, 12_000, 4, 80, 90, 70),
+    level(1605, 4, 16_000, 4, 70, 80, 60),
+    level(1725, 4, 24_000, 3, 60, 70, 50),
+    level(1760, 5, 32_000, 3, 50, 60, 40),
+    level(1780, 5, 48_000, 3, 45, 50, 32),
+    level(1845, 6, 64_000, 3, 40, 40, 25),
+    level(1910, 6, 96_000, 3, 30, 35, 18),
+    level(1990, 7, 128_000, 2, 25, 30, 12),
+    level(2075, 8, 192_000, 2, 20, 25, 8),
+    level(2125, 9, 256_000, 2, 15, 20, 5),
+    level(2180, 10, 384_000, 2, 10, 15, 3),
+    level(2240, 12, 512_000, 2, 5, 10, 1),
+    level(2300, 0, 0, 1, 0, 0, 0),
+];
+
+// Parameter einer Stufe; Werte über `MAX_SKILL_LEVEL` gelten als volle Stärke.
+pub(crate) fn skill_params(level: u32) -> SkillParams {
+    SKILL_LEVELS[level.min(MAX_SKILL_LEVEL) as usize]
+}
+
+// Elo einer Stufe (für Anzeige und UCI_Elo).
+pub fn skill_level_elo(level: u32) -> u32 {
+    skill_params(level).elo
+}
+
+// Was: Höchste Stufe, deren Elo die gewünschte nicht übersteigt (mindestens Stufe 0).
+// Warum: UCI `UCI_Elo` und Elo-Auswahl im UI bilden auf die Tabelle ab.
+// Kosten: Linear in der Tabellengröße.
+pub fn skill_level_for_elo(elo: u32) -> u32 {
+    SKILL_LEVELS
+        .iter()
+        .rposition(|p| p.elo <= elo)
+        .unwrap_or(0) as u32
+}
+
+// Was: Deterministisches Rauschen in [-amplitude, amplitude] pro Stellung und Suche.
+// Warum: Gleiche Stellung bekommt innerhalb einer Suche denselben Fehler, damit TT-Werte
+// und Re-Searches konsistent bleiben; ein neuer Seed ändert die "Fehleinschätzungen".
+// Kosten: Konstant (ein splitmix64-Schritt).
+pub(crate) fn eval_noise(hash: u64, seed: u64, amplitude: i32) -> i32 {
+    if amplitude <= 0 {
+        return 0;
+    }
+    let span = (2 * amplitude + 1) as u64;
+    (mix(hash ^ seed) % span) as i32 - amplitude
+}
+
+// Was: Wählt den zu spielenden Wurzelzug aus den Multi-PV-Kandidaten.
+// Warum: Mit `blunder_permille` ein beliebiger Kandidat, sonst zufällig unter allen Zügen
+// innerhalb von `pick_margin` zum besten; Index 0 ist immer der beste Zug.
+// Kosten: Linear in der Kandidatenzahl.
+pub(crate) fn pick_root_line(lines: &[PvLine], params: &SkillParams, rng: &mut u64) -> usize {
+    if lines.len() <= 1 {
+        return 0;
+    }
+    if next_below(rng, 1000) < params.blunder_permille as u64 {
+        return next_below(rng, lines.len() as u64) as usize;
+    }
+    let best = lines[0].score;
+    let close = lines
+        .iter()
+        .take_while(|line| line.score >= best.saturating_sub(params.pick_margin))
+        .count()
+        .max(1);
+    next_below(rng, close as u64) as usize
+}
+
+// splitmix64-Finalizer (wie bei den Zobrist-Schlüsseln).
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+// Gleichverteilte Zahl in [0, n) aus dem Zustand `rng`.
+pub(crate) fn next_below(rng: &mut u64, n: u64) -> u64 {
+    *rng = mix(*rng);
+    *rng % n.max(1)
+}
@@ -0,0 +1,227 @@
+// =====================================================================================
+// Lazy SMP
+// -------------------------------------------------------------------------------------
+// Zusätzliche Such-Threads laufen eigenes Iterative Deepening auf derselben Stellung und
+// teilen nur die lock-freie TT mit dem Haupt-Thread. Versetzte Tiefen (Skip-Tabellen)
+// verteilen die Helfer auf verschiedene Iterationen; ihre TT-Einträge beschleunigen die
+// Hauptsuche, die allein Bestzug, PV und Ausgaben bestimmt.
+// Nativ sind die Helfer Scoped Threads. Auf wasm32 gibt es keine `std`-Threads: Im
+// Thread-Build (`+atomics`, gemeinsamer Speicher) startet das JS-Frontend Web Worker, die
+// sich über `helper_worker_main` als Helfer anmelden; ohne Atomics bleibt die Suche einsträngig.
+// =====================================================================================
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+use std::thread;
+
+use crate::platform::*;
+use crate::position::*;
+use crate::search::*;
+use crate::syzygy::*;
+use crate::tt::*;
+use crate::tune::*;
+use crate::zobrist::*;
+
+// Obergrenze für `SearchLimits::threads` (UCI-Option `Threads`).
+pub const MAX_THREADS: u32 = 64;
+
+// Tiefenversatz je Helfer (Helfer-Index modulo 20): Helfer `i` überspringt Tiefe `d`,
+// wenn `((d + SKIP_PHASE[i]) / SKIP_SIZE[i])` ungerade ist.
+const SKIP_SIZE: [u32; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+const SKIP_PHASE: [u32; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+
+// Was: Anzahl der Such-Threads inklusive Haupt-Thread.
+// Warum: Reproduzierbare Suchen und Skill-Stufen (Rauschen nur im Haupt-Thread) laufen
+// immer einsträngig; auf wasm32 gibt es höchstens so viele Helfer wie angemeldete Worker.
+// Kosten: Konstant.
+pub(crate) fn search_threads(limits: &SearchLimits) -> usize {
+    if limits.deterministic || limits.skill_level.is_some() {
+        return 1;
+    }
+    (limits.threads.clamp(1, MAX_THREADS) as usize).min(helper_capacity() + 1)
+}
+
+// Höchstzahl gleichzeitiger Helfer: nativ durch `MAX_THREADS` begrenzt, auf wasm32 die
+// angemeldeten Helfer-Worker (ohne Atomics keine).
+pub(crate) fn helper_capacity() -> usize {
+    #[cfg(not(target_arch = "wasm32"))]
+    let capacity = MAX_THREADS as usize - 1;
+    #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+    let capacity = pool::WORKERS.load(Ordering::SeqCst);
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    let capacity = 0;
+    capacity
+}
+
+#[inline]
+fn skip_depth(helper: usize, depth: u32) -> bool {
+    let i = (helper - 1) % SKIP_SIZE.len();
+    !((depth + SKIP_PHASE[i]) / SKIP_SIZE[i]).is_multiple_of(2)
+}
+
+// Uhr der Helfer: Sie haben kein Zeitlimit und enden über `HelperSetup::stop`.
+struct NoClock;
+
+impl Clock for NoClock {
+    fn now_ms(&self) -> f64 {
+        0.0
+    }
+}
+
+// Gemeinsamer, unveränderlicher Startzustand aller Helfer einer Suche.
+pub(crate) struct HelperSetup<'a> {
+    pub(crate) pos: Position,
+    pub(crate) tt: &'a Option<TT>,
+    pub(crate) zob: &'a Zobrist,
+    pub(crate) root_hash: u64,
+    pub(crate) history: Vec<u64>,
+    pub(crate) root_moves: Vec<(Move, Option<char>)>,
+    pub(crate) history_heur: [[[i32; 64]; 64]; 2],
+    pub(crate) tt_gen: u8,
+    pub(crate) max_depth: u32,
+    pub(crate) max_ply: usize,
+    pub(crate) stop: Arc<AtomicBool>,
+    pub(crate) tablebases: Option<Arc<Tablebases>>,
+    pub(crate) tb_cardinality: u32,
+    pub(crate) tune: TuneValues,
+}
+
+// Was: Startet `threads - 1` Helfer, führt `main` (die Hauptsuche) aus und stoppt die
+// Helfer danach; liefert das Ergebnis von `main` und die Knoten aller Helfer.
+// Warum: Scoped Threads dürfen TT und Zobrist-Tabelle ausleihen, ohne sie zu kopieren.
+// Kosten: Ein Thread-Start je Helfer pro Suche; ohne Helfer nur der Aufruf von `main`.
+pub(crate) fn run_with_helpers<R>(threads: usize, setup: &HelperSetup, main: impl FnOnce() -> R) -> (R, u64) {
+    if threads <= 1 {
+        return (main(), 0);
+    }
+    run_helpers(threads - 1, setup, main)
+}
+
+// Scoped Threads; auf wasm32 ohne Atomics nie erreicht, weil `helper_capacity` dort 0 ist.
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+fn run_helpers<R>(helpers: usize, setup: &HelperSetup, main: impl FnOnce() -> R) -> (R, u64) {
+    thread::scope(|scope| {
+        let helpers: Vec<_> = (1..=helpers).map(|id| scope.spawn(move || helper_search(id, setup))).collect();
+        let result = main();
+        setup.stop.store(true, Ordering::Relaxed);
+        let nodes = helpers.into_iter().map(|h| h.join().unwrap_or(0)).sum();
+        (result, nodes)
+    })
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+use pool::run as run_helpers;
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+pub(crate) use pool::worker_main as helper_worker_main;
+
+// Was: Iterative Deepening eines Helfers mit eigenen Killern/History bis zum Stop.
+// Warum: Füllt die TT mit Ergebnissen anderer Tiefen und Zugreihenfolgen als die Hauptsuche.
+// Kosten: Läuft, bis der Haupt-Thread fertig ist oder `max_depth` erreicht ist.
+fn helper_search(id: usize, setup: &HelperSetup) -> u64 {
+    let mut pos = clone_position(&setup.pos);
+    let limits = SearchLimits {
+        stop: Some(Arc::clone(&setup.stop)),
+        tablebases: setup.tablebases.clone(),
+        tune: setup.tune,
+        ..SearchLimits::default()
+    };
+    let mut sink = ();
+    let mut ctx = SearchContext::new(&NoClock, &mut sink, &limits, setup.max_ply, setup.history.clone());
+    ctx.tt_gen = setup.tt_gen;
+    ctx.history_heur = setup.history_heur;
+    ctx.root_moves = setup.root_moves.clone();
+    ctx.tb_cardinality = setup.tb_cardinality;
+
+    let mut center = 0;
+    let mut hint = None;
+    for depth in 1..=setup.max_depth {
+        if skip_depth(id, depth) {
+            continue;
+        }
+        let Some((score, mv, _)) =
+            search_iteration(&mut pos, depth, &mut ctx, setup.tt, setup.zob, setup.root_hash, center, hint, &[])
+        else {
+            break;
+        };
+        center = score;
+        hint = mv.map(|(m, promo)| (m.from, m.to, promo));
+    }
+    ctx.nodes
+}
+
+// Helfer-Worker des wasm-Thread-Builds. Jeder Worker ruft `worker_main` einmal auf und schläft
+// an einer Condvar (im Thread-Build `memory.atomic.wait`), bis `run` einen Auftrag mit freien
+// Plätzen veröffentlicht. Der Haupt-Thread läuft selbst in einem Worker (im Fenster-Thread ist
+// Warten verboten) und wartet am Ende, bis alle beanspruchten Helfer fertig sind; erst dann
+// endet die Ausleihe von `HelperSetup`.
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+mod pool {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+    use super::{helper_search, HelperSetup};
+
+    // Angemeldete Helfer-Worker.
+    pub(super) static WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+    // Laufender Auftrag: Adresse des `HelperSetup`, gewünschte/vergebene/fertige Helfer.
+    struct Job {
+        setup: usize,
+        wanted: usize,
+        claimed: usize,
+        finished: usize,
+        nodes: u64,
+    }
+
+    static JOB: Mutex<Job> = Mutex::new(Job { setup: 0, wanted: 0, claimed: 0, finished: 0, nodes: 0 });
+    // Weckt Helfer bei neuem Auftrag bzw. den Haupt-Thread bei fertigen Helfern.
+    static STARTED: Condvar = Condvar::new();
+    static FINISHED: Condvar = Condvar::new();
+
+    fn job() -> MutexGuard<'static, Job> {
+        JOB.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Was: Endlosschleife eines Helfer-Workers: Platz beanspruchen, suchen, wieder schlafen.
+    // Kosten: Blockiert den Worker dauerhaft; im Leerlauf ohne CPU-Last.
+    pub(crate) fn worker_main() -> ! {
+        WORKERS.fetch_add(1, Ordering::SeqCst);
+        loop {
+            let (id, setup) = {
+                let mut job = job();
+                while job.claimed >= job.wanted {
+                    job = STARTED.wait(job).unwrap_or_else(PoisonError::into_inner);
+                }
+                job.claimed += 1;
+                (job.claimed, job.setup)
+            };
+            // SAFETY: `run` kehrt erst zurück, wenn alle beanspruchten Helfer fertig gemeldet
+            // haben; bis dahin lebt das ausgeliehene Setup.
+            let nodes = helper_search(id, unsafe { &*(setup as *const HelperSetup) });
+            let mut job = job();
+            job.finished += 1;
+            job.nodes += nodes;
+            FINISHED.notify_all();
+        }
+    }
+
+    // Was: Wie die native Variante, aber mit den angemeldeten Workern statt neuer Threads.
+    // Kosten: Ein `notify` zum Start; am Ende Warten, bis die Helfer ihre Iteration abbrechen.
+    pub(super) fn run<R>(helpers: usize, setup: &HelperSetup, main: impl FnOnce() -> R) -> (R, u64) {
+        let helpers = helpers.min(WORKERS.load(Ordering::SeqCst));
+        let setup_addr = setup as *const HelperSetup as usize;
+        *job() = Job { setup: setup_addr, wanted: helpers, claimed: 0, finished: 0, nodes: 0 };
+        STARTED.notify_all();
+
+        let result = main();
+        setup.stop.store(true, Ordering::Relaxed);
+        let mut job = job();
+        while job.finished < helpers {
+            job = FINISHED.wait(job).unwrap_or_else(PoisonError::into_inner);
+        }
+        job.wanted = 0;
+        job.setup = 0;
+        (result, job.nodes)
+    }
+}
@@ -0,0 +1,150 @@
+// =====================================================================================
+// Partiestatus
+// -------------------------------------------------------------------------------------
+// Erkennt das Partieende nach FIDE-Regeln: Matt, Patt, tote Stellung (ungenügendes
+// Material), 75-Züge-Regel und fünffache Wiederholung (automatisch) sowie die
+// reklamierbaren Remis nach 50-Züge-Regel und dreifacher Wiederholung.
+// =====================================================================================
+
+use crate::bitboard::*;
+use crate::movegen::*;
+use crate::position::*;
+use crate::zobrist::*;
+
+// Ergebnis von `game_status`. Die Reihenfolge der Varianten entspricht der Priorität
+// der Prüfung: ein Matt zählt auch dann, wenn gleichzeitig eine Remisregel greift.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate { winner: Color },
+    Stalemate,
+    InsufficientMaterial,
+    FivefoldRepetition,
+    SeventyFiveMoveRule,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+}
+
+impl GameStatus {
+    // Kurzname für JSON/JS (gleiche Schreibweise wie `reason` im GameController).
+    pub fn reason(self) -> &'static str {
+        match self {
+            GameStatus::Ongoing => "ongoing",
+            GameStatus::Checkmate { .. } => "checkmate",
+            GameStatus::Stalemate => "stalemate",
+            GameStatus::InsufficientMaterial => "insufficient_material",
+            GameStatus::FivefoldRepetition => "fivefold_repetition",
+            GameStatus::SeventyFiveMoveRule => "seventy_five_move_rule",
+            GameStatus::ThreefoldRepetition => "threefold_repetition",
+            GameStatus::FiftyMoveRule => "fifty_move_rule",
+        }
+    }
+
+    // Partie ist ohne Zutun eines Spielers beendet.
+    pub fn is_game_over(self) -> bool {
+        !matches!(
+            self,
+            GameStatus::Ongoing | GameStatus::ThreefoldRepetition | GameStatus::FiftyMoveRule
+        )
+    }
+
+    // Remis darf reklamiert werden, die Partie läuft aber ohne Reklamation weiter.
+    pub fn is_claimable_draw(self) -> bool {
+        matches!(self, GameStatus::ThreefoldRepetition | GameStatus::FiftyMoveRule)
+    }
+}
+
+// Was: Bestimmt den Status von `pos`; `history` sind die FENs der Stellungen davor
+// (zeilengetrennt wie bei `search_with_history`).
+// Warum: Ein Regelwerk für UI, UCI und Match-Runner statt Einzelprüfungen in JS.
+// Kosten: Eine legale Zuggenerierung plus eine pro History-FEN (En-passant-Normalisierung).
+pub fn game_status(pos: &mut Position, history: &str) -> GameStatus {
+    let in_check = is_in_check(pos, pos.side_to_move);
+    let moves = generate_legal_moves(pos);
+    if moves.is_empty() {
+        return if in_check {
+            GameStatus::Checkmate { winner: pos.side_to_move.opposite() }
+        } else {
+            GameStatus::Stalemate
+        };
+    }
+    if is_insufficient_material(pos) {
+        return GameStatus::InsufficientMaterial;
+    }
+
+    let repetitions = repetition_count(pos, history);
+    if repetitions >= 5 {
+        return GameStatus::FivefoldRepetition;
+    }
+    if pos.halfmove >= 150 {
+        return GameStatus::SeventyFiveMoveRule;
+    }
+    if repetitions >= 3 {
+        return GameStatus::ThreefoldRepetition;
+    }
+    if pos.halfmove >= 100 {
+        return GameStatus::FiftyMoveRule;
+    }
+    GameStatus::Ongoing
+}
+
+// Was: Prüft die materialbedingten toten Stellungen nach FIDE 5.2.2.
+// Warum: Kein Matt möglich bei K-K, K+L-K, K+S-K und bei beliebig vielen Läufern,
+// die alle auf Feldern derselben Farbe stehen.
+// Kosten: Konstant (einige Bitboard-Operationen).
+pub fn is_insufficient_material(pos: &Position) -> bool {
+    let b = &pos.bb;
+    if (b.wp | b.bp | b.wr | b.br | b.wq | b.bq) != 0 {
+        return false;
+    }
+    let knights = b.wn | b.bn;
+    let bishops = b.wb | b.bb;
+    if knights != 0 {
+        return popcnt(knights) == 1 && bishops == 0;
+    }
+    // Helle Felder: (Datei + Reihe) ungerade, a1 ist dunkel.
+    const LIGHT_SQUARES: u64 = 0x55AA_55AA_55AA_55AA;
+    bishops & LIGHT_SQUARES == 0 || bishops & !LIGHT_SQUARES == 0
+}
+
+// Was: Zählt, wie oft die aktuelle Stellung inklusive ihr selbst vorkam.
+// Warum: Grundlage für drei- und fünffache Wiederholung.
+// Kosten: Linear in der History-Länge (FEN-Parsing + Zuggenerierung je Eintrag).
+fn repetition_count(pos: &mut Position, history: &str) -> u32 {
+    ZOBRIST_TABLE.with(|zob| {
+        let key = repetition_key(pos, zob);
+        let mut count = 1;
+        for line in history.lines() {
+            let fen = line.trim();
+            if fen.is_empty() {
+                continue;
+            }
+            if let Some(mut prev) = parse_fen(fen) {
+                if repetition_key(&mut prev, zob) == key {
+                    count += 1;
+                }
+            }
+        }
+        count
+    })
+}
+
+// Was: Zobrist-Hash, bei dem das En-passant-Feld nur zählt, wenn ein legaler
+// En-passant-Schlag existiert.
+// Warum: Nach FIDE 9.2.3 sind Stellungen nur dann verschieden, wenn sich die
+// tatsächlich möglichen Züge unterscheiden; die FEN setzt das Feld nach jedem Doppelschritt.
+// Kosten: Eine legale Zuggenerierung, falls ein En-passant-Feld gesetzt ist.
+fn repetition_key(pos: &mut Position, zob: &Zobrist) -> u64 {
+    let hash = compute_hash(pos, zob);
+    let Some(ep) = pos.ep else {
+        return hash;
+    };
+    let ep_possible = generate_legal_moves(pos)
+        .iter()
+        .any(|(mv, _)| matches!(mv.kind, MoveKind::EnPassant));
+    if ep_possible {
+        hash
+    } else {
+        hash ^ zob.ep_file[(ep % 8) as usize + 1]
+    }
+}
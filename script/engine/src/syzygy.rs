@@ -0,0 +1,1380 @@
+// =====================================================================================
+// Syzygy-Endspieldatenbanken
+// -------------------------------------------------------------------------------------
+// Liest WDL- (`.rtbw`) und DTZ-Tabellen (`.rtbz`) im Syzygy-Format und beantwortet
+// Proben für Stellungen mit bis zu 7 Steinen ohne Rochaderechte. Indizierung und
+// Dekompression (kanonischer Huffman-Code + Recursive Pairing) folgen dem Referenz-
+// Prober (`tbprobe` aus Fathom/Stockfish), damit dieselben Dateien dieselben Werte liefern.
+//
+// Tabellen kommen nativ aus Verzeichnissen (per `memmap2` eingeblendet, wo das scheitert
+// je Probe stückweise gelesen; nichts wird komplett geladen) oder als Bytes
+// (wasm: `ArrayBuffer` aus dem Worker).
+// Der Header einer Tabelle wird erst bei ihrer ersten Probe geparst.
+//
+// WDL-Werte aus Sicht der Seite am Zug: -2 Verlust, -1 Verlust, den die 50-Züge-Regel
+// rettet, 0 Remis, 1 Gewinn, den die 50-Züge-Regel verhindert, 2 Gewinn.
+// =====================================================================================
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::bitboard::*;
+use crate::movegen::*;
+use crate::position::*;
+
+// Maximale Steinzahl (inklusive Könige) der Syzygy-Tabellen.
+const TB_PIECES: usize = 7;
+
+// Score einer sicher gewonnenen Datenbank-Stellung; liegt unter den Matt-Scores der Suche.
+pub(crate) const TB_WIN_SCORE: i32 = 28_000;
+
+const MAX_DTZ: i32 = 1 << 18;
+
+const WDL_MAGIC: [u8; 4] = [0x71, 0xE8, 0x23, 0x5D];
+const DTZ_MAGIC: [u8; 4] = [0xD7, 0x66, 0x0C, 0xA5];
+
+// Flags je Teiltabelle (`Pairs::flags`).
+const FLAG_STM: u8 = 1;
+const FLAG_MAPPED: u8 = 2;
+const FLAG_WIN_PLIES: u8 = 4;
+const FLAG_LOSS_PLIES: u8 = 8;
+const FLAG_WIDE: u8 = 16;
+const FLAG_SINGLE_VALUE: u8 = 128;
+
+// Ergebnis einer WDL-Probe aus Sicht der Seite am Zug.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Wdl {
+    Loss,
+    // Verloren, aber die 50-Züge-Regel rettet das Remis.
+    BlessedLoss,
+    Draw,
+    // Gewonnen, aber nicht innerhalb der 50-Züge-Regel.
+    CursedWin,
+    Win,
+}
+
+impl Wdl {
+    fn from_raw(v: i32) -> Wdl {
+        match v {
+            i32::MIN..=-2 => Wdl::Loss,
+            -1 => Wdl::BlessedLoss,
+            0 => Wdl::Draw,
+            1 => Wdl::CursedWin,
+            _ => Wdl::Win,
+        }
+    }
+
+    // -2..=2 wie im Syzygy-Format.
+    pub fn as_i32(self) -> i32 {
+        match self {
+            Wdl::Loss => -2,
+            Wdl::BlessedLoss => -1,
+            Wdl::Draw => 0,
+            Wdl::CursedWin => 1,
+            Wdl::Win => 2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ProbeState {
+    Ok,
+    Fail,
+    // Die DTZ-Tabelle speichert nur die andere Seite am Zug.
+    ChangeStm,
+    // Bester Zug setzt den 50-Züge-Zähler zurück; die DTZ-Tabelle ist hier unzuverlässig.
+    ZeroingBestMove,
+}
+
+// ---------------------------
+// Index-Tabellen
+// ---------------------------
+
+struct IndexTables {
+    // a2..h7 -> 0..47; der Bauer mit dem größten Wert ist der führende.
+    map_pawns: [usize; 64],
+    // Felder unterhalb der a1-h8-Diagonale -> 0..27.
+    map_b1h1h7: [u64; 64],
+    // Dreieck a1-d1-d4 -> 0..9 (Diagonalfelder zuletzt).
+    map_a1d1d4: [usize; 64],
+    // Die 462 legalen Königspaare mit erstem König im Dreieck a1-d1-d4.
+    map_kk: [[u64; 64]; 10],
+    binomial: [[u64; 64]; TB_PIECES],
+    lead_pawn_idx: [[u64; 64]; 6],
+    lead_pawns_size: [[u64; 4]; 6],
+}
+
+#[inline]
+fn off_a1h8(sq: u8) -> i32 {
+    (sq >> 3) as i32 - (sq & 7) as i32
+}
+
+impl IndexTables {
+    // Was: Baut die festen Abbildungen der Syzygy-Indizierung.
+    // Kosten: Einmalig wenige tausend Schritte (über `index_tables` gecacht).
+    fn build() -> Self {
+        let mut t = IndexTables {
+            map_pawns: [0; 64],
+            map_b1h1h7: [0; 64],
+            map_a1d1d4: [0; 64],
+            map_kk: [[0; 64]; 10],
+            binomial: [[0; 64]; TB_PIECES],
+            lead_pawn_idx: [[0; 64]; 6],
+            lead_pawns_size: [[0; 4]; 6],
+        };
+
+        let mut code = 0;
+        for s in 0..64u8 {
+            if off_a1h8(s) < 0 {
+                t.map_b1h1h7[s as usize] = code;
+                code += 1;
+            }
+        }
+
+        let mut diagonal = Vec::new();
+        let mut code = 0;
+        for s in 0..=27u8 {
+            if off_a1h8(s) < 0 && s & 7 <= 3 {
+                t.map_a1d1d4[s as usize] = code;
+                code += 1;
+            } else if off_a1h8(s) == 0 && s & 7 <= 3 {
+                diagonal.push(s);
+            }
+        }
+        for s in diagonal {
+            t.map_a1d1d4[s as usize] = code;
+            code += 1;
+        }
+
+        // Beide Könige auf der Diagonale werden zuletzt codiert.
+        let mut both_on_diagonal = Vec::new();
+        let mut code = 0;
+        for idx in 0..10 {
+            for s1 in 0..=27u8 {
+                if t.map_a1d1d4[s1 as usize] != idx || (idx == 0 && s1 != 1) {
+                    continue;
+                }
+                for s2 in 0..64u8 {
+                    if (KING_ATTACKS[s1 as usize] | bb(s1)) & bb(s2) != 0 {
+                        continue;
+                    }
+                    if off_a1h8(s1) == 0 && off_a1h8(s2) > 0 {
+                        continue;
+                    }
+                    if off_a1h8(s1) == 0 && off_a1h8(s2) == 0 {
+                        both_on_diagonal.push((idx, s2));
+                    } else {
+                        t.map_kk[idx][s2 as usize] = code;
+                        code += 1;
+                    }
+                }
+            }
+        }
+        for (idx, s2) in both_on_diagonal {
+            t.map_kk[idx][s2 as usize] = code;
+            code += 1;
+        }
+
+        t.binomial[0][0] = 1;
+        for n in 1..64 {
+            for k in 0..TB_PIECES.min(n + 1) {
+                let take = if k > 0 { t.binomial[k - 1][n - 1] } else { 0 };
+                let skip = if k < n { t.binomial[k][n - 1] } else { 0 };
+                t.binomial[k][n] = take + skip;
+            }
+        }
+
+        let mut available = 47usize;
+        for lead in 1..=5 {
+            for f in 0..4 {
+                let mut idx = 0;
+                for r in 1..=6 {
+                    let sq = r * 8 + f;
+                    if lead == 1 {
+                        t.map_pawns[sq] = available;
+                        t.map_pawns[sq ^ 7] = available.saturating_sub(1);
+                        available = available.saturating_sub(2);
+                    }
+                    t.lead_pawn_idx[lead][sq] = idx;
+                    idx += t.binomial[lead - 1][t.map_pawns[sq]];
+                }
+                t.lead_pawns_size[lead][f] = idx;
+            }
+        }
+        t
+    }
+}
+
+fn index_tables() -> &'static IndexTables {
+    static TABLES: OnceLock<IndexTables> = OnceLock::new();
+    TABLES.get_or_init(IndexTables::build)
+}
+
+// ---------------------------
+// Datenquellen
+// ---------------------------
+
+enum TbSource {
+    Bytes(Vec<u8>),
+    // Nur-lesende Einblendung einer Tabellendatei. Proben im Suchbaum kosten so keinen
+    // Systemaufruf; das Betriebssystem lädt und verdrängt die Seiten selbst.
+    #[cfg(any(unix, windows))]
+    Mapped(memmap2::Mmap),
+    #[cfg(any(unix, windows))]
+    File(std::fs::File, u64),
+}
+
+impl TbSource {
+    fn len(&self) -> u64 {
+        match self {
+            TbSource::Bytes(bytes) => bytes.len() as u64,
+            #[cfg(any(unix, windows))]
+            TbSource::Mapped(map) => map.len() as u64,
+            #[cfg(any(unix, windows))]
+            TbSource::File(_, len) => *len,
+        }
+    }
+
+    // Was: Liest ab `offset` in `buf`; Bytes hinter dem Dateiende werden mit 0 gefüllt.
+    // Warum: Der Huffman-Decoder liest am Blockende bis zu 8 Bytes über den Block hinaus.
+    // Kosten: Ein Kopiervorgang (Bytes, `mmap`) bzw. ein positionierter Dateizugriff.
+    fn read(&self, offset: u64, buf: &mut [u8]) -> bool {
+        let len = self.len();
+        if offset >= len {
+            return false;
+        }
+        let n = ((len - offset) as usize).min(buf.len());
+        buf[n..].fill(0);
+        let start = offset as usize;
+        match self {
+            TbSource::Bytes(bytes) => buf[..n].copy_from_slice(&bytes[start..start + n]),
+            #[cfg(any(unix, windows))]
+            TbSource::Mapped(map) => buf[..n].copy_from_slice(&map[start..start + n]),
+            #[cfg(any(unix, windows))]
+            TbSource::File(file, _) => return read_at(file, offset, &mut buf[..n]).is_ok(),
+        }
+        true
+    }
+}
+
+#[cfg(unix)]
+fn read_at(file: &std::fs::File, offset: u64, buf: &mut [u8]) -> std::io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.read_exact_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &std::fs::File, offset: u64, buf: &mut [u8]) -> std::io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    let mut done = 0;
+    while done < buf.len() {
+        let n = file.seek_read(&mut buf[done..], offset + done as u64)?;
+        if n == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        done += n;
+    }
+    Ok(())
+}
+
+// Sequentieller Leser für den Tabellen-Header (Little Endian).
+struct Reader<'a> {
+    src: &'a TbSource,
+    pos: u64,
+}
+
+impl Reader<'_> {
+    fn bytes(&mut self, n: usize) -> Option<Vec<u8>> {
+        if self.pos + n as u64 > self.src.len() {
+            return None;
+        }
+        let mut buf = vec![0; n];
+        if n > 0 && !self.src.read(self.pos, &mut buf) {
+            return None;
+        }
+        self.pos += n as u64;
+        Some(buf)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn align(&mut self, to: u64) {
+        self.pos = (self.pos + to - 1) & !(to - 1);
+    }
+}
+
+// ---------------------------
+// Tabellen-Struktur
+// ---------------------------
+
+// Dekompressions- und Indexdaten einer Teiltabelle (Seite am Zug × Datei des führenden Bauern).
+#[derive(Default)]
+struct Pairs {
+    flags: u8,
+    min_sym_len: u8,
+    block_size: u64,
+    span: u64,
+    num_blocks: u64,
+    block_length_size: u64,
+    sparse_index_size: u64,
+    // `lowest_sym[l]`: Symbol der Länge `min_sym_len + l` mit dem kleinsten Wert.
+    lowest_sym: Vec<u16>,
+    // `base64[l]`: kleinstes Symbol der Länge `min_sym_len + l`, auf 64 Bit aufgefüllt.
+    base64: Vec<u64>,
+    // Anzahl der Werte (-1), für die ein Symbol steht.
+    symlen: Vec<u8>,
+    // Linkes und rechtes Kind je Symbol (je 12 Bit).
+    btree: Vec<[u8; 3]>,
+    sparse_index: u64,
+    block_length: u64,
+    data: u64,
+    pieces: [u8; TB_PIECES],
+    group_idx: [u64; TB_PIECES + 1],
+    group_len: [usize; TB_PIECES + 1],
+    // DTZ: Start der Wertetabellen für Gewinn, Verlust, Cursed Win, Blessed Loss.
+    map_idx: [u16; 4],
+}
+
+#[inline]
+fn lr_left(lr: [u8; 3]) -> usize {
+    (((lr[1] & 0xF) as usize) << 8) | lr[0] as usize
+}
+
+#[inline]
+fn lr_right(lr: [u8; 3]) -> usize {
+    ((lr[2] as usize) << 4) | (lr[1] >> 4) as usize
+}
+
+// Geparster Header einer Datei.
+struct Parsed {
+    // `[seite * 4 + datei]`
+    pairs: Vec<Pairs>,
+    // DTZ-Wertetabellen (leer bei WDL).
+    map: Vec<u8>,
+}
+
+impl Parsed {
+    fn pairs(&self, stm: usize, file: usize, dtz: bool, has_pawns: bool) -> &Pairs {
+        let side = if dtz { 0 } else { stm & 1 };
+        &self.pairs[side * 4 + if has_pawns { file } else { 0 }]
+    }
+}
+
+struct TbFile {
+    source: TbSource,
+    parsed: OnceLock<Option<Parsed>>,
+}
+
+// Eine Materialverteilung (z. B. KRvK) mit ihren WDL- und DTZ-Dateien.
+struct TbEntry {
+    // Materialschlüssel mit der stärkeren Seite als Weiß bzw. als Schwarz.
+    key: u64,
+    key2: u64,
+    piece_count: usize,
+    has_pawns: bool,
+    has_unique_pieces: bool,
+    // [führende Farbe, andere Farbe]
+    pawn_count: [u8; 2],
+    wdl: Option<TbFile>,
+    dtz: Option<TbFile>,
+}
+
+// Stückzahl je Figurtyp (Index 1..=6 = P, N, B, R, Q, K).
+type PieceCounts = [u8; 7];
+
+#[inline]
+fn material_key(white: &PieceCounts, black: &PieceCounts) -> u64 {
+    (1..=5).fold(0u64, |key, t| {
+        key | (white[t] as u64) << (4 * (t - 1)) | (black[t] as u64) << (4 * (t + 4))
+    })
+}
+
+fn piece_type(c: char) -> Option<usize> {
+    match c.to_ascii_uppercase() {
+        'P' => Some(1),
+        'N' => Some(2),
+        'B' => Some(3),
+        'R' => Some(4),
+        'Q' => Some(5),
+        'K' => Some(6),
+        _ => None,
+    }
+}
+
+// Syzygy-Figurcode: Typ 1..=6, Schwarz zusätzlich mit Bit 8.
+#[inline]
+fn piece_code(piece: char) -> u8 {
+    let t = piece_type(piece).unwrap_or(0) as u8;
+    if piece.is_ascii_lowercase() {
+        t | 8
+    } else {
+        t
+    }
+}
+
+fn position_key(pos: &Position) -> u64 {
+    let b = &pos.bb;
+    let white = [0, popcnt(b.wp), popcnt(b.wn), popcnt(b.wb), popcnt(b.wr), popcnt(b.wq), 1].map(|c| c as u8);
+    let black = [0, popcnt(b.bp), popcnt(b.bn), popcnt(b.bb), popcnt(b.br), popcnt(b.bq), 1].map(|c| c as u8);
+    material_key(&white, &black)
+}
+
+// Was: Materialdaten aus einem Tabellennamen wie "KRPvKN" (stärkere Seite zuerst).
+// Kosten: Linear in der Namenslänge.
+fn parse_material(name: &str) -> Option<TbEntry> {
+    let side = |s: &str| -> Option<PieceCounts> {
+        let mut counts = [0u8; 7];
+        for c in s.chars() {
+            counts[piece_type(c)?] += 1;
+        }
+        (counts[6] == 1).then_some(counts)
+    };
+    let (w, b) = name.split_once('v')?;
+    let (white, black) = (side(w)?, side(b)?);
+    let piece_count = w.len() + b.len();
+    if piece_count > TB_PIECES {
+        return None;
+    }
+    let has_unique_pieces = (1..=5).any(|t| white[t] == 1 || black[t] == 1);
+    let white_leads = black[1] == 0 || (white[1] > 0 && black[1] >= white[1]);
+    let pawn_count = if white_leads { [white[1], black[1]] } else { [black[1], white[1]] };
+    Some(TbEntry {
+        key: material_key(&white, &black),
+        key2: material_key(&black, &white),
+        piece_count,
+        has_pawns: white[1] + black[1] > 0,
+        has_unique_pieces,
+        pawn_count,
+        wdl: None,
+        dtz: None,
+    })
+}
+
+// ---------------------------
+// Header-Parser
+// ---------------------------
+
+// Was: Legt Gruppen (gleichartige Steine) und deren Index-Faktoren einer Teiltabelle fest.
+// Warum: Die Codierungsreihenfolge der Gruppen (`order`) ist ein Parameter je Tabelle.
+// Kosten: Konstant (höchstens 7 Gruppen).
+fn set_groups(e: &TbEntry, d: &mut Pairs, order: [usize; 2], file: usize) {
+    let t = index_tables();
+    let mut n = 0;
+    let mut first_len: i32 = if e.has_pawns {
+        0
+    } else if e.has_unique_pieces {
+        3
+    } else {
+        2
+    };
+    d.group_len[0] = 1;
+    for i in 1..e.piece_count {
+        first_len -= 1;
+        if first_len > 0 || d.pieces[i] == d.pieces[i - 1] {
+            d.group_len[n] += 1;
+        } else {
+            n += 1;
+            d.group_len[n] = 1;
+        }
+    }
+    n += 1;
+    d.group_len[n] = 0;
+
+    let pp = e.has_pawns && e.pawn_count[1] > 0;
+    let mut next = if pp { 2 } else { 1 };
+    let mut free_squares = 64 - d.group_len[0] - if pp { d.group_len[1] } else { 0 };
+    let mut idx: u64 = 1;
+    let mut k = 0;
+    while next < n || k == order[0] || k == order[1] {
+        if k == order[0] {
+            d.group_idx[0] = idx;
+            idx *= if e.has_pawns {
+                t.lead_pawns_size[d.group_len[0]][file]
+            } else if e.has_unique_pieces {
+                31332
+            } else {
+                462
+            };
+        } else if k == order[1] {
+            d.group_idx[1] = idx;
+            idx *= t.binomial[d.group_len[1]][48 - d.group_len[0]];
+        } else {
+            d.group_idx[next] = idx;
+            idx *= t.binomial[d.group_len[next]][free_squares];
+            free_squares -= d.group_len[next];
+            next += 1;
+        }
+        k += 1;
+    }
+    d.group_idx[n] = idx;
+}
+
+// Was: Länge (Anzahl Werte - 1) eines Symbols durch Expansion in seine Kinder.
+// Kosten: Linear in der Baumgröße über alle Aufrufe (jedes Symbol einmal).
+fn set_symlen(btree: &[[u8; 3]], symlen: &mut [u8], visited: &mut [bool], s: usize) -> Option<u8> {
+    visited[s] = true;
+    let sr = lr_right(btree[s]);
+    if sr == 0xFFF {
+        return Some(0);
+    }
+    let sl = lr_left(btree[s]);
+    if sl >= btree.len() || sr >= btree.len() {
+        return None;
+    }
+    if !visited[sl] {
+        symlen[sl] = set_symlen(btree, symlen, visited, sl)?;
+    }
+    if !visited[sr] {
+        symlen[sr] = set_symlen(btree, symlen, visited, sr)?;
+    }
+    Some(symlen[sl].wrapping_add(symlen[sr]).wrapping_add(1))
+}
+
+// Was: Liest Blockgrößen, Huffman-Parameter und den Pairing-Baum einer Teiltabelle.
+// Kosten: Linear in der Symbolanzahl.
+fn set_sizes(d: &mut Pairs, r: &mut Reader) -> Option<()> {
+    d.flags = r.u8()?;
+    if d.flags & FLAG_SINGLE_VALUE != 0 {
+        d.min_sym_len = r.u8()?;
+        return Some(());
+    }
+
+    let groups = d.group_len[..TB_PIECES].iter().position(|&l| l == 0).unwrap_or(TB_PIECES);
+    let tb_size = d.group_idx[groups];
+
+    d.block_size = 1u64.checked_shl(r.u8()? as u32)?;
+    d.span = 1u64.checked_shl(r.u8()? as u32)?;
+    d.sparse_index_size = tb_size.div_ceil(d.span);
+    let padding = r.u8()?;
+    d.num_blocks = r.u32()? as u64;
+    d.block_length_size = d.num_blocks + padding as u64;
+    let max_sym_len = r.u8()?;
+    d.min_sym_len = r.u8()?;
+    if max_sym_len < d.min_sym_len {
+        return None;
+    }
+    let lengths = (max_sym_len - d.min_sym_len) as usize + 1;
+    d.lowest_sym = (0..lengths).map(|_| r.u16()).collect::<Option<_>>()?;
+
+    // Kanonischer Huffman-Code: längere Symbole haben kleinere Werte.
+    d.base64 = vec![0; lengths];
+    for i in (0..lengths - 1).rev() {
+        d.base64[i] = d.base64[i + 1]
+            .wrapping_add(d.lowest_sym[i] as u64)
+            .wrapping_sub(d.lowest_sym[i + 1] as u64)
+            / 2;
+    }
+    for (i, base) in d.base64.iter_mut().enumerate() {
+        let shift = 64usize.checked_sub(i + d.min_sym_len as usize)?;
+        *base = base.checked_shl(shift as u32).unwrap_or(0);
+    }
+
+    let symbols = r.u16()? as usize;
+    let raw = r.bytes(symbols * 3)?;
+    d.btree = raw.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect();
+    d.symlen = vec![0; symbols];
+    let mut visited = vec![false; symbols];
+    for s in 0..symbols {
+        if !visited[s] {
+            d.symlen[s] = set_symlen(&d.btree, &mut d.symlen, &mut visited, s)?;
+        }
+    }
+    r.pos += (symbols & 1) as u64;
+    Some(())
+}
+
+// Was: Liest die DTZ-Wertetabellen (Abbildung gespeicherter Werte auf echte DTZ).
+// Kosten: Linear in der Tabellengröße (wenige hundert Bytes).
+fn set_dtz_map(pairs: &mut [Pairs], r: &mut Reader, max_file: usize) -> Option<Vec<u8>> {
+    let start = r.pos;
+    for d in pairs.iter_mut().take(max_file + 1) {
+        if d.flags & FLAG_MAPPED == 0 {
+            continue;
+        }
+        if d.flags & FLAG_WIDE != 0 {
+            r.pos += r.pos & 1;
+            for i in 0..4 {
+                d.map_idx[i] = ((r.pos - start) / 2 + 1) as u16;
+                let n = r.u16()? as u64;
+                r.pos += 2 * n;
+            }
+        } else {
+            for i in 0..4 {
+                d.map_idx[i] = (r.pos - start + 1) as u16;
+                let n = r.u8()? as u64;
+                r.pos += n;
+            }
+        }
+    }
+    r.pos += r.pos & 1;
+    let len = (r.pos - start) as usize;
+    let mut head = Reader { src: r.src, pos: start };
+    head.bytes(len)
+}
+
+// Was: Parst den kompletten Header einer Datei (alle Teiltabellen).
+// Warum: Liefert nur Offsets für Sparse-Index, Blocklängen und Daten; die großen Bereiche
+// bleiben in der Quelle und werden je Probe gelesen.
+// Kosten: Einmal je Datei, linear in der Header-Größe.
+fn parse_file(e: &TbEntry, src: &TbSource, dtz: bool) -> Option<Parsed> {
+    let mut r = Reader { src, pos: 4 };
+    let flags = r.u8()?;
+    if (flags & 2 != 0) != e.has_pawns {
+        return None;
+    }
+
+    let sides = if !dtz && e.key != e.key2 { 2 } else { 1 };
+    let max_file = if e.has_pawns { 3 } else { 0 };
+    let pp = e.has_pawns && e.pawn_count[1] > 0;
+    let mut pairs: Vec<Pairs> = (0..8).map(|_| Pairs::default()).collect();
+
+    for f in 0..=max_file {
+        let b0 = r.u8()?;
+        let b1 = if pp { r.u8()? } else { 0 };
+        let order = [
+            [(b0 & 0xF) as usize, if pp { (b1 & 0xF) as usize } else { 0xF }],
+            [(b0 >> 4) as usize, if pp { (b1 >> 4) as usize } else { 0xF }],
+        ];
+        for k in 0..e.piece_count {
+            let byte = r.u8()?;
+            for i in 0..sides {
+                pairs[i * 4 + f].pieces[k] = if i == 1 { byte >> 4 } else { byte & 0xF };
+            }
+        }
+        for (i, side_order) in order.iter().enumerate().take(sides) {
+            set_groups(e, &mut pairs[i * 4 + f], *side_order, f);
+        }
+    }
+    r.align(2);
+
+    for f in 0..=max_file {
+        for i in 0..sides {
+            set_sizes(&mut pairs[i * 4 + f], &mut r)?;
+        }
+    }
+    let map = if dtz { set_dtz_map(&mut pairs, &mut r, max_file)? } else { Vec::new() };
+
+    for f in 0..=max_file {
+        for i in 0..sides {
+            let d = &mut pairs[i * 4 + f];
+            d.sparse_index = r.pos;
+            r.pos += d.sparse_index_size * 6;
+        }
+    }
+    for f in 0..=max_file {
+        for i in 0..sides {
+            let d = &mut pairs[i * 4 + f];
+            d.block_length = r.pos;
+            r.pos += d.block_length_size * 2;
+        }
+    }
+    for f in 0..=max_file {
+        for i in 0..sides {
+            r.align(64);
+            let d = &mut pairs[i * 4 + f];
+            d.data = r.pos;
+            r.pos += d.num_blocks * d.block_size;
+        }
+    }
+    Some(Parsed { pairs, map })
+}
+
+// ---------------------------
+// Dekompression
+// ---------------------------
+
+// Was: Wert an Position `idx` einer Teiltabelle.
+// Warum: Der Sparse-Index führt in die Nähe des Blocks; innerhalb des Blocks werden
+// Huffman-Symbole dekodiert und das Treffer-Symbol per Recursive Pairing expandiert.
+// Kosten: Zwei bis wenige Lesezugriffe plus ein Block (`block_size` Bytes).
+fn decompress_pairs(src: &TbSource, d: &Pairs, idx: u64) -> Option<i32> {
+    if d.flags & FLAG_SINGLE_VALUE != 0 {
+        return Some(d.min_sym_len as i32);
+    }
+
+    let k = idx / d.span;
+    let mut entry = [0u8; 6];
+    if !src.read(d.sparse_index + k * 6, &mut entry) {
+        return None;
+    }
+    let mut block = u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]) as u64;
+    let mut offset = u16::from_le_bytes([entry[4], entry[5]]) as i64;
+    offset += (idx % d.span) as i64 - (d.span / 2) as i64;
+
+    let block_length = |b: u64| -> Option<i64> {
+        let mut buf = [0u8; 2];
+        src.read(d.block_length + b * 2, &mut buf).then(|| u16::from_le_bytes(buf) as i64)
+    };
+    while offset < 0 {
+        block = block.checked_sub(1)?;
+        offset += block_length(block)? + 1;
+    }
+    loop {
+        let len = block_length(block)?;
+        if offset <= len {
+            break;
+        }
+        offset -= len + 1;
+        block += 1;
+    }
+
+    let mut data = vec![0u8; d.block_size as usize + 8];
+    if !src.read(d.data + block * d.block_size, &mut data) {
+        return None;
+    }
+    let mut ptr = 8;
+    let mut buf64 = u64::from_be_bytes(data[..8].try_into().ok()?);
+    let mut buf64_size = 64i32;
+    let min_len = d.min_sym_len as usize;
+
+    let mut sym = loop {
+        let mut len = 0;
+        while buf64 < d.base64[len] {
+            len += 1;
+            if len >= d.base64.len() {
+                return None;
+            }
+        }
+        let shift = 64usize.checked_sub(len + min_len)? as u32;
+        let s = ((buf64 - d.base64[len]).checked_shr(shift).unwrap_or(0) as u16).wrapping_add(d.lowest_sym[len]) as usize;
+        let span = *d.symlen.get(s)? as i64 + 1;
+        if offset < span {
+            break s;
+        }
+        offset -= span;
+        let consumed = len + min_len;
+        buf64 = buf64.checked_shl(consumed as u32).unwrap_or(0);
+        buf64_size -= consumed as i32;
+        if buf64_size <= 32 {
+            buf64_size += 32;
+            let word = data.get(ptr..ptr + 4)?;
+            buf64 |= (u32::from_be_bytes([word[0], word[1], word[2], word[3]]) as u64) << (64 - buf64_size);
+            ptr += 4;
+        }
+    };
+
+    while d.symlen[sym] != 0 {
+        let left = lr_left(d.btree[sym]);
+        let left_span = *d.symlen.get(left)? as i64 + 1;
+        if offset < left_span {
+            sym = left;
+        } else {
+            offset -= left_span;
+            sym = lr_right(d.btree[sym]);
+            d.symlen.get(sym)?;
+        }
+    }
+    Some(lr_left(d.btree[sym]) as i32)
+}
+
+#[inline]
+fn dtz_before_zeroing(wdl: i32) -> i32 {
+    match wdl {
+        2 => 1,
+        1 => 101,
+        -1 => -101,
+        -2 => -1,
+        _ => 0,
+    }
+}
+
+// ---------------------------
+// Öffentliche Schnittstelle
+// ---------------------------
+
+// Geladene Syzygy-Tabellen; nach dem Laden nur noch lesend genutzt (threadsicher).
+#[derive(Default)]
+pub struct Tablebases {
+    entries: Vec<TbEntry>,
+    by_key: HashMap<u64, usize>,
+    max_pieces: u32,
+    wdl_count: usize,
+    dtz_count: usize,
+}
+
+impl Tablebases {
+    pub fn new() -> Self {
+        Tablebases::default()
+    }
+
+    // Was: Lädt alle `.rtbw`/`.rtbz`-Dateien eines Verzeichnisses; liefert die Anzahl
+    // übernommener Dateien.
+    // Warum: Native Frontends (UCI `SyzygyPath`); Dateien bleiben auf der Platte.
+    // Kosten: Ein `open` (plus `mmap`) je Datei; Header werden erst bei der ersten Probe gelesen.
+    #[cfg(any(unix, windows))]
+    pub fn add_directory(&mut self, dir: impl AsRef<std::path::Path>) -> std::io::Result<usize> {
+        let mut added = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            if !(name.ends_with(".rtbw") || name.ends_with(".rtbz")) {
+                continue;
+            }
+            let Ok(file) = std::fs::File::open(&path) else { continue };
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            // Ohne Einblendung (leere Datei, kein Adressraum) wird je Probe gelesen.
+            // SAFETY: Tabellendateien werden nur gelesen; wer sie während der Sitzung
+            // überschreibt, verfälscht Proben, wie bei jedem anderen Prober auch.
+            let source = match unsafe { memmap2::Mmap::map(&file) } {
+                Ok(map) if len > 0 => TbSource::Mapped(map),
+                _ => TbSource::File(file, len),
+            };
+            if self.add_source(name, source) {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    // Was: Übernimmt eine Tabelle als Bytes; `file_name` wie "KQvK.rtbw" bestimmt Material
+    // und Art. Liefert false bei unbekanntem Namen oder falscher Magic.
+    // Warum: wasm hat kein Dateisystem; der Worker reicht `ArrayBuffer`s durch.
+    // Kosten: Konstant (die Bytes werden übernommen, nicht kopiert).
+    pub fn add_table(&mut self, file_name: &str, bytes: Vec<u8>) -> bool {
+        self.add_source(file_name, TbSource::Bytes(bytes))
+    }
+
+    fn add_source(&mut self, file_name: &str, source: TbSource) -> bool {
+        let (stem, dtz) = if let Some(stem) = file_name.strip_suffix(".rtbw") {
+            (stem, false)
+        } else if let Some(stem) = file_name.strip_suffix(".rtbz") {
+            (stem, true)
+        } else {
+            return false;
+        };
+        let Some(material) = parse_material(stem) else { return false };
+        let mut magic = [0u8; 4];
+        if !source.read(0, &mut magic) || magic != if dtz { DTZ_MAGIC } else { WDL_MAGIC } {
+            return false;
+        }
+
+        let index = match self.by_key.get(&material.key) {
+            Some(&index) => index,
+            None => {
+                self.by_key.insert(material.key, self.entries.len());
+                self.by_key.insert(material.key2, self.entries.len());
+                self.entries.push(material);
+                self.entries.len() - 1
+            }
+        };
+        let entry = &mut self.entries[index];
+        let slot = if dtz { &mut entry.dtz } else { &mut entry.wdl };
+        if slot.is_none() {
+            if dtz {
+                self.dtz_count += 1;
+            } else {
+                self.wdl_count += 1;
+                self.max_pieces = self.max_pieces.max(entry.piece_count as u32);
+            }
+        }
+        *slot = Some(TbFile { source, parsed: OnceLock::new() });
+        true
+    }
+
+    // Anzahl geladener WDL- bzw. DTZ-Dateien.
+    pub fn wdl_count(&self) -> usize {
+        self.wdl_count
+    }
+
+    pub fn dtz_count(&self) -> usize {
+        self.dtz_count
+    }
+
+    // Größte Steinzahl (inklusive Könige), für die eine WDL-Tabelle geladen ist; 0 ohne Tabellen.
+    pub fn max_pieces(&self) -> u32 {
+        self.max_pieces
+    }
+
+    // Ob `pos` überhaupt probiert werden kann (Steinzahl, keine Rochaderechte).
+    pub(crate) fn covers(&self, pos: &Position) -> bool {
+        pos.castling == 0 && (popcnt(pos.bb.occ) as u32) <= self.max_pieces
+    }
+
+    // Was: WDL-Wert von `pos` aus Sicht der Seite am Zug; `None`, wenn eine Tabelle fehlt
+    // oder die Stellung nicht abgedeckt ist.
+    // Warum: Schläge werden vorab durchgerechnet, weil die Tabellen En-passant-Rechte und
+    // Stellungen, in denen Schlagen erzwungen ist, nicht korrekt abbilden.
+    // Kosten: Eine Tabellenprobe je Knoten eines kleinen Schlagabtausch-Baums.
+    pub fn probe_wdl(&self, pos: &mut Position) -> Option<Wdl> {
+        if !self.covers(pos) {
+            return None;
+        }
+        self.wdl(pos).map(Wdl::from_raw)
+    }
+
+    // Was: Distanz in Halbzügen bis zum nächsten Zug, der den 50-Züge-Zähler zurücksetzt
+    // (positiv = Gewinn, negativ = Verlust, 0 = Remis); Cursed Wins/Blessed Losses liegen
+    // jenseits von ±100.
+    // Kosten: Wie `probe_wdl`, bei einseitigen DTZ-Tabellen zusätzlich eine Probe je Zug.
+    pub fn probe_dtz(&self, pos: &mut Position) -> Option<i32> {
+        if !self.covers(pos) {
+            return None;
+        }
+        let mut state = ProbeState::Ok;
+        let dtz = self.dtz(pos, &mut state);
+        (state != ProbeState::Fail).then_some(dtz)
+    }
+
+    // Roh-WDL (-2..=2) ohne Abdeckungsprüfung; Aufrufer prüft `covers`.
+    pub(crate) fn wdl(&self, pos: &mut Position) -> Option<i32> {
+        let mut state = ProbeState::Ok;
+        let wdl = self.search(pos, false, &mut state);
+        (state != ProbeState::Fail).then_some(wdl)
+    }
+
+    // Was: Rangfolge der Wurzelzüge aus DTZ (bzw. nur WDL, wenn DTZ fehlt): je Zug
+    // `(rang, score)`; höherer Rang ist besser, gleich gute Gewinne teilen sich den Rang.
+    // `repetition_draws[i]` markiert Züge, die sofort remis durch Wiederholung sind,
+    // `repeated` eine Wiederholung seit dem letzten Bauernzug/Schlag.
+    // Warum: Die Suche spielt dann nur tablebase-optimale Züge (Gewinn bleibt Gewinn,
+    // die 50-Züge-Regel wird beachtet).
+    // Kosten: Eine DTZ-Probe je Wurzelzug.
+    pub(crate) fn rank_root_moves(
+        &self,
+        pos: &mut Position,
+        moves: &[(Move, Option<char>)],
+        repetition_draws: &[bool],
+        repeated: bool,
+    ) -> Option<(Vec<(i32, i32)>, bool)> {
+        if let Some(ranks) = self.root_dtz(pos, moves, repetition_draws, repeated) {
+            return Some((ranks, true));
+        }
+        self.root_wdl(pos, moves, repetition_draws).map(|ranks| (ranks, false))
+    }
+
+    fn root_dtz(
+        &self,
+        pos: &mut Position,
+        moves: &[(Move, Option<char>)],
+        repetition_draws: &[bool],
+        repeated: bool,
+    ) -> Option<Vec<(i32, i32)>> {
+        let cnt50 = pos.halfmove as i32;
+        let bound = MAX_DTZ - 100;
+        let mut ranks = Vec::with_capacity(moves.len());
+        for (i, &(mv, promo)) in moves.iter().enumerate() {
+            let undo = make_move_in_place(pos, mv, promo)?;
+            let mut state = ProbeState::Ok;
+            let mut dtz = if pos.halfmove == 0 {
+                dtz_before_zeroing(-self.search(pos, false, &mut state))
+            } else if repetition_draws.get(i).copied().unwrap_or(false) || pos.halfmove >= 100 {
+                0
+            } else {
+                let dtz = -self.dtz(pos, &mut state);
+                dtz + dtz.signum()
+            };
+            if dtz == 2 && is_in_check(pos, pos.side_to_move) && generate_legal_moves(pos).is_empty() {
+                dtz = 1;
+            }
+            unmake_move_in_place(pos, mv, promo, undo);
+            if state == ProbeState::Fail {
+                return None;
+            }
+
+            let rank = if dtz > 0 {
+                if dtz + cnt50 <= 99 && !repeated { MAX_DTZ } else { MAX_DTZ - (dtz + cnt50) }
+            } else if dtz < 0 {
+                if -dtz * 2 + cnt50 < 100 { -MAX_DTZ } else { -MAX_DTZ + (-dtz + cnt50) }
+            } else {
+                0
+            };
+            // Cursed Wins zeigen wenige cp, die mit sinkendem Abstand zum Gewinn wachsen.
+            let score = if rank >= bound {
+                TB_WIN_SCORE
+            } else if rank > 0 {
+                (rank - (MAX_DTZ - 200)).max(3) * 100 / 200
+            } else if rank == 0 {
+                0
+            } else if rank > -bound {
+                (rank + (MAX_DTZ - 200)).min(-3) * 100 / 200
+            } else {
+                -TB_WIN_SCORE
+            };
+            ranks.push((rank, score));
+        }
+        Some(ranks)
+    }
+
+    fn root_wdl(
+        &self,
+        pos: &mut Position,
+        moves: &[(Move, Option<char>)],
+        repetition_draws: &[bool],
+    ) -> Option<Vec<(i32, i32)>> {
+        const WDL_TO_RANK: [i32; 5] = [-MAX_DTZ, -MAX_DTZ + 101, 0, MAX_DTZ - 101, MAX_DTZ];
+        const WDL_TO_SCORE: [i32; 5] = [-TB_WIN_SCORE, -2, 0, 2, TB_WIN_SCORE];
+        let mut ranks = Vec::with_capacity(moves.len());
+        for (i, &(mv, promo)) in moves.iter().enumerate() {
+            let undo = make_move_in_place(pos, mv, promo)?;
+            let wdl = if repetition_draws.get(i).copied().unwrap_or(false) || pos.halfmove >= 100 {
+                Some(0)
+            } else {
+                self.wdl(pos).map(|w| -w)
+            };
+            unmake_move_in_place(pos, mv, promo, undo);
+            let slot = (wdl? + 2) as usize;
+            ranks.push((WDL_TO_RANK[slot], WDL_TO_SCORE[slot]));
+        }
+        Some(ranks)
+    }
+
+    // Was: WDL mit vorgeschaltetem Schlag-Baum (bei `check_zeroing` auch Bauernzüge).
+    // Warum: Tabellen enthalten keine En-passant-Rechte; ein gewinnender Schlag/Bauernzug
+    // macht den gespeicherten (DTZ-)Wert außerdem irrelevant (`ZeroingBestMove`).
+    // Kosten: Rekursiv über alle Schlagfolgen der Stellung.
+    fn search(&self, pos: &mut Position, check_zeroing: bool, state: &mut ProbeState) -> i32 {
+        let moves = generate_legal_moves(pos);
+        let total = moves.len();
+        let mut searched = 0;
+        let mut best = -2;
+
+        for (mv, promo) in moves {
+            let pawn_move = matches!(pos.board[mv.from as usize], Some('P') | Some('p'));
+            if !(move_is_capture(pos, mv) || (check_zeroing && pawn_move)) {
+                continue;
+            }
+            searched += 1;
+            let Some(undo) = make_move_in_place(pos, mv, promo) else { continue };
+            let value = -self.search(pos, false, state);
+            unmake_move_in_place(pos, mv, promo, undo);
+            if *state == ProbeState::Fail {
+                return 0;
+            }
+            if value > best {
+                best = value;
+                if value >= 2 {
+                    *state = ProbeState::ZeroingBestMove;
+                    return value;
+                }
+            }
+        }
+
+        // Sind alle legalen Züge Schläge, ist der gespeicherte Wert womöglich falsch.
+        let no_more_moves = searched > 0 && searched == total;
+        let value = if no_more_moves {
+            best
+        } else {
+            let value = self.probe_table(pos, false, 0, state);
+            if *state == ProbeState::Fail {
+                return 0;
+            }
+            value
+        };
+
+        if best >= value {
+            *state = if best > 0 || no_more_moves { ProbeState::ZeroingBestMove } else { ProbeState::Ok };
+            return best;
+        }
+        *state = ProbeState::Ok;
+        value
+    }
+
+    fn dtz(&self, pos: &mut Position, state: &mut ProbeState) -> i32 {
+        *state = ProbeState::Ok;
+        let wdl = self.search(pos, true, state);
+        if *state == ProbeState::Fail || wdl == 0 {
+            return 0;
+        }
+        if *state == ProbeState::ZeroingBestMove {
+            return dtz_before_zeroing(wdl);
+        }
+
+        let dtz = self.probe_table(pos, true, wdl, state);
+        match *state {
+            ProbeState::Fail => return 0,
+            ProbeState::ChangeStm => {}
+            _ => return (dtz + if wdl.abs() == 1 { 100 } else { 0 }) * wdl.signum(),
+        }
+
+        // Die Tabelle speichert nur die Gegenseite: ein Halbzug Suche, kleinste passende DTZ.
+        let mut min_dtz = 0xFFFF;
+        for (mv, promo) in generate_legal_moves(pos) {
+            let zeroing = move_is_capture(pos, mv) || matches!(pos.board[mv.from as usize], Some('P') | Some('p'));
+            let Some(undo) = make_move_in_place(pos, mv, promo) else { continue };
+            let mut dtz = if zeroing {
+                -dtz_before_zeroing(self.search(pos, false, state))
+            } else {
+                -self.dtz(pos, state)
+            };
+            if dtz == 1 && is_in_check(pos, pos.side_to_move) && generate_legal_moves(pos).is_empty() {
+                min_dtz = 1;
+            }
+            if !zeroing {
+                dtz += dtz.signum();
+            }
+            if dtz < min_dtz && dtz.signum() == wdl.signum() {
+                min_dtz = dtz;
+            }
+            unmake_move_in_place(pos, mv, promo, undo);
+            if *state == ProbeState::Fail {
+                return 0;
+            }
+        }
+        if min_dtz == 0xFFFF { -1 } else { min_dtz }
+    }
+
+    fn probe_table(&self, pos: &Position, dtz: bool, wdl: i32, state: &mut ProbeState) -> i32 {
+        if popcnt(pos.bb.occ) == 2 {
+            return 0;
+        }
+        let entry = self.by_key.get(&position_key(pos)).map(|&i| &self.entries[i]);
+        let Some((e, file)) = entry.and_then(|e| Some((e, if dtz { e.dtz.as_ref()? } else { e.wdl.as_ref()? })))
+        else {
+            *state = ProbeState::Fail;
+            return 0;
+        };
+        let Some(parsed) = file.parsed.get_or_init(|| parse_file(e, &file.source, dtz)).as_ref() else {
+            *state = ProbeState::Fail;
+            return 0;
+        };
+        match probe_index(e, &file.source, parsed, pos, dtz, wdl) {
+            Ok(value) => value,
+            Err(s) => {
+                *state = s;
+                0
+            }
+        }
+    }
+}
+
+// Was: Berechnet den Tabellenindex von `pos` und dekodiert den gespeicherten Wert.
+// Warum: Symmetrien (Farbtausch, Spiegelungen) und Gruppencodierung bilden jede Stellung
+// auf genau einen Index der Referenz-Tabellen ab.
+// Kosten: Konstant plus eine Dekompression.
+fn probe_index(e: &TbEntry, src: &TbSource, p: &Parsed, pos: &Position, dtz: bool, wdl: i32) -> Result<i32, ProbeState> {
+    let t = index_tables();
+    let mut squares = [0u8; TB_PIECES];
+    let mut pieces = [0u8; TB_PIECES];
+
+    // Tabellen speichern die stärkere Seite als Weiß und bei gleichem Material nur Weiß am
+    // Zug; sonst werden Farben getauscht und Felder vertikal gespiegelt.
+    let black_to_move = pos.side_to_move == Color::Black;
+    let flip = (e.key == e.key2 && black_to_move) || position_key(pos) != e.key;
+    let flip_color = if flip { 8 } else { 0 };
+    let flip_squares = if flip { 56 } else { 0 };
+    let stm = (flip ^ black_to_move) as usize;
+
+    let mut size = 0;
+    let mut lead_count = 0;
+    let mut lead_pawns = 0u64;
+    let mut tb_file = 0;
+    if e.has_pawns {
+        let lead = p.pairs(0, 0, dtz, true).pieces[0] ^ flip_color;
+        lead_pawns = if lead & 8 == 0 { pos.bb.wp } else { pos.bb.bp };
+        let mut b = lead_pawns;
+        while b != 0 {
+            squares[size] = pop_lsb(&mut b) ^ flip_squares;
+            size += 1;
+        }
+        lead_count = size;
+        let mut lead_at = 0;
+        for i in 1..lead_count {
+            if t.map_pawns[squares[i] as usize] > t.map_pawns[squares[lead_at] as usize] {
+                lead_at = i;
+            }
+        }
+        squares.swap(0, lead_at);
+        let file = (squares[0] & 7) as usize;
+        tb_file = file.min(7 - file);
+    }
+
+    if dtz {
+        let flags = p.pairs(stm, tb_file, true, e.has_pawns).flags;
+        if (flags & FLAG_STM) as usize != stm && (e.key != e.key2 || e.has_pawns) {
+            return Err(ProbeState::ChangeStm);
+        }
+    }
+
+    let mut b = pos.bb.occ ^ lead_pawns;
+    while b != 0 {
+        let s = pop_lsb(&mut b);
+        squares[size] = s ^ flip_squares;
+        pieces[size] = piece_code(pos.board[s as usize].ok_or(ProbeState::Fail)?) ^ flip_color;
+        size += 1;
+    }
+
+    let d = p.pairs(stm, tb_file, dtz, e.has_pawns);
+    for i in lead_count..size.saturating_sub(1) {
+        for j in i + 1..size {
+            if d.pieces[i] == pieces[j] {
+                pieces.swap(i, j);
+                squares.swap(i, j);
+                break;
+            }
+        }
+    }
+
+    // Der führende Stein kommt in die Bretthälfte a-d.
+    if squares[0] & 7 > 3 {
+        for sq in &mut squares[..size] {
+            *sq ^= 7;
+        }
+    }
+
+    let mut idx: u64;
+    if e.has_pawns {
+        idx = t.lead_pawn_idx[lead_count][squares[0] as usize];
+        squares[1..lead_count].sort_by_key(|&s| t.map_pawns[s as usize]);
+        for (i, &sq) in squares.iter().enumerate().take(lead_count).skip(1) {
+            idx += t.binomial[i][t.map_pawns[sq as usize]];
+        }
+    } else {
+        if squares[0] >> 3 > 3 {
+            for sq in &mut squares[..size] {
+                *sq ^= 56;
+            }
+        }
+        // Erster Stein der führenden Gruppe abseits der Diagonale a1-h8 kommt unter sie.
+        for i in 0..d.group_len[0] {
+            let off = off_a1h8(squares[i]);
+            if off == 0 {
+                continue;
+            }
+            if off > 0 {
+                for sq in &mut squares[i..size] {
+                    *sq = ((*sq >> 3) | (*sq << 3)) & 63;
+                }
+            }
+            break;
+        }
+
+        let [s0, s1, s2] = [squares[0], squares[1], squares[2]];
+        let rank = |s: u8| (s >> 3) as u64;
+        if e.has_unique_pieces {
+            let adjust1 = (s1 > s0) as u64;
+            let adjust2 = (s2 > s0) as u64 + (s2 > s1) as u64;
+            idx = if off_a1h8(s0) != 0 {
+                (t.map_a1d1d4[s0 as usize] as u64 * 63 + (s1 as u64 - adjust1)) * 62 + s2 as u64 - adjust2
+            } else if off_a1h8(s1) != 0 {
+                (6 * 63 + rank(s0) * 28 + t.map_b1h1h7[s1 as usize]) * 62 + s2 as u64 - adjust2
+            } else if off_a1h8(s2) != 0 {
+                6 * 63 * 62 + 4 * 28 * 62 + rank(s0) * 7 * 28 + (rank(s1) - adjust1) * 28 + t.map_b1h1h7[s2 as usize]
+            } else {
+                6 * 63 * 62 + 4 * 28 * 62 + 4 * 7 * 28 + rank(s0) * 7 * 6 + (rank(s1) - adjust1) * 6 + (rank(s2) - adjust2)
+            };
+        } else {
+            idx = t.map_kk[t.map_a1d1d4[s0 as usize]][s1 as usize];
+        }
+    }
+
+    // Restliche Gruppen: Felder aufsteigend, abzüglich der schon belegten Felder davor.
+    idx *= d.group_idx[0];
+    let mut group_start = d.group_len[0];
+    let mut remaining_pawns = e.has_pawns && e.pawn_count[1] > 0;
+    let mut next = 1;
+    while d.group_len[next] != 0 {
+        let len = d.group_len[next];
+        squares[group_start..group_start + len].sort_unstable();
+        let mut n = 0u64;
+        for i in 0..len {
+            let sq = squares[group_start + i];
+            let adjust = squares[..group_start].iter().filter(|&&s| sq > s).count();
+            let rel = (sq as usize)
+                .checked_sub(adjust + if remaining_pawns { 8 } else { 0 })
+                .ok_or(ProbeState::Fail)?;
+            n += t.binomial[i + 1][rel];
+        }
+        remaining_pawns = false;
+        idx += n * d.group_idx[next];
+        group_start += len;
+        next += 1;
+    }
+
+    let value = decompress_pairs(src, d, idx).ok_or(ProbeState::Fail)?;
+    if !dtz {
+        return Ok(value - 2);
+    }
+    map_dtz(p, tb_file, e.has_pawns, value, wdl).ok_or(ProbeState::Fail)
+}
+
+// Was: Übersetzt einen gespeicherten DTZ-Wert in Halbzüge.
+// Kosten: Konstant.
+fn map_dtz(p: &Parsed, tb_file: usize, has_pawns: bool, value: i32, wdl: i32) -> Option<i32> {
+    const WDL_MAP: [usize; 5] = [1, 3, 0, 2, 0];
+    let d = p.pairs(0, tb_file, true, has_pawns);
+    let mut value = value;
+    if d.flags & FLAG_MAPPED != 0 {
+        let i = d.map_idx[WDL_MAP[(wdl + 2) as usize]] as usize + value as usize;
+        value = if d.flags & FLAG_WIDE != 0 {
+            let b = p.map.get(2 * i..2 * i + 2)?;
+            u16::from_le_bytes([b[0], b[1]]) as i32
+        } else {
+            *p.map.get(i)? as i32
+        };
+    }
+    // Manche Tabellen speichern Züge statt Halbzüge.
+    if (wdl == 2 && d.flags & FLAG_WIN_PLIES == 0) || (wdl == -2 && d.flags & FLAG_LOSS_PLIES == 0) || wdl.abs() == 1 {
+        value *= 2;
+    }
+    Some(value + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endgame::kpk_probe;
+
+    // Gegenprobe der KPvK-Testtabelle mit der KPK-Bitbase der Bewertung: zwei unabhängig
+    // erzeugte Lösungen, verglichen über alle Stellungen und beide Seiten am Zug.
+    #[test]
+    fn kpvk_fixture_matches_kpk_bitbase() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/syzygy/KPvK.rtbw");
+        let mut tb = Tablebases::new();
+        assert!(tb.add_table("KPvK.rtbw", std::fs::read(path).unwrap()));
+
+        let mut checked = 0;
+        for pawn in 8..56u8 {
+            for wk in 0..64u8 {
+                for bk in 0..64u8 {
+                    let near = (wk & 7).abs_diff(bk & 7) <= 1 && (wk >> 3).abs_diff(bk >> 3) <= 1;
+                    if near || wk == pawn || bk == pawn {
+                        continue;
+                    }
+                    let mirror = if pawn & 7 > 3 { 7 } else { 0 };
+                    for white_to_move in [true, false] {
+                        let mut board = [None; 64];
+                        board[wk as usize] = Some('K');
+                        board[bk as usize] = Some('k');
+                        board[pawn as usize] = Some('P');
+                        let fen = board_fen(&board, white_to_move);
+                        let mut pos = parse_fen(&fen).unwrap();
+                        if is_in_check(&pos, pos.side_to_move.opposite()) {
+                            continue;
+                        }
+                        let win = kpk_probe(wk ^ mirror, pawn ^ mirror, bk ^ mirror, white_to_move);
+                        let expected = match (win, white_to_move) {
+                            (false, _) => Wdl::Draw,
+                            (true, true) => Wdl::Win,
+                            (true, false) => Wdl::Loss,
+                        };
+                        assert_eq!(tb.probe_wdl(&mut pos), Some(expected), "{}", fen);
+                        checked += 1;
+                    }
+                }
+            }
+        }
+        assert!(checked > 300_000, "{}", checked);
+    }
+
+    fn board_fen(board: &[Option<char>; 64], white_to_move: bool) -> String {
+        let mut rows = Vec::new();
+        for rank in (0..8).rev() {
+            let mut row = String::new();
+            for file in 0..8 {
+                row.push(board[rank * 8 + file].unwrap_or('1'));
+            }
+            rows.push(row);
+        }
+        format!("{} {} - - 0 1", rows.join("/"), if white_to_move { "w" } else { "b" })
+    }
+}
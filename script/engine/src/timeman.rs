@@ -0,0 +1,120 @@
+// =====================================================================================
+// Zeitmanagement
+// -------------------------------------------------------------------------------------
+// Teilt die Partieuhr (Restzeit, Inkrement, Züge bis zur Zeitkontrolle) in ein weiches
+// und ein hartes Limit pro Zug auf. Das harte Limit bricht die Suche mitten in einer
+// Iteration ab; das weiche entscheidet nach jeder Iteration, ob eine weitere beginnt,
+// und wird mit Bestzug-Stabilität, Score-Einbrüchen und Aspiration-Fehlschlägen skaliert.
+// =====================================================================================
+
+use crate::position::Color;
+
+// Sicherheitsreserve gegen Zeitüberschreitung (GUI-/Pipe-/Worker-Latenz).
+pub const MOVE_OVERHEAD_MS: u64 = 30;
+// Annahme für Sudden-Death-Partien ohne `movestogo`.
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+// Höchstanteil der Restzeit für einen Zug (beim letzten Zug vor der Kontrolle mehr).
+const MAX_TIME_SHARE: f64 = 0.5;
+const LAST_MOVE_TIME_SHARE: f64 = 0.9;
+// Hartes Limit als Vielfaches des weichen.
+const HARD_FACTOR: f64 = 4.0;
+// Grenzen für den Skalierungsfaktor des weichen Limits.
+const MIN_SCALE: f64 = 0.5;
+const MAX_SCALE: f64 = 3.0;
+
+// Stand der Partieuhr in ms; `movestogo == 0` heißt Sudden Death (ggf. mit Inkrement).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GameClock {
+    pub wtime: u64,
+    pub btime: u64,
+    pub winc: u64,
+    pub binc: u64,
+    pub movestogo: u32,
+}
+
+// Zeitlimits eines Zuges: `soft_ms` vor einer neuen Iteration, `hard_ms` jederzeit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimeLimits {
+    pub soft_ms: u64,
+    pub hard_ms: u64,
+}
+
+impl GameClock {
+    // Was: Berechnet weiches und hartes Limit für die Seite `side`.
+    // Warum: Restzeit / erwartete Restzüge + Großteil des Inkrements; wer auf der Uhr vorne
+    // liegt, darf etwas mehr investieren. Nie mehr als ein fester Anteil der Restzeit.
+    // Kosten: Konstant.
+    pub fn limits(&self, side: Color) -> TimeLimits {
+        let (time, inc, opp_time) = match side {
+            Color::White => (self.wtime, self.winc, self.btime),
+            Color::Black => (self.btime, self.binc, self.wtime),
+        };
+        let available = time.saturating_sub(MOVE_OVERHEAD_MS) as f64;
+        let moves_to_go = if self.movestogo > 0 { self.movestogo as u64 } else { DEFAULT_MOVES_TO_GO };
+        let share = if moves_to_go == 1 { LAST_MOVE_TIME_SHARE } else { MAX_TIME_SHARE };
+        let cap = available * share;
+
+        let balance = if opp_time > 0 { (time as f64 / opp_time as f64).clamp(0.8, 1.2) } else { 1.0 };
+        let base = (available / moves_to_go as f64 + inc as f64 * 0.75) * balance;
+        let soft = base.min(cap).max(1.0);
+        let hard = (soft * HARD_FACTOR).min(cap).max(soft);
+        TimeLimits { soft_ms: soft as u64, hard_ms: hard as u64 }
+    }
+}
+
+// Zustand des Zeitmanagements über die Iterationen einer Suche.
+pub(crate) struct TimeManager {
+    soft_ms: f64,
+    hard_ms: f64,
+    prev_best: Option<(u8, u8, Option<char>)>,
+    prev_score: Option<i32>,
+    // Abklingendes Maß für Bestzugwechsel (0 = stabil, bis knapp 2 = wechselt ständig).
+    instability: f64,
+    scale: f64,
+}
+
+impl TimeManager {
+    pub(crate) fn new(limits: TimeLimits) -> Self {
+        TimeManager {
+            soft_ms: limits.soft_ms as f64,
+            hard_ms: limits.hard_ms as f64,
+            prev_best: None,
+            prev_score: None,
+            instability: 0.5,
+            scale: 1.0,
+        }
+    }
+
+    pub(crate) fn hard_ms(&self) -> f64 {
+        self.hard_ms
+    }
+
+    // Aktuelles weiches Limit nach Skalierung, nie über dem harten.
+    pub(crate) fn soft_limit_ms(&self) -> f64 {
+        (self.soft_ms * self.scale).min(self.hard_ms)
+    }
+
+    // Was: Verarbeitet eine abgeschlossene Iteration und skaliert das weiche Limit.
+    // Warum: Wechselnder Bestzug, fallender Score und Aspiration-Fehlschläge deuten auf eine
+    // kritische Stellung (mehr Zeit); ein über Iterationen stabiler Bestzug spart Zeit.
+    // Kosten: Konstant.
+    pub(crate) fn update(&mut self, best: Option<(u8, u8, Option<char>)>, score: i32, aspiration_fails: u32) {
+        let changed = self.prev_best.is_some() && best != self.prev_best;
+        self.instability = self.instability * 0.5 + if changed { 1.0 } else { 0.0 };
+        let best_factor = 0.7 + 0.6 * self.instability;
+
+        let drop = self.prev_score.map_or(0, |prev| prev.saturating_sub(score));
+        let score_factor = 1.0 + (drop as f64 / 100.0).clamp(0.0, 1.0) * 0.8;
+
+        let aspiration_factor = 1.0 + 0.15 * aspiration_fails.min(4) as f64;
+
+        self.scale = (best_factor * score_factor * aspiration_factor).clamp(MIN_SCALE, MAX_SCALE);
+        self.prev_best = best;
+        self.prev_score = Some(score);
+    }
+
+    // Ob nach `elapsed_ms` keine weitere Iteration mehr beginnen soll.
+    pub(crate) fn out_of_time(&self, elapsed_ms: f64) -> bool {
+        elapsed_ms >= self.soft_limit_ms()
+    }
+}
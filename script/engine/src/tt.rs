@@ -0,0 +1,297 @@
+// =====================================================================================
+// Transposition Table
+// -------------------------------------------------------------------------------------
+// Bucket-basierte TT mit Generationszähler. Ein Eintrag ist ein einziges `AtomicU64`
+// (16-Bit-Schlüsselprüfung, gepackter Zug, Score, Tiefe, Bound, Generation), vier
+// Einträge bilden einen 32-Byte-Bucket. Damit ist die Tabelle ohne Locks von mehreren
+// Such-Threads nutzbar (Lazy SMP): Jeder Eintrag wird als Ganzes gelesen/geschrieben.
+// Der Bucket-Index kommt aus den oberen Schlüsselbits (Multiplikation mit der Bucket-Zahl),
+// die Prüfung aus den unteren 16 Bits; so bleiben beide unabhängig, egal wie groß die Tabelle ist.
+// =====================================================================================
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::search::*;
+use crate::position::*;
+
+// TT-Bound-Typen:
+// EXACT = genauer Wert, LOWER = Fail-High-Untergrenze, UPPER = Fail-Low-Obergrenze.
+pub(crate) const TT_BOUND_EXACT: u8 = 0;
+pub(crate) const TT_BOUND_LOWER: u8 = 1;
+pub(crate) const TT_BOUND_UPPER: u8 = 2;
+
+// Größte TT in MB. Im Browser begrenzt der 4-GB-Adressraum von wasm32 die Tabelle.
+#[cfg(not(target_arch = "wasm32"))]
+pub const MAX_TT_MB: u32 = 65536;
+#[cfg(target_arch = "wasm32")]
+pub const MAX_TT_MB: u32 = 1024;
+
+// Die Generation belegt 6 Bit im Eintrag; Vergleiche und Alter rechnen modulo 64.
+const GEN_MASK: u8 = 0x3f;
+
+#[derive(Copy, Clone)]
+pub(crate) struct TTEntry {
+    pub(crate) depth: u16,
+    pub(crate) value: i32,
+    pub(crate) bound: u8,
+    pub(crate) best_from: u8,
+    pub(crate) best_to: u8,
+    pub(crate) best_promo: u8,
+    pub(crate) gen: u8,
+}
+
+impl TTEntry {
+    // Was: Packt den Eintrag mit Schlüsselprüfung `check` in 64 Bit:
+    // check (16) | Zug (16: von 6, nach 6, Umwandlung 3) | Score (i16) | Tiefe (8) | Bound (2) | Gen (6).
+    // Warum: Ein Wort pro Eintrag ist atomar und passt viermal in einen 32-Byte-Bucket.
+    // Kosten: Konstant.
+    fn pack(&self, check: u16) -> u64 {
+        let mv = (self.best_from as u64 & 0x3f) | ((self.best_to as u64 & 0x3f) << 6) | ((self.best_promo as u64 & 0x7) << 12);
+        check as u64
+            | (mv << 16)
+            | ((self.value.clamp(i16::MIN as i32, i16::MAX as i32) as i16 as u16 as u64) << 32)
+            | ((self.depth.min(u8::MAX as u16) as u64) << 48)
+            | (((self.bound & 0x3) as u64) << 56)
+            | (((self.gen & GEN_MASK) as u64) << 58)
+    }
+
+    fn unpack(data: u64) -> TTEntry {
+        let mv = (data >> 16) as u16;
+        TTEntry {
+            best_from: (mv & 0x3f) as u8,
+            best_to: ((mv >> 6) & 0x3f) as u8,
+            best_promo: ((mv >> 12) & 0x7) as u8,
+            value: (data >> 32) as u16 as i16 as i32,
+            depth: ((data >> 48) & 0xff) as u16,
+            bound: ((data >> 56) & 0x3) as u8,
+            gen: (data >> 58) as u8 & GEN_MASK,
+        }
+    }
+}
+
+// Die oberen Bits wählen schon den Bucket (ab 65536 Buckets alle oberen 16); die Prüfung
+// muss deshalb aus den unteren Bits kommen, sonst träfe jeder Eintrag im Bucket.
+#[inline]
+fn key_check(key: u64) -> u16 {
+    key as u16
+}
+
+const TT_BUCKET_SIZE: usize = 4;
+
+// Vier Einträge auf einer halben Cache-Line; 0 = leerer Eintrag.
+#[derive(Default)]
+#[repr(align(32))]
+struct Bucket([AtomicU64; TT_BUCKET_SIZE]);
+
+// Bucket-Transposition-Table mit Ersatzstrategie nach Alter/Tiefe; über `&self`
+// von mehreren Such-Threads gleichzeitig nutzbar.
+pub(crate) struct TT {
+    buckets: Vec<Bucket>,
+}
+
+impl TT {
+    // Was: Tabelle mit so vielen Buckets, wie in `tt_mb` MB passen (höchstens `MAX_TT_MB`).
+    // Warum: Beliebige Größen statt Zweierpotenzen; der Index wird per Multiplikation
+    // auf die Bucket-Zahl abgebildet.
+    // Kosten: Allokation und Nullung der Tabelle.
+    pub(crate) fn new(tt_mb: u32) -> Option<TT> {
+        if tt_mb == 0 {
+            return None;
+        }
+        let bytes = (tt_mb.min(MAX_TT_MB) as usize).saturating_mul(1024 * 1024);
+        let count = bytes / size_of::<Bucket>();
+        if count == 0 {
+            return None;
+        }
+        let buckets: Vec<Bucket> = (0..count).map(|_| Bucket::default()).collect();
+        Some(TT { buckets })
+    }
+
+    // Index = (key * Bucket-Zahl) >> 64, also aus den oberen Schlüsselbits.
+    #[inline]
+    fn bucket(&self, key: u64) -> &Bucket {
+        let idx = ((key as u128 * self.buckets.len() as u128) >> 64) as usize;
+        &self.buckets[idx]
+    }
+
+    // Was: Setzt alle Einträge auf leer zurück, ohne neu zu allozieren.
+    // Warum: Deterministische Suchen dürfen nichts aus früheren Suchen sehen.
+    // Kosten: Linear in der Tabellengröße.
+    pub(crate) fn clear(&self) {
+        for bucket in &self.buckets {
+            for slot in &bucket.0 {
+                slot.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    // Was: Sucht den Eintrag zu `key` im Bucket (Tiefe 0 gilt als leer).
+    // Warum: 16 Prüfbits plus Bucket-Index machen Fehltreffer selten; Züge aus der TT werden
+    // vor Gebrauch ohnehin gegen die legalen Züge geprüft.
+    // Kosten: Vier Relaxed-Loads auf einer Cache-Line.
+    pub(crate) fn probe(&self, key: u64) -> Option<TTEntry> {
+        let check = key_check(key);
+        for slot in &self.bucket(key).0 {
+            let data = slot.load(Ordering::Relaxed);
+            if data == 0 || data as u16 != check {
+                continue;
+            }
+            let entry = TTEntry::unpack(data);
+            if entry.depth != 0 {
+                return Some(entry);
+            }
+        }
+        None
+    }
+
+    // Was: Speichert ein Ergebnis; ersetzt den gleichen Schlüssel (außer bei tieferem Eintrag
+    // aus der laufenden Generation), sonst einen leeren, den ältesten bzw. flachsten Eintrag.
+    // Warum: Alte Suchen weichen zuerst, tiefe Ergebnisse der aktuellen Suche bleiben.
+    // Kosten: Vier Loads und ein Store; parallele Schreiber können sich überschreiben (harmlos).
+    pub(crate) fn store(&self, key: u64, depth: u32, value: i32, bound: u8, best: Option<(Move, Option<char>)>, gen: u8) {
+        let check = key_check(key);
+        let gen = gen & GEN_MASK;
+        let depth_u16 = depth.min(u8::MAX as u32) as u16;
+        let bucket = self.bucket(key);
+
+        let mut replace_idx: Option<usize> = None;
+        let mut oldest_age: u8 = 0;
+        let mut shallowest_depth: u16 = u16::MAX;
+
+        for (i, slot) in bucket.0.iter().enumerate() {
+            let data = slot.load(Ordering::Relaxed);
+            let entry = TTEntry::unpack(data);
+            if data == 0 || entry.depth == 0 {
+                replace_idx = Some(i);
+                break;
+            }
+            if data as u16 == check {
+                if entry.depth > depth_u16 && entry.gen == gen {
+                    return;
+                }
+                replace_idx = Some(i);
+                break;
+            }
+
+            let age = gen.wrapping_sub(entry.gen) & GEN_MASK;
+            if replace_idx.is_none()
+                || age > oldest_age
+                || (age == oldest_age && entry.depth < shallowest_depth)
+            {
+                replace_idx = Some(i);
+                oldest_age = age;
+                shallowest_depth = entry.depth;
+            }
+        }
+        let idx = replace_idx.unwrap_or(0);
+        let (best_from, best_to, best_promo) = if let Some((mv, promo)) = best {
+            (mv.from, mv.to, encode_promo(promo))
+        } else {
+            (0, 0, 0)
+        };
+        let entry = TTEntry {
+            depth: depth_u16,
+            value,
+            bound,
+            best_from,
+            best_to,
+            best_promo,
+            gen,
+        };
+        bucket.0[idx].store(entry.pack(check), Ordering::Relaxed);
+    }
+
+    // Was: Promille der Einträge aus Generation `gen` in einer Stichprobe der ersten 1000 Einträge.
+    // Warum: UCI `hashfull`; zeigt, ob die Hash-Größe zur Bedenkzeit passt.
+    // Kosten: Konstant (höchstens 250 Buckets lesen).
+    pub(crate) fn hashfull(&self, gen: u8) -> u32 {
+        let gen = gen & GEN_MASK;
+        let sample_buckets = self.buckets.len().min(1000 / TT_BUCKET_SIZE);
+        if sample_buckets == 0 {
+            return 0;
+        }
+        let used = self.buckets[..sample_buckets]
+            .iter()
+            .flat_map(|bucket| bucket.0.iter())
+            .map(|slot| slot.load(Ordering::Relaxed))
+            .filter(|&data| data != 0)
+            .map(TTEntry::unpack)
+            .filter(|e| e.depth != 0 && e.gen == gen)
+            .count();
+        (used * 1000 / (sample_buckets * TT_BUCKET_SIZE)) as u32
+    }
+}
+
+// Was: Führt `encode_promo` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn encode_promo(promo: Option<char>) -> u8 {
+    match promo.map(|c| c.to_ascii_lowercase()) {
+        Some('q') => 1,
+        Some('r') => 2,
+        Some('b') => 3,
+        Some('n') => 4,
+        _ => 0,
+    }
+}
+
+// Was: Führt `decode_promo` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn decode_promo(code: u8) -> Option<char> {
+    match code {
+        1 => Some('q'),
+        2 => Some('r'),
+        3 => Some('b'),
+        4 => Some('n'),
+        _ => None,
+    }
+}
+
+// Was: Führt `entry_best_move` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn entry_best_move(entry: TTEntry) -> Option<(u8, u8, Option<char>)> {
+    if entry.best_from == 0 && entry.best_to == 0 && entry.best_promo == 0 {
+        None
+    } else {
+        Some((entry.best_from, entry.best_to, decode_promo(entry.best_promo)))
+    }
+}
+
+#[inline]
+// Was: Führt `tt_cutoff_value` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub(crate) fn tt_cutoff_value(entry: TTEntry, depth: u32, alpha: i32, beta: i32, ply: i32) -> Option<i32> {
+    if (entry.depth as u32) < depth {
+        return None;
+    }
+
+    let val = tt_probe_score(entry.value, ply);
+    match entry.bound {
+        TT_BOUND_EXACT => Some(val),
+        TT_BOUND_LOWER if val >= beta => Some(val),
+        TT_BOUND_UPPER if val <= alpha => Some(val),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_sharing_a_bucket_are_told_apart() {
+        // Gleiche obere 48 Bits -> gleicher Bucket bei jeder Tabellengröße; nur die unteren Bits
+        // unterscheiden sich. Ab 2 MB (65536 Buckets) legt der Bucket die oberen 16 Bits fest.
+        let stored = 0xabcd_1234_5678_0001;
+        let other = 0xabcd_1234_5678_0002;
+        for mb in [1, 2, 16] {
+            let tt = TT::new(mb).unwrap();
+            tt.store(stored, 5, 0, TT_BOUND_EXACT, None, 1);
+            assert!(tt.probe(stored).is_some(), "{} MB", mb);
+            assert!(tt.probe(other).is_none(), "{} MB", mb);
+        }
+    }
+}
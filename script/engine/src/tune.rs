@@ -0,0 +1,87 @@
+// =====================================================================================
+// Tuning-Parameter
+// -------------------------------------------------------------------------------------
+// Einige Bewertungs- und Suchkonstanten sind zur Laufzeit änderbar, damit Selbstspiel
+// (`match-runner`) und UCI-Tuner zwei Einstellungen vergleichen können, ohne neu zu bauen.
+// Die Werte reisen als `TuneValues` in `SearchLimits::tune` mit jeder Suche (auch zu den
+// Lazy-SMP-Helfern); unabhängige Engines im selben Prozess beeinflussen sich daher nicht.
+// Standard sind die bisherigen Konstanten.
+// =====================================================================================
+
+// Ein Parameter mit Name (wie die frühere Konstante), Standardwert und erlaubtem Bereich.
+pub struct TuneParam {
+    pub name: &'static str,
+    pub default: i32,
+    pub min: i32,
+    pub max: i32,
+}
+
+// Indizes in `TUNE_PARAMS` für Eval und Suche.
+pub(crate) const KING_PRESSURE_MG: usize = 0;
+pub(crate) const KING_PRESSURE_EG: usize = 1;
+pub(crate) const PAWN_SHIELD_MG: usize = 2;
+pub(crate) const PAWN_SHIELD_EG: usize = 3;
+pub(crate) const ASP_WINDOW: usize = 4;
+
+const TUNE_PARAM_COUNT: usize = 5;
+
+pub const TUNE_PARAMS: [TuneParam; TUNE_PARAM_COUNT] = [
+    TuneParam { name: "KING_PRESSURE_MG", default: 8, min: 0, max: 40 },
+    TuneParam { name: "KING_PRESSURE_EG", default: 3, min: 0, max: 20 },
+    TuneParam { name: "PAWN_SHIELD_MG", default: 12, min: 0, max: 60 },
+    TuneParam { name: "PAWN_SHIELD_EG", default: 4, min: 0, max: 30 },
+    TuneParam { name: "ASP_WINDOW", default: 50, min: 5, max: 500 },
+];
+
+// Ein vollständiger Satz Parameterwerte; `Default` sind die Standardwerte.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TuneValues {
+    values: [i32; TUNE_PARAM_COUNT],
+}
+
+impl Default for TuneValues {
+    fn default() -> Self {
+        let mut values = [0; TUNE_PARAM_COUNT];
+        for (value, param) in values.iter_mut().zip(&TUNE_PARAMS) {
+            *value = param.default;
+        }
+        TuneValues { values }
+    }
+}
+
+impl TuneValues {
+    #[inline]
+    pub(crate) fn value(&self, index: usize) -> i32 {
+        self.values[index]
+    }
+
+    // Was: Setzt einen Parameter (Name ohne Groß-/Kleinschreibung), begrenzt auf seinen Bereich.
+    // Liefert false bei unbekanntem Namen.
+    pub fn set(&mut self, name: &str, value: i32) -> bool {
+        let Some(index) = param_index(name) else {
+            return false;
+        };
+        let param = &TUNE_PARAMS[index];
+        self.values[index] = value.clamp(param.min, param.max);
+        true
+    }
+
+    // Aktueller Wert eines Parameters oder `None` bei unbekanntem Namen.
+    pub fn get(&self, name: &str) -> Option<i32> {
+        param_index(name).map(|index| self.values[index])
+    }
+
+    // Parameter, die vom Standard abweichen (Name, Wert), in der Reihenfolge von `TUNE_PARAMS`.
+    pub fn changed(&self) -> Vec<(&'static str, i32)> {
+        TUNE_PARAMS
+            .iter()
+            .zip(self.values)
+            .filter(|(param, value)| *value != param.default)
+            .map(|(param, value)| (param.name, value))
+            .collect()
+    }
+}
+
+fn param_index(name: &str) -> Option<usize> {
+    TUNE_PARAMS.iter().position(|p| p.name.eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,1075 @@
+// =====================================================================================
+// WASM-Bindings (Feature `wasm`)
+// -------------------------------------------------------------------------------------
+// Exports für den Browser-Worker (`get_valid_moves`, `apply_move`, `search`, Klasse `Engine`, ...)
+// und die JS-Imports `Date.now` / `globalThis.__engine_progress` / `globalThis.__engine_should_stop`.
+// Stop-Flag und Ponderhit fragt die Suche erst nach `set_stop_polling(true)` ab.
+// Info-Events pro Iteration gehen an den über `set_info_callback` gesetzten Callback.
+// Syzygy-Tabellen kommen als Bytes (`syzygy_add_table`) und gelten für alle Suchen im Worker.
+// Ein Polyglot-Buch (`book_load`) beantwortet `book_probe` / `book_move`.
+// =====================================================================================
+
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::position::*;
+use crate::book::*;
+use crate::engine::*;
+use crate::mate::*;
+use crate::movegen::*;
+use crate::perft::*;
+use crate::pgn::*;
+use crate::position_book::*;
+use crate::san::*;
+use crate::eval::*;
+use crate::search::*;
+use crate::skill::*;
+use crate::smp::*;
+use crate::timeman::*;
+use crate::platform::*;
+use crate::status::*;
+use crate::syzygy::*;
+use crate::tune::*;
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+
+    #[wasm_bindgen(js_namespace = globalThis, js_name = __engine_progress)]
+    fn engine_progress(depth: u32, nodes_completed: f64, nodes_total: f64, elapsed_ms: u32);
+
+    #[wasm_bindgen(js_namespace = globalThis, js_name = __engine_should_stop)]
+    fn engine_should_stop() -> bool;
+
+    #[wasm_bindgen(js_namespace = globalThis, js_name = __engine_ponderhit)]
+    fn engine_ponderhit() -> bool;
+
+    #[wasm_bindgen(js_namespace = globalThis, js_name = __engine_wait_stop)]
+    fn engine_wait_stop(timeout_ms: u32);
+
+    // Beliebige JS-Funktion; Aufruf über `Function.prototype.call`.
+    type InfoCallback;
+
+    #[wasm_bindgen(method, js_name = call)]
+    fn call(this: &InfoCallback, this_arg: &JsValue, info_json: &str);
+}
+
+// Uhr des Browser-Workers. Auf dem Host (Feature `wasm` ist Default, z. B. bei
+// `cargo test`) gibt es `Date.now` nicht, dort springt die Systemuhr ein.
+struct JsClock;
+
+impl Clock for JsClock {
+    #[cfg(target_arch = "wasm32")]
+    fn now_ms(&self) -> f64 {
+        date_now()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn now_ms(&self) -> f64 {
+        SystemClock.now_ms()
+    }
+}
+
+// Leitet den Suchfortschritt an `globalThis.__engine_progress` (Timebar im UI) weiter
+// und fragt Stop-Wunsch und Ponderhit über `globalThis.__engine_should_stop` bzw.
+// `globalThis.__engine_ponderhit` ab. Der Worker liest dort SharedArrayBuffer-Flags,
+// weil er während der Suche keine Nachrichten empfangen kann. Ohne solche Flags
+// (`set_stop_polling(false)`, Standard) spart die Suche sich die JS-Aufrufe ganz.
+struct JsProgress;
+
+impl ProgressSink for JsProgress {
+    #[cfg(target_arch = "wasm32")]
+    fn progress(&mut self, depth: u32, nodes_completed: u64, nodes_total: u64, elapsed_ms: u32) {
+        engine_progress(depth, nodes_completed as f64, nodes_total as f64, elapsed_ms);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn stop_requested(&mut self) -> bool {
+        stop_polling() && engine_should_stop()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn ponderhit(&mut self) -> bool {
+        stop_polling() && engine_ponderhit()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn iteration(&mut self, info: &SearchInfo) {
+        INFO_CALLBACK.with(|cb| {
+            if let Some(callback) = cb.borrow().as_ref() {
+                callback.unchecked_ref::<InfoCallback>().call(&JsValue::NULL, &search_info_json(info));
+            }
+        });
+    }
+}
+
+thread_local! {
+    static ROOT_EVAL_DEBUG: Cell<bool> = const { Cell::new(false) };
+    static INFO_CALLBACK: RefCell<Option<JsValue>> = const { RefCell::new(None) };
+    static SKILL_LEVEL: Cell<Option<u32>> = const { Cell::new(None) };
+    static THREADS: Cell<u32> = const { Cell::new(1) };
+    static STOP_POLLING: Cell<bool> = const { Cell::new(false) };
+    static TABLEBASES: RefCell<Option<Arc<Tablebases>>> = const { RefCell::new(None) };
+    static BOOK: RefCell<Option<PolyglotBook>> = const { RefCell::new(None) };
+    static POSITION_BOOK: RefCell<PositionBook> = RefCell::new(PositionBook::default());
+}
+
+// Geladene Syzygy-Tabellen für die Limits der nächsten Suche.
+fn current_tablebases() -> Option<Arc<Tablebases>> {
+    TABLEBASES.with(|tb| tb.borrow().clone())
+}
+
+// Thread-Zahl aus `set_threads` für die Limits der nächsten Suche.
+fn current_threads() -> u32 {
+    THREADS.with(|t| t.get())
+}
+
+// Ob der Worker Stop-/Ponderhit-Flags hat (siehe `set_stop_polling`).
+#[cfg(target_arch = "wasm32")]
+fn stop_polling() -> bool {
+    STOP_POLLING.with(|v| v.get())
+}
+
+// Wartezeit je `__engine_wait_stop`-Aufruf; Stop und Ponderhit wecken den Worker sofort.
+#[cfg(target_arch = "wasm32")]
+const STOP_WAIT_MS: u32 = 100;
+
+// Was: Hält eine unendliche Suche (bzw. eine Ponder-Suche ohne Ponderhit) nach ihrer letzten
+// Iteration an, bis das Stop-Flag (bzw. der Ponderhit) gesetzt ist.
+// Warum: Wie UCI `go infinite`: Das Ergebnis kommt erst auf Stop, auch wenn die Suche die
+// Maximaltiefe schon erreicht hat; ohne Stop-Flags gibt es nichts zu warten.
+// Kosten: Der Worker schläft in `Atomics.wait` (`__engine_wait_stop`), statt zu rechnen.
+#[cfg(target_arch = "wasm32")]
+fn wait_for_stop(limits: &SearchLimits) {
+    let waiting = (limits.infinite || limits.ponder) && stop_polling();
+    while waiting && !engine_should_stop() && !(limits.ponder && engine_ponderhit()) {
+        engine_wait_stop(STOP_WAIT_MS);
+    }
+}
+
+// Auf dem Host (Tests) gibt es keine Stop-Flags, also nichts zu warten.
+#[cfg(not(target_arch = "wasm32"))]
+fn wait_for_stop(_limits: &SearchLimits) {}
+
+// WASM-Export: übernimmt eine Syzygy-Datei (z. B. "KRvK.rtbw") aus einem `ArrayBuffer`.
+#[wasm_bindgen]
+// Was: Fügt die Tabelle den Datenbanken aller folgenden Suchen hinzu; false bei unbekanntem
+// Dateinamen, falscher Magic oder während einer laufenden Suche.
+// Warum: Im Browser gibt es kein Dateisystem; das UI lädt die Dateien selbst.
+// Kosten: Übernahme der Bytes (wasm-bindgen kopiert sie einmal in den WASM-Speicher).
+pub fn syzygy_add_table(name: &str, bytes: Vec<u8>) -> bool {
+    TABLEBASES.with(|tb| {
+        let mut slot = tb.borrow_mut();
+        let tablebases = slot.get_or_insert_with(|| Arc::new(Tablebases::new()));
+        Arc::get_mut(tablebases).is_some_and(|t| t.add_table(name, bytes))
+    })
+}
+
+// WASM-Export: entlädt alle Syzygy-Tabellen.
+#[wasm_bindgen]
+pub fn syzygy_clear() {
+    TABLEBASES.with(|tb| *tb.borrow_mut() = None);
+}
+
+// WASM-Export: `{"wdl":N,"dtz":N,"max_pieces":N}` der geladenen Tabellen.
+#[wasm_bindgen]
+pub fn syzygy_info() -> String {
+    let (wdl, dtz, max_pieces) = current_tablebases()
+        .map_or((0, 0, 0), |tb| (tb.wdl_count(), tb.dtz_count(), tb.max_pieces()));
+    format!("{{\"wdl\":{},\"dtz\":{},\"max_pieces\":{}}}", wdl, dtz, max_pieces)
+}
+
+// WASM-Export: lädt ein Polyglot-Buch (`.bin` als `ArrayBuffer`) und ersetzt das bisherige.
+// Liefert die Anzahl der Einträge (0: leer oder kein gültiges Buch).
+#[wasm_bindgen]
+pub fn book_load(bytes: Vec<u8>) -> u32 {
+    let book = PolyglotBook::from_bytes(&bytes);
+    let len = book.len() as u32;
+    BOOK.with(|b| *b.borrow_mut() = (len > 0).then_some(book));
+    len
+}
+
+// WASM-Export: entlädt das Polyglot-Buch.
+#[wasm_bindgen]
+pub fn book_clear() {
+    BOOK.with(|b| *b.borrow_mut() = None);
+}
+
+// WASM-Export: alle Buchzüge der Stellung als JSON `[{"uci":..,"weight":..,"learn":..}, ...]`.
+#[wasm_bindgen]
+pub fn book_probe(fen: &str) -> String {
+    let moves = BOOK.with(|b| b.borrow().as_ref().and_then(|book| book.probe(fen))).unwrap_or_default();
+    let items: Vec<String> = moves
+        .iter()
+        .map(|m| format!("{{\"uci\":\"{}\",\"weight\":{},\"learn\":{}}}", m.uci, m.weight, m.learn))
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+// WASM-Export: Buchzug (UCI) nach `selection` ("best", "weighted", "random"), sonst "".
+#[wasm_bindgen]
+pub fn book_move(fen: &str, selection: &str) -> String {
+    let selection = BookSelection::parse(selection).unwrap_or(BookSelection::Weighted);
+    let seed = JsClock.now_ms().to_bits();
+    BOOK.with(|b| b.borrow().as_ref().and_then(|book| book.book_move(fen, selection, seed))).unwrap_or_default()
+}
+
+// WASM-Export: lädt ein stellungsbasiertes Buch (Binärformat von `PositionBook::to_bytes`)
+// und ersetzt das bisherige. Liefert die Anzahl der Einträge (0: leer oder ungültig).
+#[wasm_bindgen]
+pub fn positionbook_load(bytes: Vec<u8>) -> u32 {
+    let book = PositionBook::from_bytes(&bytes).unwrap_or_default();
+    let len = book.len() as u32;
+    POSITION_BOOK.with(|b| *b.borrow_mut() = book);
+    len
+}
+
+// WASM-Export: fügt ein history-basiertes JSON-Buch hinzu (Text wie `opening_book_1000.json`).
+// Liefert die Anzahl der übernommenen Stellungen; 0 bei ungültigem JSON.
+#[wasm_bindgen]
+pub fn positionbook_add_json(json: &str) -> u32 {
+    POSITION_BOOK.with(|b| b.borrow_mut().merge_history_json(json).unwrap_or(0) as u32)
+}
+
+// WASM-Export: entlädt das stellungsbasierte Buch.
+#[wasm_bindgen]
+pub fn positionbook_clear() {
+    POSITION_BOOK.with(|b| *b.borrow_mut() = PositionBook::default());
+}
+
+// WASM-Export: Buchzüge der Stellung als JSON `[{"uci":..,"weight":..}, ...]`, Gewicht in 1/10000.
+#[wasm_bindgen]
+pub fn positionbook_probe(fen: &str) -> String {
+    let moves = POSITION_BOOK.with(|b| b.borrow().probe(fen)).unwrap_or_default();
+    let items: Vec<String> =
+        moves.iter().map(|m| format!("{{\"uci\":\"{}\",\"weight\":{}}}", m.uci, m.weight)).collect();
+    format!("[{}]", items.join(","))
+}
+
+// ---------------------------
+// WASM Exports
+// ---------------------------
+#[wasm_bindgen]
+// Was: Führt `set_root_eval_debug` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn set_root_eval_debug(flag: bool) {
+    ROOT_EVAL_DEBUG.with(|v| v.set(flag));
+}
+
+// WASM-Export: Callback für Info-Events der Suche; alles außer einer Funktion schaltet ab.
+// Der Callback erhält einen JSON-String (siehe `search_info_json`).
+#[wasm_bindgen]
+// Was: Merkt sich die JS-Funktion für `JsProgress::iteration`.
+// Warum: Das UI wählt selbst, wohin Tiefe/Score/PV während der Suche gehen, statt eines festen Globals.
+// Kosten: Konstant.
+pub fn set_info_callback(callback: JsValue) {
+    let callback = callback.is_function().then_some(callback);
+    INFO_CALLBACK.with(|cb| *cb.borrow_mut() = callback);
+}
+
+// WASM-Export: Spielstärke für folgende Suchen; negative Werte oder die Höchststufe
+// bedeuten volle Stärke.
+#[wasm_bindgen]
+// Was: Setzt die Skill-Stufe, die `search_impl` in die Limits übernimmt.
+// Warum: Schwächere Gegner im UI, ohne jede Such-Signatur zu erweitern.
+// Kosten: Konstant.
+pub fn set_skill_level(level: i32) {
+    SKILL_LEVEL.with(|v| v.set(skill_from_js(level)));
+}
+
+// WASM-Export: meldet, ob der Worker Stop-/Ponderhit-Flags hat (SharedArrayBuffer).
+#[wasm_bindgen]
+// Was: Schaltet die Abfrage von `__engine_should_stop` / `__engine_ponderhit` während der Suche ein.
+// Warum: Ohne Flags kann niemand abbrechen; dann kostet der JS-Aufruf alle paar hundert Knoten nur Zeit.
+// Kosten: Konstant.
+pub fn set_stop_polling(enabled: bool) {
+    STOP_POLLING.with(|v| v.set(enabled));
+}
+
+// WASM-Export: Such-Threads (Lazy SMP) inklusive Haupt-Thread für folgende Suchen.
+#[wasm_bindgen]
+// Was: Setzt die Thread-Zahl, die `search_json` in die Limits übernimmt; liefert die Zahl,
+// die mit den bisher angemeldeten Helfer-Workern tatsächlich läuft.
+// Warum: Nur der Thread-Build (`+atomics`) mit gestarteten Helfer-Workern sucht parallel;
+// sonst bleibt es bei einem Thread, ohne dass das UI den Build kennen muss.
+// Kosten: Konstant.
+pub fn set_threads(threads: u32) -> u32 {
+    let threads = threads.clamp(1, MAX_THREADS);
+    THREADS.with(|t| t.set(threads));
+    threads.min(helper_capacity() as u32 + 1)
+}
+
+// WASM-Export: Einstieg eines Helfer-Workers (siehe `script/worker/smpWorker.js`).
+#[wasm_bindgen]
+// Was: Meldet den Worker als Lazy-SMP-Helfer an und kehrt im Thread-Build nie zurück;
+// ohne Atomics (kein gemeinsamer Speicher) kehrt er sofort zurück.
+// Kosten: Blockiert den aufrufenden Worker.
+pub fn smp_helper_main() {
+    #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+    helper_worker_main();
+}
+
+// Negative Werte und die Höchststufe sind volle Stärke (`None`).
+fn skill_from_js(level: i32) -> Option<u32> {
+    u32::try_from(level).ok().filter(|&l| l < MAX_SKILL_LEVEL)
+}
+
+// WASM-Export: Kalibrierungstabelle als JSON, z. B. `[{"level":0,"elo":800},...]`.
+#[wasm_bindgen]
+// Was: Listet alle Stufen mit ihrer ungefähren Elo.
+// Warum: Das UI bietet Elo-Werte an und bildet sie auf Stufen ab.
+// Kosten: Linear in der Stufenzahl.
+pub fn skill_levels() -> String {
+    let entries: Vec<String> = (0..=MAX_SKILL_LEVEL)
+        .map(|level| format!("{{\"level\":{},\"elo\":{}}}", level, skill_level_elo(level)))
+        .collect();
+    format!("[{}]", entries.join(","))
+}
+
+// WASM-Export: liefert alle legalen Ziel-Felder für die Figur auf `field`.
+// Berücksichtigt Schach, Rochade und En-passant.
+// Gibt eine Liste von Feldindizes (0..63) zurück.
+#[wasm_bindgen]
+// Was: Führt `get_valid_moves` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn get_valid_moves(fen: &str, field: u8) -> Vec<u8> {
+    // Liefert nur legale Ziele (inkl. Rochade/En-passant)
+    let mut pos = match parse_fen(fen) {
+        Some(p) => p,
+        None => return Vec::new(),
+    };
+
+    if field >= 64 {
+        return Vec::new();
+    }
+
+    let piece = match pos.board[field as usize] {
+        Some(p) => p,
+        None => return Vec::new(),
+    };
+
+    let color = piece_color(piece);
+    if color != pos.side_to_move {
+        return Vec::new();
+    }
+
+    let mut moves: Vec<Move> = Vec::with_capacity(32);
+    generate_moves_for_piece_into(&pos, field, &mut moves);
+    let mut legal = Vec::new();
+    for mv in moves {
+        if is_move_legal(&mut pos, mv, color) {
+            legal.push(mv.to);
+        }
+    }
+    legal
+}
+
+// WASM-Export: wendet einen legalen Zug an und gibt die neue FEN zurück.
+// Prüft Legalität (inkl. Schach) und lehnt ungültige Züge ab.
+// `promotion` ist optional (Q/R/B/N), Standard ist Queen.
+#[wasm_bindgen]
+// Was: Führt `apply_move` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn apply_move(fen: &str, from: u8, to: u8, promotion: &str) -> String {
+    // Illegaler Zug? -> ursprüngliche FEN zurückgeben
+    let mut pos = match parse_fen(fen) {
+        Some(p) => p,
+        None => return fen.to_string(),
+    };
+
+    if from >= 64 || to >= 64 {
+        return fen.to_string();
+    }
+
+    let mv = match find_legal_move(&mut pos, from, to) {
+        Some(m) => m,
+        None => return fen.to_string(),
+    };
+
+    let piece = match pos.board[from as usize] {
+        Some(p) => p,
+        None => return fen.to_string(),
+    };
+
+    let color = piece_color(piece);
+
+    // Eventuell geschlagene Figur (inkl. En passant)
+    let captured_piece = match mv.kind {
+        MoveKind::EnPassant => {
+            let cap_sq = if color == Color::White { to - 8 } else { to + 8 };
+            pos.board[cap_sq as usize]
+        }
+        _ => pos.board[to as usize],
+    };
+
+    let promo_char = promotion.chars().next();
+    apply_move_to_board(&mut pos.board, mv, color, promo_char);
+
+    // Rochaderechte aktualisieren
+    let mut castling = pos.castling;
+    if piece.eq_ignore_ascii_case(&'k') {
+        if color == Color::White {
+            castling &= !(CASTLE_WK | CASTLE_WQ);
+        } else {
+            castling &= !(CASTLE_BK | CASTLE_BQ);
+        }
+    }
+    if piece.eq_ignore_ascii_case(&'r') {
+        match from {
+            0 => castling &= !CASTLE_WQ,
+            7 => castling &= !CASTLE_WK,
+            56 => castling &= !CASTLE_BQ,
+            63 => castling &= !CASTLE_BK,
+            _ => {}
+        }
+    }
+    if let Some(cap) = captured_piece {
+        if cap.eq_ignore_ascii_case(&'r') {
+            match to {
+                0 => castling &= !CASTLE_WQ,
+                7 => castling &= !CASTLE_WK,
+                56 => castling &= !CASTLE_BQ,
+                63 => castling &= !CASTLE_BK,
+                _ => {}
+            }
+        }
+    }
+
+    // En-passant Ziel aktualisieren
+    let mut new_ep = None;
+    if piece.eq_ignore_ascii_case(&'p') {
+        let from_rank = from / 8;
+        let to_rank = to / 8;
+        if color == Color::White && from_rank == 1 && to_rank == 3 {
+            new_ep = Some(from + 8);
+        } else if color == Color::Black && from_rank == 6 && to_rank == 4 {
+            new_ep = Some(from - 8);
+        }
+    }
+
+    // Halbzugzähler
+    let mut halfmove = pos.halfmove;
+    let is_capture = match mv.kind {
+        MoveKind::EnPassant => true,
+        _ => captured_piece.is_some(),
+    };
+    if piece.eq_ignore_ascii_case(&'p') || is_capture {
+        halfmove = 0;
+    } else {
+        halfmove += 1;
+    }
+
+    // Vollzugnummer
+    let mut fullmove = pos.fullmove;
+    if pos.side_to_move == Color::Black {
+        fullmove += 1;
+    }
+
+    let new_board_part = board_to_fen(&pos.board);
+    let new_side = if pos.side_to_move == Color::White { "b" } else { "w" };
+    let new_castling = castling_to_string(castling);
+    let new_ep_str = match new_ep {
+        Some(sq) => field_to_lan(sq),
+        None => "-".to_string(),
+    };
+
+    format!(
+        "{} {} {} {} {} {}",
+        new_board_part, new_side, new_castling, new_ep_str, halfmove, fullmove
+    )
+}
+
+// Was: Führt `root_eval_breakdown_json` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn root_eval_breakdown_json(pos: &mut Position) -> String {
+    let mut moves = Vec::new();
+    generate_legal_moves_into(pos, &mut moves);
+
+    let mut out = String::new();
+    out.push('[');
+    let mut first = true;
+
+    for (mv, promo) in moves.iter().copied() {
+        let Some(undo) = make_move_in_place(pos, mv, promo) else { continue; };
+        let bd = negate_breakdown(evaluate_breakdown(pos, &TuneValues::default()));
+        unmake_move_in_place(pos, mv, promo, undo);
+
+        if !first {
+            out.push(',');
+        }
+        first = false;
+
+        let uci = move_to_uci(mv, promo);
+        out.push_str(&format!(
+            "{{\"move\":\"{}\",\"total\":{},\"material\":{},\"pst\":{},\"pawn\":{},\"king\":{},\"misc\":{}}}",
+            uci, bd.total, bd.material, bd.pst, bd.pawn, bd.king, bd.misc
+        ));
+    }
+
+    out.push(']');
+    out
+}
+
+// Was: Führt `search_impl` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+fn search_impl(fen: &str, depth: u32, time_ms: u32, tt_mb: u32, history: &str, multipv: u32) -> String {
+    let limits = SearchLimits {
+        depth,
+        time_ms,
+        tt_mb,
+        multipv,
+        skill_level: SKILL_LEVEL.with(|v| v.get()),
+        ..SearchLimits::default()
+    };
+    search_json(fen, history, &limits)
+}
+
+// Was: Führt `run_search` mit fertigen Limits aus und serialisiert das Ergebnis.
+// Warum: Zentraler Such-Entry für alle Such-Exports (begrenzt und unendlich).
+// Kosten: Die Suche selbst plus ein JSON-Eintrag pro PV-Zeile.
+fn search_json(fen: &str, history: &str, limits: &SearchLimits) -> String {
+    let limits = SearchLimits { tablebases: current_tablebases(), threads: current_threads(), ..limits.clone() };
+    let result = run_search(fen, history, &limits, &JsClock, &mut JsProgress);
+    wait_for_stop(&limits);
+    search_result_json(fen, result)
+}
+
+// Baut aus dem Suchergebnis den JSON-String für den Worker (`None` = ungültige FEN).
+fn search_result_json(fen: &str, result: Option<SearchResult>) -> String {
+    let Some(result) = result else {
+        return "{\"error\":\"invalid fen\"}".to_string();
+    };
+
+    let debug_root = ROOT_EVAL_DEBUG.with(|v| v.get());
+    let root_eval_field = match parse_fen(fen) {
+        Some(mut pos) if debug_root => {
+            let root_json = root_eval_breakdown_json(&mut pos);
+            format!(",\"root_eval\":{}", root_json)
+        }
+        _ => String::new(),
+    };
+
+    let mut out = format!(
+        "{{\"depth\":{},\"nodes\":{},\"nodes_completed\":{},\"time_ms\":{},\"nps\":{},\"score\":{},\"best\":\"{}\",\"pv\":\"{}\",\"rep_avoid\":{},\"tbhits\":{},\"lines\":[{}]}}",
+        result.depth,
+        result.nodes,
+        result.nodes_completed,
+        result.time_ms,
+        result.nps,
+        result.score,
+        result.best.unwrap_or_default(),
+        result.pv.join(" "),
+        result.rep_avoid,
+        result.tbhits,
+        pv_lines_json(&result.lines)
+    );
+    out.push_str(&root_eval_field);
+    out
+}
+
+// Eine Variante pro Eintrag, `best` ist jeweils der erste PV-Zug (ohne äußere Klammern).
+fn pv_lines_json(lines: &[PvLine]) -> String {
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            format!(
+                "{{\"multipv\":{},\"depth\":{},\"score\":{},\"best\":\"{}\",\"pv\":\"{}\"}}",
+                line.multipv,
+                line.depth,
+                line.score,
+                line.pv.first().map(String::as_str).unwrap_or_default(),
+                line.pv.join(" ")
+            )
+        })
+        .collect();
+    lines.join(",")
+}
+
+// Was: Serialisiert einen Info-Event für den JS-Callback.
+// Warum: `mate` (Züge bis Matt, sonst null) spart dem UI die Umrechnung des Mattscores.
+// Kosten: Linear in PV-Länge und Variantenzahl.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn search_info_json(info: &SearchInfo) -> String {
+    let mate = mate_in_moves(info.score).map_or("null".to_string(), |n| n.to_string());
+    format!(
+        "{{\"depth\":{},\"seldepth\":{},\"score\":{},\"mate\":{},\"bound\":\"{}\",\"best\":\"{}\",\"pv\":\"{}\",\"nodes\":{},\"time_ms\":{},\"nps\":{},\"hashfull\":{},\"tbhits\":{},\"lines\":[{}]}}",
+        info.depth,
+        info.seldepth,
+        info.score,
+        mate,
+        info.bound.as_str(),
+        info.best.as_deref().unwrap_or_default(),
+        info.pv.join(" "),
+        info.nodes,
+        info.time_ms,
+        info.nps,
+        info.hashfull,
+        info.tbhits,
+        pv_lines_json(&info.lines)
+    )
+}
+
+// WASM-Export: einfache Suche (Alpha-Beta, Material-Eval).
+// Rückgabe ist ein JSON-String für den Worker.
+#[wasm_bindgen]
+// Was: Führt `search` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn search(fen: &str, depth: u32, time_ms: u32, tt_mb: u32) -> String {
+    search_impl(fen, depth, time_ms, tt_mb, "", 1)
+}
+
+// WASM-Export: Suche mit History für echte Repetition-Erkennung.
+#[wasm_bindgen]
+// Was: Führt `search_with_history` aus und kapselt einen klar abgegrenzten Engine-Teilschritt.
+// Warum: Hält die Gesamtlogik modular, nachvollziehbar und für Optimierungen isolierbar.
+// Kosten: Laufzeit ist kontextabhängig und wird durch Aufruftiefe/Branching bestimmt.
+pub fn search_with_history(fen: &str, depth: u32, time_ms: u32, tt_mb: u32, history: &str) -> String {
+    search_impl(fen, depth, time_ms, tt_mb, history, 1)
+}
+
+// WASM-Export: Suche mit History, die die besten `multipv` Wurzelzüge liefert.
+#[wasm_bindgen]
+// Was: Wie `search_with_history`, zusätzlich mit Multi-PV-Anzahl.
+// Warum: Analyseansicht zeigt mehrere Kandidatenzüge mit eigener Bewertung und PV.
+// Kosten: Etwa `multipv`-mal eine Suche pro Iteration (ausgeschlossene Züge werden neu gesucht).
+pub fn search_multipv(fen: &str, depth: u32, time_ms: u32, tt_mb: u32, history: &str, multipv: u32) -> String {
+    search_impl(fen, depth, time_ms, tt_mb, history, multipv)
+}
+
+// WASM-Export: Suche mit Partieuhr (Restzeiten, Inkremente in ms, `movestogo` 0 = Sudden Death).
+#[wasm_bindgen]
+// Was: Wie `search_multipv`, aber das Zeitmanagement teilt die Uhr in weiches/hartes Limit auf.
+// Warum: Das UI muss keine eigene Zeiteinteilung erfinden; Inkrement-Partien geraten nicht in Zeitnot.
+// Kosten: Suche bis zum weichen Limit (skaliert nach Stabilität/Score/Aspiration), höchstens das harte.
+#[allow(clippy::too_many_arguments)]
+pub fn search_clock(
+    fen: &str,
+    history: &str,
+    tt_mb: u32,
+    wtime: u32,
+    btime: u32,
+    winc: u32,
+    binc: u32,
+    movestogo: u32,
+    multipv: u32,
+) -> String {
+    let limits = SearchLimits {
+        tt_mb,
+        multipv,
+        game_clock: Some(GameClock {
+            wtime: wtime as u64,
+            btime: btime as u64,
+            winc: winc as u64,
+            binc: binc as u64,
+            movestogo,
+        }),
+        skill_level: SKILL_LEVEL.with(|v| v.get()),
+        ..SearchLimits::default()
+    };
+    search_json(fen, history, &limits)
+}
+
+// WASM-Export: Reproduzierbare Suche bis `depth` und/oder `nodes` (0 = unbegrenzt), ohne Zeitlimit.
+#[wasm_bindgen]
+// Was: Sucht mit `SearchLimits::deterministic`; gleiche Eingaben liefern bit-identische Ergebnisse.
+// Warum: Regressionstests und Vergleiche im UI, unabhängig von Uhr und vorherigen Suchen.
+// Kosten: Leeren der TT pro Aufruf plus Suche bis Tiefen-/Knotenlimit.
+pub fn search_deterministic(fen: &str, history: &str, tt_mb: u32, depth: u32, nodes: f64, multipv: u32) -> String {
+    let limits = SearchLimits {
+        depth,
+        nodes: nodes.max(0.0) as u64,
+        tt_mb,
+        multipv,
+        deterministic: true,
+        skill_level: SKILL_LEVEL.with(|v| v.get()),
+        ..SearchLimits::default()
+    };
+    search_json(fen, history, &limits)
+}
+
+// WASM-Export: Suche nur über die Wurzelzüge in `moves` (UCI, leerzeichengetrennt).
+// Mit `each` enthält `lines` je legalem Zug eine eigene Bewertung samt PV.
+#[wasm_bindgen]
+// Was: `searchmoves`-Suche bzw. `score_root_moves` mit Tiefe/Zeit/TT wie `search_with_history`.
+// Warum: "Was wäre, wenn ich das spiele?"-Analyse im UI.
+// Kosten: Wie eine normale Suche; mit `each` wie Multi-PV über alle gelisteten Züge.
+#[allow(clippy::too_many_arguments)]
+pub fn search_moves(fen: &str, history: &str, depth: u32, time_ms: u32, tt_mb: u32, moves: &str, each: bool) -> String {
+    let moves: Vec<String> = moves.split_whitespace().map(str::to_string).collect();
+    let limits = SearchLimits {
+        depth,
+        time_ms,
+        tt_mb,
+        skill_level: SKILL_LEVEL.with(|v| v.get()),
+        tablebases: current_tablebases(),
+        threads: current_threads(),
+        ..SearchLimits::default()
+    };
+    let result = if each {
+        score_root_moves(fen, history, &moves, &limits, &JsClock, &mut JsProgress)
+    } else {
+        let limits = SearchLimits { searchmoves: moves, ..limits };
+        run_search(fen, history, &limits, &JsClock, &mut JsProgress)
+    };
+    search_result_json(fen, result)
+}
+
+// WASM-Export: Analysemodus, läuft bis `__engine_should_stop` true liefert (braucht
+// `set_stop_polling(true)`, sonst endet sie nach der Maximaltiefe).
+#[wasm_bindgen]
+// Was: Sucht ohne Tiefen-/Zeitlimit und ohne Matt-Frühabbruch.
+// Warum: Endlose Analyse im UI; Ergebnis ist Bestzug und PV der letzten abgeschlossenen Iteration.
+// Kosten: Läuft bis zum Stop; nach der Maximaltiefe (64) wartet sie ohne Rechenlast darauf.
+pub fn search_infinite(fen: &str, tt_mb: u32, history: &str, multipv: u32) -> String {
+    let limits = SearchLimits {
+        tt_mb,
+        multipv,
+        infinite: true,
+        ..SearchLimits::default()
+    };
+    search_json(fen, history, &limits)
+}
+
+// WASM-Export: Ponder-Suche in der Stellung nach dem erwarteten Gegnerzug.
+#[wasm_bindgen]
+// Was: Sucht ohne Zeitlimit, bis `__engine_ponderhit` true liefert; danach gilt `time_ms`
+// ab dem Ponderhit. Ohne Ponderhit endet sie über das Stop-Flag.
+// Warum: Nutzt die Bedenkzeit des Menschen; TT, Killer und History der Suche bleiben beim
+// Ponderhit erhalten, weil dieselbe Suche einfach weiterläuft.
+// Kosten: Wie `search_infinite` bis zum Ponderhit, danach wie eine Suche mit `time_ms`.
+pub fn search_ponder(fen: &str, time_ms: u32, tt_mb: u32, history: &str, multipv: u32) -> String {
+    let limits = SearchLimits {
+        time_ms,
+        tt_mb,
+        multipv,
+        ponder: true,
+        skill_level: SKILL_LEVEL.with(|v| v.get()),
+        ..SearchLimits::default()
+    };
+    search_json(fen, history, &limits)
+}
+
+// WASM-Klasse `Engine`: eigene TT, Killer/History-Heuristik und Skill-Stufe pro Instanz,
+// z. B. je eine für die laufende Partie und ein Analysebrett im selben Worker.
+// Die Such-Methoden entsprechen den freien Exports ohne `tt_mb` (Größe der Instanz).
+#[wasm_bindgen(js_name = Engine)]
+pub struct WasmEngine {
+    inner: Engine,
+    skill_level: Option<u32>,
+}
+
+#[wasm_bindgen(js_class = Engine)]
+impl WasmEngine {
+    #[wasm_bindgen(constructor)]
+    pub fn new(hash_mb: u32) -> WasmEngine {
+        WasmEngine {
+            inner: Engine::new(hash_mb),
+            skill_level: None,
+        }
+    }
+
+    pub fn new_game(&mut self) {
+        self.inner.new_game();
+    }
+
+    pub fn clear_hash(&mut self) {
+        self.inner.clear_hash();
+    }
+
+    pub fn set_hash_size(&mut self, hash_mb: u32) {
+        self.inner.set_hash_size(hash_mb);
+    }
+
+    pub fn hash_size(&self) -> u32 {
+        self.inner.hash_size()
+    }
+
+    pub fn hashfull(&self) -> u32 {
+        self.inner.hashfull()
+    }
+
+    // Wie `set_skill_level`, gilt aber nur für diese Instanz.
+    pub fn set_skill_level(&mut self, level: i32) {
+        self.skill_level = skill_from_js(level);
+    }
+
+    pub fn search(&mut self, fen: &str, history: &str, depth: u32, time_ms: u32, multipv: u32) -> String {
+        let limits = SearchLimits {
+            depth,
+            time_ms,
+            multipv,
+            skill_level: self.skill_level,
+            ..SearchLimits::default()
+        };
+        self.search_json(fen, history, &limits)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_clock(
+        &mut self,
+        fen: &str,
+        history: &str,
+        wtime: u32,
+        btime: u32,
+        winc: u32,
+        binc: u32,
+        movestogo: u32,
+        multipv: u32,
+    ) -> String {
+        let limits = SearchLimits {
+            multipv,
+            game_clock: Some(GameClock {
+                wtime: wtime as u64,
+                btime: btime as u64,
+                winc: winc as u64,
+                binc: binc as u64,
+                movestogo,
+            }),
+            skill_level: self.skill_level,
+            ..SearchLimits::default()
+        };
+        self.search_json(fen, history, &limits)
+    }
+
+    pub fn search_deterministic(&mut self, fen: &str, history: &str, depth: u32, nodes: f64, multipv: u32) -> String {
+        let limits = SearchLimits {
+            depth,
+            nodes: nodes.max(0.0) as u64,
+            multipv,
+            deterministic: true,
+            skill_level: self.skill_level,
+            ..SearchLimits::default()
+        };
+        self.search_json(fen, history, &limits)
+    }
+
+    pub fn search_moves(&mut self, fen: &str, history: &str, depth: u32, time_ms: u32, moves: &str, each: bool) -> String {
+        let moves: Vec<String> = moves.split_whitespace().map(str::to_string).collect();
+        let limits = SearchLimits {
+            depth,
+            time_ms,
+            skill_level: self.skill_level,
+            tablebases: current_tablebases(),
+            threads: current_threads(),
+            ..SearchLimits::default()
+        };
+        let result = if each {
+            self.inner.score_root_moves(fen, history, &moves, &limits, &JsClock, &mut JsProgress)
+        } else {
+            let limits = SearchLimits { searchmoves: moves, ..limits };
+            self.inner.search(fen, history, &limits, &JsClock, &mut JsProgress)
+        };
+        search_result_json(fen, result)
+    }
+
+    pub fn search_infinite(&mut self, fen: &str, history: &str, multipv: u32) -> String {
+        let limits = SearchLimits {
+            multipv,
+            infinite: true,
+            ..SearchLimits::default()
+        };
+        self.search_json(fen, history, &limits)
+    }
+
+    pub fn search_ponder(&mut self, fen: &str, history: &str, time_ms: u32, multipv: u32) -> String {
+        let limits = SearchLimits {
+            time_ms,
+            multipv,
+            ponder: true,
+            skill_level: self.skill_level,
+            ..SearchLimits::default()
+        };
+        self.search_json(fen, history, &limits)
+    }
+}
+
+impl WasmEngine {
+    fn search_json(&mut self, fen: &str, history: &str, limits: &SearchLimits) -> String {
+        let limits = SearchLimits { tablebases: current_tablebases(), threads: current_threads(), ..limits.clone() };
+        let result = self.inner.search(fen, history, &limits, &JsClock, &mut JsProgress);
+        wait_for_stop(&limits);
+        search_result_json(fen, result)
+    }
+}
+
+// WASM-Export: Mattlöser, JSON z. B.
+// `{"mate_in":2,"line":"e2e8 a8e8 e1e8","complete":true,"searched":2,"nodes":N,"time_ms":T}`.
+#[wasm_bindgen(js_name = solve_mate)]
+// Was: Führt `mate::solve_mate` mit Zeitlimit/TT-Größe aus und serialisiert das Ergebnis.
+// Warum: Puzzle-Modus im UI; Abbruch über dasselbe Stop-Flag wie die normale Suche.
+// Kosten: Exponentiell in `moves`; `time_ms == 0` heißt ohne Zeitlimit.
+pub fn solve_mate_fen(fen: &str, moves: u32, time_ms: u32, tt_mb: u32) -> String {
+    let limits = SearchLimits {
+        time_ms,
+        tt_mb,
+        ..SearchLimits::default()
+    };
+    let Some(result) = solve_mate(fen, moves, &limits, &JsClock, &mut JsProgress) else {
+        return "{\"error\":\"invalid fen\"}".to_string();
+    };
+    let mate_in = result.mate_in.map_or("null".to_string(), |n| n.to_string());
+    format!(
+        "{{\"mate_in\":{},\"line\":\"{}\",\"complete\":{},\"searched\":{},\"nodes\":{},\"time_ms\":{}}}",
+        mate_in,
+        result.line.join(" "),
+        result.complete,
+        result.searched,
+        result.nodes,
+        result.time_ms
+    )
+}
+
+// WASM-Export: Perft-Knotenzahl für `fen` bis `depth`.
+// Rückgabe als f64, damit JS eine normale Zahl statt BigInt bekommt (exakt bis 2^53).
+#[wasm_bindgen(js_name = perft)]
+// Was: Parst die FEN und zählt den legalen Zugbaum über `perft::perft`.
+// Warum: Ersetzt die frühere JS-Rekursion über `get_valid_moves`/`apply_move` im Worker.
+// Kosten: Exponentiell in `depth`; ungültige FEN liefert 0.
+pub fn perft_fen(fen: &str, depth: u32) -> f64 {
+    match parse_fen(fen) {
+        Some(mut pos) => perft(&mut pos, depth) as f64,
+        None => 0.0,
+    }
+}
+
+// WASM-Export: Perft je Wurzelzug als JSON `{"nodes":N,"moves":{"e2e4":n,...}}`.
+#[wasm_bindgen(js_name = perft_divide)]
+// Was: Parst die FEN und serialisiert `perft::perft_divide` für den Worker.
+// Warum: Eingrenzen von Abweichungen gegenüber Referenz-Engines im Browser-Debug.
+// Kosten: Wie `perft_fen` plus ein JSON-Eintrag pro Wurzelzug.
+pub fn perft_divide_fen(fen: &str, depth: u32) -> String {
+    let Some(mut pos) = parse_fen(fen) else {
+        return "{\"error\":\"invalid fen\"}".to_string();
+    };
+    let divide = perft_divide(&mut pos, depth);
+    let total: u64 = divide.iter().map(|(_, n)| n).sum();
+    let moves: Vec<String> = divide
+        .iter()
+        .map(|(uci, nodes)| format!("\"{}\":{}", uci, nodes))
+        .collect();
+    format!("{{\"nodes\":{},\"moves\":{{{}}}}}", total, moves.join(","))
+}
+
+// WASM-Export: Partiestatus als JSON, z. B.
+// `{"reason":"checkmate","winner":"w","game_over":true,"claimable":false}`.
+#[wasm_bindgen(js_name = game_status)]
+// Was: Parst FEN + FEN-History und serialisiert `status::game_status`.
+// Warum: Matt, Patt, Remisregeln und tote Stellungen werden nur noch in Rust erkannt.
+// Kosten: Eine Zuggenerierung plus History-Parsing (siehe `game_status`).
+pub fn game_status_fen(fen: &str, history: &str) -> String {
+    let Some(mut pos) = parse_fen(fen) else {
+        return "{\"error\":\"invalid fen\"}".to_string();
+    };
+    let status = game_status(&mut pos, history);
+    let winner = match status {
+        GameStatus::Checkmate { winner: Color::White } => "\"w\"",
+        GameStatus::Checkmate { winner: Color::Black } => "\"b\"",
+        _ => "null",
+    };
+    format!(
+        "{{\"reason\":\"{}\",\"winner\":{},\"game_over\":{},\"claimable\":{}}}",
+        status.reason(),
+        winner,
+        status.is_game_over(),
+        status.is_claimable_draw()
+    )
+}
+
+// WASM-Export: UCI-Zug (`e7e8q`) in SAN (`e8=Q+`) umwandeln; leerer String bei illegalem Zug.
+#[wasm_bindgen(js_name = move_to_san)]
+// Was: Parst FEN und UCI-Zug und formatiert über `san::move_to_san`.
+// Warum: Zugliste und Notationspanel zeigen SAN, ohne die Regeln in JS nachzubauen.
+// Kosten: Zwei legale Zuggenerierungen (Disambiguierung, Matt-Test).
+pub fn move_to_san_fen(fen: &str, uci: &str) -> String {
+    let Some(mut pos) = parse_fen(fen) else {
+        return String::new();
+    };
+    match parse_uci_move(&mut pos, uci) {
+        Some((mv, promo)) => move_to_san(&mut pos, mv, promo),
+        None => String::new(),
+    }
+}
+
+// WASM-Export: SAN-Zug in UCI-Notation umwandeln; leerer String bei illegalem/mehrdeutigem Zug.
+#[wasm_bindgen(js_name = san_to_uci)]
+// Was: Parst FEN und SAN über `san::parse_san`.
+// Warum: Eingaben aus PGN oder Tastatur landen als UCI bei `apply_move`/Suche.
+// Kosten: Eine legale Zuggenerierung.
+pub fn san_to_uci_fen(fen: &str, san: &str) -> String {
+    let Some(mut pos) = parse_fen(fen) else {
+        return String::new();
+    };
+    match parse_san(&mut pos, san) {
+        Some((mv, promo)) => move_to_uci(mv, promo),
+        None => String::new(),
+    }
+}
+
+// WASM-Export: erste Partie eines PGN-Texts als JSON
+// `{"tags":{...},"start_fen":"...","moves":["e2e4",...],"fens":[...],"result":"1-0"}`.
+#[wasm_bindgen(js_name = parse_pgn)]
+// Was: Parst PGN über `pgn::parse_pgn` und serialisiert das Ergebnis.
+// Warum: Partien laden, ohne SAN/PGN-Logik im UI nachzubauen.
+// Kosten: Linear in der Textlänge plus eine SAN-Auflösung pro Zug.
+pub fn parse_pgn_json(pgn: &str) -> String {
+    let game = match parse_pgn(pgn) {
+        Ok(game) => game,
+        Err(err) => return format!("{{\"error\":\"{}\"}}", json_escape(&err)),
+    };
+    let tags: Vec<String> = game
+        .tags
+        .iter()
+        .map(|(k, v)| format!("\"{}\":\"{}\"", json_escape(k), json_escape(v)))
+        .collect();
+    let quoted = |items: &[String]| -> String {
+        items.iter().map(|s| format!("\"{}\"", json_escape(s))).collect::<Vec<_>>().join(",")
+    };
+    format!(
+        "{{\"tags\":{{{}}},\"start_fen\":\"{}\",\"moves\":[{}],\"fens\":[{}],\"result\":\"{}\"}}",
+        tags.join(","),
+        json_escape(&game.start_fen),
+        quoted(&game.moves),
+        quoted(&game.fens),
+        json_escape(&game.result)
+    )
+}
+
+// WASM-Export: PGN aus Start-FEN, leerzeichengetrennten UCI-Zügen und optionalen
+// Tag-Zeilen (`[White "..."]`, eine pro Zeile). Leerer String bei Fehlern.
+#[wasm_bindgen(js_name = write_pgn)]
+// Was: Übersetzt die Eingaben für `pgn::write_pgn`.
+// Warum: Partie-Export aus dem UI; Tags als PGN-Zeilen vermeiden einen JSON-Parser im Kern.
+// Kosten: Eine SAN-Formatierung pro Zug.
+pub fn write_pgn_text(fen: &str, moves: &str, tags: &str) -> String {
+    let moves: Vec<String> = moves.split_whitespace().map(str::to_string).collect();
+    let tags: Vec<(String, String)> = match parse_pgn(tags) {
+        Ok(game) if !tags.trim().is_empty() => game.tags,
+        _ => Vec::new(),
+    };
+    write_pgn(fen, &moves, &tags).unwrap_or_default()
+}
+
+// Minimal-Escaping für die handgebauten JSON-Strings.
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
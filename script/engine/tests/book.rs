@@ -0,0 +1,85 @@
+use chess_engine::{parse_fen, polyglot_key, BookSelection, PolyglotBook, START_FEN};
+
+// Testvektoren aus der Polyglot-Spezifikation.
+const KEYS: [(&str, u64); 9] = [
+    (START_FEN, 0x463b96181691fc9c),
+    ("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", 0x823c9b50fd114196),
+    ("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", 0x0756b94461c50fb0),
+    ("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2", 0x662fafb965db29d4),
+    ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", 0x22a48b5a8e47ff78),
+    ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3", 0x652a607ca3f242c1),
+    ("rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4", 0x00fdd303c946bdd9),
+    ("rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3", 0x3c8123ea7b067637),
+    ("rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4", 0x5c3f9b829b279560),
+];
+
+const CASTLING_FEN: &str = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
+
+fn key(fen: &str) -> u64 {
+    polyglot_key(&parse_fen(fen).unwrap())
+}
+
+// Polyglot-Zug: Ziel in Bits 0..5, Start in 6..11.
+fn entry(out: &mut Vec<u8>, key: u64, from: u16, to: u16, weight: u16) {
+    out.extend_from_slice(&key.to_be_bytes());
+    out.extend_from_slice(&(from << 6 | to).to_be_bytes());
+    out.extend_from_slice(&weight.to_be_bytes());
+    out.extend_from_slice(&0u32.to_be_bytes());
+}
+
+fn sample_book() -> PolyglotBook {
+    let mut bytes = Vec::new();
+    entry(&mut bytes, key(START_FEN), 12, 28, 30); // e2e4
+    entry(&mut bytes, key(START_FEN), 11, 27, 10); // d2d4
+    entry(&mut bytes, key(START_FEN), 12, 36, 50); // e2e5: illegal
+    entry(&mut bytes, key(CASTLING_FEN), 4, 7, 5); // e1h1 = O-O
+    entry(&mut bytes, key(CASTLING_FEN), 4, 0, 0); // e1a1 = O-O-O, Gewicht 0
+    bytes.extend_from_slice(&[0xAB; 7]); // abgeschnittener Rest
+    PolyglotBook::from_bytes(&bytes)
+}
+
+#[test]
+fn keys_match_the_polyglot_reference() {
+    for (fen, expected) in KEYS {
+        assert_eq!(key(fen), expected, "{fen}");
+    }
+}
+
+#[test]
+fn probe_lists_legal_moves_by_weight() {
+    let book = sample_book();
+    assert_eq!(book.len(), 5);
+    let moves = book.probe(START_FEN).unwrap();
+    let listed: Vec<(&str, u16)> = moves.iter().map(|m| (m.uci.as_str(), m.weight)).collect();
+    assert_eq!(listed, [("e2e4", 30), ("d2d4", 10)]);
+
+    let castles = book.probe(CASTLING_FEN).unwrap();
+    let listed: Vec<&str> = castles.iter().map(|m| m.uci.as_str()).collect();
+    assert_eq!(listed, ["e1g1", "e1c1"]);
+
+    assert!(book.probe("8/8/8/4k3/8/8/8/4K3 w - - 0 1").unwrap().is_empty());
+    assert!(book.probe("kein fen").is_none());
+}
+
+#[test]
+fn selection_modes() {
+    let book = sample_book();
+    assert_eq!(book.book_move(START_FEN, BookSelection::Best, 1).as_deref(), Some("e2e4"));
+    // Gewicht 0 wird nie gewählt, solange es Alternativen gibt.
+    for seed in 0..50 {
+        assert_eq!(book.book_move(CASTLING_FEN, BookSelection::Random, seed).as_deref(), Some("e1g1"));
+    }
+
+    // Gewichtete Auswahl: beide Züge kommen vor, e2e4 etwa dreimal so oft.
+    let e4 = (0..400).filter(|&seed| book.book_move(START_FEN, BookSelection::Weighted, seed).as_deref() == Some("e2e4")).count();
+    assert!((240..360).contains(&e4), "{e4}");
+    assert_eq!(BookSelection::parse(" Weighted "), Some(BookSelection::Weighted));
+    assert_eq!(BookSelection::parse("meist"), None);
+}
+
+#[test]
+fn missing_positions_give_no_move() {
+    let book = sample_book();
+    assert_eq!(book.book_move("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", BookSelection::Best, 0), None);
+    assert!(PolyglotBook::from_bytes(&[]).is_empty());
+}
@@ -0,0 +1,80 @@
+use chess_engine::{BookBuilder, BookBuilderOptions, PolyglotBook, START_FEN};
+
+fn game(moves: &str, result: &str) -> String {
+    format!("[Result \"{}\"]\n\n{} {}\n\n", result, moves, result)
+}
+
+fn builder(min_games: u32, max_ply: usize, pgn: &str) -> BookBuilder {
+    let mut builder = BookBuilder::new(BookBuilderOptions { min_games, max_ply, ..BookBuilderOptions::default() });
+    let (_, failed) = builder.add_pgn(pgn);
+    assert_eq!(failed, 0);
+    builder
+}
+
+#[test]
+fn json_shares_and_frequency_cutoff() {
+    let pgn = [
+        game("1. e4 e5 2. Nf3", "1-0"),
+        game("1. e4 e5 2. Nf3", "0-1"),
+        game("1. e4 c5", "1/2-1/2"),
+        game("1. d4 d5", "1-0"),
+    ]
+    .concat();
+    let builder = builder(2, 20, &pgn);
+    assert_eq!(builder.games(), 4);
+    // d2d4 und e4 c5 kommen nur einmal vor; der Anteil bezieht sich trotzdem auf alle Partien.
+    assert_eq!(
+        builder.to_history_json(),
+        r#"{"":{"moves":{"e2e4":0.75}},"e2e4":{"moves":{"e7e5":0.6667}},"e2e4 e7e5":{"moves":{"g1f3":1}}}"#
+    );
+}
+
+#[test]
+fn polyglot_output_round_trips() {
+    let pgn = [
+        game("1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. O-O", "1-0"),
+        game("1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. O-O", "1/2-1/2"),
+        game("1. d4 d5", "1-0"),
+    ]
+    .concat();
+    let book = PolyglotBook::from_bytes(&builder(1, 20, &pgn).to_polyglot());
+    // Gewicht 2 x Siege + Remis: e4 = 2 + 1, d4 = 2.
+    let start = book.probe(START_FEN).unwrap();
+    assert_eq!(start.iter().map(|m| (m.uci.as_str(), m.weight)).collect::<Vec<_>>(), [("e2e4", 3), ("d2d4", 2)]);
+    // Rochade wird als "König schlägt Turm" geschrieben und wieder zu e1g1.
+    let castle = book.probe("r1bqk1nr/pppp1ppp/2n5/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4").unwrap();
+    assert_eq!(castle[0].uci, "e1g1");
+}
+
+#[test]
+fn ply_cutoff_and_transpositions() {
+    let pgn = [game("1. e4 e5 2. Nf3 Nc6 3. Bb5", "1-0"), game("1. Nf3 Nc6 2. e4 e5 3. Bc4", "1-0")].concat();
+    let after_four = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+
+    // Polyglot führt beide Zugfolgen in derselben Stellung zusammen.
+    let full = builder(1, 20, &pgn);
+    let mut moves: Vec<String> =
+        PolyglotBook::from_bytes(&full.to_polyglot()).probe(after_four).unwrap().into_iter().map(|m| m.uci).collect();
+    moves.sort();
+    assert_eq!(moves, ["f1b5", "f1c4"]);
+    // Das JSON bleibt nach Zugfolge getrennt.
+    let json = full.to_history_json();
+    assert!(json.contains(r#""e2e4 e7e5 g1f3 b8c6":{"moves":{"f1b5":1}}"#));
+    assert!(json.contains(r#""g1f3 b8c6 e2e4 e7e5":{"moves":{"f1c4":1}}"#));
+
+    // Mit max_ply 4 endet das Buch vor dem fünften Halbzug.
+    let short = builder(1, 4, &pgn);
+    assert!(PolyglotBook::from_bytes(&short.to_polyglot()).probe(after_four).unwrap().is_empty());
+    assert!(!short.to_history_json().contains("f1b5"));
+}
+
+#[test]
+fn weights_follow_the_side_to_move() {
+    let pgn = [game("1. f3 e5 2. g4 Qh4#", "0-1"), game("1. f3 e5 2. g4 Qh4#", "0-1")].concat();
+    let book = PolyglotBook::from_bytes(&builder(1, 20, &pgn).to_polyglot());
+    // Weiß hat nur verloren: keine Einträge mit Gewicht 0 für seine Züge.
+    assert!(book.probe(START_FEN).unwrap().is_empty());
+    let black = book.probe("rnbqkbnr/pppppppp/8/8/8/5P2/PPPPP1PP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!((black[0].uci.as_str(), black[0].weight), ("e7e5", 4));
+    assert_eq!(book.len(), 2);
+}
@@ -0,0 +1,30 @@
+// Gemeinsame Helfer der Integrationstests; jede Testdatei nutzt nur einen Teil davon.
+#![allow(dead_code)]
+
+use chess_engine::{Engine, SearchLimits, SearchResult, SystemClock};
+
+// Ruhiges Mittelspiel mit vielen Zügen: genug Arbeit für TT, Helfer-Threads und Knotenlimits.
+pub const MIDGAME: &str = "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N2N2/PP2BPPP/R2QKB1R w KQ - 0 8";
+
+// Suche bis `depth` auf einer bestehenden Instanz, sonst mit Standard-Limits.
+pub fn search(engine: &mut Engine, fen: &str, depth: u32) -> SearchResult {
+    search_with(engine, fen, &SearchLimits { depth, ..SearchLimits::default() })
+}
+
+pub fn search_with(engine: &mut Engine, fen: &str, limits: &SearchLimits) -> SearchResult {
+    engine.search(fen, "", limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+// Alles Sichtbare eines Suchergebnisses als Text, um zwei Suchen auf Gleichheit zu prüfen.
+pub fn fingerprint(result: &SearchResult) -> String {
+    let lines: Vec<String> = result.lines.iter().map(|l| format!("{}:{}:{}", l.multipv, l.score, l.pv.join(" "))).collect();
+    format!(
+        "{:?} {} {} {} {} {}",
+        result.best,
+        result.score,
+        result.depth,
+        result.nodes,
+        result.pv.join(" "),
+        lines.join("|")
+    )
+}
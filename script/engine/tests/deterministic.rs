@@ -0,0 +1,63 @@
+mod common;
+
+use std::thread;
+
+use chess_engine::{run_search, GameClock, SearchLimits, SearchResult, SystemClock, START_FEN};
+
+use common::{fingerprint, MIDGAME};
+
+fn deterministic(fen: &str, depth: u32, nodes: u64) -> SearchResult {
+    let limits = SearchLimits { depth, nodes, tt_mb: 8, multipv: 2, deterministic: true, ..SearchLimits::default() };
+    run_search(fen, "", &limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+#[test]
+fn identical_after_unrelated_searches() {
+    let first = fingerprint(&deterministic(MIDGAME, 0, 30_000));
+
+    // Eine andere Suche dazwischen darf keine Spuren in der nächsten hinterlassen.
+    let limits = SearchLimits { depth: 5, tt_mb: 8, ..SearchLimits::default() };
+    run_search(START_FEN, "", &limits, &SystemClock, &mut ()).unwrap();
+
+    let second = fingerprint(&deterministic(MIDGAME, 0, 30_000));
+    assert_eq!(first, second);
+}
+
+#[test]
+fn identical_across_threads() {
+    let here = fingerprint(&deterministic(MIDGAME, 5, 0));
+    let there = thread::spawn(|| fingerprint(&deterministic(MIDGAME, 5, 0))).join().unwrap();
+    assert_eq!(here, there);
+}
+
+#[test]
+fn node_limit_is_exact() {
+    let result = deterministic(MIDGAME, 0, 12_345);
+    assert!(result.nodes <= 12_345);
+    assert!(result.depth >= 1);
+    assert!(result.best.is_some());
+}
+
+#[test]
+fn time_limit_is_ignored() {
+    let limits = SearchLimits { depth: 5, time_ms: 1, tt_mb: 8, deterministic: true, ..SearchLimits::default() };
+    let result = run_search(MIDGAME, "", &limits, &SystemClock, &mut ()).unwrap();
+    assert_eq!(result.depth, 5);
+}
+
+#[test]
+fn time_limits_do_not_open_the_depth() {
+    // Zeitvorgaben zählen deterministisch nicht: ohne `depth`/`nodes` bleibt es bei Tiefe 1,
+    // mit `nodes` entscheidet allein das Knotenlimit.
+    let clock = GameClock { wtime: 60_000, btime: 60_000, ..GameClock::default() };
+    for (time_ms, game_clock) in [(300, None), (0, Some(clock))] {
+        let limits = SearchLimits { time_ms, game_clock, tt_mb: 8, deterministic: true, ..SearchLimits::default() };
+        let result = run_search(MIDGAME, "", &limits, &SystemClock, &mut ()).unwrap();
+        assert_eq!(result.depth, 1);
+
+        let limits = SearchLimits { nodes: 20_000, ..limits };
+        let first = run_search(MIDGAME, "", &limits, &SystemClock, &mut ()).unwrap();
+        assert!(first.nodes <= 20_000 && first.depth > 1);
+        assert_eq!(fingerprint(&first), fingerprint(&run_search(MIDGAME, "", &limits, &SystemClock, &mut ()).unwrap()));
+    }
+}
@@ -0,0 +1,47 @@
+use chess_engine::{run_search, SearchLimits, SearchResult, SystemClock};
+
+// Weit über jedem Materialvorteil: die Spezialbewertung erkennt den Gewinn.
+const KNOWN_WIN: i32 = 5_000;
+
+fn search(fen: &str, depth: u32) -> SearchResult {
+    let limits = SearchLimits { depth, tt_mb: 8, deterministic: true, ..SearchLimits::default() };
+    run_search(fen, "", &limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+#[test]
+fn kpk_bitbase_separates_wins_from_draws() {
+    // Schwarz am Zug hält mit Kd8 (e7+ führt ins Patt); Weiß am Zug gewinnt mit e7.
+    assert_eq!(search("4k3/8/3KP3/8/8/8/8/8 b - - 0 1", 3).score, 0);
+    assert!(search("4k3/8/3KP3/8/8/8/8/8 w - - 0 1", 3).score > KNOWN_WIN);
+    // Gespiegelt (Farben und Linien) ergibt dasselbe.
+    assert_eq!(search("8/8/8/8/8/3pk3/8/3K4 w - - 0 1", 3).score, 0);
+    assert!(search("8/8/8/8/8/3pk3/8/3K4 b - - 0 1", 3).score > KNOWN_WIN);
+    // Randbauer mit König vor dem Bauern ist remis, egal wer zieht.
+    assert_eq!(search("k7/8/K7/P7/8/8/8/8 w - - 0 1", 3).score, 0);
+}
+
+#[test]
+fn minor_piece_endings_are_draws() {
+    assert_eq!(search("8/8/4k3/8/8/3BK3/8/8 w - - 0 1", 3).score, 0);
+    assert_eq!(search("8/8/4k3/8/8/3NK3/8/8 b - - 0 1", 3).score, 0);
+    assert_eq!(search("8/8/4k3/8/8/2NNK3/8/8 w - - 0 1", 3).score, 0);
+}
+
+#[test]
+fn wrong_rook_pawn_is_a_draw() {
+    // Dunkler Läufer, helles Umwandlungsfeld a8, König in der Ecke.
+    assert_eq!(search("k7/8/8/8/8/8/P7/2B1K3 w - - 0 1", 3).score, 0);
+    // Mit dem richtigen Läufer bleibt es ein klarer Vorteil.
+    assert!(search("k7/8/8/8/8/8/P7/3BK3 w - - 0 1", 3).score > 300);
+}
+
+#[test]
+fn mating_material_drives_the_king() {
+    for fen in ["8/8/3k4/8/8/8/8/R3K3 w - - 0 1", "8/8/3k4/8/8/8/8/Q3K3 w - - 0 1", "8/8/3k4/8/8/8/8/1B1NK3 w - - 0 1"] {
+        assert!(search(fen, 4).score > KNOWN_WIN, "{fen}");
+    }
+    // K+L+S-K: die Ecke der Läuferfarbe (dunkel: h8) ist besser als die andere.
+    let right = search("7k/8/5K2/8/8/8/8/2N1B3 b - - 0 1", 1).score;
+    let wrong = search("k7/8/2K5/8/8/8/8/2N1B3 b - - 0 1", 1).score;
+    assert!(right < wrong, "{right} {wrong}");
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use chess_engine::{Engine, SearchLimits, SystemClock, START_FEN};
+
+use common::{fingerprint, search, MIDGAME};
+
+#[test]
+fn instances_do_not_share_state() {
+    let reference = fingerprint(&search(&mut Engine::new(8), MIDGAME, 5));
+
+    let mut game = Engine::new(8);
+    search(&mut game, START_FEN, 5);
+    search(&mut game, MIDGAME, 4);
+
+    let mut analysis = Engine::new(8);
+    assert_eq!(fingerprint(&search(&mut analysis, MIDGAME, 5)), reference);
+}
+
+#[test]
+fn new_game_forgets_previous_searches() {
+    let mut engine = Engine::new(8);
+    let first = fingerprint(&search(&mut engine, MIDGAME, 5));
+    search(&mut engine, START_FEN, 5);
+
+    engine.new_game();
+    assert_eq!(fingerprint(&search(&mut engine, MIDGAME, 5)), first);
+}
+
+#[test]
+fn clear_hash_drops_table_entries() {
+    let mut engine = Engine::new(8);
+    let cold = search(&mut engine, MIDGAME, 6).nodes;
+    let warm = search(&mut engine, MIDGAME, 6).nodes;
+    assert!(warm < cold, "warm {} cold {}", warm, cold);
+
+    engine.clear_hash();
+    let cleared = search(&mut engine, MIDGAME, 6).nodes;
+    assert!(cleared > warm, "cleared {} warm {}", cleared, warm);
+}
+
+#[test]
+fn set_hash_size_resizes_and_ignores_limits() {
+    let mut engine = Engine::new(8);
+    engine.set_hash_size(1);
+    assert_eq!(engine.hash_size(), 1);
+
+    // `tt_mb` der Limits gilt nur für `run_search`, nicht für Instanzen.
+    let limits = SearchLimits { depth: 4, tt_mb: 64, ..SearchLimits::default() };
+    assert!(engine.search(MIDGAME, "", &limits, &SystemClock, &mut ()).unwrap().best.is_some());
+    assert_eq!(engine.hash_size(), 1);
+
+    engine.set_hash_size(0);
+    assert!(search(&mut engine, MIDGAME, 4).best.is_some());
+}
@@ -0,0 +1,64 @@
+use chess_engine::{parse_epd, parse_epd_suite, solve_epd, Engine, SearchLimits, SystemClock};
+
+const WAC_001: &str = r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#;
+
+#[test]
+fn parses_best_move_and_id() {
+    let record = parse_epd(WAC_001).unwrap();
+    assert_eq!(record.fen, "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1");
+    assert_eq!(record.id.as_deref(), Some("WAC.001"));
+    assert_eq!(record.best_moves, ["g3g6"]);
+    assert!(record.avoid_moves.is_empty());
+    assert!(record.is_solved_by("g3g6"));
+    assert!(!record.is_solved_by("f6h5"));
+    assert_eq!((record.points_for("g3g6"), record.points_for("f6h5"), record.max_points()), (10, 0, 10));
+}
+
+#[test]
+fn parses_sts_points_avoid_moves_and_counters() {
+    let sts = parse_epd(
+        r#"8/P7/8/8/8/7k/8/4K2R w K - bm a8=Q; c0 "a8=Q=10, O-O=4, Rh2+=2"; id "promo; castle"; hmvc 3; fmvn 40;"#,
+    )
+    .unwrap();
+    assert_eq!(sts.fen, "8/P7/8/8/8/7k/8/4K2R w K - 3 40");
+    assert_eq!(sts.id.as_deref(), Some("promo; castle"));
+    assert_eq!(sts.best_moves, ["a7a8q"]);
+    assert_eq!(sts.points, [("a7a8q".to_string(), 10), ("e1g1".to_string(), 4), ("h1h2".to_string(), 2)]);
+    assert_eq!((sts.points_for("e1g1"), sts.points_for("e1d1")), (4, 0));
+
+    let am = parse_epd("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - am Ke2 Qe2; id \"x\";").unwrap();
+    assert_eq!(am.avoid_moves, ["e1e2", "d1e2"]);
+    assert!(am.is_solved_by("f1b5"));
+    assert!(!am.is_solved_by("e1e2"));
+}
+
+#[test]
+fn suite_skips_comments_and_reports_bad_lines() {
+    let text = format!(
+        "# WAC-Auszug\n\n{}\n{}\n{}\n",
+        WAC_001,
+        "8/8/8/8/8/8/8/8 w - - bm Ka1;",
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qh8;"
+    );
+    let records = parse_epd_suite(&text);
+    assert_eq!(records.len(), 3);
+    assert!(records[0].is_ok());
+    assert!(records[1].is_err());
+    assert!(records[2].as_ref().unwrap_err().contains("Qh8"));
+    assert!(parse_epd("8/8/8 w -").is_err());
+}
+
+#[test]
+fn solves_wac_001_with_time_to_solve() {
+    let record = parse_epd(WAC_001).unwrap();
+    let limits = SearchLimits { depth: 4, deterministic: true, ..SearchLimits::default() };
+    let mut engine = Engine::new(16);
+    let outcome = solve_epd(&mut engine, &record, &limits, &SystemClock);
+    assert_eq!(outcome.best.as_deref(), Some("g3g6"));
+    assert!(outcome.solved);
+    assert_eq!(outcome.points, 10);
+    // Das Matt beendet die Suche unter Umständen vor Tiefe 4.
+    let solve_depth = outcome.solve_depth.unwrap();
+    assert!(solve_depth >= 1 && solve_depth <= outcome.depth && outcome.depth <= 4);
+    assert!(outcome.solve_nodes.unwrap() <= outcome.nodes);
+}
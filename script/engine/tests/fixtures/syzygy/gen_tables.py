@@ -0,0 +1,582 @@
+#!/usr/bin/env python3
+# =====================================================================================
+# Syzygy-Testtabellen (KRvK, KPvK)
+# -------------------------------------------------------------------------------------
+# Erzeugt `KRvK.rtbw/.rtbz` und `KPvK.rtbw/.rtbz` für `tests/syzygy.rs`. Die Werte kommen
+# aus eigenen Rückwärtsanalysen (eigene Zuggenerierung, unabhängig vom Rust-Code);
+# geschrieben wird das Dateiformat des Referenz-Probers (`tbprobe` aus Fathom/Stockfish):
+# - KRvK: Indizierung ohne Bauern mit Einzelsteinen (31332 Indizes, Gruppe aus drei
+#   Steinen), Steinreihenfolge WK, WR, BK,
+# - KPvK: Bauerntabelle mit einer Teiltabelle je Linie a..d des führenden Bauern und drei
+#   Gruppen (P, K, k), Steinreihenfolge WP, WK, BK,
+# - WDL mit beiden Seiten am Zug, DTZ nur mit Weiß am Zug (Flag STM = 0, Werte in Zügen),
+# - Kompression aus Recursive Pairing (Paarsymbole) und kanonischem Huffman-Code,
+#   kleine Blöcke und Sparse-Index, damit der Decoder auch Blockgrenzen überquert.
+# Die Dateien sind klein, aber vollständige Tabellen; Upstream-Dateien (tbgen) würden nur
+# anders komprimieren. Aufruf: python3 gen_tables.py [Zielverzeichnis]
+# =====================================================================================
+
+import heapq
+import os
+import struct
+import sys
+
+WDL_MAGIC = bytes([0x71, 0xE8, 0x23, 0x5D])
+DTZ_MAGIC = bytes([0xD7, 0x66, 0x0C, 0xA5])
+
+# Syzygy-Figurcodes: Typ 1..6 (P, N, B, R, Q, K), Schwarz mit Bit 8.
+KRVK_PIECES = [6, 4, 14]  # WK, WR, BK
+KPVK_PIECES = [1, 6, 14]  # WP, WK, BK
+
+BLOCK_BITS = 6  # 64-Byte-Blöcke
+SPAN_BITS = 7  # ein Sparse-Index-Eintrag je 128 Werte
+PAIR_ROUNDS = 40
+
+KRVK_SIZE = 31332
+# Je Linie: 6 Reihen des Bauern, 63 Felder für WK, 62 für BK.
+KPVK_SIZE = 6 * 63 * 62
+
+
+# ---------------------------
+# Schachlogik (a1 = 0)
+# ---------------------------
+
+def file_of(s):
+    return s & 7
+
+
+def rank_of(s):
+    return s >> 3
+
+
+def king_near(a, b):
+    return max(abs(file_of(a) - file_of(b)), abs(rank_of(a) - rank_of(b))) <= 1
+
+
+def king_moves(s):
+    out = []
+    for df in (-1, 0, 1):
+        for dr in (-1, 0, 1):
+            if df == 0 and dr == 0:
+                continue
+            f, r = file_of(s) + df, rank_of(s) + dr
+            if 0 <= f < 8 and 0 <= r < 8:
+                out.append(r * 8 + f)
+    return out
+
+
+ROOK_DIRS = ((1, 0), (-1, 0), (0, 1), (0, -1))
+QUEEN_DIRS = ROOK_DIRS + ((1, 1), (1, -1), (-1, 1), (-1, -1))
+
+
+def rook_rays(s, blockers, dirs=ROOK_DIRS):
+    # Felder, die der Turm erreicht; ein Blocker beendet den Strahl (und zählt mit).
+    out = []
+    for df, dr in dirs:
+        f, r = file_of(s) + df, rank_of(s) + dr
+        while 0 <= f < 8 and 0 <= r < 8:
+            t = r * 8 + f
+            out.append(t)
+            if t in blockers:
+                break
+            f, r = f + df, r + dr
+    return out
+
+
+def queen_rays(s, blockers):
+    return rook_rays(s, blockers, QUEEN_DIRS)
+
+
+def pawn_attacks(s):
+    # Felder, die ein weißer Bauer auf `s` angreift.
+    return [s + d for d, edge in ((7, 0), (9, 7)) if file_of(s) != edge]
+
+
+# ---------------------------
+# KRvK
+# ---------------------------
+
+def krvk_legal(wk, wr, bk, white_to_move):
+    if len({wk, wr, bk}) < 3 or king_near(wk, bk):
+        return False
+    # Die Seite, die nicht am Zug ist, darf nicht im Schach stehen.
+    return not (white_to_move and bk in rook_rays(wr, {wk, bk}))
+
+
+def white_successors(wk, wr, bk):
+    out = []
+    for t in king_moves(wk):
+        if t != wr and not king_near(t, bk):
+            out.append((t, wr, bk))
+    for t in rook_rays(wr, {wk, bk}):
+        if t not in (wk, bk):
+            out.append((wk, t, bk))
+    return out
+
+
+def black_moves(wk, wr, bk):
+    # (Folgestellungen ohne Schlag, Turm schlagbar?)
+    quiet, capture = [], False
+    for t in king_moves(bk):
+        if king_near(t, wk):
+            continue
+        if t == wr:
+            capture = True
+            continue
+        if t in rook_rays(wr, {wk}):
+            continue
+        quiet.append((wk, wr, t))
+    return quiet, capture
+
+
+def solve_krvk():
+    # Distanz bis Matt in Halbzügen: wtm[p] (Gewinn) bzw. btm[p] (Verlust); None = Remis/illegal.
+    key = lambda wk, wr, bk: (wk * 64 + wr) * 64 + bk
+    wtm, btm = [None] * 262144, [None] * 262144
+    btm_draw = [False] * 262144
+    white_succ, black_succ = {}, {}
+    for wk in range(64):
+        for wr in range(64):
+            for bk in range(64):
+                p = key(wk, wr, bk)
+                if krvk_legal(wk, wr, bk, True):
+                    white_succ[p] = [key(*s) for s in white_successors(wk, wr, bk)]
+                if krvk_legal(wk, wr, bk, False):
+                    quiet, capture = black_moves(wk, wr, bk)
+                    in_check = bk in rook_rays(wr, {wk, bk})
+                    if capture or (not quiet and not in_check):
+                        btm_draw[p] = True
+                    elif not quiet:
+                        btm[p] = 0
+                    else:
+                        black_succ[p] = [key(*s) for s in quiet]
+
+    depth = 0
+    while True:
+        changed = False
+        for p, succ in white_succ.items():
+            if wtm[p] is None and any(btm[s] == depth for s in succ):
+                wtm[p] = depth + 1
+                changed = True
+        for p, succ in black_succ.items():
+            if btm[p] is None and not btm_draw[p] and all(wtm[s] is not None for s in succ):
+                if max(wtm[s] for s in succ) == depth + 1:
+                    btm[p] = depth + 2
+                    changed = True
+        if not changed:
+            break
+        depth += 2
+    for p in white_succ:
+        assert wtm[p] is not None, "KRvK mit Weiß am Zug ist immer gewonnen"
+    return key, wtm, btm, btm_draw
+
+
+# ---------------------------
+# KPvK
+# ---------------------------
+
+def kpvk_legal(wk, wp, bk, white_to_move):
+    if len({wk, wp, bk}) < 3 or king_near(wk, bk) or not 8 <= wp < 56:
+        return False
+    return not (white_to_move and bk in pawn_attacks(wp))
+
+
+def pawn_pushes(wk, wp, bk):
+    # Zielfelder des Bauern; zu schlagen gibt es außer dem König nichts.
+    out = []
+    if wp + 8 not in (wk, bk):
+        out.append(wp + 8)
+        if rank_of(wp) == 1 and wp + 16 not in (wk, bk):
+            out.append(wp + 16)
+    return out
+
+
+def promotion_wins(wk, t, bk):
+    # Umwandlung auf `t`, Schwarz am Zug. Dame oder Turm gewinnen, außer der neue Stein
+    # geht sofort verloren oder Schwarz ist patt; Springer und Läufer halten nur Remis.
+    if t in king_moves(bk) and not king_near(t, wk):
+        return False
+    for rays in (queen_rays, rook_rays):
+        # Ohne den schwarzen König als Blocker: er kann nicht entlang des Strahls fliehen.
+        attacked = rays(t, {wk})
+        escapes = [s for s in king_moves(bk) if s not in attacked and not king_near(s, wk)]
+        if escapes or bk in attacked:
+            return True
+    return False
+
+
+def solve_kpvk():
+    # DTZ in Halbzügen: wtm[p] (Gewinn) bzw. btm[p] (Verlust); None = Remis/illegal.
+    # Bis zum nächsten Bauernzug steht der Bauer still: die Bauernfelder werden von der
+    # 7. Reihe abwärts gelöst, die Stellungen nach dem Bauernzug sind dann schon bekannt.
+    key = lambda wk, wp, bk: (wk * 64 + wp) * 64 + bk
+    wtm, btm = [None] * 262144, [None] * 262144
+    for wp in range(55, 7, -1):
+        # Schwarz am Zug: offene Königszüge zählen; Bauernschlag oder Patt ist Remis.
+        remaining = {}
+        for wk in range(64):
+            for bk in range(64):
+                if not kpvk_legal(wk, wp, bk, False):
+                    continue
+                quiet = [t for t in king_moves(bk) if not king_near(t, wk) and t not in pawn_attacks(wp)]
+                if wp in quiet:
+                    continue
+                if quiet:
+                    remaining[key(wk, wp, bk)] = len(quiet)
+                elif bk in pawn_attacks(wp):
+                    btm[key(wk, wp, bk)] = 0
+
+        # Weiß am Zug: ein gewinnender Bauernzug hat DTZ 1, Königszüge bauen darauf auf.
+        queue = []
+        for wk in range(64):
+            for bk in range(64):
+                if not kpvk_legal(wk, wp, bk, True):
+                    continue
+                for t in pawn_pushes(wk, wp, bk):
+                    if promotion_wins(wk, t, bk) if t >= 56 else btm[key(wk, t, bk)] is not None:
+                        wtm[key(wk, wp, bk)] = 1
+                        queue.append((wk, bk))
+                        break
+
+        # Rückwärts in DTZ-Reihenfolge: Schwarz verliert, sobald sein letzter Zug in einen
+        # Gewinn für Weiß führt, und zwar mit dem längsten davon.
+        for wk, bk in queue:
+            d = wtm[key(wk, wp, bk)]
+            for b in king_moves(bk):
+                p = key(wk, wp, b)
+                if p not in remaining:
+                    continue
+                remaining[p] -= 1
+                if remaining[p] > 0:
+                    continue
+                btm[p] = d + 1
+                for w in king_moves(wk):
+                    q = key(w, wp, b)
+                    if wtm[q] is None and kpvk_legal(w, wp, b, True):
+                        wtm[q] = d + 2
+                        queue.append((w, b))
+    return key, wtm, btm
+
+
+# ---------------------------
+# Indizierung
+# ---------------------------
+
+def off_a1h8(s):
+    return rank_of(s) - file_of(s)
+
+
+MAP_B1H1H7 = {}
+for _s in range(64):
+    if off_a1h8(_s) < 0:
+        MAP_B1H1H7[_s] = len(MAP_B1H1H7)
+
+MAP_A1D1D4 = {}
+for _s in range(28):
+    if off_a1h8(_s) < 0 and file_of(_s) <= 3:
+        MAP_A1D1D4[_s] = len(MAP_A1D1D4)
+for _s in range(28):
+    if off_a1h8(_s) == 0 and file_of(_s) <= 3:
+        MAP_A1D1D4[_s] = len(MAP_A1D1D4)
+
+
+# Tabellen ohne Bauern, Einzelsteine: eine Teiltabelle.
+def krvk_index(squares):
+    s = list(squares)
+    if file_of(s[0]) > 3:
+        s = [x ^ 7 for x in s]
+    if rank_of(s[0]) > 3:
+        s = [x ^ 56 for x in s]
+    for i in range(3):
+        off = off_a1h8(s[i])
+        if off == 0:
+            continue
+        if off > 0:
+            s = s[:i] + [((x >> 3) | (x << 3)) & 63 for x in s[i:]]
+        break
+    s0, s1, s2 = s
+    adj1 = int(s1 > s0)
+    adj2 = int(s2 > s0) + int(s2 > s1)
+    if off_a1h8(s0) != 0:
+        return (MAP_A1D1D4[s0] * 63 + (s1 - adj1)) * 62 + s2 - adj2
+    if off_a1h8(s1) != 0:
+        return (6 * 63 + rank_of(s0) * 28 + MAP_B1H1H7[s1]) * 62 + s2 - adj2
+    if off_a1h8(s2) != 0:
+        return 6 * 63 * 62 + 4 * 28 * 62 + rank_of(s0) * 7 * 28 + (rank_of(s1) - adj1) * 28 + MAP_B1H1H7[s2]
+    return 6 * 63 * 62 + 4 * 28 * 62 + 4 * 7 * 28 + rank_of(s0) * 7 * 6 + (rank_of(s1) - adj1) * 6 + rank_of(s2) - adj2
+
+
+# Bauerntabelle: Teiltabelle nach Linie des Bauern (a..d, e..h gespiegelt), darin der
+# Bauer (Reihe 2..7), dann WK und BK als Feld abzüglich der belegten kleineren Felder.
+def kpvk_index(squares):
+    wp, wk, bk = squares
+    if file_of(wp) > 3:
+        wp, wk, bk = wp ^ 7, wk ^ 7, bk ^ 7
+    n1 = wk - int(wk > wp)
+    n2 = bk - int(bk > wp) - int(bk > wk)
+    return file_of(wp), rank_of(wp) - 1 + n1 * 6 + n2 * 6 * 63
+
+
+def fill_table(size, values_by_index):
+    # Index -> Wert; gleiche Indizes (Symmetrien) müssen gleiche Werte haben.
+    table = [None] * size
+    for i, value in values_by_index:
+        assert table[i] is None or table[i] == value, ("Symmetrie-Konflikt", i)
+        table[i] = value
+    # Unerreichbare Indizes (illegale Stellungen) bekommen den häufigsten Wert.
+    counts = {}
+    for v in table:
+        if v is not None:
+            counts[v] = counts.get(v, 0) + 1
+    filler = max(counts, key=counts.get)
+    return [filler if v is None else v for v in table]
+
+
+# ---------------------------
+# Kompression
+# ---------------------------
+
+class Compressed:
+    pass
+
+
+def compress(values):
+    c = Compressed()
+    c.single, c.sparse, c.block_lengths, c.blocks = None, [], [], []
+    if len(set(values)) == 1:
+        # Konstante Teiltabelle: nur Flag 0x80 und der Wert, keine Blöcke.
+        c.single = values[0]
+        return c
+
+    # Recursive Pairing: häufigstes Nachbarpaar wird zu einem neuen Symbol.
+    leaves = sorted(set(values))
+    sym_of_leaf = {v: i for i, v in enumerate(leaves)}
+    tree = [(v, None) for v in leaves]  # (links, rechts); Blatt: (Wert, None)
+    length = [1] * len(leaves)  # Anzahl Werte je Symbol
+    seq = [sym_of_leaf[v] for v in values]
+    if len(leaves) > 1:
+        for _ in range(PAIR_ROUNDS):
+            counts = {}
+            prev = None
+            for a, b in zip(seq, seq[1:]):
+                if (a, b) == prev:
+                    prev = None  # überlappende Paare (aaa) nicht doppelt zählen
+                    continue
+                counts[(a, b)] = counts.get((a, b), 0) + 1
+                prev = (a, b)
+            if not counts:
+                break
+            pair, n = max(counts.items(), key=lambda kv: kv[1])
+            if n < 4 or length[pair[0]] + length[pair[1]] > 256:
+                break
+            new = len(tree)
+            tree.append(pair)
+            length.append(length[pair[0]] + length[pair[1]])
+            out, i = [], 0
+            while i < len(seq):
+                if i + 1 < len(seq) and (seq[i], seq[i + 1]) == pair:
+                    out.append(new)
+                    i += 2
+                else:
+                    out.append(seq[i])
+                    i += 1
+            seq = out
+
+    # Huffman-Codelängen über die verwendeten Symbole.
+    freq = {}
+    for s in seq:
+        freq[s] = freq.get(s, 0) + 1
+    if len(freq) == 1:
+        code_len = {next(iter(freq)): 1}
+    else:
+        heap = [(n, i, [s]) for i, (s, n) in enumerate(sorted(freq.items()))]
+        heapq.heapify(heap)
+        code_len = {s: 0 for s in freq}
+        tie = len(heap)
+        while len(heap) > 1:
+            n1, _, a = heapq.heappop(heap)
+            n2, _, b = heapq.heappop(heap)
+            for s in a + b:
+                code_len[s] += 1
+            heapq.heappush(heap, (n1 + n2, tie, a + b))
+            tie += 1
+    assert max(code_len.values()) <= 32
+
+    # Kanonische Nummerierung: längste Codes bekommen die kleinsten Symbolnummern,
+    # ungenutzte Symbole (nur als Kinder von Paaren) liegen dahinter.
+    used = sorted(code_len, key=lambda s: (-code_len[s], s))
+    unused = [s for s in range(len(tree)) if s not in code_len]
+    new_id = {s: i for i, s in enumerate(used + unused)}
+    min_len, max_len = min(code_len.values()), max(code_len.values())
+    h = max_len - min_len + 1
+    count = [0] * h
+    for s in used:
+        count[code_len[s] - min_len] += 1
+    lowest = [0] * h
+    for i in range(h - 2, -1, -1):
+        lowest[i] = lowest[i + 1] + count[i + 1]
+    base = [0] * h
+    for i in range(h - 2, -1, -1):
+        assert (base[i + 1] + count[i + 1]) % 2 == 0
+        base[i] = (base[i + 1] + count[i + 1]) // 2
+    code = {}
+    for s in used:
+        l = code_len[s] - min_len
+        code[s] = (base[l] + new_id[s] - lowest[l], code_len[s])
+
+    btree = [None] * len(tree)
+    for s, (left, right) in enumerate(tree):
+        if right is None:
+            btree[new_id[s]] = (left, 0xFFF)
+        else:
+            btree[new_id[s]] = (new_id[left], new_id[right])
+
+    # Blöcke: ganze Symbole, höchstens 2^BLOCK_BITS Bytes je Block.
+    block_bits = (1 << BLOCK_BITS) * 8
+    blocks, block_counts = [], []
+    bits, nbits, nvalues = 0, 0, 0
+    for s in seq:
+        word, l = code[s]
+        if nbits + l > block_bits:
+            blocks.append(bits << (block_bits - nbits))
+            block_counts.append(nvalues)
+            bits, nbits, nvalues = 0, 0, 0
+        bits = (bits << l) | word
+        nbits += l
+        nvalues += length[s]
+    blocks.append(bits << (block_bits - nbits))
+    block_counts.append(nvalues)
+    assert sum(block_counts) == len(values)
+
+    # Sparse-Index: Block und Offset der Mitte jedes Spans.
+    starts, acc = [], 0
+    for n in block_counts:
+        starts.append(acc)
+        acc += n
+    span = 1 << SPAN_BITS
+    sparse = []
+    b = 0
+    for k in range((len(values) + span - 1) // span):
+        p = k * span + span // 2
+        while b + 1 < len(starts) and starts[b + 1] <= p:
+            b += 1
+        assert p - starts[b] < 65536
+        sparse.append((b, p - starts[b]))
+
+    c.min_len, c.max_len, c.lowest = min_len, max_len, lowest
+    c.btree = btree
+    c.blocks = [blk.to_bytes(1 << BLOCK_BITS, "big") for blk in blocks]
+    c.block_lengths = [n - 1 for n in block_counts]
+    c.sparse = sparse
+    return c
+
+
+def sizes_header(flags, c):
+    if c.single is not None:
+        return bytearray([flags | 0x80, c.single])
+    out = bytearray([flags, BLOCK_BITS, SPAN_BITS, 0])
+    out += struct.pack("<I", len(c.blocks))
+    out += bytes([c.max_len, c.min_len])
+    out += b"".join(struct.pack("<H", x) for x in c.lowest)
+    out += struct.pack("<H", len(c.btree))
+    for left, right in c.btree:
+        out += bytes([left & 0xFF, ((left >> 8) & 0xF) | ((right & 0xF) << 4), right >> 4])
+    if len(c.btree) & 1:
+        out += b"\0"
+    return out
+
+
+def write_table(path, magic, file_flags, pieces, files):
+    # files: je Linie a..d des führenden Bauern (ohne Bauern nur eine) die Teiltabellen als
+    # (Flags, Werte) in Reihenfolge Seite 0, Seite 1.
+    out = bytearray(magic)
+    out.append(file_flags)
+    for sides in files:
+        out.append(0x00)  # Reihenfolge: Gruppe 0 zuerst (beide Seiten)
+        for piece in pieces:
+            hi = piece if len(sides) > 1 else 0
+            out.append(piece | (hi << 4))
+    if len(out) & 1:
+        out.append(0)
+    compressed = [(flags, compress(values)) for sides in files for flags, values in sides]
+    for flags, c in compressed:
+        out += sizes_header(flags, c)
+    if magic == DTZ_MAGIC and len(out) & 1:
+        out.append(0)
+    for _, c in compressed:
+        for b, off in c.sparse:
+            out += struct.pack("<IH", b, off)
+    for _, c in compressed:
+        out += b"".join(struct.pack("<H", n) for n in c.block_lengths)
+    for _, c in compressed:
+        out += bytes(-len(out) % 64)
+        out += b"".join(c.blocks)
+    with open(path, "wb") as f:
+        f.write(out)
+    return len(out)
+
+
+def write_krvk(target):
+    key, wtm, btm, btm_draw = solve_krvk()
+    longest = max(v for v in wtm if v is not None)
+    assert longest == 31, "längster KRvK-Gewinn: Matt in 16 Zügen"
+
+    wdl_white, wdl_black, dtz_white = [], [], []
+    for wk in range(64):
+        for wr in range(64):
+            for bk in range(64):
+                p = key(wk, wr, bk)
+                i = krvk_index((wk, wr, bk))
+                if krvk_legal(wk, wr, bk, True):
+                    wdl_white.append((i, 4))
+                    dtz_white.append((i, (wtm[p] - 1) // 2))
+                if krvk_legal(wk, wr, bk, False):
+                    wdl_black.append((i, 2 if btm_draw[p] else 0))
+
+    # WDL: Wert + 2 (0 = Verlust, 2 = Remis, 4 = Gewinn); Datei-Flag 1 = beide Seiten.
+    n = write_table(os.path.join(target, "KRvK.rtbw"), WDL_MAGIC, 1, KRVK_PIECES,
+                    [[(0, fill_table(KRVK_SIZE, wdl_white)), (0, fill_table(KRVK_SIZE, wdl_black))]])
+    print("KRvK.rtbw", n, "Bytes")
+    # DTZ: nur Weiß am Zug (STM-Flag 0), Werte in Zügen ohne Wertetabelle.
+    n = write_table(os.path.join(target, "KRvK.rtbz"), DTZ_MAGIC, 0, KRVK_PIECES,
+                    [[(0, fill_table(KRVK_SIZE, dtz_white))]])
+    print("KRvK.rtbz", n, "Bytes")
+
+
+def write_kpvk(target):
+    key, wtm, btm = solve_kpvk()
+    # König vor dem Bauern auf der 6. Reihe gewinnt mit beiden Seiten am Zug, der
+    # Randbauer mit dem schwarzen König in der Ecke nie.
+    assert wtm[key(44, 36, 60)] is not None and btm[key(44, 36, 60)] is not None
+    assert all(v is None for v in (wtm[key(42, 48, 56)], btm[key(42, 48, 56)]))
+
+    wdl = [[[], []] for _ in range(4)]
+    dtz = [[] for _ in range(4)]
+    for wk in range(64):
+        for wp in range(8, 56):
+            for bk in range(64):
+                p = key(wk, wp, bk)
+                f, i = kpvk_index((wp, wk, bk))
+                if kpvk_legal(wk, wp, bk, True):
+                    wdl[f][0].append((i, 2 if wtm[p] is None else 4))
+                    dtz[f].append((i, 0 if wtm[p] is None else (wtm[p] - 1) // 2))
+                if kpvk_legal(wk, wp, bk, False):
+                    wdl[f][1].append((i, 2 if btm[p] is None else 0))
+
+    # Datei-Flag 2 = Bauerntabelle, bei WDL zusätzlich 1 = beide Seiten.
+    n = write_table(os.path.join(target, "KPvK.rtbw"), WDL_MAGIC, 3, KPVK_PIECES,
+                    [[(0, fill_table(KPVK_SIZE, side)) for side in sides] for sides in wdl])
+    print("KPvK.rtbw", n, "Bytes")
+    n = write_table(os.path.join(target, "KPvK.rtbz"), DTZ_MAGIC, 2, KPVK_PIECES,
+                    [[(0, fill_table(KPVK_SIZE, values))] for values in dtz])
+    print("KPvK.rtbz", n, "Bytes")
+
+
+def main():
+    target = sys.argv[1] if len(sys.argv) > 1 else os.path.dirname(os.path.abspath(__file__))
+    write_krvk(target)
+    write_kpvk(target)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,80 @@
+use chess_engine::{game_status, parse_fen, Color, GameStatus};
+
+fn status(fen: &str, history: &[&str]) -> GameStatus {
+    let mut pos = parse_fen(fen).expect("valid fen");
+    game_status(&mut pos, &history.join("\n"))
+}
+
+#[test]
+fn mate_and_stalemate() {
+    // Fool's Mate.
+    let mate = "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3";
+    assert_eq!(status(mate, &[]), GameStatus::Checkmate { winner: Color::Black });
+    assert_eq!(status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", &[]), GameStatus::Stalemate);
+}
+
+#[test]
+fn dead_positions() {
+    let dead = [
+        "8/8/4k3/8/8/3K4/8/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/5B2/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/5N2/8 b - - 0 1",
+        // Alle Läufer auf dunklen Feldern (c1, f8, h2).
+        "5b2/8/4k3/8/8/3K4/7B/2B5 w - - 0 1",
+    ];
+    for fen in dead {
+        assert_eq!(status(fen, &[]), GameStatus::InsufficientMaterial, "{}", fen);
+    }
+    let alive = [
+        // Läufer auf verschiedenfarbigen Feldern.
+        "4b3/8/4k3/8/8/3K4/8/2B5 w - - 0 1",
+        "8/8/4k3/8/8/3K4/5NN1/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/5NB1/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/5P2/8 w - - 0 1",
+    ];
+    for fen in alive {
+        assert_eq!(status(fen, &[]), GameStatus::Ongoing, "{}", fen);
+    }
+}
+
+#[test]
+fn move_rules() {
+    assert_eq!(status("8/8/4k3/8/8/3K4/5R2/8 w - - 99 80", &[]), GameStatus::Ongoing);
+    assert_eq!(status("8/8/4k3/8/8/3K4/5R2/8 w - - 100 80", &[]), GameStatus::FiftyMoveRule);
+    assert_eq!(status("8/8/4k3/8/8/3K4/5R2/8 w - - 150 105", &[]), GameStatus::SeventyFiveMoveRule);
+    // Matt hat Vorrang vor der 75-Züge-Regel.
+    assert_eq!(
+        status("4R1k1/5ppp/8/8/8/8/8/6K1 b - - 150 105", &[]),
+        GameStatus::Checkmate { winner: Color::White }
+    );
+}
+
+#[test]
+fn repetitions() {
+    let a = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1";
+    let b = "4k3/8/8/8/8/8/8/R2K4 b - - 1 1";
+    let c = "3k4/8/8/8/8/8/8/R2K4 w - - 2 2";
+    let d = "3k4/8/8/8/8/8/8/R3K3 b - - 3 2";
+    // Halbzug- und Zugzähler spielen für die Wiederholung keine Rolle.
+    assert_eq!(status("4k3/8/8/8/8/8/8/R3K3 w - - 4 3", &[a, b, c, d]), GameStatus::Ongoing);
+    assert_eq!(
+        status("4k3/8/8/8/8/8/8/R3K3 w - - 8 5", &[a, b, c, d, a, b, c, d]),
+        GameStatus::ThreefoldRepetition
+    );
+    assert_eq!(
+        status("4k3/8/8/8/8/8/8/R3K3 w - - 16 9", &[a, b, c, d, a, b, c, d, a, b, c, d, a, b, c, d]),
+        GameStatus::FivefoldRepetition
+    );
+}
+
+#[test]
+fn en_passant_square_without_capture_is_ignored() {
+    // Nach 1.e4 setzt die FEN e3, ein Schlag ist aber nicht möglich.
+    let with_ep = "4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1";
+    let without_ep = "4k3/8/8/8/4P3/8/8/4K3 b - - 0 1";
+    assert_eq!(status(without_ep, &[with_ep, with_ep]), GameStatus::ThreefoldRepetition);
+    // Mit schlagbereitem Bauern auf d4 unterscheiden sich die Stellungen.
+    let with_capture = "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1";
+    let no_capture = "4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1";
+    assert_eq!(status(no_capture, &[with_capture, with_capture]), GameStatus::Ongoing);
+}
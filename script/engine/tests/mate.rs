@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use chess_engine::{
+    game_status, generate_legal_moves, is_in_check, make_move_in_place, parse_fen, parse_uci_move,
+    solve_mate, unmake_move_in_place, GameStatus, MateResult, Position, SearchLimits, SystemClock,
+    Tablebases, START_FEN,
+};
+
+fn solve(fen: &str, moves: u32) -> MateResult {
+    let limits = SearchLimits { tt_mb: 16, ..SearchLimits::default() };
+    solve_mate(fen, moves, &limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+// Spielt die Lösung nach und prüft, dass sie legal ist und mit Matt endet.
+fn assert_mates(fen: &str, result: &MateResult) {
+    let mut pos = parse_fen(fen).unwrap();
+    let attacker = pos.side_to_move;
+    for uci in &result.line {
+        let (mv, promo) = parse_uci_move(&mut pos, uci).unwrap_or_else(|| panic!("illegal {}", uci));
+        make_move_in_place(&mut pos, mv, promo).unwrap();
+    }
+    assert_eq!(game_status(&mut pos, ""), GameStatus::Checkmate { winner: attacker }, "{:?}", result.line);
+    assert_eq!(result.line.len() as u32, 2 * result.mate_in.unwrap() - 1);
+}
+
+#[test]
+fn back_rank_mate_in_one() {
+    let fen = "6k1/5ppp/8/8/8/8/8/3R2K1 w - - 0 1";
+    let result = solve(fen, 1);
+    assert_eq!(result.mate_in, Some(1));
+    assert_eq!(result.line, vec!["d1d8"]);
+    assert!(result.complete);
+}
+
+#[test]
+fn mate_in_two_with_sacrifice() {
+    // Re8+ Rxe8 Rxe8#
+    let fen = "r5k1/5ppp/8/8/8/8/4RPPP/4R1K1 w - - 0 1";
+    let result = solve(fen, 3);
+    assert_eq!(result.mate_in, Some(2));
+    assert_eq!(result.line[0], "e2e8");
+    assert_mates(fen, &result);
+}
+
+#[test]
+fn mate_for_black() {
+    let fen = "3r2k1/8/8/8/8/8/5PPP/6K1 b - - 0 1";
+    let result = solve(fen, 2);
+    assert_eq!(result.mate_in, Some(1));
+    assert_eq!(result.line, vec!["d8d1"]);
+}
+
+// Unabhängige Referenz ohne TT/Ordnung: Kann die Seite am Zug in `moves` Zügen mattsetzen?
+fn brute_force_mate(pos: &mut Position, moves: u32) -> bool {
+    if moves == 0 {
+        return false;
+    }
+    for (mv, promo) in generate_legal_moves(pos) {
+        let undo = make_move_in_place(pos, mv, promo).unwrap();
+        let replies = generate_legal_moves(pos);
+        let forced = if replies.is_empty() {
+            is_in_check(pos, pos.side_to_move)
+        } else {
+            replies.into_iter().all(|(reply, reply_promo)| {
+                let reply_undo = make_move_in_place(pos, reply, reply_promo).unwrap();
+                let mated = brute_force_mate(pos, moves - 1);
+                unmake_move_in_place(pos, reply, reply_promo, reply_undo);
+                mated
+            })
+        };
+        unmake_move_in_place(pos, mv, promo, undo);
+        if forced {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn agrees_with_brute_force() {
+    let fens = [
+        "k7/8/2K5/8/8/8/8/7R w - - 0 1",
+        "7k/8/5K2/8/8/8/8/6Q1 w - - 0 1",
+        "8/8/8/8/8/1k6/8/1K1R4 w - - 0 1",
+        "8/8/8/3k4/8/3K4/8/R7 w - - 0 1",
+        // Matt in 3: kürzere Lösungen gibt es nicht.
+        "1k6/8/3K4/8/8/8/8/7R w - - 0 1",
+    ];
+    for fen in fens {
+        let mut pos = parse_fen(fen).unwrap();
+        let expected = (1..=3).find(|&n| brute_force_mate(&mut pos, n));
+        let result = solve(fen, 3);
+        assert!(result.complete);
+        assert_eq!(result.mate_in, expected, "{}", fen);
+        if expected.is_some() {
+            assert_mates(fen, &result);
+        }
+    }
+}
+
+#[test]
+fn no_mate_is_definitive() {
+    let result = solve(START_FEN, 2);
+    assert_eq!(result.mate_in, None);
+    assert!(result.line.is_empty());
+    assert!(result.complete);
+    assert_eq!(result.searched, 2);
+}
+
+#[test]
+fn tablebases_do_not_shortcut_the_proof() {
+    let mut tb = Tablebases::new();
+    tb.add_directory(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/syzygy")).unwrap();
+    let plain = SearchLimits { tt_mb: 16, ..SearchLimits::default() };
+    let probed = SearchLimits { tablebases: Some(Arc::new(tb)), ..plain.clone() };
+    // KRvKN: Schläge des Springers führen in die KRvK-Tabelle. Mit Tabellen muss die Suche
+    // Knoten für Knoten dieselbe bleiben (keine Tablebase-Cutoffs im Beweis).
+    for fen in ["5n1k/4K3/8/5R2/8/8/8/8 w - - 0 1", "R1n5/8/5K1k/8/8/8/8/8 w - - 0 1"] {
+        let a = solve_mate(fen, 3, &plain, &SystemClock, &mut ()).unwrap();
+        let b = solve_mate(fen, 3, &probed, &SystemClock, &mut ()).unwrap();
+        assert_eq!(a.mate_in, Some(3));
+        assert_mates(fen, &b);
+        assert_eq!((a.mate_in, a.line, a.nodes), (b.mate_in, b.line, b.nodes), "{}", fen);
+    }
+}
@@ -0,0 +1,44 @@
+use chess_engine::{run_search, SearchLimits, SystemClock, START_FEN};
+
+fn search(fen: &str, depth: u32, multipv: u32) -> chess_engine::SearchResult {
+    let limits = SearchLimits { depth, multipv, tt_mb: 8, ..SearchLimits::default() };
+    run_search(fen, "", &limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+#[test]
+fn returns_distinct_root_moves() {
+    let result = search(START_FEN, 4, 3);
+    assert_eq!(result.lines.len(), 3);
+    let firsts: Vec<&str> = result.lines.iter().map(|l| l.pv[0].as_str()).collect();
+    assert_eq!(Some(firsts[0]), result.best.as_deref());
+    assert!(firsts[0] != firsts[1] && firsts[1] != firsts[2] && firsts[0] != firsts[2]);
+    for (k, line) in result.lines.iter().enumerate() {
+        assert_eq!(line.multipv, k as u32 + 1);
+        assert_eq!(line.depth, 4);
+    }
+    assert_eq!(result.lines[0].score, result.score);
+}
+
+#[test]
+fn capped_by_legal_moves() {
+    // Turm auf der b-Linie lässt nur Ka7.
+    let result = search("k7/8/8/8/8/8/8/1R5K b - - 0 1", 3, 5);
+    assert_eq!(result.lines.len(), 1);
+    let result = search("7k/8/8/8/8/8/8/K7 w - - 0 1", 2, 5);
+    assert_eq!(result.lines.len(), 3);
+}
+
+#[test]
+fn single_line_by_default() {
+    let result = search(START_FEN, 3, 0);
+    assert_eq!(result.lines.len(), 1);
+    assert_eq!(result.lines[0].pv, result.pv);
+}
+
+#[test]
+fn second_line_is_not_the_winning_capture() {
+    // Dame hängt auf d5: Bester Zug schlägt sie, die zweite Variante ist deutlich schlechter.
+    let result = search("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", 4, 2);
+    assert_eq!(result.best.as_deref(), Some("d2d5"));
+    assert!(result.lines[1].score < result.lines[0].score - 300);
+}
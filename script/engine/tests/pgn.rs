@@ -0,0 +1,63 @@
+use chess_engine::{parse_pgn, parse_pgn_games, write_pgn, START_FEN};
+
+const GAME: &str = r#"[Event "Casual \"Blitz\""]
+[Site "?"]
+[White "Anderssen"]
+[Black "Kieseritzky"]
+[Result "1-0"]
+
+1. e4 e5 2. f4 exf4 {King's Gambit} 3. Bc4 Qh4+ 4. Kf1 b5 $1 5. Bxb5 Nf6
+(5... Qf6 6. Nc3 (6. e5) ) 6. Nf3 Qh6 7. d3 Nh5 8. Nh4 Qg5 ; Kommentar bis Zeilenende
+9. Nf5 c6 10. g4 Nf6 11. Rg1 cxb5 12. h4 Qg6 13. h5 Qg5 14. Qf3 Ng8 15. Bxf4 Qf6
+16. Nc3 Bc5 17. Nd5 Qxb2 18. Bd6 Bxg1 19. e5 Qxa1+ 20. Ke2 Na6 21. Nxg7+ Kd8
+22. Qf6+ Nxf6 23. Be7# 1-0
+"#;
+
+#[test]
+fn parses_immortal_game() {
+    let game = parse_pgn(GAME).expect("valid pgn");
+    assert_eq!(game.tag("Event"), Some("Casual \"Blitz\""));
+    assert_eq!(game.tag("White"), Some("Anderssen"));
+    assert_eq!(game.start_fen, START_FEN);
+    assert_eq!(game.moves.len(), 45);
+    assert_eq!(game.fens.len(), 46);
+    assert_eq!(&game.moves[..4], ["e2e4", "e7e5", "f2f4", "e5f4"]);
+    assert_eq!(game.moves.last().map(String::as_str), Some("d6e7"));
+    assert_eq!(game.result, "1-0");
+}
+
+#[test]
+fn round_trips_through_writer() {
+    let game = parse_pgn(GAME).unwrap();
+    let tags = vec![("White".to_string(), "Anderssen".to_string()), ("ECO".to_string(), "C33".to_string())];
+    let text = write_pgn(START_FEN, &game.moves, &tags).unwrap();
+    assert!(text.starts_with("[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"Anderssen\"]\n"));
+    assert!(text.contains("[Result \"1-0\"]\n[ECO \"C33\"]\n\n1. e4 e5 2. f4 exf4 3. Bc4 Qh4+"));
+    assert!(text.trim_end().ends_with("23. Be7# 1-0"));
+    assert!(text.lines().all(|l| l.len() <= 79));
+
+    let again = parse_pgn(&text).unwrap();
+    assert_eq!(again.moves, game.moves);
+}
+
+#[test]
+fn writes_setup_for_custom_start() {
+    let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 30";
+    let moves = vec!["e8d7".to_string(), "e2e4".to_string()];
+    let text = write_pgn(fen, &moves, &[]).unwrap();
+    assert!(text.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 30\"]\n"));
+    assert!(text.contains("30... Kd7 31. e4 *"));
+    let game = parse_pgn(&text).unwrap();
+    assert_eq!(game.start_fen, fen);
+    assert_eq!(game.moves, moves);
+}
+
+#[test]
+fn multiple_games_and_errors() {
+    let text = format!("{}\n[Event \"Broken\"]\n\n1. e4 e5 2. Ke3 *\n\n1. d4 d5 *\n", GAME);
+    let games = parse_pgn_games(&text);
+    assert_eq!(games.len(), 3);
+    assert!(games[0].is_ok());
+    assert!(games[1].as_ref().unwrap_err().contains("Ke3"));
+    assert_eq!(games[2].as_ref().unwrap().moves, ["d2d4", "d7d5"]);
+}
@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chess_engine::{run_search, ProgressSink, SearchLimits, SystemClock, START_FEN};
+
+fn ponder_limits(time_ms: u32, ponderhit: &Arc<AtomicBool>, stop: &Arc<AtomicBool>) -> SearchLimits {
+    SearchLimits {
+        time_ms,
+        tt_mb: 8,
+        ponder: true,
+        ponderhit: Some(Arc::clone(ponderhit)),
+        stop: Some(Arc::clone(stop)),
+        ..SearchLimits::default()
+    }
+}
+
+fn set_after(flag: &Arc<AtomicBool>, ms: u64) -> thread::JoinHandle<()> {
+    let flag = Arc::clone(flag);
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(ms));
+        flag.store(true, Ordering::Relaxed);
+    })
+}
+
+#[test]
+fn time_limit_starts_at_ponderhit() {
+    let ponderhit = Arc::new(AtomicBool::new(false));
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = ponder_limits(100, &ponderhit, &stop);
+    let setter = set_after(&ponderhit, 400);
+    let started = Instant::now();
+    let result = run_search(START_FEN, "", &limits, &SystemClock, &mut ()).unwrap();
+    setter.join().unwrap();
+
+    // Vor dem Ponderhit zählt das Zeitlimit nicht, danach endet die Suche zügig.
+    assert!(started.elapsed() >= Duration::from_millis(400));
+    assert!(started.elapsed() < Duration::from_millis(2_000));
+    assert!(result.best.is_some());
+}
+
+#[test]
+fn stop_ends_ponder_without_ponderhit() {
+    let ponderhit = Arc::new(AtomicBool::new(false));
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = ponder_limits(50, &ponderhit, &stop);
+    let setter = set_after(&stop, 300);
+    let started = Instant::now();
+    let result = run_search(START_FEN, "", &limits, &SystemClock, &mut ()).unwrap();
+    setter.join().unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(300));
+    assert!(!ponderhit.load(Ordering::Relaxed));
+    assert!(result.depth >= 1);
+}
+
+// Meldet den Ponderhit über den Sink, wie der Browser-Worker über sein Flag.
+struct HitAfter(u32);
+
+impl ProgressSink for HitAfter {
+    fn ponderhit(&mut self) -> bool {
+        self.0 = self.0.saturating_sub(1);
+        self.0 == 0
+    }
+}
+
+#[test]
+fn sink_reports_ponderhit() {
+    let limits = SearchLimits { time_ms: 100, tt_mb: 8, ponder: true, ..SearchLimits::default() };
+    let started = Instant::now();
+    let result = run_search(START_FEN, "", &limits, &SystemClock, &mut HitAfter(3)).unwrap();
+
+    assert!(started.elapsed() < Duration::from_millis(2_000));
+    assert!(result.best.is_some());
+}
@@ -0,0 +1,81 @@
+use chess_engine::{BookSelection, PositionBook, START_FEN};
+
+const AFTER_E4_E5_NF3: &str = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2";
+
+const TRANSPOSED: &str = r#"{
+    "": {"moves": {"e2e4": 0.6, "g1f3": 0.4}},
+    "e2e4": {"moves": {"e7e5": 1}},
+    "g1f3": {"moves": {"e7e5": 1}},
+    "e2e4 e7e5": {"moves": {"g1f3": 1}},
+    "g1f3 e7e5": {"moves": {"e2e4": 1}},
+    "e2e4 e7e5 g1f3": {"moves": {"b8c6": 0.6}},
+    "g1f3 e7e5 e2e4": {"moves": {"b8c6": 0.2, "g8f6": 0.2}}
+}"#;
+
+fn moves(book: &PositionBook, fen: &str) -> Vec<(String, u16)> {
+    book.probe(fen).unwrap().into_iter().map(|m| (m.uci, m.weight)).collect()
+}
+
+#[test]
+fn transpositions_share_one_entry() {
+    let book = PositionBook::from_history_json(TRANSPOSED).unwrap();
+    assert_eq!(moves(&book, START_FEN), [("e2e4".to_string(), 6000), ("g1f3".to_string(), 4000)]);
+    // Beide Zugfolgen führen in dieselbe Stellung; die Anteile addieren sich.
+    assert_eq!(moves(&book, AFTER_E4_E5_NF3), [("b8c6".to_string(), 8000), ("g8f6".to_string(), 2000)]);
+    assert_eq!(book.book_move(AFTER_E4_E5_NF3, BookSelection::Best, 0).as_deref(), Some("b8c6"));
+    assert!(book.probe("8/8/8/8/8/8/8/K1k5 w - - 0 1").unwrap().is_empty());
+}
+
+#[test]
+fn binary_round_trip() {
+    let book = PositionBook::from_history_json(TRANSPOSED).unwrap();
+    let bytes = book.to_bytes();
+    assert_eq!(bytes.len(), 8 + 12 * book.len());
+    let loaded = PositionBook::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.len(), book.len());
+    assert_eq!(loaded.positions(), 6);
+    assert_eq!(moves(&loaded, AFTER_E4_E5_NF3), moves(&book, AFTER_E4_E5_NF3));
+
+    assert!(PositionBook::from_bytes(b"CPB0\0\0\0\0").is_err());
+    assert!(PositionBook::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn skips_illegal_moves_and_extra_fields() {
+    let json = r#"{"": {"moves": {"e2e4": 0.9, "e2e5": 0.1}, "scores": {"e2e4": 30}},
+                   "e2e5": {"moves": {"e7e5": 1}},
+                   "e2e4": {"moves": {"c7c5": 1}}}"#;
+    let mut book = PositionBook::default();
+    assert_eq!(book.merge_history_json(json), Ok(2));
+    assert_eq!(moves(&book, START_FEN), [("e2e4".to_string(), 9000)]);
+    // Ein nicht schlagbares En-passant-Feld ändert den Schlüssel nicht.
+    let after_e4 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+    assert_eq!(moves(&book, after_e4), moves(&book, &after_e4.replace(" e3 ", " - ")));
+    assert_eq!(moves(&book, after_e4), [("c7c5".to_string(), 10000)]);
+
+    assert!(book.merge_history_json(r#"{"": {"moves": {"e2e4": }}}"#).is_err());
+}
+
+#[test]
+fn replays_histories_without_parent_entry() {
+    // Weder die Grundstellung noch "e2e4" oder "e2e4 e7e5" stehen im Buch.
+    let json = r#"{"e2e4 e7e5 g1f3": {"moves": {"b8c6": 1}},
+                   "e2e4 e7e5 g1f3 b8c6": {"moves": {"f1b5": 1}},
+                   "e2e4 e7e5 e1e3": {"moves": {"d7d6": 1}}}"#;
+    let mut book = PositionBook::default();
+    assert_eq!(book.merge_history_json(json), Ok(2));
+    assert_eq!(moves(&book, AFTER_E4_E5_NF3), [("b8c6".to_string(), 10000)]);
+    let after_nc6 = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+    assert_eq!(moves(&book, after_nc6), [("f1b5".to_string(), 10000)]);
+    assert!(moves(&book, START_FEN).is_empty());
+}
+
+#[test]
+fn converts_bundled_book() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/openingBook/opening_book_1000.json");
+    let json = std::fs::read_to_string(path).unwrap();
+    let book = PositionBook::from_history_json(&json).unwrap();
+    assert!(book.positions() > 1000);
+    let start = book.probe(START_FEN).unwrap();
+    assert_eq!((start[0].uci.as_str(), start[0].weight), ("e2e4", 5341));
+}
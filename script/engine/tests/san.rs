@@ -0,0 +1,62 @@
+use chess_engine::{move_to_san, move_to_uci, parse_fen, parse_san, parse_uci_move, START_FEN};
+
+fn san(fen: &str, uci: &str) -> String {
+    let mut pos = parse_fen(fen).expect("valid fen");
+    let (mv, promo) = parse_uci_move(&mut pos, uci).expect("legal move");
+    move_to_san(&mut pos, mv, promo)
+}
+
+fn uci(fen: &str, san: &str) -> Option<String> {
+    let mut pos = parse_fen(fen).expect("valid fen");
+    parse_san(&mut pos, san).map(|(mv, promo)| move_to_uci(mv, promo))
+}
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn formats_san() {
+    assert_eq!(san(START_FEN, "e2e4"), "e4");
+    assert_eq!(san(START_FEN, "g1f3"), "Nf3");
+    assert_eq!(san(KIWIPETE, "e1g1"), "O-O");
+    assert_eq!(san(KIWIPETE, "e1c1"), "O-O-O");
+    assert_eq!(san(KIWIPETE, "d5e6"), "dxe6");
+    assert_eq!(san(KIWIPETE, "e5f7"), "Nxf7");
+    assert_eq!(san(KIWIPETE, "f3f6"), "Qxf6");
+    assert_eq!(san("4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "a1d1"), "Rad1");
+    assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3"), "R1a3");
+    assert_eq!(san("4k3/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1", "a1b2"), "Qa1b2");
+    assert_eq!(san("8/4P3/8/8/8/8/8/k3K3 w - - 0 1", "e7e8q"), "e8=Q");
+    assert_eq!(san("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "h1h8"), "Rh8+");
+    assert_eq!(san("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2", "d8h4"), "Qh4#");
+    assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), "exd6");
+}
+
+#[test]
+fn parses_san() {
+    assert_eq!(uci(START_FEN, "e4").as_deref(), Some("e2e4"));
+    assert_eq!(uci(START_FEN, "Nf3").as_deref(), Some("g1f3"));
+    assert_eq!(uci(START_FEN, "Ngf3!?").as_deref(), Some("g1f3"));
+    assert_eq!(uci(START_FEN, "e2-e4").as_deref(), Some("e2e4"));
+    assert_eq!(uci(KIWIPETE, "0-0-0").as_deref(), Some("e1c1"));
+    assert_eq!(uci(KIWIPETE, "O-O+").as_deref(), Some("e1g1"));
+    assert_eq!(uci(KIWIPETE, "de6").as_deref(), Some("d5e6"));
+    assert_eq!(uci("8/4P3/8/8/8/8/8/k3K3 w - - 0 1", "e8=N").as_deref(), Some("e7e8n"));
+    assert_eq!(uci("8/4P3/8/8/8/8/8/k3K3 w - - 0 1", "e8r").as_deref(), Some("e7e8r"));
+    assert_eq!(uci("8/4P3/8/8/8/8/8/k3K3 w - - 0 1", "e8").as_deref(), Some("e7e8q"));
+    // Mehrdeutig bzw. illegal.
+    assert_eq!(uci("4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "Rd1"), None);
+    assert_eq!(uci(START_FEN, "e5"), None);
+    assert_eq!(uci(START_FEN, "Nf4"), None);
+}
+
+#[test]
+fn round_trip_all_moves() {
+    for fen in [START_FEN, KIWIPETE, "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1"] {
+        let mut pos = parse_fen(fen).unwrap();
+        for (mv, promo) in chess_engine::generate_legal_moves(&mut pos) {
+            let text = move_to_san(&mut pos, mv, promo);
+            let back = parse_san(&mut pos, &text).map(|(m, p)| move_to_uci(m, p));
+            assert_eq!(back, Some(move_to_uci(mv, promo)), "{} in {}", text, fen);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use chess_engine::{run_search, ProgressSink, ScoreBound, SearchInfo, SearchLimits, SystemClock};
+
+#[derive(Default)]
+struct Collect(Vec<SearchInfo>);
+
+impl ProgressSink for Collect {
+    fn iteration(&mut self, info: &SearchInfo) {
+        self.0.push(info.clone());
+    }
+}
+
+fn infos(fen: &str, depth: u32) -> (Vec<SearchInfo>, chess_engine::SearchResult) {
+    let mut sink = Collect::default();
+    let limits = SearchLimits { depth, tt_mb: 8, ..SearchLimits::default() };
+    let result = run_search(fen, "", &limits, &SystemClock, &mut sink).unwrap();
+    (sink.0, result)
+}
+
+#[test]
+fn one_exact_info_per_iteration() {
+    let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let (infos, result) = infos(kiwipete, 5);
+
+    let exact: Vec<_> = infos.iter().filter(|i| i.bound == ScoreBound::Exact).collect();
+    assert_eq!(exact.iter().map(|i| i.depth).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    for info in &exact {
+        // Quiescence reicht über die Nominaltiefe hinaus.
+        assert!(info.seldepth >= info.depth);
+        assert_eq!(info.best.as_ref(), info.pv.first());
+        assert!(info.hashfull <= 1000);
+    }
+    let last = exact.last().unwrap();
+    assert_eq!(last.score, result.score);
+    assert_eq!(last.best, result.best);
+    assert!(last.hashfull > 0);
+}
+
+#[test]
+fn aspiration_failures_are_reported_as_bounds() {
+    // CPW-Perft-Stellung 3: auf Tiefe 6 fällt der Score unter das Aspiration-Fenster.
+    let (infos, _) = infos("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 6);
+
+    let bounds: Vec<_> = infos.iter().filter(|i| i.bound != ScoreBound::Exact).collect();
+    assert!(!bounds.is_empty());
+    for info in bounds {
+        // Tiefe 1 sucht ohne Fenster; jeder Zwischenstand hat einen Zug.
+        assert!(info.depth >= 2);
+        assert!(info.best.is_some());
+    }
+    // Die Iteration endet trotzdem mit einem exakten Event.
+    let last = infos.last().unwrap();
+    assert_eq!((last.depth, last.bound), (6, ScoreBound::Exact));
+}
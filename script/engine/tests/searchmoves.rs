@@ -0,0 +1,52 @@
+use chess_engine::{run_search, score_root_moves, SearchLimits, SearchResult, SystemClock, START_FEN};
+
+// Dame hängt auf d5, Td2 kann sie schlagen.
+const HANGING_QUEEN: &str = "4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1";
+
+fn search(fen: &str, depth: u32, searchmoves: &[&str]) -> SearchResult {
+    let searchmoves = searchmoves.iter().map(|m| m.to_string()).collect();
+    let limits = SearchLimits { depth, tt_mb: 8, searchmoves, ..SearchLimits::default() };
+    run_search(fen, "", &limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+#[test]
+fn best_move_comes_from_the_list() {
+    let result = search(START_FEN, 4, &["a2a3", "h2h3"]);
+    let best = result.best.clone().expect("move");
+    assert!(best == "a2a3" || best == "h2h3");
+    assert_eq!(result.pv.first(), Some(&best));
+}
+
+#[test]
+fn excluding_the_capture_lowers_the_score() {
+    let full = search(HANGING_QUEEN, 4, &[]);
+    let restricted = search(HANGING_QUEEN, 4, &["d2d1", "e1f1"]);
+    assert_eq!(full.best.as_deref(), Some("d2d5"));
+    assert_ne!(restricted.best.as_deref(), Some("d2d5"));
+    assert!(restricted.score < full.score - 300);
+}
+
+#[test]
+fn illegal_entries_are_ignored() {
+    let result = search(START_FEN, 3, &["e2e5", "g1f3", "zz"]);
+    assert_eq!(result.best.as_deref(), Some("g1f3"));
+
+    // Ohne einen legalen Eintrag wird wie üblich über alle Züge gesucht.
+    let result = search(START_FEN, 3, &["e2e5"]);
+    assert!(result.best.is_some());
+}
+
+#[test]
+fn scores_each_listed_move() {
+    let moves: Vec<String> = ["d2d1", "d2d5", "e1f1", "a1a2"].iter().map(|m| m.to_string()).collect();
+    let limits = SearchLimits { depth: 4, tt_mb: 8, ..SearchLimits::default() };
+    let result = score_root_moves(HANGING_QUEEN, "", &moves, &limits, &SystemClock, &mut ()).unwrap();
+
+    // `a1a2` ist illegal; die drei übrigen Züge bekommen je eine eigene Variante.
+    assert_eq!(result.lines.len(), 3);
+    let firsts: Vec<&str> = result.lines.iter().map(|l| l.pv[0].as_str()).collect();
+    assert_eq!(firsts[0], "d2d5");
+    assert!(firsts.contains(&"d2d1") && firsts.contains(&"e1f1"));
+    assert!(result.lines.windows(2).all(|w| w[0].score >= w[1].score));
+    assert!(result.lines[1].score < result.lines[0].score - 300);
+}
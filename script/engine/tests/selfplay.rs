@@ -0,0 +1,61 @@
+use chess_engine::{
+    play_game, GameResult, MatchStats, Player, PlayerConfig, Sprt, SprtDecision, SystemClock, TimeControl,
+};
+
+fn stats(wins: u32, draws: u32, losses: u32) -> MatchStats {
+    MatchStats { wins, draws, losses }
+}
+
+#[test]
+fn stats_give_score_elo_and_error() {
+    let s = stats(60, 20, 20);
+    assert_eq!(s.games(), 100);
+    assert!((s.score() - 0.7).abs() < 1e-9);
+    assert!((s.elo() - 147.2).abs() < 0.1, "{}", s.elo());
+    assert!(s.elo_error() > 0.0 && s.elo_error() < 100.0);
+    // Mehr Partien, gleiches Verhältnis: engeres Intervall.
+    assert!(stats(600, 200, 200).elo_error() < s.elo_error());
+    assert_eq!(stats(5, 0, 5).elo(), 0.0);
+
+    let mut counted = MatchStats::default();
+    counted.record(GameResult::WhiteWins, true);
+    counted.record(GameResult::WhiteWins, false);
+    counted.record(GameResult::BlackWins, false);
+    counted.record(GameResult::Draw, true);
+    assert_eq!(counted, stats(2, 1, 1));
+}
+
+#[test]
+fn sprt_accepts_h1_h0_or_continues() {
+    let sprt = Sprt::new(0.0, 5.0);
+    let (lower, upper) = sprt.bounds();
+    assert!((lower + 2.944).abs() < 1e-3 && (upper - 2.944).abs() < 1e-3);
+    assert_eq!(sprt.decision(&stats(600, 200, 200)), Some(SprtDecision::AcceptH1));
+    assert_eq!(sprt.decision(&stats(6000, 8000, 6000)), Some(SprtDecision::AcceptH0));
+    assert_eq!(sprt.decision(&stats(6, 2, 2)), None);
+    assert_eq!(sprt.decision(&MatchStats::default()), None);
+}
+
+#[test]
+fn game_ends_with_mate_in_one() {
+    let mut white = Player::new(PlayerConfig::new("A"));
+    let mut black = Player::new(PlayerConfig::new("B"));
+    let record =
+        play_game(&mut white, &mut black, "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1", TimeControl::Depth(3), &SystemClock)
+            .unwrap();
+    assert_eq!(record.result, GameResult::WhiteWins);
+    assert_eq!(record.reason, "checkmate");
+    assert_eq!(record.moves, ["a1a8"]);
+}
+
+#[test]
+fn dead_positions_are_drawn_and_bad_fens_rejected() {
+    let mut a = Player::new(PlayerConfig::new("A"));
+    let mut b = Player::new(PlayerConfig::new("B"));
+    let record = play_game(&mut a, &mut b, "8/8/4k3/8/8/3K4/8/8 w - - 0 1", TimeControl::Nodes(1000), &SystemClock)
+        .unwrap();
+    assert_eq!(record.result, GameResult::Draw);
+    assert_eq!(record.reason, "insufficient_material");
+    assert!(record.moves.is_empty());
+    assert!(play_game(&mut a, &mut b, "kein fen", TimeControl::Depth(1), &SystemClock).is_err());
+}
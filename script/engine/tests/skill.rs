@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use chess_engine::{
+    run_search, skill_level_elo, skill_level_for_elo, Engine, ProgressSink, ScoreBound, SearchInfo,
+    SearchLimits, SearchResult, SystemClock, MAX_SKILL_LEVEL, START_FEN,
+};
+
+fn search(fen: &str, depth: u32, skill_level: Option<u32>, seed: u64) -> SearchResult {
+    let limits = SearchLimits { depth, tt_mb: 8, skill_level, seed, ..SearchLimits::default() };
+    run_search(fen, "", &limits, &SystemClock, &mut ()).expect("valid fen")
+}
+
+#[test]
+fn calibration_is_monotonic() {
+    for level in 1..=MAX_SKILL_LEVEL {
+        assert!(skill_level_elo(level) > skill_level_elo(level - 1));
+        assert_eq!(skill_level_for_elo(skill_level_elo(level)), level);
+        assert_eq!(skill_level_for_elo(skill_level_elo(level) - 1), level - 1);
+    }
+    assert_eq!(skill_level_for_elo(0), 0);
+    assert_eq!(skill_level_for_elo(u32::MAX), MAX_SKILL_LEVEL);
+}
+
+#[test]
+fn same_seed_same_move() {
+    let a = search(START_FEN, 6, Some(4), 42);
+    let b = search(START_FEN, 6, Some(4), 42);
+    assert_eq!(a.best, b.best);
+    assert_eq!(a.score, b.score);
+    assert_eq!(a.nodes, b.nodes);
+}
+
+#[test]
+fn top_level_is_full_strength() {
+    let fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+    let full = search(fen, 4, None, 7);
+    let top = search(fen, 4, Some(MAX_SKILL_LEVEL), 7);
+    assert_eq!(full.best, top.best);
+    assert_eq!(full.score, top.score);
+    assert_eq!(top.lines.len(), 1);
+}
+
+#[test]
+fn weak_level_varies_within_candidates() {
+    let mut played = HashSet::new();
+    for seed in 1..=16 {
+        let result = search(START_FEN, 8, Some(0), seed);
+        // Stufe 0 rechnet höchstens einen Halbzug tief.
+        assert!(result.depth <= 1);
+        let best = result.best.expect("move");
+        assert!(result.lines.iter().any(|line| line.pv[0] == best));
+        assert_eq!(result.pv.first(), Some(&best));
+        played.insert(best);
+    }
+    assert!(played.len() > 1, "Stufe 0 spielt immer {:?}", played);
+}
+
+// Zählt die Varianten je abgeschlossener Iteration (Fail-High/-Low-Meldungen ohne Varianten zählen nicht).
+#[derive(Default)]
+struct LineCounts(Vec<usize>);
+
+impl ProgressSink for LineCounts {
+    fn iteration(&mut self, info: &SearchInfo) {
+        if info.bound == ScoreBound::Exact {
+            self.0.push(info.lines.len());
+        }
+    }
+}
+
+#[test]
+fn candidates_stay_internal() {
+    for seed in 1..=8 {
+        let mut sink = LineCounts::default();
+        let limits = SearchLimits { depth: 3, skill_level: Some(2), seed, ..SearchLimits::default() };
+        let result = Engine::new(8).search(START_FEN, "", &limits, &SystemClock, &mut sink).unwrap();
+        // Ohne Multi-PV-Wunsch genau eine Variante, und zwar die des gespielten Zugs.
+        assert!(!sink.0.is_empty() && sink.0.iter().all(|&n| n == 1), "{:?}", sink.0);
+        assert_eq!(result.lines.len(), 1);
+        assert_eq!(result.lines[0].pv.first(), result.best.as_ref());
+    }
+}
+
+#[test]
+fn noisy_searches_keep_the_engine_table_clean() {
+    let mut engine = Engine::new(1);
+    let weak = SearchLimits { depth: 4, skill_level: Some(10), seed: 3, ..SearchLimits::default() };
+    engine.search(START_FEN, "", &weak, &SystemClock, &mut ()).unwrap();
+    assert_eq!(engine.hashfull(), 0);
+
+    let full = SearchLimits { depth: 7, ..SearchLimits::default() };
+    engine.search(START_FEN, "", &full, &SystemClock, &mut ()).unwrap();
+    assert!(engine.hashfull() > 0);
+}
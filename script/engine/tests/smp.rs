@@ -0,0 +1,58 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chess_engine::{mate_in_moves, parse_fen, parse_uci_move, run_search, Engine, SearchLimits, SystemClock};
+
+use common::{search_with, MIDGAME};
+
+#[test]
+fn helpers_add_nodes_and_keep_main_result_legal() {
+    let limits = SearchLimits { depth: 7, threads: 4, ..SearchLimits::default() };
+    let result = search_with(&mut Engine::new(16), MIDGAME, &limits);
+    assert_eq!(result.depth, 7);
+    // `nodes_completed` zählt nur den Haupt-Thread, `nodes` zusätzlich die Helfer.
+    assert!(result.nodes > result.nodes_completed, "{} vs {}", result.nodes, result.nodes_completed);
+    let best = result.best.expect("best move");
+    assert!(parse_uci_move(&mut parse_fen(MIDGAME).unwrap(), &best).is_some());
+}
+
+#[test]
+fn finds_mate_with_shared_table() {
+    let fen = "6k1/5ppp/8/8/8/8/8/3R2K1 w - - 0 1";
+    let limits = SearchLimits { depth: 6, tt_mb: 8, threads: 3, ..SearchLimits::default() };
+    let result = run_search(fen, "", &limits, &SystemClock, &mut ()).unwrap();
+    assert_eq!(result.best.as_deref(), Some("d1d8"));
+    assert_eq!(mate_in_moves(result.score), Some(1));
+}
+
+#[test]
+fn deterministic_mode_ignores_threads() {
+    let single = SearchLimits { depth: 5, deterministic: true, ..SearchLimits::default() };
+    let threaded = SearchLimits { threads: 4, ..single.clone() };
+    let a = search_with(&mut Engine::new(16), MIDGAME, &single);
+    let b = search_with(&mut Engine::new(16), MIDGAME, &threaded);
+    assert_eq!((a.best, a.score, a.nodes), (b.best, b.score, b.nodes));
+}
+
+#[test]
+fn stop_flag_ends_all_threads() {
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = SearchLimits { infinite: true, threads: 4, stop: Some(Arc::clone(&stop)), ..SearchLimits::default() };
+    let setter = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            stop.store(true, Ordering::Relaxed);
+        })
+    };
+    let started = Instant::now();
+    let result = search_with(&mut Engine::new(16), MIDGAME, &limits);
+    setter.join().unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(result.best.is_some());
+}
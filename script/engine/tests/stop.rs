@@ -0,0 +1,61 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chess_engine::{run_search, ProgressSink, SearchLimits, SystemClock, START_FEN};
+
+#[test]
+fn infinite_search_runs_until_stop_flag() {
+    let stop = Arc::new(AtomicBool::new(false));
+    let limits = SearchLimits { infinite: true, tt_mb: 8, stop: Some(Arc::clone(&stop)), ..SearchLimits::default() };
+    let setter = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(300));
+            stop.store(true, Ordering::Relaxed);
+        })
+    };
+    let started = Instant::now();
+    let result = run_search(START_FEN, "", &limits, &SystemClock, &mut ()).unwrap();
+    setter.join().unwrap();
+
+    assert!(started.elapsed() >= Duration::from_millis(300));
+    assert!(started.elapsed() < Duration::from_secs(10));
+    // Ergebnis der letzten abgeschlossenen Iteration.
+    assert!(result.depth >= 1);
+    assert_eq!(result.pv.first(), result.best.as_ref());
+}
+
+// Stoppt nach einer festen Zahl an Abfragen, wie der Browser-Worker über sein Flag.
+struct StopAfter(u32);
+
+impl ProgressSink for StopAfter {
+    fn stop_requested(&mut self) -> bool {
+        self.0 = self.0.saturating_sub(1);
+        self.0 == 0
+    }
+}
+
+#[test]
+fn sink_can_stop_search() {
+    let limits = SearchLimits { infinite: true, tt_mb: 8, ..SearchLimits::default() };
+    let result = run_search(START_FEN, "", &limits, &SystemClock, &mut StopAfter(200)).unwrap();
+    assert!(result.depth < 64);
+    assert!(result.best.is_some());
+}
+
+#[test]
+fn infinite_search_does_not_stop_on_mate() {
+    // Matt in 1 (Dd8#); ohne `infinite` endet die Suche sofort nach Tiefe 1.
+    let fen = "6k1/5ppp/8/8/8/8/8/3Q2K1 w - - 0 1";
+    let finite = SearchLimits { time_ms: 5_000, tt_mb: 8, ..SearchLimits::default() };
+    let result = run_search(fen, "", &finite, &SystemClock, &mut ()).unwrap();
+    assert_eq!(result.best.as_deref(), Some("d1d8"));
+    assert!(result.depth < 4);
+
+    let limits = SearchLimits { infinite: true, tt_mb: 8, ..SearchLimits::default() };
+    let result = run_search(fen, "", &limits, &SystemClock, &mut StopAfter(2_000)).unwrap();
+    assert_eq!(result.best.as_deref(), Some("d1d8"));
+    assert!(result.depth >= 4);
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use chess_engine::{
+    generate_legal_moves, is_in_check, make_move_in_place, parse_fen, run_search, unmake_move_in_place, Position,
+    SearchLimits, SystemClock, Tablebases, Wdl, START_FEN,
+};
+
+const WDL_MAGIC: [u8; 4] = [0x71, 0xE8, 0x23, 0x5D];
+const DTZ_MAGIC: [u8; 4] = [0xD7, 0x66, 0x0C, 0xA5];
+
+const KRK: &str = "8/8/8/4k3/8/8/8/R3K3 w - - 0 1";
+
+// Echte KRvK- und KPvK-Tabellen im Syzygy-Format, erzeugt von `tests/fixtures/syzygy/gen_tables.py`.
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/syzygy");
+
+fn fixture_tables() -> Tablebases {
+    let mut tb = Tablebases::new();
+    assert_eq!(tb.add_directory(FIXTURES).unwrap(), 4);
+    tb
+}
+
+// FEN aus (Feld, Figur) mit a1 = 0; `None` bei Doppelbelegung oder illegaler Stellung.
+fn position_of(pieces: &[(usize, char)], side: &str) -> Option<Position> {
+    let mut board = [['1'; 8]; 8];
+    for &(sq, piece) in pieces {
+        if board[7 - sq / 8][sq % 8] != '1' {
+            return None;
+        }
+        board[7 - sq / 8][sq % 8] = piece;
+    }
+    let rows: Vec<String> = board.iter().map(|row| row.iter().collect()).collect();
+    let pos = parse_fen(&format!("{} {} - - 0 1", rows.join("/"), side))?;
+    let waiting = pos.side_to_move.opposite();
+    (!is_in_check(&pos, waiting)).then_some(pos)
+}
+
+fn probe(tb: &Tablebases, fen: &str) -> (Option<Wdl>, Option<i32>) {
+    let mut pos = parse_fen(fen).unwrap();
+    (tb.probe_wdl(&mut pos), tb.probe_dtz(&mut pos))
+}
+
+#[test]
+fn tables_are_registered_by_name_and_magic() {
+    let mut tb = Tablebases::new();
+    assert!(tb.add_table("KRvK.rtbw", WDL_MAGIC.to_vec()));
+    assert!(tb.add_table("KRvK.rtbz", DTZ_MAGIC.to_vec()));
+    // Falsche Magic, unbekannte Endung, kaputter Materialname.
+    assert!(!tb.add_table("KQvK.rtbw", DTZ_MAGIC.to_vec()));
+    assert!(!tb.add_table("KQvK.bin", WDL_MAGIC.to_vec()));
+    assert!(!tb.add_table("KXvK.rtbw", WDL_MAGIC.to_vec()));
+    assert_eq!((tb.wdl_count(), tb.dtz_count(), tb.max_pieces()), (1, 1, 3));
+}
+
+#[test]
+fn missing_or_truncated_tables_give_no_result() {
+    let mut tb = Tablebases::new();
+    tb.add_table("KRvK.rtbw", WDL_MAGIC.to_vec());
+    tb.add_table("KRvK.rtbz", DTZ_MAGIC.to_vec());
+
+    // Nur der Header fehlt: die Probe scheitert, statt einen Wert zu erfinden.
+    let mut krk = parse_fen(KRK).unwrap();
+    assert_eq!(tb.probe_wdl(&mut krk), None);
+    assert_eq!(tb.probe_dtz(&mut krk), None);
+
+    // Zu viele Steine bzw. Rochaderechte liegen außerhalb der Tabellen.
+    let mut castling = parse_fen("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+    assert_eq!(tb.probe_wdl(&mut castling), None);
+    let mut kqk = parse_fen("8/8/8/4k3/8/8/8/Q3K3 w - - 0 1").unwrap();
+    assert_eq!(tb.probe_wdl(&mut kqk), None);
+}
+
+#[test]
+fn directories_are_scanned_for_tables() {
+    let dir = std::env::temp_dir().join(format!("chess_engine_syzygy_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("KRvK.rtbw"), WDL_MAGIC).unwrap();
+    std::fs::write(dir.join("notes.txt"), "kein Tablebase").unwrap();
+
+    let mut tb = Tablebases::new();
+    let added = tb.add_directory(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(added.unwrap(), 1);
+    assert!(tb.add_directory(dir.join("fehlt")).is_err());
+}
+
+#[test]
+fn search_without_usable_tables_is_unchanged() {
+    let mut tb = Tablebases::new();
+    tb.add_table("KRvK.rtbw", WDL_MAGIC.to_vec());
+    let plain = SearchLimits { depth: 4, tt_mb: 8, deterministic: true, ..SearchLimits::default() };
+    let probed = SearchLimits { tablebases: Some(Arc::new(tb)), ..plain.clone() };
+
+    let a = run_search(KRK, "", &plain, &SystemClock, &mut ()).unwrap();
+    let b = run_search(KRK, "", &probed, &SystemClock, &mut ()).unwrap();
+    assert_eq!((a.best, a.score, a.pv), (b.best, b.score, b.pv));
+
+    // Ohne abgedeckte Stellungen im Baum wird nie geprobt.
+    let start = run_search(START_FEN, "", &probed, &SystemClock, &mut ()).unwrap();
+    assert_eq!(start.tbhits, 0);
+}
+
+#[test]
+fn krvk_fixture_gives_known_values() {
+    let tb = fixture_tables();
+    // Matt in einem Zug; mit Schwarz am Zug Matt in einem Zug nach einem Königszug.
+    assert_eq!(probe(&tb, "k7/8/1K6/8/8/8/8/7R w - - 0 1"), (Some(Wdl::Win), Some(1)));
+    assert_eq!(probe(&tb, "k7/8/1K6/8/8/8/8/7R b - - 0 1"), (Some(Wdl::Loss), Some(-2)));
+    // Patt und Turmschlag sind Remis.
+    assert_eq!(probe(&tb, "k7/1R6/2K5/8/8/8/8/8 b - - 0 1"), (Some(Wdl::Draw), Some(0)));
+    assert_eq!(probe(&tb, "8/8/8/8/8/2k5/1R6/7K b - - 0 1").0, Some(Wdl::Draw));
+    // Längster Gewinn: Matt in 16 (31 Halbzüge), also DTZ höchstens 31.
+    let (wdl, dtz) = probe(&tb, KRK);
+    assert_eq!(wdl, Some(Wdl::Win));
+    assert!((1..=31).contains(&dtz.unwrap()));
+}
+
+#[test]
+fn krvk_fixture_is_probed_with_colors_swapped() {
+    let tb = fixture_tables();
+    // Schwarz hat den Turm: dieselbe Tabelle, gespiegelt gelesen.
+    assert_eq!(probe(&tb, "7r/8/8/8/8/1k6/8/K7 b - - 0 1"), (Some(Wdl::Win), Some(1)));
+    assert_eq!(probe(&tb, "7r/8/8/8/8/1k6/8/K7 w - - 0 1"), (Some(Wdl::Loss), Some(-2)));
+    assert_eq!(probe(&tb, "7k/1r6/2K5/8/8/8/8/8 w - - 0 1").0, Some(Wdl::Draw));
+}
+
+#[test]
+fn mapped_and_in_memory_tables_agree() {
+    let mapped = fixture_tables();
+    let mut bytes = Tablebases::new();
+    for name in ["KRvK.rtbw", "KRvK.rtbz"] {
+        assert!(bytes.add_table(name, std::fs::read(format!("{}/{}", FIXTURES, name)).unwrap()));
+    }
+    for wk in (0..64).step_by(5) {
+        for bk in (0..64).step_by(3) {
+            for rook in (0..64).step_by(7) {
+                for side in ["w", "b"] {
+                    let Some(mut pos) = position_of(&[(wk, 'K'), (bk, 'k'), (rook, 'R')], side) else { continue };
+                    let a = (mapped.probe_wdl(&mut pos), mapped.probe_dtz(&mut pos));
+                    assert_eq!(a, (bytes.probe_wdl(&mut pos), bytes.probe_dtz(&mut pos)), "{:?}", (wk, bk, rook));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn kpvk_fixture_gives_known_values() {
+    let tb = fixture_tables();
+    // Freier Bauer: Umwandlung ist nicht aufzuhalten, mit Schwarz am Zug ein Königszug mehr.
+    assert_eq!(probe(&tb, "8/8/4P3/8/8/8/k7/4K3 w - - 0 1"), (Some(Wdl::Win), Some(1)));
+    assert_eq!(probe(&tb, "8/8/4P3/8/8/8/k7/4K3 b - - 0 1"), (Some(Wdl::Loss), Some(-2)));
+    // Randbauer mit dem König in der Ecke ist Remis, der Bauernschlag ebenso.
+    assert_eq!(probe(&tb, "k7/P7/2K5/8/8/8/8/8 w - - 0 1").0, Some(Wdl::Draw));
+    assert_eq!(probe(&tb, "8/8/8/8/8/3k4/3P4/7K b - - 0 1"), (Some(Wdl::Draw), Some(0)));
+    // König vor dem Bauern auf der 6. Reihe gewinnt mit beiden Seiten am Zug, braucht aber
+    // erst Königszüge; schwarzer Bauer liest dieselbe Tabelle gespiegelt.
+    for side in ["w", "b"] {
+        let (wdl, dtz) = probe(&tb, &format!("4k3/8/4K3/4P3/8/8/8/8 {} - - 0 1", side));
+        assert_eq!(wdl, Some(if side == "w" { Wdl::Win } else { Wdl::Loss }));
+        assert!(dtz.unwrap().abs() > 2);
+        let flipped = probe(&tb, &format!("8/8/8/8/4p3/4k3/8/4K3 {} - - 0 1", if side == "w" { "b" } else { "w" }));
+        assert_eq!(flipped, (wdl, dtz));
+    }
+}
+
+#[test]
+fn kpvk_dtz_follows_from_king_moves() {
+    // Ohne gewinnenden Bauernzug ist DTZ der beste Königszug in eine verlorene Stellung plus
+    // die längste schwarze Antwort. Bauer bis zur 6. Reihe: Umwandlungen bräuchten KQvK.
+    let tb = fixture_tables();
+    let mut checked = 0;
+    for pawn in 8..48 {
+        for wk in (0..64).step_by(5) {
+            for bk in (0..64).step_by(3) {
+                let Some(mut pos) = position_of(&[(wk, 'K'), (bk, 'k'), (pawn, 'P')], "w") else { continue };
+                let (Some(Wdl::Win), Some(dtz)) = (tb.probe_wdl(&mut pos), tb.probe_dtz(&mut pos)) else {
+                    continue;
+                };
+                let mut best = i32::MAX;
+                for (mv, promo) in generate_legal_moves(&mut pos) {
+                    let pawn_move = mv.from as usize == pawn;
+                    let undo = make_move_in_place(&mut pos, mv, promo).unwrap();
+                    let lost = tb.probe_wdl(&mut pos) == Some(Wdl::Loss);
+                    if pawn_move && lost {
+                        best = 1;
+                    } else if lost {
+                        let mut longest = 0;
+                        for (reply, reply_promo) in generate_legal_moves(&mut pos) {
+                            let reply_undo = make_move_in_place(&mut pos, reply, reply_promo).unwrap();
+                            longest = longest.max(tb.probe_dtz(&mut pos).unwrap());
+                            unmake_move_in_place(&mut pos, reply, reply_promo, reply_undo);
+                        }
+                        best = best.min(longest + 2);
+                    }
+                    unmake_move_in_place(&mut pos, mv, promo, undo);
+                }
+                assert_eq!(dtz, best, "{:?}", (wk, pawn, bk));
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked > 1000, "{}", checked);
+}
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use chess_engine::{run_search, Color, GameClock, SearchLimits, SystemClock, START_FEN};
+
+fn clock(wtime: u64, btime: u64, inc: u64, movestogo: u32) -> GameClock {
+    GameClock { wtime, btime, winc: inc, binc: inc, movestogo }
+}
+
+#[test]
+fn sudden_death_spreads_time() {
+    let limits = clock(60_000, 60_000, 0, 0).limits(Color::White);
+    assert!((1_900..=2_100).contains(&limits.soft_ms), "{:?}", limits);
+    assert_eq!(limits.hard_ms, limits.soft_ms * 4);
+}
+
+#[test]
+fn increment_and_moves_to_go_raise_budget() {
+    let base = clock(60_000, 60_000, 0, 0).limits(Color::White);
+    let with_inc = clock(60_000, 60_000, 2_000, 0).limits(Color::White);
+    assert!(with_inc.soft_ms >= base.soft_ms + 1_400);
+
+    // Letzter Zug vor der Kontrolle darf fast die ganze Restzeit nutzen, aber nie alles.
+    let last = clock(10_000, 10_000, 0, 1).limits(Color::Black);
+    assert!(last.hard_ms >= 8_000 && last.hard_ms < 10_000, "{:?}", last);
+}
+
+#[test]
+fn low_time_stays_below_remaining() {
+    let limits = clock(120, 60_000, 0, 0).limits(Color::White);
+    assert!(limits.soft_ms >= 1);
+    assert!(limits.hard_ms <= 60, "{:?}", limits);
+}
+
+#[test]
+fn uses_own_clock_and_clock_balance() {
+    let uci = clock(10_000, 60_000, 0, 0);
+    let white = uci.limits(Color::White);
+    let black = uci.limits(Color::Black);
+    assert!(black.soft_ms > white.soft_ms * 5);
+
+    // Wer auf der Uhr vorne liegt, investiert etwas mehr als bei Gleichstand.
+    let even = clock(30_000, 30_000, 0, 0).limits(Color::White);
+    let ahead = clock(30_000, 20_000, 0, 0).limits(Color::White);
+    assert!(ahead.soft_ms > even.soft_ms);
+}
+
+#[test]
+fn search_respects_hard_limit() {
+    let game_clock = clock(3_000, 3_000, 0, 0);
+    let hard = game_clock.limits(Color::White).hard_ms;
+    let limits = SearchLimits { tt_mb: 8, game_clock: Some(game_clock), ..SearchLimits::default() };
+    let started = Instant::now();
+    let result = run_search(START_FEN, "", &limits, &SystemClock, &mut ()).unwrap();
+
+    assert!(started.elapsed() < Duration::from_millis(hard + 250));
+    assert!(result.depth >= 1);
+    assert!(result.best.is_some());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use chess_engine::{Engine, ProgressSink, SearchInfo, SearchLimits, SystemClock, MAX_TT_MB};
+
+use common::{search, MIDGAME};
+
+#[derive(Default)]
+struct LastHashfull(Option<u32>);
+
+impl ProgressSink for LastHashfull {
+    fn iteration(&mut self, info: &SearchInfo) {
+        self.0 = Some(info.hashfull);
+    }
+}
+
+#[test]
+fn hashfull_tracks_searches_and_clears() {
+    let mut engine = Engine::new(1);
+    assert_eq!(engine.hashfull(), 0);
+
+    search(&mut engine, MIDGAME, 6);
+    let used = engine.hashfull();
+    assert!(used > 0 && used <= 1000, "{}", used);
+
+    engine.clear_hash();
+    assert_eq!(engine.hashfull(), 0);
+    search(&mut engine, MIDGAME, 6);
+    engine.new_game();
+    assert_eq!(engine.hashfull(), 0);
+}
+
+#[test]
+fn info_reports_same_hashfull_as_engine() {
+    let mut engine = Engine::new(1);
+    let mut sink = LastHashfull::default();
+    let limits = SearchLimits { depth: 6, ..SearchLimits::default() };
+    engine.search(MIDGAME, "", &limits, &SystemClock, &mut sink).unwrap();
+    assert_eq!(sink.0, Some(engine.hashfull()));
+}
+
+#[test]
+fn sizes_above_256_mb_are_accepted() {
+    const { assert!(MAX_TT_MB > 256) };
+    let mut engine = Engine::new(300);
+    assert_eq!(engine.hash_size(), 300);
+    search(&mut engine, MIDGAME, 4);
+    assert!(engine.hashfull() <= 1000);
+}
+
+#[test]
+fn without_table_hashfull_is_zero() {
+    let mut engine = Engine::new(0);
+    search(&mut engine, MIDGAME, 4);
+    assert_eq!(engine.hashfull(), 0);
+}
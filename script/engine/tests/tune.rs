@@ -0,0 +1,60 @@
+use std::thread;
+
+use chess_engine::{Engine, SearchLimits, SystemClock, TuneValues, TUNE_PARAMS};
+
+// Matt in 2 (WAC.001): Qg6 muss unabhängig von der Fensterbreite gefunden werden.
+const WAC_001: &str = "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1";
+
+#[test]
+fn set_and_get_are_case_insensitive_and_clamped() {
+    let mut tune = TuneValues::default();
+    assert!(tune.set("asp_window", 80));
+    assert_eq!(tune.get("ASP_WINDOW"), Some(80));
+    assert!(tune.set("ASP_WINDOW", 100_000));
+    assert_eq!(tune.get("asp_window"), Some(500));
+    assert!(tune.set("KING_PRESSURE_MG", -3));
+    assert_eq!(tune.get("KING_PRESSURE_MG"), Some(0));
+    assert_eq!(tune.changed(), vec![("KING_PRESSURE_MG", 0), ("ASP_WINDOW", 500)]);
+}
+
+#[test]
+fn unknown_names_are_rejected_and_defaults_are_in_bounds() {
+    let mut tune = TuneValues::default();
+    assert!(!tune.set("NO_SUCH_PARAM", 1));
+    assert_eq!(tune.get("NO_SUCH_PARAM"), None);
+    assert!(tune.changed().is_empty());
+    for param in &TUNE_PARAMS {
+        assert!(param.min <= param.default && param.default <= param.max, "{}", param.name);
+        assert_eq!(tune.get(param.name), Some(param.default), "{}", param.name);
+    }
+}
+
+#[test]
+fn extreme_aspiration_windows_keep_the_search_result() {
+    for value in [5, 500] {
+        let mut limits = SearchLimits { depth: 5, deterministic: true, ..SearchLimits::default() };
+        limits.tune.set("ASP_WINDOW", value);
+        let result = Engine::new(4).search(WAC_001, "", &limits, &SystemClock, &mut ()).unwrap();
+        assert_eq!(result.best.as_deref(), Some("g3g6"), "ASP_WINDOW={}", value);
+    }
+}
+
+#[test]
+fn engines_with_different_values_do_not_interfere() {
+    let search = |window: i32| {
+        let mut limits = SearchLimits { depth: 6, deterministic: true, ..SearchLimits::default() };
+        limits.tune.set("ASP_WINDOW", window);
+        let result = Engine::new(4).search(WAC_001, "", &limits, &SystemClock, &mut ()).unwrap();
+        (result.best, result.score, result.nodes)
+    };
+    let (narrow, wide) = (search(5), search(500));
+    assert_ne!(narrow.2, wide.2, "die Fensterbreite muss den Baum ändern");
+    // Gleichzeitig in zwei Threads: jede Suche sieht nur ihre eigenen Werte.
+    let (a, b) = thread::scope(|scope| {
+        let a = scope.spawn(|| search(5));
+        let b = scope.spawn(|| search(500));
+        (a.join().unwrap(), b.join().unwrap())
+    });
+    assert_eq!(a, narrow);
+    assert_eq!(b, wide);
+}
@@ -1,5 +1,5 @@
 // worker/moveWorker.js
-import init, { get_valid_moves, apply_move, search, search_with_history, set_root_eval_debug } from "../engine/pkg/chess_engine.js";
+import init, { get_valid_moves, apply_move, game_status, move_to_san, san_to_uci, parse_pgn, write_pgn, perft, perft_divide, search, search_with_history, search_multipv, search_clock, search_deterministic, search_infinite, search_moves, search_ponder, set_info_callback, set_root_eval_debug, set_skill_level, set_stop_polling, set_threads, skill_levels, solve_mate, book_load, book_clear, book_probe, book_move, positionbook_load, positionbook_add_json, positionbook_probe, syzygy_add_table, syzygy_clear, syzygy_info, Engine } from "../engine/pkg/chess_engine.js";
 
 // WASM initialisieren (einmalig); alle Worker-Aktionen warten darauf.
 const wasmReady = init().catch((err) => {
@@ -21,194 +21,69 @@ const openingBookUrls = [
     new URL("../../src/book/opening_book_1000.json", import.meta.url)
 ];
 
-// Was: Fuehrt `parseUci` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function parseUci(uci) {
-    if (typeof uci !== "string") return null;
-    const s = uci.trim();
-    if (s.length !== 4 && s.length !== 5) return null;
-    const from = lanToField(s.slice(0, 2));
-    const to = lanToField(s.slice(2, 4));
-    if (from === null || to === null) return null;
-    const promo = s.length === 5 ? s[4] : "";
-    return { from, to, promo };
-}
-
-// Was: Fuehrt `applyUciMove` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function applyUciMove(fen, uci) {
-    const parsed = parseUci(uci);
-    if (!parsed) return null;
-    const nextFen = apply_move(fen, parsed.from, parsed.to, parsed.promo || "");
-    if (!nextFen || nextFen === fen) return null;
-    return nextFen;
-}
-
-// Was: Fuehrt `fenToBookKey` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function fenToBookKey(fen) {
-    const parts = String(fen || "").trim().split(/\s+/);
-    if (parts.length < 4) return "";
-    return `${parts[0]} ${parts[1]} ${parts[2]} ${parts[3]}`;
-}
-
-// Was: Fuehrt `mergeMovesInto` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function mergeMovesInto(targetMoves, sourceMoves) {
-    if (!sourceMoves || typeof sourceMoves !== "object") return;
-    for (const uci in sourceMoves) {
-        const raw = sourceMoves[uci];
-        const score = typeof raw === "number" ? raw : Number(raw);
-        if (!Number.isFinite(score) || score <= 0) continue;
-        targetMoves[uci] = (targetMoves[uci] || 0) + score;
-    }
-}
-
-// Was: Fuehrt `mergeHistoryBooks` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function mergeHistoryBooks(bookList) {
-    const merged = Object.create(null);
-    for (const source of bookList) {
-        if (!source || typeof source !== "object") continue;
-        for (const history in source) {
-            const entry = source[history];
-            if (!entry || typeof entry !== "object" || !entry.moves) continue;
-            if (!merged[history]) {
-                merged[history] = { moves: Object.create(null) };
-            }
-            mergeMovesInto(merged[history].moves, entry.moves);
-        }
-    }
-    return merged;
-}
-
-// Was: Fuehrt `historyPly` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function historyPly(history) {
-    if (!history) return 0;
-    return String(history).trim().split(/\s+/).filter(Boolean).length;
-}
-
-// Was: Fuehrt `resolveHistoryFen` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function resolveHistoryFen(history, fenByHistory) {
-    if (fenByHistory.has(history)) {
-        return fenByHistory.get(history);
-    }
-    if (!history) {
-        fenByHistory.set("", START_FEN);
-        return START_FEN;
-    }
-    const text = String(history).trim();
-    if (!text) {
-        fenByHistory.set("", START_FEN);
-        return START_FEN;
-    }
-    const lastSpace = text.lastIndexOf(" ");
-    const parent = lastSpace >= 0 ? text.slice(0, lastSpace) : "";
-    const move = lastSpace >= 0 ? text.slice(lastSpace + 1) : text;
-    const parentFen = resolveHistoryFen(parent, fenByHistory);
-    if (!parentFen) {
-        fenByHistory.set(history, null);
-        return null;
-    }
-    const nextFen = applyUciMove(parentFen, move);
-    if (!nextFen) {
-        fenByHistory.set(history, null);
-        return null;
-    }
-    fenByHistory.set(history, nextFen);
-    return nextFen;
-}
+// Vorberechnetes stellungsbasiertes Buch (`book-builder --positions`); fehlt es, werden die
+// JSON-Bücher beim Start in der Engine nach Stellung indiziert.
+const positionBookUrl = new URL("../engine/openingBook/opening_book.cpb", import.meta.url);
 
-// Was: Fuehrt `buildPositionBook` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function buildPositionBook(historyBook) {
-    const positionBook = Object.create(null);
-    const fenByHistory = new Map();
-    fenByHistory.set("", START_FEN);
-
-    const keys = Object.keys(historyBook).sort((a, b) => historyPly(a) - historyPly(b));
-    for (const history of keys) {
-        const entry = historyBook[history];
-        if (!entry || typeof entry !== "object" || !entry.moves) continue;
-
-        const fen = resolveHistoryFen(history, fenByHistory);
-        if (!fen) continue;
-
-        const key = fenToBookKey(fen);
-        if (!key) continue;
-
-        if (!positionBook[key]) {
-            positionBook[key] = {
-                moves: Object.create(null),
-                transpositions: 0
-            };
-        }
-        mergeMovesInto(positionBook[key].moves, entry.moves);
-        positionBook[key].transpositions += 1;
+// Was: Lädt das vorberechnete Stellungsbuch, falls vorhanden.
+// Warum: Spart beim Start das Parsen und Nachspielen der JSON-Bücher.
+// Kosten: Ein Fetch; das Laden selbst ist linear in der Dateigröße.
+async function loadPositionBookBinary() {
+    try {
+        const res = await fetch(positionBookUrl);
+        if (!res.ok) return 0;
+        return positionbook_load(new Uint8Array(await res.arrayBuffer()));
+    } catch (_err) {
+        return 0;
     }
-    return positionBook;
 }
 
-// Was: Fuehrt `loadMergedHistoryBook` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-async function loadMergedHistoryBook() {
-    const loaded = (await Promise.all(openingBookUrls.map(async (url) => {
+// Was: Lädt alle verfügbaren JSON-Bücher und übergibt sie dem Stellungsbuch der Engine.
+// Warum: Die Engine spielt jede Zugfolge einmal nach und fasst Zugumstellungen zusammen;
+// der Worker hält selbst keine Buchdaten mehr.
+// Kosten: Fetch plus Konvertierung in WASM, linear in der Buchgröße.
+async function loadHistoryBooks() {
+    const texts = await Promise.all(openingBookUrls.map(async (url) => {
         try {
             const res = await fetch(url);
-            if (!res.ok) return null;
-            const data = await res.json();
-            if (!data || typeof data !== "object") return null;
-            return { url: url.pathname, data };
+            return res.ok ? { url: url.pathname, text: await res.text() } : null;
         } catch (_err) {
             // Einzeldatei optional: Fehler ignorieren und nächste testen.
             return null;
         }
-    }))).filter(Boolean);
-    if (!loaded.length) return null;
-    const merged = mergeHistoryBooks(loaded.map((x) => x.data));
-    console.log(
-        "Opening book loaded:",
-        loaded.map((x) => x.url).join(", "),
-        "| merged positions:",
-        Object.keys(merged).length
-    );
-    return merged;
+    }));
+    const loaded = texts.filter((x) => x && positionbook_add_json(x.text) > 0);
+    if (!loaded.length) return false;
+    console.log("Opening book loaded:", loaded.map((x) => x.url).join(", "));
+    return true;
 }
 
-// Was: Initialisiert `openingBookReady` als einmaliges Laden/Mergen/Indexieren der Buchdaten.
+// Was: Initialisiert `openingBookReady` als einmaliges Laden/Indexieren der Buchdaten.
 // Warum: Verhindert wiederholte I/O- und Aufbaukosten pro Suche und hält den Worker-Pfad schlank.
-// Kosten: Einmaliger Startaufwand, danach nur Promise-Resolve auf bereits vorbereitete Daten.
+// Kosten: Einmaliger Startaufwand, danach nur Promise-Resolve.
 const openingBookReady = (async () => {
     try {
         await wasmReady;
-        const historyBook = await loadMergedHistoryBook();
-        if (!historyBook) {
-            console.warn("Opening book not available");
-            return null;
+        const entries = await loadPositionBookBinary();
+        if (entries > 0) {
+            console.log("Opening book loaded:", positionBookUrl.pathname, "| entries:", entries);
+            return true;
         }
-        const positionBook = buildPositionBook(historyBook);
-        return { historyBook, positionBook };
+        if (await loadHistoryBooks()) return true;
+        console.warn("Opening book not available");
+        return false;
     } catch (err) {
         console.warn("Opening book load/build error:", err);
-        return null;
+        return false;
     }
 })();
 
 const bookState = {
     active: true,
     gameId: null,
-    lastHistory: ""
+    lastHistory: "",
+    // Ein geladenes Polyglot-Buch (`book_load`) hat Vorrang vor den JSON-Büchern.
+    polyglot: false
 };
 
 // Such-IDs für saubere Zuordnung von Progress-Events.
@@ -240,6 +115,118 @@ globalThis.__engine_progress = (depth, nodesCompleted, nodesTotal, elapsedMs) =>
     });
 };
 
+// Was: Leitet Info-Events der Engine (nach jeder Iteration und bei Aspiration-Fail-High/-Low)
+// als `search-info` an den Main-Thread weiter.
+// Warum: UI zeigt Bestzug, Score/Matt, PV, Seldepth und Hashfull schon während der Suche.
+// Kosten: Ein JSON-Parse und eine Message pro Iteration.
+function forwardSearchInfo(json) {
+    if (!activeSearchId) return;
+    try {
+        self.postMessage({ action: "search-info", searchId: activeSearchId, ...JSON.parse(json) });
+    } catch (err) {
+        console.warn("moveWorker: search info parse failed:", err, json);
+    }
+}
+
+wasmReady.then(() => set_info_callback(forwardSearchInfo)).catch(() => {});
+
+// Flags im SharedArrayBuffer: Index 0 != 0 = abbrechen, Index 1 != 0 = Ponderhit. Der
+// Main-Thread setzt sie per `Atomics.store`, weil der Worker während der synchronen
+// WASM-Suche keine Nachrichten verarbeitet. Ohne Cross-Origin-Isolation bleibt es `null`.
+let stopFlag = null;
+
+// Was: Beantwortet die periodische Stop-Abfrage der Engine (`should_stop`).
+// Warum: Einziger Weg, eine laufende (auch unendliche) Suche von außen zu beenden.
+// Kosten: Ein atomarer Lesezugriff pro Abfrage (alle paar hundert Knoten).
+globalThis.__engine_should_stop = () => stopFlag !== null && Atomics.load(stopFlag, 0) !== 0;
+
+// Was: Beantwortet die Ponderhit-Abfrage einer Ponder-Suche.
+// Warum: Nach dem Ponderhit läuft dieselbe Suche mit ihrem Zeitlimit als normale Suche weiter.
+// Kosten: Ein atomarer Lesezugriff pro Abfrage, nur solange gepondert wird.
+globalThis.__engine_ponderhit = () => stopFlag !== null && Atomics.load(stopFlag, 1) !== 0;
+
+// Was: Schläft bis zum Stop (bzw. Ponderhit, beide wecken über Index 0) oder höchstens `timeoutMs`.
+// Warum: Unendliche und Ponder-Suchen warten nach der Maximaltiefe auf Stop/Ponderhit, ohne zu rechnen.
+// Kosten: Keine Rechenzeit; `Atomics.wait` ist im Worker erlaubt.
+globalThis.__engine_wait_stop = (timeoutMs) => {
+    if (stopFlag !== null) {
+        Atomics.wait(stopFlag, 0, 0, timeoutMs);
+    }
+};
+
+// Lazy-SMP-Helfer: Nur der Thread-Build der Engine (`+atomics`) hat gemeinsamen Speicher
+// (`memory.buffer` ist dann ein SharedArrayBuffer). Jeder Helfer ist ein eigener Worker, der
+// das Modul mit diesem Speicher instanziiert; einmal gestartet bleibt er bis zum Seitenende.
+const wasmUrl = new URL("../engine/pkg/chess_engine_bg.wasm", import.meta.url);
+const smpHelpers = [];
+
+// Was: Startet Helfer-Worker, bis `count` laufen; liefert die Zahl laufender Helfer.
+// Warum: Ohne Thread-Build oder Cross-Origin-Isolation bleibt die Suche einsträngig (0 Helfer).
+// Kosten: Ein Worker-Start samt WASM-Instanziierung je neuem Helfer.
+async function ensureSmpHelpers(count) {
+    const wasm = await wasmReady;
+    if (typeof SharedArrayBuffer !== "function" || !(wasm.memory.buffer instanceof SharedArrayBuffer)) {
+        return 0;
+    }
+    while (smpHelpers.length < count) {
+        const helper = new Worker(new URL("./smpWorker.js", import.meta.url), { type: "module" });
+        const ready = await new Promise((resolve) => {
+            helper.onmessage = (msg) => resolve(msg.data && msg.data.ready === true);
+            helper.onerror = () => resolve(false);
+            helper.postMessage({ wasmUrl: wasmUrl.href, memory: wasm.memory });
+        });
+        if (!ready) {
+            helper.terminate();
+            break;
+        }
+        smpHelpers.push(helper);
+    }
+    return smpHelpers.length;
+}
+
+// Engine-Instanzen pro `engineId` (z. B. "game", "analysis"): eigene TT, Killer/History
+// und Skill-Stufe. Suchen ohne `engineId` laufen über die Standard-Engine des Workers.
+const engines = new Map();
+
+// Was: Liefert die Instanz zu `id`, legt sie bei Bedarf an und übernimmt eine geänderte TT-Größe.
+// Warum: Partie und Analysebrett im selben Worker verschmutzen sich nicht gegenseitig die TT.
+// Kosten: Allokation der TT beim ersten Zugriff bzw. Größenwechsel, sonst ein Map-Lookup.
+function getEngine(id, ttMb) {
+    let engine = engines.get(id);
+    if (!engine) {
+        engine = new Engine(ttMb);
+        engines.set(id, engine);
+    } else if (ttMb > 0 && engine.hash_size() !== ttMb) {
+        engine.set_hash_size(ttMb);
+    }
+    return engine;
+}
+
+// Was: Sucht mit einer Engine-Instanz; Modusauswahl wie bei den freien Such-Exports.
+// Warum: Gleiche Worker-Parameter, egal ob mit oder ohne `engineId` gesucht wird.
+// Kosten: Die Suche selbst.
+function searchWithEngine(engine, opts) {
+    const { fen, history, depth, timeMs, multiPv, clock } = opts;
+    engine.set_skill_level(opts.skillLevel);
+    if (opts.infinite) return engine.search_infinite(fen, history, multiPv);
+    if (opts.ponder) return engine.search_ponder(fen, history, timeMs, multiPv);
+    if (opts.searchMoves) return engine.search_moves(fen, history, depth, timeMs, opts.searchMoves, opts.scoreEach);
+    if (opts.deterministic) return engine.search_deterministic(fen, history, depth, opts.nodes, multiPv);
+    if (clock) {
+        return engine.search_clock(
+            fen,
+            history,
+            toSafeInt(clock.wtime),
+            toSafeInt(clock.btime),
+            toSafeInt(clock.winc),
+            toSafeInt(clock.binc),
+            toSafeInt(clock.movestogo),
+            multiPv
+        );
+    }
+    return engine.search(fen, history, depth, timeMs, multiPv);
+}
+
 // Was: Fuehrt `normalizeHistory` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
 // Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
 // Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
@@ -257,32 +244,6 @@ function shouldResetBook(gameId, history) {
     return false;
 }
 
-// Was: Fuehrt `lanToField` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function lanToField(lan) {
-    if (!lan || lan.length !== 2) return null;
-    const file = lan.charCodeAt(0) - 97; // 'a'
-    const rank = lan.charCodeAt(1) - 49; // '1'
-    if (file < 0 || file > 7 || rank < 0 || rank > 7) return null;
-    return rank * 8 + file;
-}
-
-// Was: Fuehrt `collectLegalBookMoves` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function collectLegalBookMoves(fen, moves) {
-    const entries = [];
-    for (const uci in moves) {
-        const raw = moves[uci];
-        const score = typeof raw === "number" ? raw : Number(raw);
-        if (!Number.isFinite(score) || score <= 0) continue;
-        if (!applyUciMove(fen, uci)) continue;
-        entries.push({ uci, score });
-    }
-    return entries;
-}
-
 // Was: Fuehrt `filterBookCandidates` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
 // Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
 // Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
@@ -319,17 +280,11 @@ function pickWeightedBookMove(entries) {
     return entries[entries.length - 1].uci;
 }
 
-// Was: Fuehrt `getBookEntry` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function getBookEntry(bookData, fen, history) {
-    if (!bookData) return null;
-    const posKey = fenToBookKey(fen);
-    const posEntry = posKey ? bookData.positionBook[posKey] : null;
-    if (posEntry && posEntry.moves) return posEntry;
-    const historyEntry = bookData.historyBook[history];
-    if (historyEntry && historyEntry.moves) return historyEntry;
-    return null;
+// Was: Buchzüge der Stellung aus dem Stellungsbuch der Engine (nur legale Züge).
+// Warum: Zugumstellungen sind im Buch bereits zusammengefasst; die Zugfolge spielt keine Rolle.
+// Kosten: Eine Binärsuche und Zuggenerierung in WASM plus JSON-Parse.
+function probePositionBook(fen) {
+    return JSON.parse(positionbook_probe(fen)).map((m) => ({ uci: m.uci, score: m.weight }));
 }
 
 // Was: Fuehrt `getBookMove` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
@@ -347,18 +302,16 @@ async function getBookMove(fen, historyUci, gameId, bookEnabled) {
 
     if (!bookState.active) return null;
 
-    const bookData = await openingBookReady;
-    if (!bookData) {
-        bookState.active = false;
-        return null;
+    if (bookState.polyglot) {
+        const move = book_move(fen, "weighted");
+        if (move) return move;
     }
-    const entry = getBookEntry(bookData, fen, history);
-    if (!entry || !entry.moves) {
+
+    if (!(await openingBookReady)) {
         bookState.active = false;
         return null;
     }
-
-    const legalEntries = collectLegalBookMoves(fen, entry.moves);
+    const legalEntries = probePositionBook(fen);
     if (!legalEntries.length) {
         bookState.active = false;
         return null;
@@ -372,99 +325,6 @@ async function getBookMove(fen, historyUci, gameId, bookEnabled) {
     return selectedMove;
 }
 
-// Was: Fuehrt `collectMoves` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function collectMoves(fen) {
-    const moves = [];
-    for (let from = 0; from < 64; from++) {
-        const targets = Array.from(get_valid_moves(fen, from));
-        if (!targets.length) continue;
-        for (const to of targets) {
-            moves.push([from, to]);
-        }
-    }
-    return moves;
-}
-
-// Was: Fuehrt `parseBoard` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function parseBoard(fen) {
-    const boardPart = String(fen || "").split(/\s+/)[0];
-    if (!boardPart) return null;
-    const ranks = boardPart.split("/");
-    if (ranks.length !== 8) return null;
-
-    const board = new Array(64).fill(null);
-    let row = 7;
-    for (const rank of ranks) {
-        let col = 0;
-        for (const ch of rank) {
-            if (ch >= "1" && ch <= "8") {
-                col += Number(ch);
-            } else {
-                if (col < 0 || col > 7) return null;
-                board[row * 8 + col] = ch;
-                col += 1;
-            }
-        }
-        if (col !== 8) return null;
-        row -= 1;
-    }
-    return board;
-}
-
-// Was: Fuehrt `isPromotionMove` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function isPromotionMove(piece, to) {
-    if (!piece) return false;
-    const toRank = Math.floor(to / 8);
-    if (piece === "P") return toRank === 7;
-    if (piece === "p") return toRank === 0;
-    return false;
-}
-
-// Was: Fuehrt `perft` aus und kapselt einen klar abgegrenzten Worker-Teilschritt.
-// Warum: Haelt die Logik modular, nachvollziehbar und separat optimierbar.
-// Kosten: Laufzeit ist kontextabhaengig und wird durch Eingabegroesse/Verzweigungen bestimmt.
-function perft(fen, depth) {
-    if (depth <= 0) return 1;
-    const board = parseBoard(fen);
-    const moves = collectMoves(fen);
-    if (depth === 1) {
-        if (!board) return moves.length;
-        let count = 0;
-        for (const [from, to] of moves) {
-            const piece = board[from];
-            if (isPromotionMove(piece, to)) {
-                count += 4;
-            } else {
-                count += 1;
-            }
-        }
-        return count;
-    }
-
-    let nodes = 0;
-    for (const [from, to] of moves) {
-        const piece = board ? board[from] : null;
-        if (board && isPromotionMove(piece, to)) {
-            for (const promo of ["q", "r", "b", "n"]) {
-                const nextFen = apply_move(fen, from, to, promo);
-                if (nextFen === fen) continue;
-                nodes += perft(nextFen, depth - 1);
-            }
-        } else {
-            const nextFen = apply_move(fen, from, to, "");
-            if (nextFen === fen) continue;
-            nodes += perft(nextFen, depth - 1);
-        }
-    }
-    return nodes;
-}
-
 // Was: Registriert globales Error-Logging fuer den Worker.
 // Warum: Macht Laufzeitfehler im Worker sofort sichtbar und erleichtert Diagnose im Browser-Log.
 // Kosten: Nur im Fehlerfall aktiv; im Normalbetrieb praktisch kein Overhead.
@@ -478,13 +338,21 @@ self.addEventListener("error", (e) => {
     );
 });
 
-// Was: Zentraler Dispatch fuer alle eingehenden Worker-Aktionen (`moves`, `apply`, `perft`, `search`).
+// Was: Zentraler Dispatch fuer alle eingehenden Worker-Aktionen (`stop_buffer`, `moves`, `apply`, `status`, `san`, `san_to_uci`, `parse_pgn`, `write_pgn`, `perft`, `perft_divide`, `solve_mate`, `threads`, `engine_*`, `book_*`, `syzygy_*`, `search`).
 // Warum: Haelt den Kommunikationspfad zwischen UI und WASM-Engine an einer Stelle konsistent.
 // Kosten: Konstante Dispatch-Kosten plus jeweilige Aktionskosten der aufgerufenen Engine-Routinen.
 self.onmessage = async function (e) {
     const data = e.data || {};
     const action = data.action || "moves";
 
+    // Stop-Puffer braucht kein WASM und keine Antwort.
+    if (action === "stop_buffer") {
+        stopFlag = data.buffer instanceof SharedArrayBuffer ? new Int32Array(data.buffer) : null;
+        // Ohne Flags fragt die Engine gar nicht erst nach (spart den JS-Aufruf je Prüfintervall).
+        wasmReady.then(() => set_stop_polling(stopFlag !== null)).catch(() => {});
+        return;
+    }
+
     // Jede Aktion wartet auf abgeschlossene WASM-Initialisierung.
     await wasmReady;
 
@@ -501,6 +369,120 @@ self.onmessage = async function (e) {
         return;
     }
 
+    if (action === "status") {
+        const status = JSON.parse(game_status(data.fen || "", typeof data.history === "string" ? data.history : ""));
+        self.postMessage({ action: "status", status });
+        return;
+    }
+
+    if (action === "san") {
+        const san = move_to_san(data.fen || "", typeof data.uci === "string" ? data.uci : "");
+        self.postMessage({ action: "san", san });
+        return;
+    }
+
+    if (action === "san_to_uci") {
+        const uci = san_to_uci(data.fen || "", typeof data.san === "string" ? data.san : "");
+        self.postMessage({ action: "san_to_uci", uci });
+        return;
+    }
+
+    if (action === "parse_pgn") {
+        const game = JSON.parse(parse_pgn(typeof data.pgn === "string" ? data.pgn : ""));
+        self.postMessage({ action: "parse_pgn", game });
+        return;
+    }
+
+    if (action === "write_pgn") {
+        const pgn = write_pgn(
+            data.fen || START_FEN,
+            Array.isArray(data.moves) ? data.moves.join(" ") : "",
+            typeof data.tags === "string" ? data.tags : ""
+        );
+        self.postMessage({ action: "write_pgn", pgn });
+        return;
+    }
+
+    if (action === "solve_mate") {
+        const fen = data.fen || "";
+        const moves = toSafeInt(data.moves);
+        if (!fen || moves < 1) {
+            self.postMessage({ action: "solve_mate", error: "FEN und Zuganzahl >= 1 nötig" });
+            return;
+        }
+        const raw = solve_mate(fen, moves, toSafeInt(data.timeMs), toSafeInt(data.ttMb));
+        self.postMessage({ action: "solve_mate", ...JSON.parse(raw) });
+        return;
+    }
+
+    // Such-Threads (Lazy SMP) für alle folgenden Suchen; Antwort: tatsächlich laufende Threads.
+    if (action === "threads") {
+        const wanted = Math.max(1, toSafeInt(data.threads));
+        const helpers = await ensureSmpHelpers(wanted - 1);
+        set_threads(wanted);
+        self.postMessage({ action: "threads", threads: Math.min(wanted, helpers + 1) });
+        return;
+    }
+
+    // Verwaltung der Engine-Instanzen: neue Partie, TT leeren, TT-Größe, freigeben.
+    if (action === "engine_new_game" || action === "engine_clear_hash" || action === "engine_set_hash" || action === "engine_free") {
+        const id = String(data.engineId || "");
+        const engine = engines.get(id);
+        if (engine && action === "engine_new_game") engine.new_game();
+        if (engine && action === "engine_clear_hash") engine.clear_hash();
+        if (action === "engine_set_hash") getEngine(id, toSafeInt(data.ttMb)).set_hash_size(toSafeInt(data.ttMb));
+        if (engine && action === "engine_free") {
+            engines.delete(id);
+            engine.free();
+        }
+        self.postMessage({ action, engineId: id });
+        return;
+    }
+
+    // Polyglot-Buch (`.bin` als ArrayBuffer); ersetzt ein vorher geladenes.
+    if (action === "book_load") {
+        const entries = data.buffer instanceof ArrayBuffer ? book_load(new Uint8Array(data.buffer)) : 0;
+        bookState.polyglot = entries > 0;
+        self.postMessage({ action: "book_load", entries });
+        return;
+    }
+
+    if (action === "book_clear") {
+        book_clear();
+        bookState.polyglot = false;
+        self.postMessage({ action: "book_clear", entries: 0 });
+        return;
+    }
+
+    if (action === "book_probe") {
+        self.postMessage({ action: "book_probe", moves: JSON.parse(book_probe(data.fen || "")) });
+        return;
+    }
+
+    // Syzygy-Tabellen: Dateien kommen als ArrayBuffer (z. B. aus `fetch` oder File-Input).
+    if (action === "syzygy_add") {
+        const files = Array.isArray(data.files) ? data.files : [];
+        let added = 0;
+        for (const file of files) {
+            if (file && typeof file.name === "string" && file.buffer instanceof ArrayBuffer) {
+                if (syzygy_add_table(file.name, new Uint8Array(file.buffer))) added++;
+            }
+        }
+        self.postMessage({ action: "syzygy_add", added, ...JSON.parse(syzygy_info()) });
+        return;
+    }
+
+    if (action === "syzygy_clear") {
+        syzygy_clear();
+        self.postMessage({ action: "syzygy_clear", ...JSON.parse(syzygy_info()) });
+        return;
+    }
+
+    if (action === "skill_levels") {
+        self.postMessage({ action: "skill_levels", levels: JSON.parse(skill_levels()) });
+        return;
+    }
+
     if (action === "perft") {
         const fen = data.fen || "";
         const depth = Number(data.depth);
@@ -516,6 +498,21 @@ self.onmessage = async function (e) {
         return;
     }
 
+    if (action === "perft_divide") {
+        const fen = data.fen || "";
+        const depth = Number(data.depth);
+        if (!fen || !Number.isFinite(depth) || depth < 1) {
+            self.postMessage({ action: "perft_divide", nodes: 0, moves: {}, depth, ms: 0 });
+            return;
+        }
+
+        const t0 = performance.now();
+        const result = JSON.parse(perft_divide(fen, depth));
+        const ms = performance.now() - t0;
+        self.postMessage({ action: "perft_divide", nodes: result.nodes || 0, moves: result.moves || {}, depth, ms });
+        return;
+    }
+
     if (action === "search") {
         const fen = data.fen || "";
         const depth = Number(data.depth);
@@ -525,6 +522,25 @@ self.onmessage = async function (e) {
         const bookEnabled = data.bookEnabled === true;
         const uciHistory = typeof data.uciHistory === "string" ? data.uciHistory : "";
         const debugRootEval = data.debugRootEval === true;
+        const multiPv = Number(data.multiPv ?? data.multipv ?? 1);
+        // Spielstärke 0..20; fehlend oder ungültig = volle Stärke.
+        const skillLevel = Number(data.skillLevel ?? -1);
+        // Analysemodus: ohne Tiefen-/Zeitlimit, endet erst über das Stop-Flag.
+        const infinite = data.infinite === true && stopFlag !== null;
+        // Ponder-Suche: wie `infinite` bis zum Ponderhit, danach mit `timeMs`.
+        const ponder = data.ponder === true && stopFlag !== null;
+        // Partieuhr `{ wtime, btime, winc, binc, movestogo }` in ms ersetzt `timeMs`.
+        const clock = data.clock && typeof data.clock === "object" ? data.clock : null;
+        // Reproduzierbar: nur Tiefe/Knoten begrenzen, kein Zeitlimit, frische TT.
+        const nodes = toSafeInt(data.nodes);
+        const deterministic = data.deterministic === true || nodes > 0;
+        // Nur diese Wurzelzüge (UCI); `scoreEach` bewertet jeden davon einzeln.
+        const searchMoves = Array.isArray(data.searchMoves)
+            ? data.searchMoves.join(" ")
+            : String(data.searchMoves || "").trim();
+        const scoreEach = data.scoreEach === true;
+        // Eigene Engine-Instanz statt der Standard-Engine des Workers.
+        const engineId = typeof data.engineId === "string" && data.engineId ? data.engineId : null;
 
         if (!fen) {
             self.postMessage({ action: "search", error: "keine FEN vorhanden" });
@@ -541,9 +557,11 @@ self.onmessage = async function (e) {
         } catch (err) {
             console.warn("set_root_eval_debug failed:", err);
         }
+        const safeSkill = Number.isFinite(skillLevel) ? Math.floor(skillLevel) : -1;
+        set_skill_level(safeSkill);
 
         // Eröffnungsbuch hat Vorrang, wenn aktiv und legaler Zug gefunden wurde.
-        const bookMove = await getBookMove(fen, uciHistory, gameId, bookEnabled);
+        const bookMove = infinite || ponder || deterministic || searchMoves ? null : await getBookMove(fen, uciHistory, gameId, bookEnabled);
         if (bookMove) {
             self.postMessage({
                 action: "search",
@@ -564,13 +582,57 @@ self.onmessage = async function (e) {
         const safeTimeMs = Number.isFinite(timeMs) && timeMs > 0 ? timeMs : 0;
         const safeTtMb = Number.isFinite(ttMb) && ttMb > 0 ? ttMb : 0;
 
+        const safeMultiPv = Number.isFinite(multiPv) && multiPv > 1 ? Math.floor(multiPv) : 1;
+
         const history = typeof data.history === "string" ? data.history : "";
         let result = null;
         activeSearchId = searchId;
         try {
-            const raw = history && history.trim().length > 0
-                ? search_with_history(fen, safeDepth, safeTimeMs, safeTtMb, history)
-                : search(fen, safeDepth, safeTimeMs, safeTtMb);
+            // Multi-PV liefert zusätzlich `lines` mit einer Variante pro Kandidatenzug.
+            let raw;
+            if (engineId) {
+                raw = searchWithEngine(getEngine(engineId, safeTtMb), {
+                    fen,
+                    history,
+                    depth: safeDepth,
+                    timeMs: safeTimeMs,
+                    multiPv: safeMultiPv,
+                    skillLevel: safeSkill,
+                    infinite,
+                    ponder,
+                    searchMoves,
+                    scoreEach,
+                    deterministic,
+                    nodes,
+                    clock
+                });
+            } else if (infinite) {
+                raw = search_infinite(fen, safeTtMb, history, safeMultiPv);
+            } else if (ponder) {
+                raw = search_ponder(fen, safeTimeMs, safeTtMb, history, safeMultiPv);
+            } else if (searchMoves) {
+                raw = search_moves(fen, history, safeDepth, safeTimeMs, safeTtMb, searchMoves, scoreEach);
+            } else if (deterministic) {
+                raw = search_deterministic(fen, history, safeTtMb, safeDepth, nodes, safeMultiPv);
+            } else if (clock) {
+                raw = search_clock(
+                    fen,
+                    history,
+                    safeTtMb,
+                    toSafeInt(clock.wtime),
+                    toSafeInt(clock.btime),
+                    toSafeInt(clock.winc),
+                    toSafeInt(clock.binc),
+                    toSafeInt(clock.movestogo),
+                    safeMultiPv
+                );
+            } else if (safeMultiPv > 1) {
+                raw = search_multipv(fen, safeDepth, safeTimeMs, safeTtMb, history, safeMultiPv);
+            } else if (history && history.trim().length > 0) {
+                raw = search_with_history(fen, safeDepth, safeTimeMs, safeTtMb, history);
+            } else {
+                raw = search(fen, safeDepth, safeTimeMs, safeTtMb);
+            }
             try {
                 result = JSON.parse(raw);
             } catch (err) {
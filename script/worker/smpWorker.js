@@ -0,0 +1,17 @@
+// worker/smpWorker.js
+import init, { smp_helper_main } from "../engine/pkg/chess_engine.js";
+
+// Lazy-SMP-Helfer des Engine-Workers (nur Thread-Build der Engine, siehe README). Der Worker
+// instanziiert dasselbe WASM-Modul mit dem gemeinsamen Speicher des Engine-Workers, meldet
+// sich mit `ready` und wartet danach in `smp_helper_main` dauerhaft auf Suchaufträge.
+self.onmessage = async function (e) {
+    const { wasmUrl, memory } = e.data || {};
+    try {
+        await init({ module_or_path: wasmUrl, memory });
+    } catch (err) {
+        self.postMessage({ ready: false, error: String(err) });
+        return;
+    }
+    self.postMessage({ ready: true });
+    smp_helper_main();
+};
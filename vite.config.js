@@ -1,7 +1,20 @@
 import { defineConfig } from "vite";
 import { resolve } from "node:path";
 
+// Cross-Origin-Isolation, damit der Engine-Worker ein SharedArrayBuffer-Stop-Flag
+// bekommt (Abbruch laufender und unendlicher Suchen).
+const isolationHeaders = {
+  "Cross-Origin-Opener-Policy": "same-origin",
+  "Cross-Origin-Embedder-Policy": "require-corp",
+};
+
 export default defineConfig({
+  server: {
+    headers: isolationHeaders,
+  },
+  preview: {
+    headers: isolationHeaders,
+  },
   build: {
     rollupOptions: {
       input: {